@@ -0,0 +1,254 @@
+
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use super::{AiError, AiResult, GenerationParams};
+
+const ANTHROPIC_API_URL: &str = "https://api.anthropic.com/v1";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+#[derive(Debug, Serialize)]
+struct MessagesRequest {
+    model: String,
+    system: String,
+    messages: Vec<Message>,
+    temperature: f32,
+    max_tokens: u32,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Message {
+    role: String,
+    content: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessagesResponse {
+    content: Vec<ContentBlock>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ContentBlock {
+    #[serde(rename = "type")]
+    block_type: String,
+    #[serde(default)]
+    text: String,
+}
+
+/// One server-sent event of a streamed message
+#[derive(Debug, Deserialize)]
+struct StreamEvent {
+    #[serde(rename = "type")]
+    event_type: String,
+    delta: Option<StreamDelta>,
+    error: Option<StreamError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamDelta {
+    #[serde(default)]
+    text: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamError {
+    message: String,
+}
+
+fn messages_request(model: &str, prompt: &str, params: &GenerationParams, stream: bool) -> MessagesRequest {
+    MessagesRequest {
+        model: model.to_string(),
+        system: params.system_prompt.clone(),
+        messages: vec![Message {
+            role: "user".to_string(),
+            content: prompt.to_string(),
+        }],
+        temperature: params.temperature,
+        max_tokens: params.max_tokens,
+        stream,
+    }
+}
+
+fn map_send_error(e: reqwest::Error) -> AiError {
+    if e.is_timeout() {
+        AiError::Timeout
+    } else {
+        AiError::RequestFailed(e)
+    }
+}
+
+/// Parse a server-sent event line; `None` marks the end of the stream
+fn parse_stream_line(line: &str) -> AiResult<Option<String>> {
+    // `event:` lines repeat the type that is also in the data
+    let Some(data) = line.strip_prefix("data:") else {
+        return Ok(Some(String::new()));
+    };
+
+    let event: StreamEvent = serde_json::from_str(data.trim())
+        .map_err(|e| AiError::InvalidResponse(e.to_string()))?;
+    match event.event_type.as_str() {
+        "content_block_delta" => Ok(Some(event.delta.map(|d| d.text).unwrap_or_default())),
+        "message_stop" => Ok(None),
+        "error" => Err(AiError::ApiError(
+            event.error.map(|e| e.message).unwrap_or_else(|| "Unknown error".to_string()),
+        )),
+        _ => Ok(Some(String::new())),
+    }
+}
+
+/// Validate an Anthropic API key
+pub async fn validate_api_key(api_key: &str) -> bool {
+    let client = Client::new();
+
+    let response = client
+        .get(format!("{}/models", ANTHROPIC_API_URL))
+        .header("x-api-key", api_key)
+        .header("anthropic-version", ANTHROPIC_VERSION)
+        .timeout(std::time::Duration::from_secs(5))
+        .send()
+        .await;
+
+    response.map(|r| r.status().is_success()).unwrap_or(false)
+}
+
+/// List available models
+pub async fn list_models(api_key: &str) -> AiResult<Vec<String>> {
+    let client = Client::new();
+
+    #[derive(Deserialize)]
+    struct ModelsResponse {
+        data: Vec<ModelInfo>,
+    }
+
+    #[derive(Deserialize)]
+    struct ModelInfo {
+        id: String,
+    }
+
+    let response = client
+        .get(format!("{}/models", ANTHROPIC_API_URL))
+        .header("x-api-key", api_key)
+        .header("anthropic-version", ANTHROPIC_VERSION)
+        .query(&[("limit", "100")])
+        .timeout(std::time::Duration::from_secs(10))
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(AiError::ApiError("Failed to list models".to_string()));
+    }
+
+    let models: ModelsResponse = response.json().await?;
+    Ok(models.data.into_iter().map(|m| m.id).collect())
+}
+
+/// Generate text using Anthropic
+pub async fn generate(
+    api_key: &str,
+    model: &str,
+    prompt: &str,
+    params: &GenerationParams,
+) -> AiResult<String> {
+    let client = Client::new();
+
+    let response = client
+        .post(format!("{}/messages", ANTHROPIC_API_URL))
+        .header("x-api-key", api_key)
+        .header("anthropic-version", ANTHROPIC_VERSION)
+        .json(&messages_request(model, prompt, params, false))
+        .timeout(std::time::Duration::from_secs(params.timeout_secs))
+        .send()
+        .await
+        .map_err(map_send_error)?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+
+        return Err(AiError::ApiError(format!(
+            "Anthropic returned {}: {}",
+            status, error_text
+        )));
+    }
+
+    let messages_response: MessagesResponse = response
+        .json()
+        .await
+        .map_err(|e| AiError::InvalidResponse(e.to_string()))?;
+
+    let message: String = messages_response
+        .content
+        .into_iter()
+        .filter(|b| b.block_type == "text")
+        .map(|b| b.text)
+        .collect();
+
+    Ok(message.trim().to_string())
+}
+
+/// Generate text using Anthropic, streaming tokens to `on_token` as they arrive
+pub async fn generate_stream(
+    api_key: &str,
+    model: &str,
+    prompt: &str,
+    params: &GenerationParams,
+    cancel: &super::GenerationCancel,
+    mut on_token: impl FnMut(&str),
+) -> AiResult<String> {
+    let client = Client::new();
+
+    let response = client
+        .post(format!("{}/messages", ANTHROPIC_API_URL))
+        .header("x-api-key", api_key)
+        .header("anthropic-version", ANTHROPIC_VERSION)
+        .json(&messages_request(model, prompt, params, true))
+        .timeout(std::time::Duration::from_secs(params.timeout_secs))
+        .send()
+        .await
+        .map_err(map_send_error)?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+
+        return Err(AiError::ApiError(format!(
+            "Anthropic returned {}: {}",
+            status, error_text
+        )));
+    }
+
+    let mut text = String::new();
+    super::read_lines(response, cancel, |line| match parse_stream_line(line)? {
+        Some(token) => {
+            if !token.is_empty() {
+                on_token(&token);
+                text.push_str(&token);
+            }
+            Ok(true)
+        }
+        None => Ok(false),
+    })
+    .await?;
+
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stream_line() {
+        let delta = r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"feat"}}"#;
+        assert_eq!(parse_stream_line(delta).unwrap(), Some("feat".to_string()));
+
+        assert_eq!(parse_stream_line("event: content_block_delta").unwrap(), Some(String::new()));
+        assert_eq!(parse_stream_line(r#"data: {"type":"ping"}"#).unwrap(), Some(String::new()));
+        assert_eq!(parse_stream_line(r#"data: {"type":"message_stop"}"#).unwrap(), None);
+
+        let error = r#"data: {"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#;
+        assert!(parse_stream_line(error).is_err());
+    }
+}
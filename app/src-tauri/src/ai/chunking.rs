@@ -0,0 +1,154 @@
+//! Splitting large diffs into pieces a model can summarize
+//!
+//! Diffs are split at file boundaries, files too large for one piece at hunk
+//! boundaries, and small files are packed together so fewer requests are needed.
+
+/// Split a patch into the sections of each file (`diff --git` header onwards)
+pub fn split_files(diff: &str) -> Vec<&str> {
+    let mut starts: Vec<usize> = Vec::new();
+    let mut offset = 0;
+    for line in diff.split_inclusive('\n') {
+        if line.starts_with("diff --git ") {
+            starts.push(offset);
+        }
+        offset += line.len();
+    }
+
+    if starts.first() != Some(&0) {
+        starts.insert(0, 0);
+    }
+    starts
+        .iter()
+        .enumerate()
+        .map(|(i, &start)| &diff[start..starts.get(i + 1).copied().unwrap_or(diff.len())])
+        .filter(|section| !section.trim().is_empty())
+        .collect()
+}
+
+/// Split text into pieces of at most `max_chars` characters, preferring to break
+/// before lines for which `is_boundary` holds, then at any line
+///
+/// A single line longer than `max_chars` is cut off.
+fn split_at_lines(text: &str, max_chars: usize, is_boundary: impl Fn(&str) -> bool) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    let mut current_chars = 0;
+    // Length of `current` up to the last boundary
+    let mut last_boundary = 0;
+
+    for line in text.split_inclusive('\n') {
+        let line: String = line.chars().take(max_chars).collect();
+        let line_chars = line.chars().count();
+
+        if current_chars + line_chars > max_chars && !current.is_empty() {
+            if last_boundary > 0 && !is_boundary(&line) {
+                let rest = current.split_off(last_boundary);
+                pieces.push(std::mem::replace(&mut current, rest));
+                current_chars = current.chars().count();
+            }
+            if current_chars + line_chars > max_chars {
+                pieces.push(std::mem::take(&mut current));
+                current_chars = 0;
+            }
+            last_boundary = 0;
+        }
+
+        if is_boundary(&line) && !current.is_empty() {
+            last_boundary = current.len();
+        }
+        current.push_str(&line);
+        current_chars += line_chars;
+    }
+
+    if !current.trim().is_empty() {
+        pieces.push(current);
+    }
+    pieces
+}
+
+/// Split a patch into pieces of at most `max_chars` characters
+///
+/// Whole files are packed together while they fit; larger files are split at
+/// their hunks.
+pub fn chunk_diff(diff: &str, max_chars: usize) -> Vec<String> {
+    let mut chunks: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    for file in split_files(diff) {
+        let file_chars = file.chars().count();
+        if file_chars > max_chars {
+            if !current.is_empty() {
+                chunks.push(std::mem::take(&mut current));
+            }
+            chunks.extend(split_at_lines(file, max_chars, |line| line.starts_with("@@")));
+            continue;
+        }
+
+        if current.chars().count() + file_chars > max_chars {
+            chunks.push(std::mem::take(&mut current));
+        }
+        current.push_str(file);
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Split text into pieces of at most `max_chars` characters at line breaks
+pub fn chunk_lines(text: &str, max_chars: usize) -> Vec<String> {
+    split_at_lines(text, max_chars, |_| true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file_diff(path: &str, hunks: &[&str]) -> String {
+        let mut diff = format!("diff --git a/{0} b/{0}\n--- a/{0}\n+++ b/{0}\n", path);
+        for hunk in hunks {
+            diff.push_str(&format!("@@ -1 +1 @@\n-{0}\n+{0}!\n", hunk));
+        }
+        diff
+    }
+
+    #[test]
+    fn test_split_files() {
+        let diff = format!("{}{}", file_diff("a.rs", &["x"]), file_diff("b.rs", &["y"]));
+        let files = split_files(&diff);
+        assert_eq!(files.len(), 2);
+        assert!(files[0].starts_with("diff --git a/a.rs"));
+        assert!(files[1].starts_with("diff --git a/b.rs"));
+    }
+
+    #[test]
+    fn test_chunk_diff_packs_small_files() {
+        let diff = format!("{}{}", file_diff("a.rs", &["x"]), file_diff("b.rs", &["y"]));
+        let chunks = chunk_diff(&diff, 1000);
+        assert_eq!(chunks, vec![diff.clone()]);
+
+        let chunks = chunk_diff(&diff, diff.len() - 1);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks.concat(), diff);
+    }
+
+    #[test]
+    fn test_chunk_diff_splits_large_files_at_hunks() {
+        let hunks: Vec<String> = (0..20).map(|i| format!("line {}", i)).collect();
+        let hunks: Vec<&str> = hunks.iter().map(|h| h.as_str()).collect();
+        let diff = file_diff("big.rs", &hunks);
+
+        let chunks = chunk_diff(&diff, 120);
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|c| c.chars().count() <= 120));
+        assert!(chunks[1..].iter().all(|c| c.starts_with("@@")));
+        assert_eq!(chunks.concat(), diff);
+    }
+
+    #[test]
+    fn test_chunk_lines() {
+        let chunks = chunk_lines("aaaa\nbbbb\ncccc\n", 10);
+        assert_eq!(chunks, vec!["aaaa\nbbbb\n".to_string(), "cccc\n".to_string()]);
+    }
+}
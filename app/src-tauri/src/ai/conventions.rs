@@ -0,0 +1,211 @@
+//! Commit conventions enforced on generated messages
+//!
+//! The conventions are described to the model in the prompt and checked again
+//! once a message has been generated, so a message that ignores them can be
+//! regenerated.
+
+use serde::{Deserialize, Serialize};
+
+use crate::git::changelog::parse_conventional_commit;
+
+/// Placeholder for the issue reference in a footer format
+const ISSUE_PLACEHOLDER: &str = "{issue}";
+
+/// Whether a commit message has a body
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BodyRule {
+    #[default]
+    Optional,
+    Required,
+    Forbidden,
+}
+
+/// Constraints on generated commit messages
+///
+/// Nothing is constrained by default, so messages are only checked against the
+/// rules the user has set up.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CommitConventions {
+    /// Allowed conventional commit types (any type when empty)
+    #[serde(default)]
+    pub types: Vec<String>,
+    /// Allowed scopes (any scope when empty); the scope itself stays optional
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    #[serde(default)]
+    pub max_subject_length: Option<usize>,
+    #[serde(default)]
+    pub body: BodyRule,
+    /// Format of a required footer line referencing an issue, e.g. `Refs: #{issue}`
+    #[serde(default)]
+    pub issue_footer: Option<String>,
+}
+
+impl CommitConventions {
+    /// Whether generated messages keep everything after the subject line
+    pub fn keeps_body(&self) -> bool {
+        self.body == BodyRule::Required || self.issue_footer.is_some()
+    }
+
+    fn matches_footer(&self, line: &str) -> bool {
+        let Some(format) = &self.issue_footer else {
+            return false;
+        };
+        let Some((prefix, suffix)) = format.split_once(ISSUE_PLACEHOLDER) else {
+            return line.trim() == format.trim();
+        };
+
+        line.strip_prefix(prefix)
+            .and_then(|rest| rest.strip_suffix(suffix))
+            .is_some_and(|issue| !issue.is_empty() && !issue.contains(char::is_whitespace))
+    }
+
+    /// Describe the conventions for the prompt (empty when nothing is constrained)
+    pub fn instructions(&self) -> String {
+        let mut rules = Vec::new();
+        if !self.types.is_empty() {
+            rules.push(format!("- The type must be one of: {}", self.types.join(", ")));
+        }
+        if !self.scopes.is_empty() {
+            rules.push(format!("- The scope, if any, must be one of: {}", self.scopes.join(", ")));
+        }
+        if let Some(max) = self.max_subject_length {
+            rules.push(format!("- The first line must be at most {} characters", max));
+        }
+        match self.body {
+            BodyRule::Optional => {}
+            BodyRule::Required => {
+                rules.push("- Add a body after a blank line explaining the change".to_string())
+            }
+            BodyRule::Forbidden => rules.push("- Write only a single subject line, no body".to_string()),
+        }
+        if let Some(footer) = &self.issue_footer {
+            rules.push(format!(
+                "- End with a footer line in the format `{}`, replacing {} with the issue reference",
+                footer, ISSUE_PLACEHOLDER
+            ));
+        }
+
+        if rules.is_empty() {
+            return String::new();
+        }
+        format!("The commit message must follow these rules:\n{}", rules.join("\n"))
+    }
+
+    /// List the ways a message breaks the conventions
+    pub fn validate(&self, message: &str) -> Vec<String> {
+        let mut violations = Vec::new();
+        let message = message.trim();
+        let mut lines = message.lines();
+        let subject = lines.next().unwrap_or("").trim();
+
+        if subject.is_empty() {
+            violations.push("The message is empty".to_string());
+            return violations;
+        }
+
+        if let Some(max) = self.max_subject_length {
+            let length = subject.chars().count();
+            if length > max {
+                violations.push(format!("The first line is {} characters, more than {}", length, max));
+            }
+        }
+
+        if !self.types.is_empty() || !self.scopes.is_empty() {
+            let parsed = parse_conventional_commit(subject);
+            match &parsed.commit_type {
+                None => violations.push("The first line is not in the form `type(scope): description`".to_string()),
+                Some(commit_type) => {
+                    if !self.types.is_empty() && !self.types.contains(commit_type) {
+                        violations.push(format!(
+                            "The type `{}` is not one of: {}",
+                            commit_type,
+                            self.types.join(", ")
+                        ));
+                    }
+                    if let Some(scope) = &parsed.scope {
+                        if !self.scopes.is_empty() && !self.scopes.contains(scope) {
+                            violations.push(format!(
+                                "The scope `{}` is not one of: {}",
+                                scope,
+                                self.scopes.join(", ")
+                            ));
+                        }
+                    }
+                    if parsed.description.is_empty() {
+                        violations.push("The description is empty".to_string());
+                    }
+                }
+            }
+        }
+
+        // Everything after the subject, without the footer line
+        let mut rest: Vec<&str> = lines.map(|l| l.trim_end()).collect();
+        if self.issue_footer.is_some() {
+            match rest.iter().rposition(|l| !l.trim().is_empty()) {
+                Some(last) if self.matches_footer(rest[last].trim()) => rest.truncate(last),
+                _ => violations.push(format!(
+                    "The message does not end with a footer in the format `{}`",
+                    self.issue_footer.as_deref().unwrap_or_default()
+                )),
+            }
+        }
+        let has_body = rest.iter().any(|l| !l.trim().is_empty());
+
+        match self.body {
+            BodyRule::Optional => {}
+            BodyRule::Required if !has_body => violations.push("The message has no body".to_string()),
+            BodyRule::Forbidden if has_body => violations.push("The message must not have a body".to_string()),
+            _ => {}
+        }
+
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_types_scopes_and_length() {
+        let conventions = CommitConventions {
+            types: vec!["feat".to_string(), "fix".to_string()],
+            scopes: vec!["ui".to_string()],
+            max_subject_length: Some(20),
+            ..Default::default()
+        };
+
+        assert!(conventions.validate("fix(ui): button").is_empty());
+        assert!(conventions.validate("fix: button").is_empty());
+        assert_eq!(conventions.validate("chore(api): bump").len(), 2);
+        assert_eq!(conventions.validate("fix: a description that is too long").len(), 1);
+        assert_eq!(conventions.validate("Update stuff").len(), 1);
+    }
+
+    #[test]
+    fn test_default_conventions_accept_any_message() {
+        let conventions = CommitConventions::default();
+        assert!(conventions.instructions().is_empty());
+        assert!(conventions.validate(&format!("Update {}", "x".repeat(100))).is_empty());
+        assert_eq!(conventions.validate("  ").len(), 1);
+    }
+
+    #[test]
+    fn test_validate_body_and_footer() {
+        let mut conventions = CommitConventions {
+            body: BodyRule::Required,
+            issue_footer: Some("Refs: #{issue}".to_string()),
+            ..Default::default()
+        };
+
+        assert!(conventions.validate("fix: crash\n\nHandle empty lists.\n\nRefs: #42").is_empty());
+        assert_eq!(conventions.validate("fix: crash\n\nRefs: #42"), vec!["The message has no body"]);
+        assert_eq!(conventions.validate("fix: crash\n\nHandle empty lists.").len(), 1);
+
+        conventions.body = BodyRule::Forbidden;
+        assert!(conventions.validate("fix: crash\n\nRefs: #42").is_empty());
+        assert_eq!(conventions.validate("fix: crash\n\nHandle empty lists.\n\nRefs: #42").len(), 1);
+    }
+}
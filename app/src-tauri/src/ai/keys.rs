@@ -0,0 +1,42 @@
+//! Provider API keys in the system keyring
+//!
+//! Keys are never kept in [`AiConfig`](super::AiConfig) once saved: the
+//! config only records a reference to the keyring entry holding each key.
+
+use super::{AiError, AiResult};
+
+const KEYRING_SERVICE: &str = "linuxgit";
+
+/// Providers that authenticate with an API key
+pub const KEY_PROVIDERS: &[&str] = &["openai", "anthropic", "compatible"];
+
+/// Keyring username (the reference stored in the config) of a provider's key
+pub fn key_ref(provider: &str) -> String {
+    format!("ai_{}_api_key", provider)
+}
+
+fn entry(reference: &str) -> AiResult<keyring::Entry> {
+    keyring::Entry::new(KEYRING_SERVICE, reference)
+        .map_err(|e| AiError::ApiError(format!("Keyring error: {}", e)))
+}
+
+/// Store a provider's key, returning its reference
+pub fn store_api_key(provider: &str, key: &str) -> AiResult<String> {
+    let reference = key_ref(provider);
+    entry(&reference)?
+        .set_password(key)
+        .map_err(|e| AiError::ApiError(format!("Failed to store API key: {}", e)))?;
+    Ok(reference)
+}
+
+/// Read the key stored under a reference
+pub fn get_api_key(reference: &str) -> Option<String> {
+    entry(reference).ok()?.get_password().ok()
+}
+
+/// Delete a provider's stored key
+pub fn delete_api_key(provider: &str) -> AiResult<()> {
+    // Ignore error if the key doesn't exist
+    let _ = entry(&key_ref(provider))?.delete_password();
+    Ok(())
+}
@@ -1,8 +1,50 @@
+pub mod anthropic;
+pub mod chunking;
+pub mod conventions;
+pub mod keys;
 pub mod ollama;
 pub mod openai;
+pub mod prompts;
+pub mod split;
+
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use tokio::sync::Notify;
+
+use conventions::CommitConventions;
+use crate::git::{changelog, CommitInfo, DiffHunk, FileStatus};
+use split::CommitGroup;
+
+/// Event emitted for each token of a streamed commit message
+pub const COMMIT_MESSAGE_TOKEN_EVENT: &str = "ai-commit-message-token";
+
+/// Generation attempts before giving up on a message that breaks the conventions
+const MAX_CONVENTION_ATTEMPTS: usize = 3;
+
+/// Most commit message candidates generated at once
+pub const MAX_CANDIDATES: usize = 5;
+
+/// Temperature of the requests for alternative texts, so they differ from each other
+const ALTERNATIVES_TEMPERATURE: f32 = 0.8;
+
+/// Longest piece (in characters) of a large diff summarized in one request
+const MAX_CHUNK_CHARS: usize = 8000;
+
+/// Summary requests for the pieces of a large diff that run at once
+const MAX_CONCURRENT_SUMMARIES: usize = 4;
+
+/// Rounds of shortening the summaries of a large diff before they are truncated
+const MAX_REDUCE_ROUNDS: usize = 2;
+
+const COMMIT_SYSTEM_PROMPT: &str = "You are a helpful assistant that generates clear, concise Git commit messages. Respond with ONLY the commit message, no explanation or additional text.";
+const RELEASE_NOTES_SYSTEM_PROMPT: &str = "You are a helpful assistant that writes clear, concise release notes for software projects.";
+const SUMMARY_SYSTEM_PROMPT: &str = "You are a helpful assistant that summarizes code changes accurately and concisely.";
+const EXPLAIN_SYSTEM_PROMPT: &str = "You are a helpful assistant that explains code changes in plain language to developers who are new to a codebase.";
+const SPLIT_SYSTEM_PROMPT: &str = "You are a helpful assistant that organizes code changes into small, logical Git commits. Respond with ONLY JSON, no explanation or additional text.";
 
 #[derive(Error, Debug)]
 pub enum AiError {
@@ -17,6 +59,22 @@ pub enum AiError {
 
     #[error("Invalid response: {0}")]
     InvalidResponse(String),
+
+    #[error("Generation cancelled")]
+    Cancelled,
+
+    #[error("Request timed out")]
+    Timeout,
+
+    #[error("{0}")]
+    Settings(#[from] crate::settings::SettingsError),
+}
+
+impl AiError {
+    /// Whether the request may succeed if it is sent again
+    pub fn is_transient(&self) -> bool {
+        matches!(self, AiError::Timeout | AiError::RequestFailed(_))
+    }
 }
 
 impl Serialize for AiError {
@@ -35,6 +93,51 @@ pub type AiResult<T> = Result<T, AiError>;
 pub enum AiProvider {
     Ollama,
     OpenAI,
+    Anthropic,
+    /// Any server implementing the OpenAI chat completions API
+    Compatible,
+}
+
+/// Request settings of a provider
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderSettings {
+    /// Seconds before a request is abandoned
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Times a request that timed out or failed in transit is sent again
+    #[serde(default)]
+    pub retries: u32,
+    /// Overrides the temperature chosen for each kind of request
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    /// Overrides the token limit chosen for each kind of request
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+}
+
+fn default_timeout_secs() -> u64 {
+    60
+}
+
+impl Default for ProviderSettings {
+    fn default() -> Self {
+        Self {
+            timeout_secs: default_timeout_secs(),
+            retries: 1,
+            temperature: None,
+            max_tokens: None,
+        }
+    }
+}
+
+impl ProviderSettings {
+    /// Defaults for local models, which can take a while to load and answer
+    fn local() -> Self {
+        Self {
+            timeout_secs: 180,
+            ..Default::default()
+        }
+    }
 }
 
 /// Configuration for AI providers
@@ -43,8 +146,47 @@ pub struct AiConfig {
     pub provider: AiProvider,
     pub ollama_url: String,
     pub ollama_model: String,
+    /// Only set until the key is moved to the keyring
     pub openai_api_key: Option<String>,
+    /// Keyring entry holding the OpenAI key
+    #[serde(default)]
+    pub openai_api_key_ref: Option<String>,
     pub openai_model: String,
+    #[serde(default)]
+    pub anthropic_api_key: Option<String>,
+    #[serde(default)]
+    pub anthropic_api_key_ref: Option<String>,
+    #[serde(default = "default_anthropic_model")]
+    pub anthropic_model: String,
+    /// API root of the OpenAI-compatible server, e.g. `https://openrouter.ai/api/v1`
+    #[serde(default)]
+    pub compatible_base_url: String,
+    #[serde(default)]
+    pub compatible_api_key: Option<String>,
+    #[serde(default)]
+    pub compatible_api_key_ref: Option<String>,
+    #[serde(default)]
+    pub compatible_model: String,
+    /// Extra headers sent to the OpenAI-compatible server
+    #[serde(default)]
+    pub compatible_headers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub conventions: CommitConventions,
+    /// Language of generated text, e.g. `German` (English when unset)
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default = "ProviderSettings::local")]
+    pub ollama_settings: ProviderSettings,
+    #[serde(default)]
+    pub openai_settings: ProviderSettings,
+    #[serde(default)]
+    pub anthropic_settings: ProviderSettings,
+    #[serde(default)]
+    pub compatible_settings: ProviderSettings,
+}
+
+fn default_anthropic_model() -> String {
+    "claude-sonnet-4-5".to_string()
 }
 
 impl Default for AiConfig {
@@ -54,54 +196,759 @@ impl Default for AiConfig {
             ollama_url: "http://localhost:11434".to_string(),
             ollama_model: "codellama".to_string(),
             openai_api_key: None,
+            openai_api_key_ref: None,
             openai_model: "gpt-4".to_string(),
+            anthropic_api_key: None,
+            anthropic_api_key_ref: None,
+            anthropic_model: default_anthropic_model(),
+            compatible_base_url: String::new(),
+            compatible_api_key: None,
+            compatible_api_key_ref: None,
+            compatible_model: String::new(),
+            compatible_headers: BTreeMap::new(),
+            conventions: CommitConventions::default(),
+            language: None,
+            ollama_settings: ProviderSettings::local(),
+            openai_settings: ProviderSettings::default(),
+            anthropic_settings: ProviderSettings::default(),
+            compatible_settings: ProviderSettings::default(),
         }
     }
 }
 
-/// Generate a commit message from a diff using the configured AI provider
-pub async fn generate_commit_message(diff: &str, config: &AiConfig) -> AiResult<String> {
-    let prompt = create_commit_prompt(diff);
+/// A key set in memory, else the one in the keyring
+fn resolve_key(key: &Option<String>, reference: &Option<String>, provider: &str) -> Option<String> {
+    key.clone().filter(|k| !k.is_empty()).or_else(|| {
+        let reference = reference.clone().unwrap_or_else(|| keys::key_ref(provider));
+        keys::get_api_key(&reference)
+    })
+}
+
+impl AiConfig {
+    fn key_fields(&mut self) -> [(&'static str, &mut Option<String>, &mut Option<String>); 3] {
+        [
+            ("openai", &mut self.openai_api_key, &mut self.openai_api_key_ref),
+            ("anthropic", &mut self.anthropic_api_key, &mut self.anthropic_api_key_ref),
+            ("compatible", &mut self.compatible_api_key, &mut self.compatible_api_key_ref),
+        ]
+    }
+
+    /// Move API keys into the keyring, keeping only references to them
+    pub fn secure_api_keys(&mut self) -> AiResult<()> {
+        for (provider, key, reference) in self.key_fields() {
+            if let Some(value) = key.take().filter(|k| !k.trim().is_empty()) {
+                *reference = Some(keys::store_api_key(provider, value.trim())?);
+            }
+        }
+        Ok(())
+    }
+
+    /// Record references to keys already in the keyring
+    pub fn load_key_refs(&mut self) {
+        for (provider, key, reference) in self.key_fields() {
+            if key.is_none() && reference.is_none() {
+                let candidate = keys::key_ref(provider);
+                if keys::get_api_key(&candidate).is_some() {
+                    *reference = Some(candidate);
+                }
+            }
+        }
+    }
+
+    /// The OpenAI API key
+    pub fn openai_key(&self) -> AiResult<String> {
+        resolve_key(&self.openai_api_key, &self.openai_api_key_ref, "openai")
+            .ok_or_else(|| AiError::ProviderNotAvailable("OpenAI API key not configured".to_string()))
+    }
+
+    /// The Anthropic API key
+    pub fn anthropic_key(&self) -> AiResult<String> {
+        resolve_key(&self.anthropic_api_key, &self.anthropic_api_key_ref, "anthropic")
+            .ok_or_else(|| AiError::ProviderNotAvailable("Anthropic API key not configured".to_string()))
+    }
+
+    /// Request settings of the selected provider
+    pub fn provider_settings(&self) -> &ProviderSettings {
+        match self.provider {
+            AiProvider::Ollama => &self.ollama_settings,
+            AiProvider::OpenAI => &self.openai_settings,
+            AiProvider::Anthropic => &self.anthropic_settings,
+            AiProvider::Compatible => &self.compatible_settings,
+        }
+    }
+
+    /// The configured OpenAI-compatible endpoint
+    pub fn compatible_endpoint(&self) -> AiResult<openai::Endpoint> {
+        if self.compatible_base_url.trim().is_empty() {
+            return Err(AiError::ProviderNotAvailable(
+                "OpenAI-compatible endpoint URL not configured".to_string(),
+            ));
+        }
+        Ok(openai::Endpoint {
+            name: self.compatible_base_url.clone(),
+            base_url: self.compatible_base_url.clone(),
+            api_key: resolve_key(&self.compatible_api_key, &self.compatible_api_key_ref, "compatible"),
+            headers: self.compatible_headers.clone(),
+        })
+    }
+}
 
+/// System prompt and sampling settings sent along with a prompt
+#[derive(Debug, Clone)]
+pub struct GenerationParams {
+    pub system_prompt: String,
+    pub temperature: f32,
+    pub max_tokens: u32,
+    pub timeout_secs: u64,
+}
+
+impl GenerationParams {
+    fn new(system_prompt: &str, max_tokens: u32) -> Self {
+        Self {
+            system_prompt: system_prompt.to_string(),
+            temperature: 0.3, // Lower temperature for more consistent output
+            max_tokens,
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+
+    /// Apply a provider's configured overrides
+    fn with_settings(mut self, settings: &ProviderSettings) -> Self {
+        self.temperature = settings.temperature.unwrap_or(self.temperature);
+        self.max_tokens = settings.max_tokens.unwrap_or(self.max_tokens);
+        self.timeout_secs = settings.timeout_secs;
+        self
+    }
+
+    /// Ask for answers in a language other than English
+    fn with_language(mut self, language: Option<&str>) -> Self {
+        if let Some(language) = language.map(str::trim).filter(|l| !l.is_empty()) {
+            self.system_prompt.push_str(&format!(" Write your answer in {}.", language));
+        }
+        self
+    }
+
+    /// Settings for a request to the configured provider
+    fn for_config(&self, config: &AiConfig) -> Self {
+        self.clone()
+            .with_settings(config.provider_settings())
+            .with_language(config.language.as_deref())
+    }
+
+    /// Settings for commit messages
+    pub fn commit_message() -> Self {
+        Self::new(COMMIT_SYSTEM_PROMPT, 300)
+    }
+
+    /// Settings for release notes
+    pub fn release_notes() -> Self {
+        Self::new(RELEASE_NOTES_SYSTEM_PROMPT, 500)
+    }
+
+    /// Settings for summaries of parts of a diff
+    pub fn diff_summary() -> Self {
+        Self::new(SUMMARY_SYSTEM_PROMPT, 400)
+    }
+
+    /// Settings for explanations of changes
+    pub fn explanation() -> Self {
+        Self::new(EXPLAIN_SYSTEM_PROMPT, 1000)
+    }
+
+    /// Settings for proposals to split changes into several commits
+    pub fn commit_split() -> Self {
+        Self::new(SPLIT_SYSTEM_PROMPT, 1500)
+    }
+}
+
+/// Run a request, sending it again after transient failures as configured
+async fn with_retries<T, F, Fut>(settings: &ProviderSettings, request: F) -> AiResult<T>
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = AiResult<T>>,
+{
+    let mut attempt = 0;
+    loop {
+        match request().await {
+            Err(e) if e.is_transient() && attempt < settings.retries => {
+                attempt += 1;
+                tracing::warn!("AI request failed ({}), retrying ({}/{})", e, attempt, settings.retries);
+                tokio::time::sleep(std::time::Duration::from_secs(1 << attempt.min(5))).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Generate text from a prompt using the configured AI provider
+async fn generate_text(prompt: &str, params: &GenerationParams, config: &AiConfig) -> AiResult<String> {
+    let settings = config.provider_settings();
+    let params = params.for_config(config);
+    with_retries(settings, || send_text(prompt, &params, config)).await
+}
+
+/// Send a single generation request to the configured AI provider
+async fn send_text(prompt: &str, params: &GenerationParams, config: &AiConfig) -> AiResult<String> {
     match config.provider {
         AiProvider::Ollama => {
-            ollama::generate(&config.ollama_url, &config.ollama_model, &prompt).await
+            ollama::generate(&config.ollama_url, &config.ollama_model, prompt, params).await
         }
         AiProvider::OpenAI => {
-            let api_key = config
-                .openai_api_key
-                .as_ref()
-                .ok_or_else(|| AiError::ProviderNotAvailable("OpenAI API key not configured".to_string()))?;
-            openai::generate(api_key, &config.openai_model, &prompt).await
+            let api_key = &config.openai_key()?;
+            openai::generate(api_key, &config.openai_model, prompt, params).await
+        }
+        AiProvider::Anthropic => {
+            let api_key = &config.anthropic_key()?;
+            anthropic::generate(api_key, &config.anthropic_model, prompt, params).await
+        }
+        AiProvider::Compatible => {
+            let endpoint = config.compatible_endpoint()?;
+            openai::generate_at(&endpoint, &config.compatible_model, prompt, params).await
         }
     }
 }
 
-/// Creates a prompt for generating commit messages
-fn create_commit_prompt(diff: &str) -> String {
-    format!(
-        r#"You are a helpful assistant that generates clear, concise Git commit messages.
+/// Cancellation of a streaming generation
+///
+/// Each generation gets a new number, sent with its events so that late events
+/// of a replaced generation can be told apart from those of the current one.
+#[derive(Debug)]
+pub struct GenerationCancel {
+    generation: u64,
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl GenerationCancel {
+    /// Start a new generation
+    pub fn next() -> Self {
+        static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
+        Self {
+            generation: NEXT_GENERATION.fetch_add(1, Ordering::Relaxed),
+            cancelled: AtomicBool::new(false),
+            notify: Notify::new(),
+        }
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Cancel the generation, waking a pending read
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+        self.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Wait until the generation is cancelled
+    pub async fn cancelled(&self) {
+        let notified = self.notify.notified();
+        if self.is_cancelled() {
+            return;
+        }
+        notified.await;
+    }
+}
+
+/// Payload of the commit message token event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitMessageToken {
+    /// Generation the token belongs to; a newer generation replaces older ones
+    pub generation: u64,
+    pub token: String,
+    pub done: bool,
+    /// The text streamed so far was rejected and a new message follows
+    #[serde(default)]
+    pub reset: bool,
+}
+
+/// Generate text from a prompt, calling `on_token` with each piece as it arrives
+///
+/// Failures are only retried while no text has arrived yet.
+async fn generate_text_stream(
+    prompt: &str,
+    params: &GenerationParams,
+    config: &AiConfig,
+    cancel: &GenerationCancel,
+    mut on_token: impl FnMut(&str),
+) -> AiResult<String> {
+    let settings = config.provider_settings();
+    let params = params.for_config(config);
+    let mut attempt = 0;
+    loop {
+        let mut received = false;
+        let result = send_text_stream(prompt, &params, config, cancel, |token| {
+            received = true;
+            on_token(token);
+        })
+        .await;
+
+        match result {
+            Err(e) if e.is_transient() && !received && attempt < settings.retries => {
+                attempt += 1;
+                tracing::warn!("AI request failed ({}), retrying ({}/{})", e, attempt, settings.retries);
+                tokio::select! {
+                    _ = tokio::time::sleep(std::time::Duration::from_secs(1 << attempt.min(5))) => {}
+                    _ = cancel.cancelled() => return Err(AiError::Cancelled),
+                }
+            }
+            result => return result,
+        }
+    }
+}
 
-Based on the following diff, generate a commit message following the Conventional Commits specification:
-- Use format: <type>(<scope>): <description>
-- Types: feat, fix, docs, style, refactor, test, chore
-- Keep the description under 72 characters
-- Focus on WHAT changed and WHY, not HOW
+/// Send a single streaming generation request to the configured AI provider
+async fn send_text_stream(
+    prompt: &str,
+    params: &GenerationParams,
+    config: &AiConfig,
+    cancel: &GenerationCancel,
+    on_token: impl FnMut(&str),
+) -> AiResult<String> {
+    match config.provider {
+        AiProvider::Ollama => {
+            let (url, model) = (&config.ollama_url, &config.ollama_model);
+            ollama::generate_stream(url, model, prompt, params, cancel, on_token).await
+        }
+        AiProvider::OpenAI => {
+            let api_key = &config.openai_key()?;
+            openai::generate_stream(api_key, &config.openai_model, prompt, params, cancel, on_token).await
+        }
+        AiProvider::Anthropic => {
+            let api_key = &config.anthropic_key()?;
+            let model = &config.anthropic_model;
+            anthropic::generate_stream(api_key, model, prompt, params, cancel, on_token).await
+        }
+        AiProvider::Compatible => {
+            let endpoint = config.compatible_endpoint()?;
+            let model = &config.compatible_model;
+            openai::generate_stream_at(&endpoint, model, prompt, params, cancel, on_token).await
+        }
+    }
+}
 
-Diff:
-```
-{}
-```
+/// Add the conventions to a rendered prompt
+fn with_conventions(prompt: &str, conventions: &CommitConventions) -> String {
+    let instructions = conventions.instructions();
+    if instructions.is_empty() {
+        prompt.to_string()
+    } else {
+        format!("{}\n\n{}", prompt, instructions)
+    }
+}
 
-Generate ONLY the commit message, no explanation:"#,
-        diff.chars().take(4000).collect::<String>() // Truncate very long diffs
+/// Ask again after a message broke the conventions
+fn retry_prompt(prompt: &str, message: &str, violations: &[String]) -> String {
+    let violations: Vec<String> = violations.iter().map(|v| format!("- {}", v)).collect();
+    format!(
+        "{}\n\nYour previous answer was:\n```\n{}\n```\nIt breaks these rules:\n{}\n\
+         Write a corrected commit message.",
+        prompt,
+        message,
+        violations.join("\n")
     )
 }
 
+/// Trim generated text down to the commit message
+fn clean_message(text: &str, conventions: &CommitConventions) -> String {
+    let text = text.trim();
+    if conventions.keeps_body() {
+        text.to_string()
+    } else {
+        text.lines().next().unwrap_or("").to_string()
+    }
+}
+
+fn conventions_error(violations: &[String]) -> AiError {
+    AiError::InvalidResponse(format!(
+        "Generated message does not follow the commit conventions: {}",
+        violations.join("; ")
+    ))
+}
+
+/// Generate a commit message from a rendered prompt using the configured AI provider
+///
+/// Messages that break the configured conventions are regenerated a few times
+/// before giving up.
+pub async fn generate_commit_message(prompt: &str, config: &AiConfig) -> AiResult<String> {
+    let conventions = &config.conventions;
+    let params = GenerationParams::commit_message();
+    let prompt = with_conventions(prompt, conventions);
+    let mut attempt_prompt = prompt.clone();
+    let mut violations = Vec::new();
+
+    for _ in 0..MAX_CONVENTION_ATTEMPTS {
+        let text = generate_text(&attempt_prompt, &params, config).await?;
+        let message = clean_message(&text, conventions);
+        violations = conventions.validate(&message);
+        if violations.is_empty() {
+            return Ok(message);
+        }
+        attempt_prompt = retry_prompt(&prompt, &message, &violations);
+    }
+
+    Err(conventions_error(&violations))
+}
+
+/// Improve a previously generated commit message according to the user's feedback
+pub async fn refine_commit_message(
+    prompt: &str,
+    previous: &str,
+    feedback: &str,
+    config: &AiConfig,
+) -> AiResult<String> {
+    let prompt = prompts::render_refine_prompt(prompt, previous, feedback);
+    generate_commit_message(&prompt, config).await
+}
+
+/// Generate markdown release notes for a list of commits
+///
+/// Commits are grouped by their Conventional Commits type, and the configured
+/// provider writes a short summary that precedes the groups.
+pub async fn generate_changelog(commits: &[CommitInfo], config: &AiConfig) -> AiResult<String> {
+    let notes = changelog::draft_release_notes(commits);
+    if notes.is_empty() {
+        return Ok(notes);
+    }
+
+    let prompt = prompts::render_changelog_prompt(commits);
+    let summary = generate_text(&prompt, &GenerationParams::release_notes(), config).await?;
+    let summary = summary.trim();
+    if summary.is_empty() {
+        return Ok(notes);
+    }
+    Ok(format!("## Summary\n\n{}\n\n{}", summary, notes))
+}
+
+/// Summarize each prompt with the configured provider, a few at a time,
+/// returning the summaries in order
+async fn summarize_all(prompts: Vec<String>, config: &AiConfig) -> AiResult<Vec<String>> {
+    let permits = Arc::new(tokio::sync::Semaphore::new(MAX_CONCURRENT_SUMMARIES));
+    let mut tasks = tokio::task::JoinSet::new();
+
+    for (index, prompt) in prompts.into_iter().enumerate() {
+        let permits = permits.clone();
+        let config = config.clone();
+        tasks.spawn(async move {
+            let _permit = permits.acquire_owned().await;
+            let summary = generate_text(&prompt, &GenerationParams::diff_summary(), &config).await;
+            (index, summary)
+        });
+    }
+
+    let mut summaries = Vec::new();
+    while let Some(result) = tasks.join_next().await {
+        let (index, summary) = result.map_err(|e| AiError::ApiError(format!("Summary task failed: {}", e)))?;
+        summaries.push((index, summary?));
+    }
+    summaries.sort_by_key(|(index, _)| *index);
+    Ok(summaries.into_iter().map(|(_, summary)| summary.trim().to_string()).collect())
+}
+
+/// Fit a diff into a commit prompt
+///
+/// Diffs that are too long are split into pieces that are summarized
+/// separately, and the summaries are merged until they fit, so the whole
+/// change is described rather than only its beginning.
+pub async fn condense_diff(diff: &str, config: &AiConfig) -> AiResult<String> {
+    if diff.chars().count() <= prompts::MAX_DIFF_CHARS {
+        return Ok(diff.to_string());
+    }
+
+    let chunk_prompts = chunking::chunk_diff(diff, MAX_CHUNK_CHARS)
+        .iter()
+        .map(|chunk| prompts::render_diff_chunk_prompt(chunk))
+        .collect();
+    let mut summary = summarize_all(chunk_prompts, config).await?.join("\n");
+
+    for _ in 0..MAX_REDUCE_ROUNDS {
+        if summary.chars().count() <= prompts::MAX_DIFF_CHARS {
+            break;
+        }
+        let reduce_prompts = chunking::chunk_lines(&summary, MAX_CHUNK_CHARS)
+            .iter()
+            .map(|part| prompts::render_summary_reduce_prompt(part))
+            .collect();
+        summary = summarize_all(reduce_prompts, config).await?.join("\n");
+    }
+
+    Ok(format!(
+        "(The diff is too large to show; these are summaries of the changes to each file.)\n{}",
+        summary
+    ))
+}
+
+/// Explain in plain language what a commit changed and why it might matter
+pub async fn explain_commit(commit: &CommitInfo, diff: &str, config: &AiConfig) -> AiResult<String> {
+    let prompt = prompts::render_explain_commit_prompt(commit, diff);
+    generate_text(&prompt, &GenerationParams::explanation(), config).await
+}
+
+/// Explain in plain language what a diff hunk of `path` changes
+pub async fn explain_hunk(path: &str, hunk: &DiffHunk, config: &AiConfig) -> AiResult<String> {
+    let prompt = prompts::render_explain_hunk_prompt(path, hunk);
+    generate_text(&prompt, &GenerationParams::explanation(), config).await
+}
+
+/// Propose how to split the staged changes into several commits
+///
+/// `staged` lists the staged files and `diff` is their patch. Every staged file
+/// is put in exactly one group, and answers that are not valid JSON or whose
+/// messages break the configured conventions are regenerated a few times.
+pub async fn suggest_commit_split(
+    diff: &str,
+    staged: &[FileStatus],
+    config: &AiConfig,
+) -> AiResult<Vec<CommitGroup>> {
+    let conventions = &config.conventions;
+    let params = GenerationParams::commit_split();
+    let diff = condense_diff(diff, config).await?;
+    let files: Vec<&str> = staged.iter().map(|f| f.path.as_str()).collect();
+    let prompt = with_conventions(&prompts::render_split_prompt(&files, &diff), conventions);
+    let mut attempt_prompt = prompt.clone();
+    let mut problems = Vec::new();
+
+    for _ in 0..MAX_CONVENTION_ATTEMPTS {
+        let text = generate_text(&attempt_prompt, &params, config).await?;
+        problems = match split::parse_groups(&text) {
+            Ok(groups) => {
+                let mut groups = split::normalize_groups(groups, staged);
+                for group in &mut groups {
+                    group.message = clean_message(&group.message, conventions);
+                }
+                let problems: Vec<String> = groups
+                    .iter()
+                    .flat_map(|group| {
+                        conventions
+                            .validate(&group.message)
+                            .into_iter()
+                            .map(move |v| format!("{} (message for {})", v, group.files.join(", ")))
+                    })
+                    .collect();
+                if problems.is_empty() {
+                    return Ok(groups);
+                }
+                problems
+            }
+            Err(problem) => vec![problem],
+        };
+        let listed: Vec<String> = problems.iter().map(|p| format!("- {}", p)).collect();
+        attempt_prompt = format!(
+            "{}\n\nYour previous answer was:\n```\n{}\n```\nIt has these problems:\n{}\n\
+             Write a corrected JSON array.",
+            prompt,
+            text.trim(),
+            listed.join("\n")
+        );
+    }
+
+    Err(AiError::InvalidResponse(format!(
+        "Could not split the changes into commits: {}",
+        problems.join("; ")
+    )))
+}
+
+/// Generate `count` alternative texts from a prompt, in a single request where
+/// the provider supports it
+async fn generate_texts(
+    prompt: &str,
+    params: &GenerationParams,
+    config: &AiConfig,
+    count: usize,
+) -> AiResult<Vec<String>> {
+    let settings = config.provider_settings();
+    let mut many_params = params.for_config(config);
+    many_params.temperature = ALTERNATIVES_TEMPERATURE;
+    let endpoint = match config.provider {
+        AiProvider::OpenAI => {
+            let api_key = &config.openai_key()?;
+            Some((openai::Endpoint::openai(api_key), &config.openai_model))
+        }
+        AiProvider::Compatible => Some((config.compatible_endpoint()?, &config.compatible_model)),
+        AiProvider::Ollama | AiProvider::Anthropic => None,
+    };
+    let mut texts = match &endpoint {
+        Some((endpoint, model)) => {
+            with_retries(settings, || {
+                openai::generate_many_at(endpoint, model, prompt, &many_params, count as u32)
+            })
+            .await?
+        }
+        None => Vec::new(),
+    };
+
+    // Request the rest one at a time, concurrently
+    let mut tasks = tokio::task::JoinSet::new();
+    for _ in texts.len()..count {
+        let prompt = prompt.to_string();
+        let params = many_params.clone();
+        let config = config.clone();
+        tasks.spawn(async move {
+            with_retries(config.provider_settings(), || send_text(&prompt, &params, &config)).await
+        });
+    }
+    while let Some(result) = tasks.join_next().await {
+        let text = result.map_err(|e| AiError::ApiError(format!("Generation task failed: {}", e)))??;
+        texts.push(text);
+    }
+
+    Ok(texts)
+}
+
+/// Clean up generated commit messages, dropping those that break the
+/// conventions or repeat an earlier one
+///
+/// An empty result means no candidate was usable.
+fn select_candidates(texts: Vec<String>, conventions: &CommitConventions) -> Vec<String> {
+    let mut candidates: Vec<String> = Vec::new();
+    for text in texts {
+        let message = clean_message(&text, conventions);
+        if conventions.validate(&message).is_empty() && !candidates.contains(&message) {
+            candidates.push(message);
+        }
+    }
+    candidates
+}
+
+/// Generate up to `count` alternative commit messages from a rendered prompt
+///
+/// Candidates that break the configured conventions or repeat another are
+/// dropped; if none are left a single message is generated with retries.
+pub async fn generate_commit_message_candidates(
+    prompt: &str,
+    config: &AiConfig,
+    count: usize,
+) -> AiResult<Vec<String>> {
+    let count = count.clamp(1, MAX_CANDIDATES);
+    let conventions = &config.conventions;
+    let prompt_with_conventions = with_conventions(prompt, conventions);
+    let params = GenerationParams::commit_message();
+    let texts = generate_texts(&prompt_with_conventions, &params, config, count).await?;
+
+    let mut candidates = select_candidates(texts, conventions);
+    if candidates.is_empty() {
+        candidates.push(generate_commit_message(prompt, config).await?);
+    }
+    Ok(candidates)
+}
+
+/// Generate a commit message from a rendered prompt, calling `on_token` with each
+/// piece of text as it arrives
+///
+/// When a message breaks the configured conventions `on_retry` is called with the
+/// violations and a new message is streamed. Setting `cancel` stops the
+/// generation with [`AiError::Cancelled`].
+pub async fn generate_commit_message_stream(
+    prompt: &str,
+    config: &AiConfig,
+    cancel: &GenerationCancel,
+    mut on_token: impl FnMut(&str),
+    mut on_retry: impl FnMut(&[String]),
+) -> AiResult<String> {
+    let conventions = &config.conventions;
+    let params = GenerationParams::commit_message();
+    let prompt = with_conventions(prompt, conventions);
+    let mut attempt_prompt = prompt.clone();
+    let mut violations = Vec::new();
+
+    for attempt in 0..MAX_CONVENTION_ATTEMPTS {
+        if attempt > 0 {
+            on_retry(&violations);
+        }
+        let text = generate_text_stream(&attempt_prompt, &params, config, cancel, &mut on_token).await?;
+        let message = clean_message(&text, conventions);
+        violations = conventions.validate(&message);
+        if violations.is_empty() {
+            return Ok(message);
+        }
+        attempt_prompt = retry_prompt(&prompt, &message, &violations);
+    }
+
+    Err(conventions_error(&violations))
+}
+
+/// Read a streamed response body line by line until it ends or `cancel` is set
+pub(crate) async fn read_lines(
+    mut response: reqwest::Response,
+    cancel: &GenerationCancel,
+    mut on_line: impl FnMut(&str) -> AiResult<bool>,
+) -> AiResult<()> {
+    let mut buffer: Vec<u8> = Vec::new();
+
+    loop {
+        // A stalled stream must not delay the cancellation
+        let chunk = tokio::select! {
+            chunk = response.chunk() => chunk?,
+            _ = cancel.cancelled() => return Err(AiError::Cancelled),
+        };
+        let Some(chunk) = chunk else {
+            break;
+        };
+
+        buffer.extend_from_slice(&chunk);
+        while let Some(pos) = buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim();
+            if !line.is_empty() && !on_line(line)? {
+                return Ok(());
+            }
+        }
+    }
+
+    let rest = String::from_utf8_lossy(&buffer);
+    if !rest.trim().is_empty() {
+        on_line(rest.trim())?;
+    }
+    Ok(())
+}
+
+/// Creates a prompt for a diff from the built-in template
+pub fn create_commit_prompt(diff: &str) -> String {
+    let context = prompts::PromptContext {
+        diff: diff.to_string(),
+        ..Default::default()
+    };
+    prompts::render_commit_prompt(prompts::DEFAULT_COMMIT_TEMPLATE, &context)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_read_lines_cancels_stalled_stream() {
+        use tokio::io::AsyncWriteExt;
+
+        // A server that sends one line and then stalls without closing the stream
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let _ = socket
+                .write_all(b"HTTP/1.1 200 OK\r\ntransfer-encoding: chunked\r\n\r\n6\r\nfirst\n\r\n")
+                .await;
+            tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+        });
+
+        let response = reqwest::get(format!("http://{}", addr)).await.unwrap();
+        let cancel = Arc::new(GenerationCancel::next());
+        let mut lines = Vec::new();
+        let read = read_lines(response, &cancel, |line| {
+            lines.push(line.to_string());
+            cancel.cancel();
+            Ok(true)
+        });
+
+        let result = tokio::time::timeout(std::time::Duration::from_secs(5), read).await;
+        assert!(matches!(result, Ok(Err(AiError::Cancelled))));
+        assert_eq!(lines, vec!["first"]);
+    }
+
     #[test]
     fn test_create_commit_prompt() {
         let diff = "+ added new function";
@@ -109,4 +956,52 @@ mod tests {
         assert!(prompt.contains("+ added new function"));
         assert!(prompt.contains("Conventional Commits"));
     }
+
+    #[test]
+    fn test_select_candidates() {
+        let conventions = CommitConventions {
+            types: vec!["feat".to_string(), "fix".to_string()],
+            ..Default::default()
+        };
+        let texts = vec![
+            " feat: add login\n\nLonger explanation".to_string(),
+            "feat: add login".to_string(),
+            "chore: bump version".to_string(),
+            "fix: handle empty input".to_string(),
+        ];
+        assert_eq!(
+            select_candidates(texts, &conventions),
+            vec!["feat: add login".to_string(), "fix: handle empty input".to_string()]
+        );
+
+        let rejected = vec!["chore: bump version".to_string(), String::new()];
+        assert!(select_candidates(rejected, &conventions).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_key_prefers_key_in_memory() {
+        let key = Some("sk-test".to_string());
+        assert_eq!(resolve_key(&key, &None, "openai").as_deref(), Some("sk-test"));
+    }
+
+    #[test]
+    fn test_provider_settings_defaults_and_overrides() {
+        let config: AiConfig = serde_json::from_value(serde_json::json!({
+            "provider": "ollama",
+            "ollama_url": "http://localhost:11434",
+            "ollama_model": "codellama",
+            "openai_api_key": null,
+            "openai_model": "gpt-4",
+            "openai_settings": { "retries": 3, "temperature": 0.7 }
+        }))
+        .unwrap();
+
+        assert_eq!(config.provider_settings().timeout_secs, 180);
+        assert_eq!(config.openai_settings.timeout_secs, 60);
+
+        let params = GenerationParams::commit_message().with_settings(&config.openai_settings);
+        assert_eq!(params.temperature, 0.7);
+        assert_eq!(params.max_tokens, 300);
+        assert_eq!(params.timeout_secs, 60);
+    }
 }
@@ -1,13 +1,35 @@
+
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
-use super::{AiError, AiResult};
+use super::{AiError, AiResult, GenerationParams};
 
 #[derive(Debug, Serialize)]
 struct OllamaRequest {
     model: String,
     prompt: String,
+    system: String,
     stream: bool,
+    options: OllamaOptions,
+}
+
+#[derive(Debug, Serialize)]
+struct OllamaOptions {
+    temperature: f32,
+    num_predict: u32,
+}
+
+fn ollama_request(model: &str, prompt: &str, params: &GenerationParams, stream: bool) -> OllamaRequest {
+    OllamaRequest {
+        model: model.to_string(),
+        prompt: prompt.to_string(),
+        system: params.system_prompt.clone(),
+        stream,
+        options: OllamaOptions {
+            temperature: params.temperature,
+            num_predict: params.max_tokens,
+        },
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -15,6 +37,36 @@ struct OllamaResponse {
     response: String,
 }
 
+/// One line of a streamed Ollama response
+#[derive(Debug, Deserialize)]
+struct OllamaStreamChunk {
+    #[serde(default)]
+    response: String,
+    #[serde(default)]
+    done: bool,
+    error: Option<String>,
+}
+
+/// Parse a streamed line into its text and whether it is the last one
+fn parse_stream_line(line: &str) -> AiResult<(String, bool)> {
+    let chunk: OllamaStreamChunk = serde_json::from_str(line)
+        .map_err(|e| AiError::InvalidResponse(e.to_string()))?;
+    if let Some(error) = chunk.error {
+        return Err(AiError::ApiError(error));
+    }
+    Ok((chunk.response, chunk.done))
+}
+
+fn map_send_error(e: reqwest::Error) -> AiError {
+    if e.is_connect() {
+        AiError::ProviderNotAvailable("Ollama is not running. Start it with 'ollama serve'".to_string())
+    } else if e.is_timeout() {
+        AiError::Timeout
+    } else {
+        AiError::RequestFailed(e)
+    }
+}
+
 /// Check if Ollama is available
 pub async fn is_available(base_url: &str) -> bool {
     let client = Client::new();
@@ -52,30 +104,23 @@ pub async fn list_models(base_url: &str) -> AiResult<Vec<String>> {
 }
 
 /// Generate text using Ollama
-pub async fn generate(base_url: &str, model: &str, prompt: &str) -> AiResult<String> {
+pub async fn generate(
+    base_url: &str,
+    model: &str,
+    prompt: &str,
+    params: &GenerationParams,
+) -> AiResult<String> {
     let client = Client::new();
 
-    let request = OllamaRequest {
-        model: model.to_string(),
-        prompt: prompt.to_string(),
-        stream: false,
-    };
+    let request = ollama_request(model, prompt, params, false);
 
     let response = client
         .post(format!("{}/api/generate", base_url))
         .json(&request)
-        .timeout(std::time::Duration::from_secs(60))
+        .timeout(std::time::Duration::from_secs(params.timeout_secs))
         .send()
         .await
-        .map_err(|e| {
-            if e.is_connect() {
-                AiError::ProviderNotAvailable("Ollama is not running. Start it with 'ollama serve'".to_string())
-            } else if e.is_timeout() {
-                AiError::ApiError("Request timed out".to_string())
-            } else {
-                AiError::RequestFailed(e)
-            }
-        })?;
+        .map_err(map_send_error)?;
 
     if !response.status().is_success() {
         let status = response.status();
@@ -91,16 +136,51 @@ pub async fn generate(base_url: &str, model: &str, prompt: &str) -> AiResult<Str
         .await
         .map_err(|e| AiError::InvalidResponse(e.to_string()))?;
 
-    // Clean up the response (remove extra whitespace, etc.)
-    let message = ollama_response
-        .response
-        .trim()
-        .lines()
-        .next()
-        .unwrap_or("")
-        .to_string();
+    Ok(ollama_response.response.trim().to_string())
+}
 
-    Ok(message)
+/// Generate text using Ollama, streaming tokens to `on_token` as they are produced
+pub async fn generate_stream(
+    base_url: &str,
+    model: &str,
+    prompt: &str,
+    params: &GenerationParams,
+    cancel: &super::GenerationCancel,
+    mut on_token: impl FnMut(&str),
+) -> AiResult<String> {
+    let client = Client::new();
+
+    let request = ollama_request(model, prompt, params, true);
+
+    let response = client
+        .post(format!("{}/api/generate", base_url))
+        .json(&request)
+        .timeout(std::time::Duration::from_secs(params.timeout_secs))
+        .send()
+        .await
+        .map_err(map_send_error)?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AiError::ApiError(format!(
+            "Ollama returned {}: {}",
+            status, error_text
+        )));
+    }
+
+    let mut text = String::new();
+    super::read_lines(response, cancel, |line| {
+        let (token, done) = parse_stream_line(line)?;
+        if !token.is_empty() {
+            on_token(&token);
+            text.push_str(&token);
+        }
+        Ok(!done)
+    })
+    .await?;
+
+    Ok(text)
 }
 
 #[cfg(test)]
@@ -114,4 +194,17 @@ mod tests {
         // Don't assert - it depends on whether Ollama is running
         println!("Ollama available: {}", result);
     }
+
+    #[test]
+    fn test_parse_stream_line() {
+        let (token, done) = parse_stream_line(r#"{"model":"m","response":"feat","done":false}"#).unwrap();
+        assert_eq!(token, "feat");
+        assert!(!done);
+
+        let (token, done) = parse_stream_line(r#"{"model":"m","response":"","done":true}"#).unwrap();
+        assert_eq!(token, "");
+        assert!(done);
+
+        assert!(parse_stream_line(r#"{"error":"model not found"}"#).is_err());
+    }
 }
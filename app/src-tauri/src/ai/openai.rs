@@ -1,9 +1,11 @@
+use std::collections::BTreeMap;
+
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
-use super::{AiError, AiResult};
+use super::{AiError, AiResult, GenerationParams};
 
-const OPENAI_API_URL: &str = "https://api.openai.com/v1/chat/completions";
+const OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
 
 #[derive(Debug, Serialize)]
 struct ChatRequest {
@@ -11,6 +13,11 @@ struct ChatRequest {
     messages: Vec<Message>,
     temperature: f32,
     max_tokens: u32,
+    /// Number of completions to generate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    n: Option<u32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    stream: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -29,13 +36,146 @@ struct Choice {
     message: Message,
 }
 
+/// One server-sent event of a streamed chat completion
+#[derive(Debug, Deserialize)]
+struct ChatStreamChunk {
+    choices: Vec<StreamChoice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamChoice {
+    delta: Delta,
+}
+
+#[derive(Debug, Deserialize)]
+struct Delta {
+    content: Option<String>,
+}
+
+fn chat_request(model: &str, prompt: &str, params: &GenerationParams, stream: bool) -> ChatRequest {
+    ChatRequest {
+        model: model.to_string(),
+        messages: vec![
+            Message {
+                role: "system".to_string(),
+                content: params.system_prompt.clone(),
+            },
+            Message {
+                role: "user".to_string(),
+                content: prompt.to_string(),
+            },
+        ],
+        temperature: params.temperature,
+        max_tokens: params.max_tokens,
+        n: None,
+        stream,
+    }
+}
+
+/// Parse a server-sent event line; `None` marks the end of the stream
+fn parse_stream_line(line: &str) -> AiResult<Option<String>> {
+    let Some(data) = line.strip_prefix("data:") else {
+        // Comments and other SSE fields carry no content
+        return Ok(Some(String::new()));
+    };
+    let data = data.trim();
+    if data == "[DONE]" {
+        return Ok(None);
+    }
+
+    let chunk: ChatStreamChunk = serde_json::from_str(data)
+        .map_err(|e| AiError::InvalidResponse(e.to_string()))?;
+    Ok(Some(
+        chunk
+            .choices
+            .into_iter()
+            .filter_map(|c| c.delta.content)
+            .collect(),
+    ))
+}
+
+/// An OpenAI-compatible chat completions server
+///
+/// Besides OpenAI itself this covers OpenRouter, Groq, vLLM, LM Studio, and
+/// other servers implementing the same API.
+#[derive(Debug, Clone)]
+pub struct Endpoint {
+    /// Name used in error messages
+    pub name: String,
+    /// API root, e.g. `https://api.openai.com/v1`
+    pub base_url: String,
+    pub api_key: Option<String>,
+    /// Extra headers sent with every request
+    pub headers: BTreeMap<String, String>,
+}
+
+impl Endpoint {
+    /// The OpenAI API
+    pub fn openai(api_key: &str) -> Self {
+        Self {
+            name: "OpenAI".to_string(),
+            base_url: OPENAI_BASE_URL.to_string(),
+            api_key: Some(api_key.to_string()),
+            headers: BTreeMap::new(),
+        }
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url.trim_end_matches('/'), path)
+    }
+
+    fn request(&self, client: &Client, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let mut request = client.request(method, self.url(path));
+        if let Some(api_key) = self.api_key.as_deref().filter(|k| !k.is_empty()) {
+            request = request.header("Authorization", format!("Bearer {}", api_key));
+        }
+        for (name, value) in &self.headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        request
+    }
+
+    async fn post_chat(&self, request: &ChatRequest, timeout_secs: u64) -> AiResult<reqwest::Response> {
+        let client = Client::new();
+
+        let response = self
+            .request(&client, reqwest::Method::POST, "chat/completions")
+            .header("Content-Type", "application/json")
+            .json(request)
+            .timeout(std::time::Duration::from_secs(timeout_secs))
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_connect() {
+                    AiError::ProviderNotAvailable(format!("Could not connect to {}", self.name))
+                } else if e.is_timeout() {
+                    AiError::Timeout
+                } else {
+                    AiError::RequestFailed(e)
+                }
+            })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+
+            return Err(AiError::ApiError(format!(
+                "{} returned {}: {}",
+                self.name, status, error_text
+            )));
+        }
+
+        Ok(response)
+    }
+}
+
 /// Validate an OpenAI API key
 pub async fn validate_api_key(api_key: &str) -> bool {
+    let endpoint = Endpoint::openai(api_key);
     let client = Client::new();
 
-    let response = client
-        .get("https://api.openai.com/v1/models")
-        .header("Authorization", format!("Bearer {}", api_key))
+    let response = endpoint
+        .request(&client, reqwest::Method::GET, "models")
         .timeout(std::time::Duration::from_secs(5))
         .send()
         .await;
@@ -45,6 +185,26 @@ pub async fn validate_api_key(api_key: &str) -> bool {
 
 /// List available models
 pub async fn list_models(api_key: &str) -> AiResult<Vec<String>> {
+    let models = list_endpoint_models(&Endpoint::openai(api_key)).await?;
+
+    // Filter to chat models that are useful for text generation
+    let mut chat_models: Vec<String> = models.into_iter().filter(|id| is_chat_model(id)).collect();
+    chat_models.sort();
+
+    Ok(chat_models)
+}
+
+/// Whether an OpenAI model id names a chat model (rather than audio, image, or embedding models)
+fn is_chat_model(id: &str) -> bool {
+    const EXCLUDED: &[&str] = &["audio", "realtime", "tts", "transcribe", "image", "search", "embedding"];
+
+    let reasoning = id.starts_with('o') && id[1..].starts_with(|c: char| c.is_ascii_digit());
+    let chat = id.starts_with("gpt-") || id.starts_with("chatgpt-") || reasoning;
+    chat && !EXCLUDED.iter().any(|word| id.contains(word))
+}
+
+/// List all models served by an endpoint
+pub async fn list_endpoint_models(endpoint: &Endpoint) -> AiResult<Vec<String>> {
     let client = Client::new();
 
     #[derive(Deserialize)]
@@ -57,9 +217,8 @@ pub async fn list_models(api_key: &str) -> AiResult<Vec<String>> {
         id: String,
     }
 
-    let response = client
-        .get("https://api.openai.com/v1/models")
-        .header("Authorization", format!("Bearer {}", api_key))
+    let response = endpoint
+        .request(&client, reqwest::Method::GET, "models")
         .timeout(std::time::Duration::from_secs(10))
         .send()
         .await?;
@@ -69,79 +228,108 @@ pub async fn list_models(api_key: &str) -> AiResult<Vec<String>> {
     }
 
     let models: ModelsResponse = response.json().await?;
-
-    // Filter to GPT models that are useful for text generation
-    let gpt_models: Vec<String> = models
-        .data
-        .into_iter()
-        .map(|m| m.id)
-        .filter(|id| id.starts_with("gpt-"))
-        .collect();
-
-    Ok(gpt_models)
+    Ok(models.data.into_iter().map(|m| m.id).collect())
 }
 
 /// Generate text using OpenAI
-pub async fn generate(api_key: &str, model: &str, prompt: &str) -> AiResult<String> {
-    let client = Client::new();
+pub async fn generate(
+    api_key: &str,
+    model: &str,
+    prompt: &str,
+    params: &GenerationParams,
+) -> AiResult<String> {
+    generate_at(&Endpoint::openai(api_key), model, prompt, params).await
+}
 
-    let request = ChatRequest {
-        model: model.to_string(),
-        messages: vec![
-            Message {
-                role: "system".to_string(),
-                content: "You are a helpful assistant that generates clear, concise Git commit messages. Respond with ONLY the commit message, no explanation or additional text.".to_string(),
-            },
-            Message {
-                role: "user".to_string(),
-                content: prompt.to_string(),
-            },
-        ],
-        temperature: 0.3, // Lower temperature for more consistent output
-        max_tokens: 100,
-    };
+/// Generate text using an OpenAI-compatible endpoint
+pub async fn generate_at(
+    endpoint: &Endpoint,
+    model: &str,
+    prompt: &str,
+    params: &GenerationParams,
+) -> AiResult<String> {
+    let request = chat_request(model, prompt, params, false);
+    let response = endpoint.post_chat(&request, params.timeout_secs).await?;
 
-    let response = client
-        .post(OPENAI_API_URL)
-        .header("Authorization", format!("Bearer {}", api_key))
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .timeout(std::time::Duration::from_secs(30))
-        .send()
+    let chat_response: ChatResponse = response
+        .json()
         .await
-        .map_err(|e| {
-            if e.is_timeout() {
-                AiError::ApiError("Request timed out".to_string())
-            } else {
-                AiError::RequestFailed(e)
-            }
-        })?;
+        .map_err(|e| AiError::InvalidResponse(e.to_string()))?;
 
-    if !response.status().is_success() {
-        let status = response.status();
-        let error_text = response.text().await.unwrap_or_default();
+    let message = chat_response
+        .choices
+        .first()
+        .map(|c| c.message.content.trim().to_string())
+        .unwrap_or_default();
 
-        return Err(AiError::ApiError(format!(
-            "OpenAI returned {}: {}",
-            status, error_text
-        )));
-    }
+    Ok(message)
+}
+
+/// Generate `count` alternative texts in one request to an OpenAI-compatible endpoint
+///
+/// Servers that ignore the `n` parameter may return fewer texts. The texts only
+/// differ from each other at a high enough temperature in `params`.
+pub async fn generate_many_at(
+    endpoint: &Endpoint,
+    model: &str,
+    prompt: &str,
+    params: &GenerationParams,
+    count: u32,
+) -> AiResult<Vec<String>> {
+    let mut request = chat_request(model, prompt, params, false);
+    request.n = Some(count);
+    let response = endpoint.post_chat(&request, params.timeout_secs).await?;
 
     let chat_response: ChatResponse = response
         .json()
         .await
         .map_err(|e| AiError::InvalidResponse(e.to_string()))?;
 
-    let message = chat_response
+    Ok(chat_response
         .choices
-        .first()
+        .into_iter()
         .map(|c| c.message.content.trim().to_string())
-        .unwrap_or_default();
+        .collect())
+}
 
-    // Clean up: get first line only (commit subject)
-    let message = message.lines().next().unwrap_or("").to_string();
+/// Generate text using OpenAI, streaming tokens to `on_token` as they arrive
+pub async fn generate_stream(
+    api_key: &str,
+    model: &str,
+    prompt: &str,
+    params: &GenerationParams,
+    cancel: &super::GenerationCancel,
+    on_token: impl FnMut(&str),
+) -> AiResult<String> {
+    generate_stream_at(&Endpoint::openai(api_key), model, prompt, params, cancel, on_token).await
+}
 
-    Ok(message)
+/// Generate text using an OpenAI-compatible endpoint, streaming tokens to `on_token`
+pub async fn generate_stream_at(
+    endpoint: &Endpoint,
+    model: &str,
+    prompt: &str,
+    params: &GenerationParams,
+    cancel: &super::GenerationCancel,
+    mut on_token: impl FnMut(&str),
+) -> AiResult<String> {
+    let request = chat_request(model, prompt, params, true);
+    let response = endpoint.post_chat(&request, params.timeout_secs).await?;
+
+    let mut text = String::new();
+    super::read_lines(response, cancel, |line| match parse_stream_line(line)? {
+        Some(token) => {
+            if !token.is_empty() {
+                on_token(&token);
+                text.push_str(&token);
+            }
+            Ok(true)
+        }
+        None => Ok(false),
+    })
+    .await?;
+
+    Ok(text)
 }
 
 #[cfg(test)]
@@ -153,4 +341,38 @@ mod tests {
         let result = validate_api_key("invalid-key").await;
         assert!(!result);
     }
+
+    #[test]
+    fn test_is_chat_model() {
+        assert!(is_chat_model("gpt-4o"));
+        assert!(is_chat_model("o3-mini"));
+        assert!(is_chat_model("chatgpt-4o-latest"));
+        assert!(!is_chat_model("gpt-4o-audio-preview"));
+        assert!(!is_chat_model("text-embedding-3-small"));
+        assert!(!is_chat_model("omni-moderation-latest"));
+    }
+
+    #[test]
+    fn test_endpoint_url() {
+        let endpoint = Endpoint {
+            name: "LM Studio".to_string(),
+            base_url: "http://localhost:1234/v1/".to_string(),
+            api_key: None,
+            headers: BTreeMap::new(),
+        };
+        assert_eq!(endpoint.url("chat/completions"), "http://localhost:1234/v1/chat/completions");
+        assert_eq!(Endpoint::openai("k").url("models"), "https://api.openai.com/v1/models");
+    }
+
+    #[test]
+    fn test_parse_stream_line() {
+        let line = r#"data: {"choices":[{"index":0,"delta":{"content":"fix"}}]}"#;
+        assert_eq!(parse_stream_line(line).unwrap(), Some("fix".to_string()));
+
+        let role_only = r#"data: {"choices":[{"index":0,"delta":{"role":"assistant"}}]}"#;
+        assert_eq!(parse_stream_line(role_only).unwrap(), Some(String::new()));
+
+        assert_eq!(parse_stream_line(": keep-alive").unwrap(), Some(String::new()));
+        assert_eq!(parse_stream_line("data: [DONE]").unwrap(), None);
+    }
 }
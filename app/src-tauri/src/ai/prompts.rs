@@ -0,0 +1,337 @@
+//! Prompt templates for commit message generation
+//!
+//! Templates are plain text with `{{diff}}`, `{{branch}}`, `{{recent_commits}}`,
+//! and `{{language}}` placeholders. A global template and per-repository
+//! overrides are stored in the app config directory; without either the
+//! built-in template is used.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::AiResult;
+use crate::git::{CommitInfo, DiffHunk, DiffLineType};
+use crate::settings::{load_json_settings, save_json_settings};
+
+/// Longest diff (in characters) included in a prompt
+pub const MAX_DIFF_CHARS: usize = 4000;
+
+/// Longest diff (in characters) included in a prompt asking for an explanation
+const MAX_EXPLAIN_DIFF_CHARS: usize = 12000;
+
+/// Most commit subjects included in a changelog prompt
+const MAX_CHANGELOG_COMMITS: usize = 200;
+
+/// Built-in commit message template
+pub const DEFAULT_COMMIT_TEMPLATE: &str = r#"You are a helpful assistant that generates clear, concise Git commit messages.
+
+Based on the following diff, generate a commit message following the Conventional Commits specification:
+- Use format: <type>(<scope>): <description>
+- Types: feat, fix, docs, style, refactor, test, chore
+- Keep the description under 72 characters
+- Focus on WHAT changed and WHY, not HOW
+
+Diff:
+```
+{{diff}}
+```
+
+Generate ONLY the commit message, no explanation:"#;
+
+/// Values substituted into a template
+#[derive(Debug, Clone, Default)]
+pub struct PromptContext {
+    pub diff: String,
+    pub branch: Option<String>,
+    /// Subjects of recent commits, newest first
+    pub recent_commits: Vec<String>,
+    /// Language the message is written in (English when unset)
+    pub language: Option<String>,
+}
+
+/// Saved templates
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PromptTemplates {
+    /// Global commit template (the built-in one when unset)
+    pub commit: Option<String>,
+    /// Commit templates by repository path, taking precedence over the global one
+    #[serde(default)]
+    pub repositories: BTreeMap<String, String>,
+}
+
+impl PromptTemplates {
+    /// Commit template that applies to a repository
+    pub fn commit_template_for(&self, repo_path: Option<&str>) -> &str {
+        repo_path
+            .and_then(|path| self.repositories.get(path))
+            .or(self.commit.as_ref())
+            .map(|t| t.as_str())
+            .unwrap_or(DEFAULT_COMMIT_TEMPLATE)
+    }
+}
+
+/// Fill in a commit template
+///
+/// The diff is truncated to keep prompts small, and appended if the template
+/// has no `{{diff}}` placeholder so it is never left out.
+pub fn render_commit_prompt(template: &str, context: &PromptContext) -> String {
+    let diff: String = context.diff.chars().take(MAX_DIFF_CHARS).collect();
+    let recent_commits = context
+        .recent_commits
+        .iter()
+        .map(|subject| format!("- {}", subject))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut prompt = template
+        .replace("{{branch}}", context.branch.as_deref().unwrap_or("(detached HEAD)"))
+        .replace("{{recent_commits}}", &recent_commits);
+
+    if prompt.contains("{{language}}") {
+        prompt = prompt.replace("{{language}}", context.language.as_deref().unwrap_or("English"));
+    } else if let Some(language) = &context.language {
+        prompt.push_str(&format!(
+            "\n\nWrite the commit message in {}, keeping any commit type and scope in English.",
+            language
+        ));
+    }
+
+    if prompt.contains("{{diff}}") {
+        prompt = prompt.replace("{{diff}}", &diff);
+    } else {
+        prompt.push_str(&format!("\n\nDiff:\n```\n{}\n```", diff));
+    }
+    prompt
+}
+
+/// Extend a commit prompt with a previous suggestion and the user's feedback on it
+pub fn render_refine_prompt(prompt: &str, previous: &str, feedback: &str) -> String {
+    format!(
+        "{}\n\nA previous suggestion was:\n```\n{}\n```\n\
+         Improve it according to this feedback: {}\n\
+         Generate ONLY the improved commit message, no explanation:",
+        prompt,
+        previous.trim(),
+        feedback.trim()
+    )
+}
+
+/// Prompt asking for a summary of the changes in a release
+pub fn render_changelog_prompt(commits: &[CommitInfo]) -> String {
+    let subjects: Vec<String> = commits
+        .iter()
+        .filter(|c| c.parents.len() <= 1)
+        .take(MAX_CHANGELOG_COMMITS)
+        .map(|c| format!("- {}", c.message.lines().next().unwrap_or("").trim()))
+        .collect();
+
+    format!(
+        "You are writing release notes for a software project.\n\n\
+         These commits are part of the release:\n{}\n\n\
+         Summarize the most important changes for users in 2-4 sentences of markdown prose. \
+         Do not use headings or list every commit.\n\n\
+         Generate ONLY the summary, no explanation:",
+        subjects.join("\n")
+    )
+}
+
+/// Prompt asking for a summary of one piece of a large diff
+pub fn render_diff_chunk_prompt(chunk: &str) -> String {
+    format!(
+        "Summarize the changes in this part of a Git diff. For each file write one line \
+         in the form `path: summary of the change`, and nothing else.\n\n\
+         ```diff\n{}\n```",
+        chunk
+    )
+}
+
+/// Prompt asking to shorten per-file summaries of a large diff
+pub fn render_summary_reduce_prompt(summaries: &str) -> String {
+    format!(
+        "These lines summarize changes to files in one commit. Merge them into fewer, \
+         shorter lines in the form `path or area: summary of the change`, keeping every \
+         significant change, and write nothing else.\n\n{}",
+        summaries
+    )
+}
+
+fn truncate_diff(diff: &str, max_chars: usize) -> String {
+    let mut truncated: String = diff.chars().take(max_chars).collect();
+    if truncated.len() < diff.len() {
+        truncated.push_str("\n... (diff truncated)");
+    }
+    truncated
+}
+
+/// Prompt asking to split staged changes into logical commits
+pub fn render_split_prompt(files: &[&str], diff: &str) -> String {
+    let files: Vec<String> = files.iter().map(|f| format!("- {}", f)).collect();
+    format!(
+        "Split the following staged changes into a few logical commits, each one a \
+         single self-contained change. Keep related files together and put every file \
+         in exactly one commit. Write a commit message for each commit following the \
+         Conventional Commits format.\n\n\
+         Respond with ONLY a JSON array in the form \
+         [{{\"files\": [\"path\"], \"message\": \"type(scope): description\"}}], \
+         listing the commits in the order they should be made.\n\n\
+         Staged files:\n{}\n\nDiff:\n```diff\n{}\n```",
+        files.join("\n"),
+        diff
+    )
+}
+
+/// Prompt asking what a commit changed and why it matters
+pub fn render_explain_commit_prompt(commit: &CommitInfo, diff: &str) -> String {
+    format!(
+        "Explain the following Git commit to a developer who is new to this codebase. \
+         Describe in plain language what changed, why it was likely changed, \
+         and what effects it might have.\n\n\
+         Commit {} by {}:\n{}\n\nDiff:\n```diff\n{}\n```",
+        commit.short_sha,
+        commit.author,
+        commit.message.trim(),
+        truncate_diff(diff, MAX_EXPLAIN_DIFF_CHARS)
+    )
+}
+
+/// Prompt asking what a single diff hunk changes and why it matters
+pub fn render_explain_hunk_prompt(path: &str, hunk: &DiffHunk) -> String {
+    let mut text = hunk.header.trim_end().to_string();
+    for line in &hunk.lines {
+        let prefix = match line.line_type {
+            DiffLineType::Addition => "+",
+            DiffLineType::Deletion => "-",
+            DiffLineType::Context => " ",
+            DiffLineType::Header => continue,
+        };
+        text.push('\n');
+        text.push_str(prefix);
+        text.push_str(line.content.trim_end_matches('\n'));
+    }
+
+    format!(
+        "Explain the following change to `{}` to a developer who is new to this codebase. \
+         Describe in plain language what the change does and why it might matter.\n\n\
+         ```diff\n{}\n```",
+        path,
+        truncate_diff(&text, MAX_EXPLAIN_DIFF_CHARS)
+    )
+}
+
+/// Name of the settings file in the app config directory
+const SETTINGS_FILE: &str = "prompt_templates.json";
+
+/// Load the saved templates (empty if none have been saved)
+pub fn load_templates() -> AiResult<PromptTemplates> {
+    Ok(load_json_settings(SETTINGS_FILE)?)
+}
+
+/// Save the templates
+pub fn save_templates(templates: &PromptTemplates) -> AiResult<()> {
+    Ok(save_json_settings(SETTINGS_FILE, templates)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_commit_prompt() {
+        let context = PromptContext {
+            diff: "+ fn new()".to_string(),
+            branch: Some("feature/login".to_string()),
+            recent_commits: vec!["feat: add form".to_string(), "fix: typo".to_string()],
+            language: None,
+        };
+
+        let prompt = render_commit_prompt("On {{branch}}:\n{{recent_commits}}\n{{diff}}", &context);
+        assert_eq!(prompt, "On feature/login:\n- feat: add form\n- fix: typo\n+ fn new()");
+
+        let without_diff = render_commit_prompt("Write a message", &context);
+        assert!(without_diff.ends_with("```\n+ fn new()\n```"));
+    }
+
+    #[test]
+    fn test_render_commit_prompt_language() {
+        let context = PromptContext {
+            diff: "+ fn new()".to_string(),
+            language: Some("German".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(render_commit_prompt("In {{language}}: {{diff}}", &context), "In German: + fn new()");
+        assert!(render_commit_prompt("{{diff}}", &context).contains("in German, keeping"));
+        let english = render_commit_prompt("In {{language}}", &PromptContext::default());
+        assert!(english.starts_with("In English\n"));
+    }
+
+    #[test]
+    fn test_render_refine_prompt() {
+        let prompt = render_refine_prompt("Diff: x", "fix: stuff\n", " shorter ");
+        assert!(prompt.starts_with("Diff: x"));
+        assert!(prompt.contains("```\nfix: stuff\n```"));
+        assert!(prompt.contains("feedback: shorter\n"));
+    }
+
+    #[test]
+    fn test_render_changelog_prompt_skips_merges() {
+        let commit = |message: &str, parents: usize| CommitInfo {
+            sha: String::new(),
+            short_sha: String::new(),
+            message: message.to_string(),
+            author: String::new(),
+            email: String::new(),
+            date: String::new(),
+            absolute_date: String::new(),
+            timestamp: 0,
+            parents: vec![String::new(); parents],
+        };
+
+        let prompt = render_changelog_prompt(&[
+            commit("feat: add sync\n\nBody", 1),
+            commit("Merge branch 'main'", 2),
+        ]);
+        assert!(prompt.contains("- feat: add sync\n"));
+        assert!(!prompt.contains("Merge branch"));
+        assert!(!prompt.contains("Body"));
+    }
+
+    #[test]
+    fn test_render_explain_hunk_prompt() {
+        use crate::git::DiffLine;
+
+        let line = |line_type: DiffLineType, content: &str| DiffLine {
+            line_type,
+            content: content.to_string(),
+            old_line: None,
+            new_line: None,
+        };
+        let hunk = DiffHunk {
+            header: "@@ -1,2 +1,2 @@\n".to_string(),
+            old_start: 1,
+            old_lines: 2,
+            new_start: 1,
+            new_lines: 2,
+            lines: vec![
+                line(DiffLineType::Context, "fn main() {\n"),
+                line(DiffLineType::Deletion, "    run();\n"),
+                line(DiffLineType::Addition, "    run_all();\n"),
+            ],
+        };
+
+        let prompt = render_explain_hunk_prompt("src/main.rs", &hunk);
+        assert!(prompt.contains("`src/main.rs`"));
+        assert!(prompt.contains("```diff\n@@ -1,2 +1,2 @@\n fn main() {\n-    run();\n+    run_all();\n```"));
+    }
+
+    #[test]
+    fn test_commit_template_for_prefers_repository_override() {
+        let mut templates = PromptTemplates::default();
+        assert_eq!(templates.commit_template_for(Some("/repo")), DEFAULT_COMMIT_TEMPLATE);
+
+        templates.commit = Some("global".to_string());
+        templates.repositories.insert("/repo".to_string(), "repo".to_string());
+        assert_eq!(templates.commit_template_for(Some("/repo")), "repo");
+        assert_eq!(templates.commit_template_for(Some("/other")), "global");
+    }
+}
@@ -0,0 +1,144 @@
+//! Splitting staged changes into several logical commits
+//!
+//! The model proposes groups of staged files with a message for each. Its
+//! answer is checked against the staged files so every file ends up in
+//! exactly one group. Files are never split: all staged hunks of a file go
+//! into the same commit.
+
+use serde::{Deserialize, Serialize};
+
+use crate::git::FileStatus;
+
+/// Files committed together, with the message for their commit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommitGroup {
+    pub files: Vec<String>,
+    pub message: String,
+}
+
+/// Read the groups from the model's answer, ignoring any text around the JSON
+pub fn parse_groups(text: &str) -> Result<Vec<CommitGroup>, String> {
+    let (Some(start), Some(end)) = (text.find('['), text.rfind(']')) else {
+        return Err("The answer does not contain a JSON array".to_string());
+    };
+    if end < start {
+        return Err("The answer does not contain a JSON array".to_string());
+    }
+
+    serde_json::from_str(&text[start..=end]).map_err(|e| format!("The answer is not valid JSON: {}", e))
+}
+
+/// Make the groups cover exactly the staged files
+///
+/// Unknown files are dropped, files named twice stay in their first group, and
+/// files the model left out are added to the last group. The old path of a
+/// renamed file joins the group of its new path, so the rename is committed as
+/// a whole.
+pub fn normalize_groups(groups: Vec<CommitGroup>, staged: &[FileStatus]) -> Vec<CommitGroup> {
+    let mut assigned: Vec<&str> = Vec::new();
+    let mut normalized: Vec<CommitGroup> = Vec::new();
+
+    for group in groups {
+        let mut files = Vec::new();
+        for file in group.files {
+            let Some(status) = staged.iter().find(|s| s.path == file) else {
+                continue;
+            };
+            if assigned.contains(&status.path.as_str()) {
+                continue;
+            }
+            assigned.push(&status.path);
+            files.push(file);
+        }
+        if !files.is_empty() {
+            normalized.push(CommitGroup {
+                files,
+                message: group.message.trim().to_string(),
+            });
+        }
+    }
+
+    let missing: Vec<String> = staged
+        .iter()
+        .filter(|s| !assigned.contains(&s.path.as_str()))
+        .map(|s| s.path.clone())
+        .collect();
+    if !missing.is_empty() {
+        match normalized.last_mut() {
+            Some(last) => last.files.extend(missing),
+            None => normalized.push(CommitGroup {
+                files: missing,
+                message: String::new(),
+            }),
+        }
+    }
+
+    for group in &mut normalized {
+        let old_paths: Vec<String> = staged
+            .iter()
+            .filter(|s| group.files.contains(&s.path))
+            .filter_map(|s| s.old_path.clone())
+            .filter(|old| !group.files.contains(old))
+            .collect();
+        group.files.extend(old_paths);
+    }
+
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::FileStatusType;
+
+    fn staged(path: &str, old_path: Option<&str>) -> FileStatus {
+        FileStatus {
+            path: path.to_string(),
+            status: if old_path.is_some() { FileStatusType::Renamed } else { FileStatusType::Modified },
+            staged: true,
+            old_path: old_path.map(str::to_string),
+        }
+    }
+
+    fn group(files: &[&str], message: &str) -> CommitGroup {
+        CommitGroup {
+            files: files.iter().map(|f| f.to_string()).collect(),
+            message: message.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_groups_ignores_surrounding_text() {
+        let text = "Here you go:\n```json\n[{\"files\": [\"a.rs\"], \"message\": \"fix: a\"}]\n```";
+        assert_eq!(parse_groups(text).unwrap(), vec![group(&["a.rs"], "fix: a")]);
+        assert!(parse_groups("no groups").is_err());
+        assert!(parse_groups("[{\"files\": 1}]").is_err());
+    }
+
+    #[test]
+    fn test_normalize_groups() {
+        let staged = vec![
+            staged("a.rs", None),
+            staged("b.rs", None),
+            staged("new.rs", Some("old.rs")),
+            staged("c.rs", None),
+        ];
+        let groups = vec![
+            group(&["a.rs", "unknown.rs"], " feat: a "),
+            group(&["a.rs"], "fix: duplicate"),
+            group(&["new.rs", "b.rs"], "refactor: rename"),
+        ];
+
+        assert_eq!(
+            normalize_groups(groups, &staged),
+            vec![
+                group(&["a.rs"], "feat: a"),
+                group(&["new.rs", "b.rs", "c.rs", "old.rs"], "refactor: rename"),
+            ]
+        );
+        assert_eq!(
+            normalize_groups(Vec::new(), &staged[..1]),
+            vec![group(&["a.rs"], "")]
+        );
+    }
+}
@@ -1,7 +1,11 @@
+use std::sync::Arc;
+
 use serde::Serialize;
-use tauri::State;
-use crate::git;
-use crate::ai::{self, AiConfig};
+use tauri::{AppHandle, Emitter, State};
+use crate::git::{self, DiffHunk};
+use crate::ai::prompts::{self, PromptContext};
+use crate::ai::split::CommitGroup;
+use crate::ai::{self, AiConfig, CommitMessageToken, COMMIT_MESSAGE_TOKEN_EVENT};
 use crate::commands::state::AppState;
 
 #[derive(Debug, Serialize)]
@@ -10,6 +14,81 @@ pub struct OllamaStatus {
     pub models: Vec<String>,
 }
 
+/// Commit prompt templates as seen from the open repository
+#[derive(Debug, Serialize)]
+pub struct CommitPromptTemplates {
+    pub default_template: String,
+    pub global_template: Option<String>,
+    pub repository_template: Option<String>,
+}
+
+/// Proposed split of the staged changes into several commits
+///
+/// `staged_tree` holds the staged content when the split was proposed; each
+/// group is staged from it with `stage_commit_group` before committing.
+#[derive(Debug, Serialize)]
+pub struct CommitSplitPlan {
+    pub staged_tree: String,
+    pub groups: Vec<CommitGroup>,
+}
+
+/// Number of recent commit subjects offered to the `{{recent_commits}}` placeholder
+const RECENT_COMMITS_IN_PROMPT: usize = 5;
+
+fn open_repo_path(state: &AppState) -> Result<String, String> {
+    let path_guard = state.repo_path.lock().unwrap();
+    path_guard
+        .as_ref()
+        .ok_or_else(|| "No repository open".to_string())
+        .cloned()
+}
+
+/// Collect the staged diff, branch, and recent commits of a repository
+fn collect_prompt_context(repo_path: &str) -> Result<PromptContext, String> {
+    let repo = git::open_repo(repo_path).map_err(|e| e.to_string())?;
+    let diff = git::get_staged_diff_text(&repo).map_err(|e| e.to_string())?;
+
+    if diff.is_empty() {
+        return Err("No staged changes to generate commit message from".to_string());
+    }
+
+    let branch = repo
+        .head()
+        .ok()
+        .filter(|h| h.is_branch())
+        .and_then(|h| h.shorthand().map(|s| s.to_string()));
+    // A repository without commits has no history to show
+    let recent_commits = git::get_commit_history(&repo, RECENT_COMMITS_IN_PROMPT, 0)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|c| c.message.lines().next().unwrap_or("").to_string())
+        .collect();
+
+    Ok(PromptContext {
+        diff,
+        branch,
+        recent_commits,
+        language: None,
+    })
+}
+
+/// Render the commit prompt for the staged changes of a repository with its template
+///
+/// Large diffs are condensed with the configured provider first.
+async fn build_commit_prompt(repo_path: &str, config: &AiConfig) -> Result<String, String> {
+    let mut context = collect_prompt_context(repo_path)?;
+    context.language = config.language.clone().filter(|l| !l.trim().is_empty());
+    context.diff = ai::condense_diff(&context.diff, config)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let templates = prompts::load_templates().map_err(|e| e.to_string())?;
+    Ok(prompts::render_commit_prompt(
+        templates.commit_template_for(Some(repo_path)),
+        &context,
+    ))
+}
+
 #[tauri::command]
 pub async fn generate_commit_message(state: State<'_, AppState>) -> Result<String, String> {
     let repo_path = {
@@ -20,31 +99,245 @@ pub async fn generate_commit_message(state: State<'_, AppState>) -> Result<Strin
             .clone()
     };
 
-    let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
-    let diff = git::get_staged_diff_text(&repo).map_err(|e| e.to_string())?;
+    let config = state.ai_config.lock().unwrap().clone();
+    let prompt = build_commit_prompt(&repo_path, &config).await?;
 
-    if diff.is_empty() {
-        return Err("No staged changes to generate commit message from".to_string());
+    ai::generate_commit_message(&prompt, &config)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Generate up to `count` alternative commit messages for the staged changes
+#[tauri::command]
+pub async fn generate_commit_message_candidates(
+    count: usize,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let repo_path = open_repo_path(&state)?;
+    let config = state.ai_config.lock().unwrap().clone();
+    let prompt = build_commit_prompt(&repo_path, &config).await?;
+
+    ai::generate_commit_message_candidates(&prompt, &config, count)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Regenerate a suggested commit message following the user's instruction,
+/// e.g. "mention the config migration" or "shorter"
+#[tauri::command]
+pub async fn refine_commit_message(
+    previous: String,
+    feedback: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    if feedback.trim().is_empty() {
+        return Err("Feedback cannot be empty".to_string());
     }
 
+    let repo_path = open_repo_path(&state)?;
     let config = state.ai_config.lock().unwrap().clone();
-    ai::generate_commit_message(&diff, &config)
+    let prompt = build_commit_prompt(&repo_path, &config).await?;
+
+    ai::refine_commit_message(&prompt, &previous, &feedback, &config)
         .await
         .map_err(|e| e.to_string())
 }
 
+/// Generate markdown release notes for the commits in `to_ref` (HEAD by default)
+/// that are not in `from_ref`, suitable for a GitHub release body
 #[tauri::command]
-pub fn get_ai_config(state: State<AppState>) -> Result<AiConfig, String> {
+pub async fn generate_changelog(
+    from_ref: String,
+    to_ref: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let repo_path = open_repo_path(&state)?;
+    let commits = {
+        let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+        git::get_commits_in_range(&repo, Some(&from_ref), to_ref.as_deref().unwrap_or("HEAD"))
+            .map_err(|e| e.to_string())?
+    };
+
+    if commits.is_empty() {
+        return Err(format!("No commits since {}", from_ref));
+    }
+
+    let config = state.ai_config.lock().unwrap().clone();
+    ai::generate_changelog(&commits, &config)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Explain in plain language what a commit changed and why it might matter
+#[tauri::command]
+pub async fn explain_commit(sha: String, state: State<'_, AppState>) -> Result<String, String> {
+    let repo_path = open_repo_path(&state)?;
+    let (commit, diff) = {
+        let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+        let commit = git::get_commit_detail(&repo, &sha).map_err(|e| e.to_string())?;
+        let diff = git::get_commit_diff_text(&repo, &sha).map_err(|e| e.to_string())?;
+        (commit, diff)
+    };
+
+    let config = state.ai_config.lock().unwrap().clone();
+    ai::explain_commit(&commit, &diff, &config)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Explain in plain language what a diff hunk of a file changes
+#[tauri::command]
+pub async fn explain_hunk(
+    path: String,
+    hunk: DiffHunk,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    if hunk.lines.is_empty() {
+        return Err("The hunk has no changes to explain".to_string());
+    }
+
+    let config = state.ai_config.lock().unwrap().clone();
+    ai::explain_hunk(&path, &hunk, &config)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Propose how to split the staged changes into several commits
+///
+/// Groups are made of whole files: a file with unrelated changes in several
+/// hunks is committed in one group, as staged.
+#[tauri::command]
+pub async fn suggest_commit_split(state: State<'_, AppState>) -> Result<CommitSplitPlan, String> {
+    let repo_path = open_repo_path(&state)?;
+    let (diff, staged, staged_tree) = {
+        let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+        let diff = git::get_staged_diff_text(&repo).map_err(|e| e.to_string())?;
+        let staged = git::get_repo_status(&repo).map_err(|e| e.to_string())?.staged;
+        let staged_tree = git::snapshot_index(&repo).map_err(|e| e.to_string())?;
+        (diff, staged, staged_tree)
+    };
+
+    if staged.is_empty() {
+        return Err("No staged changes to split".to_string());
+    }
+
     let config = state.ai_config.lock().unwrap().clone();
-    Ok(config)
+    let groups = ai::suggest_commit_split(&diff, &staged, &config)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(CommitSplitPlan { staged_tree, groups })
 }
 
+/// Generate a commit message for the staged changes, emitting each token as an
+/// `ai-commit-message-token` event so the commit box fills in live
+///
+/// Starting a new generation cancels the previous one.
 #[tauri::command]
-pub fn set_ai_config(config: AiConfig, state: State<AppState>) -> Result<(), String> {
+pub async fn generate_commit_message_stream(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let repo_path = {
+        let path_guard = state.repo_path.lock().unwrap();
+        path_guard
+            .as_ref()
+            .ok_or("No repository open")?
+            .clone()
+    };
+
+    let config = state.ai_config.lock().unwrap().clone();
+    let prompt = build_commit_prompt(&repo_path, &config).await?;
+
+    let cancel = Arc::new(ai::GenerationCancel::next());
+    let generation = cancel.generation();
+    if let Some(previous) = state.ai_generation.lock().unwrap().replace(cancel.clone()) {
+        previous.cancel();
+    }
+
+    let result = ai::generate_commit_message_stream(
+        &prompt,
+        &config,
+        &cancel,
+        |token| {
+            let _ = app.emit(COMMIT_MESSAGE_TOKEN_EVENT, CommitMessageToken {
+                generation,
+                token: token.to_string(),
+                done: false,
+                reset: false,
+            });
+        },
+        |_| {
+            let _ = app.emit(COMMIT_MESSAGE_TOKEN_EVENT, CommitMessageToken {
+                generation,
+                token: String::new(),
+                done: false,
+                reset: true,
+            });
+        },
+    )
+    .await;
+
+    {
+        let mut current = state.ai_generation.lock().unwrap();
+        if current.as_ref().is_some_and(|c| Arc::ptr_eq(c, &cancel)) {
+            *current = None;
+        }
+    }
+    let _ = app.emit(COMMIT_MESSAGE_TOKEN_EVENT, CommitMessageToken {
+        generation,
+        token: String::new(),
+        done: true,
+        reset: false,
+    });
+
+    result.map_err(|e| e.to_string())
+}
+
+/// Cancel the streaming commit message generation in progress
+#[tauri::command]
+pub fn cancel_commit_message_generation(state: State<AppState>) -> Result<(), String> {
+    if let Some(cancel) = state.ai_generation.lock().unwrap().take() {
+        cancel.cancel();
+    }
+    Ok(())
+}
+
+/// Get the AI configuration; API keys are only referenced, never returned
+#[tauri::command]
+pub fn get_ai_config(state: State<AppState>) -> Result<AiConfig, String> {
+    let mut config = state.ai_config.lock().unwrap();
+    config.load_key_refs();
+    Ok(config.clone())
+}
+
+/// Set the AI configuration, moving any API keys in it to the system keyring
+///
+/// Keys that are left out keep their stored value.
+#[tauri::command]
+pub fn set_ai_config(mut config: AiConfig, state: State<AppState>) -> Result<(), String> {
+    config.secure_api_keys().map_err(|e| e.to_string())?;
+    config.load_key_refs();
     *state.ai_config.lock().unwrap() = config;
     Ok(())
 }
 
+/// Delete a provider's API key (`openai`, `anthropic`, or `compatible`) from the keyring
+#[tauri::command]
+pub fn delete_ai_api_key(provider: String, state: State<AppState>) -> Result<(), String> {
+    if !ai::keys::KEY_PROVIDERS.contains(&provider.as_str()) {
+        return Err(format!("Unknown AI provider: {}", provider));
+    }
+    ai::keys::delete_api_key(&provider).map_err(|e| e.to_string())?;
+
+    let mut config = state.ai_config.lock().unwrap();
+    match provider.as_str() {
+        "openai" => config.openai_api_key_ref = None,
+        "anthropic" => config.anthropic_api_key_ref = None,
+        _ => config.compatible_api_key_ref = None,
+    }
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn check_ollama_status(state: State<'_, AppState>) -> Result<OllamaStatus, String> {
     let config = state.ai_config.lock().unwrap().clone();
@@ -66,6 +359,16 @@ pub async fn validate_openai_key(api_key: String) -> Result<bool, String> {
     Ok(ai::openai::validate_api_key(&api_key).await)
 }
 
+/// List the chat models available to the configured OpenAI API key
+#[tauri::command]
+pub async fn list_openai_models(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let config = state.ai_config.lock().unwrap().clone();
+    let api_key = config.openai_key().map_err(|e| e.to_string())?;
+    ai::openai::list_models(&api_key)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn list_ollama_models(state: State<'_, AppState>) -> Result<Vec<String>, String> {
     let config = state.ai_config.lock().unwrap().clone();
@@ -73,3 +376,78 @@ pub async fn list_ollama_models(state: State<'_, AppState>) -> Result<Vec<String
         .await
         .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub async fn validate_anthropic_key(api_key: String) -> Result<bool, String> {
+    Ok(ai::anthropic::validate_api_key(&api_key).await)
+}
+
+#[tauri::command]
+pub async fn list_anthropic_models(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let config = state.ai_config.lock().unwrap().clone();
+    let api_key = config.anthropic_key().map_err(|e| e.to_string())?;
+    ai::anthropic::list_models(&api_key)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// List the models served by the configured OpenAI-compatible endpoint
+#[tauri::command]
+pub async fn list_compatible_models(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let config = state.ai_config.lock().unwrap().clone();
+    let endpoint = config.compatible_endpoint().map_err(|e| e.to_string())?;
+    ai::openai::list_endpoint_models(&endpoint)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get the built-in, global, and open repository's commit prompt templates
+#[tauri::command]
+pub fn get_commit_prompt_templates(state: State<AppState>) -> Result<CommitPromptTemplates, String> {
+    let repo_path = state.repo_path.lock().unwrap().clone();
+    let mut templates = prompts::load_templates().map_err(|e| e.to_string())?;
+
+    Ok(CommitPromptTemplates {
+        default_template: prompts::DEFAULT_COMMIT_TEMPLATE.to_string(),
+        global_template: templates.commit,
+        repository_template: repo_path.and_then(|path| templates.repositories.remove(&path)),
+    })
+}
+
+/// Set the global commit prompt template, or restore the built-in one with `None`
+#[tauri::command]
+pub fn set_commit_prompt_template(template: Option<String>) -> Result<(), String> {
+    let mut templates = prompts::load_templates().map_err(|e| e.to_string())?;
+    templates.commit = template.filter(|t| !t.trim().is_empty());
+    prompts::save_templates(&templates).map_err(|e| e.to_string())
+}
+
+/// Set the open repository's commit prompt template, or remove it with `None`
+#[tauri::command]
+pub fn set_repo_commit_prompt_template(
+    template: Option<String>,
+    state: State<AppState>,
+) -> Result<(), String> {
+    let repo_path = open_repo_path(&state)?;
+    let mut templates = prompts::load_templates().map_err(|e| e.to_string())?;
+
+    match template.filter(|t| !t.trim().is_empty()) {
+        Some(template) => {
+            templates.repositories.insert(repo_path, template);
+        }
+        None => {
+            templates.repositories.remove(&repo_path);
+        }
+    }
+    prompts::save_templates(&templates).map_err(|e| e.to_string())
+}
+
+/// Render the prompt that would be sent for the staged changes
+///
+/// Large diffs are condensed with the configured provider, as for generation.
+#[tauri::command]
+pub async fn preview_commit_prompt(state: State<'_, AppState>) -> Result<String, String> {
+    let repo_path = open_repo_path(&state)?;
+    let config = state.ai_config.lock().unwrap().clone();
+    build_commit_prompt(&repo_path, &config).await
+}
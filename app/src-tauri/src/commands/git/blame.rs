@@ -0,0 +1,25 @@
+use tauri::State;
+use crate::git::{self, FileBlame, FileBlameOptions};
+use crate::commands::state::AppState;
+
+/// Blame every line of a file at `rev`, or in the working directory when no
+/// revision is given. Renames are followed unless `follow_renames` is false.
+#[tauri::command]
+pub fn get_file_blame(
+    path: String,
+    rev: Option<String>,
+    ignore_whitespace: Option<bool>,
+    follow_renames: Option<bool>,
+    state: State<AppState>,
+) -> Result<FileBlame, String> {
+    let repo_path = state.repo_path.lock().unwrap()
+        .as_ref()
+        .ok_or("No repository open")?
+        .clone();
+    let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    let options = FileBlameOptions {
+        ignore_whitespace: ignore_whitespace.unwrap_or(false),
+        follow_renames: follow_renames.unwrap_or(true),
+    };
+    git::get_file_blame(&repo, &path, rev.as_deref(), &options).map_err(|e| e.to_string())
+}
@@ -1,5 +1,5 @@
 use tauri::State;
-use crate::git::{self, BranchInfo};
+use crate::git::{self, AutoStashOutcome, BranchInfo, GitError};
 use crate::commands::state::AppState;
 
 #[tauri::command]
@@ -26,14 +26,45 @@ pub fn create_branch(
     git::create_branch(&repo, &name, from_sha.as_deref()).map_err(|e| e.to_string())
 }
 
+/// Error string for a failed checkout, as structured JSON when local changes are in the way
+pub(crate) fn checkout_error(target: &str, error: GitError) -> String {
+    match error {
+        GitError::CheckoutConflict(paths) => git::checkout_conflict_error(target, paths).to_json(),
+        error => error.to_string(),
+    }
+}
+
+#[tauri::command]
+pub fn checkout_branch(
+    name: String,
+    auto_stash: Option<bool>,
+    state: State<AppState>,
+) -> Result<Option<AutoStashOutcome>, String> {
+    let repo_path = state.repo_path.lock().unwrap()
+        .as_ref()
+        .ok_or("No repository open")?
+        .clone();
+    let mut repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+
+    if !auto_stash.unwrap_or(false) {
+        return git::checkout_branch(&repo, &name).map(|_| None).map_err(|e| checkout_error(&name, e));
+    }
+
+    let description = format!("checking out {}", name);
+    let ((), outcome) = git::with_auto_stash(&mut repo, &description, |repo| git::checkout_branch(repo, &name))
+        .map_err(|e| e.to_structured().to_json())?;
+    Ok(Some(outcome))
+}
+
+/// Check out a branch, discarding local changes that would conflict with it
 #[tauri::command]
-pub fn checkout_branch(name: String, state: State<AppState>) -> Result<(), String> {
+pub fn force_checkout_branch(name: String, state: State<AppState>) -> Result<(), String> {
     let repo_path = state.repo_path.lock().unwrap()
         .as_ref()
         .ok_or("No repository open")?
         .clone();
     let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
-    git::checkout_branch(&repo, &name).map_err(|e| e.to_string())
+    git::force_checkout_branch(&repo, &name).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -1,6 +1,11 @@
+use serde::Serialize;
 use tauri::State;
-use crate::git::{self, CommitInfo, FileDiff, ResetType};
+use crate::git::{
+    self, CommitInfo, FileDiff, HistoryExportFormat, HistoryFilters, RebasePlan, RebaseProgress, ResetType,
+    SecretFinding,
+};
 use crate::commands::state::AppState;
+use super::branch::checkout_error;
 
 // Helper to get repo path from state
 fn get_repo_path(state: &State<AppState>) -> Result<String, String> {
@@ -10,14 +15,65 @@ fn get_repo_path(state: &State<AppState>) -> Result<String, String> {
         .map(|p| p.clone())
 }
 
+/// A new commit, with the possible secrets it contains when secret scanning
+/// only warns
+#[derive(Debug, Serialize)]
+pub struct CreateCommitResult {
+    #[serde(flatten)]
+    pub commit: CommitInfo,
+    pub secret_findings: Vec<SecretFinding>,
+}
+
 #[tauri::command]
-pub fn create_commit(message: String, state: State<AppState>) -> Result<CommitInfo, String> {
+pub fn create_commit(
+    message: String,
+    allow_secrets: Option<bool>,
+    allow_large_files: Option<bool>,
+    allow_protected: Option<bool>,
+    state: State<AppState>,
+) -> Result<CreateCommitResult, String> {
     let repo_path = state.repo_path.lock().unwrap()
         .as_ref()
         .ok_or("No repository open")?
         .clone();
     let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
-    git::create_commit(&repo, &message).map_err(|e| e.to_string())
+
+    if !allow_protected.unwrap_or(false) {
+        let protected = git::load_protected_branch_settings().map_err(|e| e.to_string())?;
+        protected.check(&repo, "commit").map_err(|e| e.to_json())?;
+    }
+
+    let lint_settings = git::load_commit_lint_settings().map_err(|e| e.to_string())?;
+    if lint_settings.enabled {
+        let violations = git::lint_commit_message(&message, &lint_settings);
+        if !violations.is_empty() {
+            return Err(git::commit_lint_error(violations).to_json());
+        }
+    }
+
+    // In block mode, refuse staged secrets unless the user chose to commit them
+    // anyway; in warn mode, report them with the new commit
+    let settings = git::load_secret_scan_settings().map_err(|e| e.to_string())?;
+    let secret_findings = if settings.mode == git::SecretScanMode::Off {
+        Vec::new()
+    } else {
+        let allowlist = settings.repositories.get(&repo_path).cloned().unwrap_or_default();
+        git::scan_staged_changes(&repo, &allowlist).map_err(|e| e.to_string())?
+    };
+    if settings.mode == git::SecretScanMode::Block && !allow_secrets.unwrap_or(false) && !secret_findings.is_empty() {
+        return Err(git::secrets_found_error(secret_findings).to_json());
+    }
+
+    // Likewise for large files when the check is set to block
+    let large_file_settings = git::load_large_file_settings().map_err(|e| e.to_string())?;
+    if large_file_settings.block && !allow_large_files.unwrap_or(false) {
+        if let Some(warning) = git::check_staged_large_files(&repo, &large_file_settings).map_err(|e| e.to_string())? {
+            return Err(git::large_files_error(warning).to_json());
+        }
+    }
+
+    let commit = git::create_commit(&repo, &message).map_err(|e| e.to_string())?;
+    Ok(CreateCommitResult { commit, secret_findings })
 }
 
 #[tauri::command]
@@ -69,6 +125,7 @@ pub fn revert_commit(sha: String, state: State<AppState>) -> Result<CommitInfo,
 pub fn reset_to_commit(
     sha: String,
     reset_type: String,
+    allow_protected: Option<bool>,
     state: State<AppState>,
 ) -> Result<(), String> {
     let repo_path = state.repo_path.lock().unwrap()
@@ -84,6 +141,10 @@ pub fn reset_to_commit(
     };
 
     let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    if matches!(reset, ResetType::Hard) && !allow_protected.unwrap_or(false) {
+        let protected = git::load_protected_branch_settings().map_err(|e| e.to_string())?;
+        protected.check(&repo, "hard_reset").map_err(|e| e.to_json())?;
+    }
     git::reset_to_commit(&repo, &sha, reset).map_err(|e| e.to_string())
 }
 
@@ -94,7 +155,7 @@ pub fn checkout_commit(sha: String, state: State<AppState>) -> Result<(), String
         .ok_or("No repository open")?
         .clone();
     let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
-    git::checkout_commit(&repo, &sha).map_err(|e| e.to_string())
+    git::checkout_commit(&repo, &sha).map_err(|e| checkout_error(&sha, e))
 }
 
 #[tauri::command]
@@ -134,10 +195,55 @@ pub fn rebase_onto(sha: String, state: State<AppState>) -> Result<(), String> {
     git::rebase_onto(&repo_path, &sha).map_err(|e| e.to_string())
 }
 
+/// Get the default plan for interactively rebasing the commits from `sha` to HEAD
+#[tauri::command]
+pub fn interactive_rebase(sha: String, state: State<AppState>) -> Result<RebasePlan, String> {
+    let repo_path = get_repo_path(&state)?;
+    let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    let commit = repo.revparse_single(&sha)
+        .and_then(|obj| obj.peel_to_commit())
+        .map_err(|e| e.to_string())?;
+    let base = commit.parent_id(0)
+        .map_err(|_| "Cannot rebase from the first commit".to_string())?;
+    git::get_rebase_plan(&repo, &base.to_string()).map_err(|e| e.to_string())
+}
+
+/// Get the default plan for interactively rebasing the commits after `base`
 #[tauri::command]
-pub fn interactive_rebase(sha: String, state: State<AppState>) -> Result<(), String> {
+pub fn get_rebase_plan(base: String, state: State<AppState>) -> Result<RebasePlan, String> {
     let repo_path = get_repo_path(&state)?;
-    git::interactive_rebase(&repo_path, &sha).map_err(|e| e.to_string())
+    let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    git::get_rebase_plan(&repo, &base).map_err(|e| e.to_string())
+}
+
+/// Start a rebase with a plan edited in the UI; it stops if a step has conflicts
+#[tauri::command]
+pub fn set_rebase_plan(plan: RebasePlan, state: State<AppState>) -> Result<RebaseProgress, String> {
+    let repo_path = get_repo_path(&state)?;
+    let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    git::set_rebase_plan(&repo, plan).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_rebase_progress(state: State<AppState>) -> Result<Option<RebaseProgress>, String> {
+    let repo_path = get_repo_path(&state)?;
+    let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    git::get_rebase_progress(&repo).map_err(|e| e.to_string())
+}
+
+/// Continue a rebase stopped on conflicts, once they are resolved and staged
+#[tauri::command]
+pub fn rebase_continue(state: State<AppState>) -> Result<RebaseProgress, String> {
+    let repo_path = get_repo_path(&state)?;
+    let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    git::rebase_continue(&repo).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn rebase_abort(state: State<AppState>) -> Result<(), String> {
+    let repo_path = get_repo_path(&state)?;
+    let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    git::rebase_abort(&repo).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -148,20 +254,36 @@ pub fn delete_tag(tag_name: String, state: State<AppState>) -> Result<(), String
 }
 
 #[tauri::command]
-pub fn squash_commits(sha: String, state: State<AppState>) -> Result<(), String> {
+pub fn squash_commits(sha: String, state: State<AppState>) -> Result<RebaseProgress, String> {
     let repo_path = get_repo_path(&state)?;
-    git::squash_commits(&repo_path, &sha).map_err(|e| e.to_string())
+    let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    git::squash_commits(&repo, &sha).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub fn amend_commit_message(sha: String, message: String, state: State<AppState>) -> Result<CommitInfo, String> {
     let repo_path = get_repo_path(&state)?;
     let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
-    git::amend_commit_message(&repo, &repo_path, &sha, &message).map_err(|e| e.to_string())
+    git::amend_commit_message(&repo, &sha, &message).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn drop_commit(sha: String, state: State<AppState>) -> Result<RebaseProgress, String> {
+    let repo_path = get_repo_path(&state)?;
+    let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    git::drop_commit(&repo, &sha).map_err(|e| e.to_string())
 }
 
+/// Write the (optionally filtered) commit history with change statistics to a
+/// JSON or CSV file, returning the number of commits written
 #[tauri::command]
-pub fn drop_commit(sha: String, state: State<AppState>) -> Result<(), String> {
+pub fn export_history(
+    format: HistoryExportFormat,
+    filters: Option<HistoryFilters>,
+    path: String,
+    state: State<AppState>,
+) -> Result<usize, String> {
     let repo_path = get_repo_path(&state)?;
-    git::drop_commit(&repo_path, &sha).map_err(|e| e.to_string())
+    let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    git::export_history_to_file(&repo, format, &filters.unwrap_or_default(), &path).map_err(|e| e.to_string())
 }
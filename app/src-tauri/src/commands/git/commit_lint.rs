@@ -0,0 +1,22 @@
+use crate::git::{self, CommitLintSettings, LintViolation};
+
+/// Check a commit message against the lint rules; nothing is reported while
+/// linting is disabled
+#[tauri::command]
+pub fn lint_commit_message(message: String) -> Result<Vec<LintViolation>, String> {
+    let settings = git::load_commit_lint_settings().map_err(|e| e.to_string())?;
+    if !settings.enabled {
+        return Ok(Vec::new());
+    }
+    Ok(git::lint_commit_message(&message, &settings))
+}
+
+#[tauri::command]
+pub fn get_commit_lint_settings() -> Result<CommitLintSettings, String> {
+    git::load_commit_lint_settings().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn set_commit_lint_settings(settings: CommitLintSettings) -> Result<(), String> {
+    git::save_commit_lint_settings(&settings).map_err(|e| e.to_string())
+}
@@ -1,5 +1,5 @@
 use tauri::State;
-use crate::git::{self, FileDiff};
+use crate::git::{self, DiffSummary, FileDiff};
 use crate::commands::state::AppState;
 
 #[tauri::command]
@@ -11,3 +11,31 @@ pub fn get_file_diff(path: String, staged: bool, state: State<AppState>) -> Resu
     let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
     git::get_file_diff(&repo, &path, staged).map_err(|e| e.to_string())
 }
+
+/// Get the changed files with their line counts, without their hunks
+#[tauri::command]
+pub fn get_diff_summary(staged: bool, state: State<AppState>) -> Result<DiffSummary, String> {
+    let repo_path = state.repo_path.lock().unwrap()
+        .as_ref()
+        .ok_or("No repository open")?
+        .clone();
+    let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    git::get_diff_summary(&repo, staged).map_err(|e| e.to_string())
+}
+
+/// Apply a patch of one file edited in the UI, such as a tweaked hunk, to the
+/// index (`to_index`) or the working directory
+#[tauri::command]
+pub fn apply_edited_patch(
+    path: String,
+    patch_text: String,
+    to_index: bool,
+    state: State<AppState>,
+) -> Result<(), String> {
+    let repo_path = state.repo_path.lock().unwrap()
+        .as_ref()
+        .ok_or("No repository open")?
+        .clone();
+    let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    git::apply_edited_patch(&repo, &path, &patch_text, to_index).map_err(|e| e.to_string())
+}
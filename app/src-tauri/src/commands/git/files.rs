@@ -0,0 +1,56 @@
+use tauri::State;
+use crate::git::{self, StatusInfo, WorktreeFile};
+use crate::commands::state::AppState;
+
+/// Open a file of the open repository for editing; binary and too large files
+/// come back without their content
+#[tauri::command]
+pub fn read_repository_file(path: String, state: State<AppState>) -> Result<WorktreeFile, String> {
+    let repo_path = state.repo_path.lock().unwrap()
+        .as_ref()
+        .ok_or("No repository open")?
+        .clone();
+    let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    git::read_worktree_file(&repo, &path).map_err(|e| e.to_string())
+}
+
+/// Save a file of the open repository, staging it when `stage` is set, and
+/// return the refreshed status
+#[tauri::command]
+pub fn write_repository_file(
+    path: String,
+    content: String,
+    stage: Option<bool>,
+    state: State<AppState>,
+) -> Result<StatusInfo, String> {
+    let repo_path = state.repo_path.lock().unwrap()
+        .as_ref()
+        .ok_or("No repository open")?
+        .clone();
+    let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    git::write_worktree_file(&repo, &path, &content).map_err(|e| e.to_string())?;
+    if stage.unwrap_or(false) {
+        git::stage_files(&repo, &[path]).map_err(|e| e.to_string())?;
+    }
+    git::get_repo_status(&repo).map_err(|e| e.to_string())
+}
+
+/// Delete a file of the open repository, staging the deletion when `stage` is
+/// set, and return the refreshed status
+#[tauri::command]
+pub fn delete_repository_file(
+    path: String,
+    stage: Option<bool>,
+    state: State<AppState>,
+) -> Result<StatusInfo, String> {
+    let repo_path = state.repo_path.lock().unwrap()
+        .as_ref()
+        .ok_or("No repository open")?
+        .clone();
+    let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    git::delete_worktree_file(&repo, &path).map_err(|e| e.to_string())?;
+    if stage.unwrap_or(false) {
+        git::stage_files(&repo, &[path]).map_err(|e| e.to_string())?;
+    }
+    git::get_repo_status(&repo).map_err(|e| e.to_string())
+}
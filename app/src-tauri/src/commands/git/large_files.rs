@@ -0,0 +1,27 @@
+use tauri::State;
+use crate::git::{self, LargeFileCheckSettings, LargeFileWarning};
+use crate::commands::state::AppState;
+
+#[tauri::command]
+pub fn check_staged_large_files(state: State<AppState>) -> Result<Option<LargeFileWarning>, String> {
+    let repo_path = state.repo_path.lock().unwrap()
+        .as_ref()
+        .ok_or("No repository open")?
+        .clone();
+    let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    let settings = git::load_large_file_settings().map_err(|e| e.to_string())?;
+    git::check_staged_large_files(&repo, &settings).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_large_file_settings() -> Result<LargeFileCheckSettings, String> {
+    git::load_large_file_settings().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn set_large_file_settings(settings: LargeFileCheckSettings) -> Result<(), String> {
+    if settings.threshold_bytes == 0 {
+        return Err("The size threshold must be greater than zero".to_string());
+    }
+    git::save_large_file_settings(&settings).map_err(|e| e.to_string())
+}
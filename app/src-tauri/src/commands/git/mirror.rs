@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, State};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::commands::state::AppState;
+use crate::git::{self, MirrorConfig, MIRROR_STATUS_EVENT};
+
+/// Interval between checks for scheduled mirror pushes that are due
+const MIRROR_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+fn get_repo_path(state: &State<AppState>) -> Result<String, String> {
+    state.repo_path.lock().unwrap()
+        .as_ref()
+        .ok_or("No repository open".to_string())
+        .cloned()
+}
+
+/// Mirror a repository in the background, emitting its updated status and
+/// notifying when the push fails
+async fn mirror_repo(app: &AppHandle, repo_path: String) -> Result<MirrorConfig, String> {
+    let mirror = tokio::task::spawn_blocking(move || git::run_mirror(&repo_path))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+
+    let _ = app.emit(MIRROR_STATUS_EVENT, &mirror);
+    if let Some(error) = &mirror.status.last_error {
+        let _ = app
+            .notification()
+            .builder()
+            .title(format!("Mirror push to {} failed", mirror.remote))
+            .body(format!("{}: {}", mirror.repo_path, error))
+            .show();
+    }
+    Ok(mirror)
+}
+
+/// Mirror the repository after a successful push, if its mirror asks for it
+pub(crate) fn mirror_after_push(app: &AppHandle, repo_path: &str) {
+    let wanted = git::load_mirror_settings()
+        .ok()
+        .and_then(|settings| settings.find(repo_path).cloned())
+        .is_some_and(|mirror| mirror.enabled && mirror.after_push);
+    if wanted {
+        let app = app.clone();
+        let repo_path = repo_path.to_string();
+        tauri::async_runtime::spawn(async move {
+            let _ = mirror_repo(&app, repo_path).await;
+        });
+    }
+}
+
+/// Push every mirror whose schedule is due, checking every minute, forever
+pub async fn run_mirror_scheduler(app: AppHandle) {
+    loop {
+        match git::load_mirror_settings() {
+            Ok(settings) => {
+                let now = chrono::Utc::now().timestamp();
+                for mirror in settings.mirrors.iter().filter(|m| m.is_due(now)) {
+                    if let Err(e) = mirror_repo(&app, mirror.repo_path.clone()).await {
+                        tracing::warn!("Mirror push of {} failed: {}", mirror.repo_path, e);
+                    }
+                }
+            }
+            Err(e) => tracing::warn!("Could not load mirror settings: {}", e),
+        }
+
+        tokio::time::sleep(MIRROR_CHECK_INTERVAL).await;
+    }
+}
+
+/// Get the mirror configuration and last push status of the open repository
+#[tauri::command]
+pub fn get_mirror_config(state: State<AppState>) -> Result<Option<MirrorConfig>, String> {
+    let repo_path = get_repo_path(&state)?;
+    let settings = git::load_mirror_settings().map_err(|e| e.to_string())?;
+    Ok(settings.find(&repo_path).cloned())
+}
+
+/// Configure the backup remote of the open repository
+#[tauri::command]
+pub fn save_mirror_config(
+    remote: String,
+    interval_minutes: Option<u64>,
+    after_push: bool,
+    enabled: bool,
+    prune: Option<bool>,
+    state: State<AppState>,
+) -> Result<MirrorConfig, String> {
+    let repo_path = get_repo_path(&state)?;
+    let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    git::validate_mirror_remote(&repo, remote.trim()).map_err(|e| e.to_string())?;
+
+    git::update_mirror_settings(|settings| {
+        settings.upsert(MirrorConfig {
+            repo_path: repo_path.clone(),
+            remote,
+            interval_minutes,
+            after_push,
+            enabled,
+            prune: prune.unwrap_or(false),
+            status: Default::default(),
+        })?;
+        settings
+            .find(&repo_path)
+            .cloned()
+            .ok_or_else(|| git::GitError::Generic("Mirror was not saved".to_string()))
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn delete_mirror_config(state: State<AppState>) -> Result<(), String> {
+    let repo_path = get_repo_path(&state)?;
+    git::update_mirror_settings(|settings| {
+        if settings.remove(&repo_path) {
+            Ok(())
+        } else {
+            Err(git::GitError::Generic("No mirror configured for this repository".to_string()))
+        }
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Mirror the open repository now, returning the updated status
+#[tauri::command]
+pub async fn run_mirror_push(app: AppHandle, state: State<'_, AppState>) -> Result<MirrorConfig, String> {
+    let repo_path = get_repo_path(&state)?;
+    mirror_repo(&app, repo_path).await
+}
@@ -6,6 +6,16 @@ mod branch;
 mod diff;
 mod remote;
 mod workflow;
+mod files;
+mod secrets;
+mod large_files;
+mod commit_lint;
+mod protected;
+mod workspaces;
+mod search_index;
+mod time_format;
+mod mirror;
+mod blame;
 
 pub use repository::*;
 pub use config::*;
@@ -15,3 +25,13 @@ pub use branch::*;
 pub use diff::*;
 pub use remote::*;
 pub use workflow::*;
+pub use files::*;
+pub use secrets::*;
+pub use large_files::*;
+pub use commit_lint::*;
+pub use protected::*;
+pub use workspaces::*;
+pub use search_index::*;
+pub use time_format::*;
+pub use mirror::*;
+pub use blame::*;
@@ -0,0 +1,12 @@
+use crate::git::{self, ProtectedBranchSettings};
+
+#[tauri::command]
+pub fn get_protected_branches() -> Result<ProtectedBranchSettings, String> {
+    git::load_protected_branch_settings().map_err(|e| e.to_string())
+}
+
+/// Set the branch patterns that are protected from commits, hard resets, and force pushes
+#[tauri::command]
+pub fn set_protected_branches(settings: ProtectedBranchSettings) -> Result<(), String> {
+    git::save_protected_branch_settings(&settings).map_err(|e| e.to_string())
+}
@@ -1,6 +1,7 @@
-use tauri::State;
+use tauri::{AppHandle, State};
 use crate::git::{self, RemoteInfo, FetchResult, PullResult, PushResult};
 use crate::commands::state::AppState;
+use super::mirror::mirror_after_push;
 
 #[tauri::command]
 pub fn get_remotes(state: State<AppState>) -> Result<Vec<RemoteInfo>, String> {
@@ -53,22 +54,37 @@ pub fn fetch_all_remotes(state: State<AppState>) -> Result<Vec<FetchResult>, Str
 }
 
 #[tauri::command]
-pub fn pull_remote(state: State<AppState>) -> Result<PullResult, String> {
+pub fn pull_remote(auto_stash: Option<bool>, state: State<AppState>) -> Result<PullResult, String> {
     let repo_path = state.repo_path.lock().unwrap()
         .as_ref()
         .ok_or("No repository open")?
         .clone();
 
-    let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    let mut repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
     let remote_name = git::get_default_remote(&repo).map_err(|e| e.to_string())?;
     let head = repo.head().map_err(|e| e.to_string())?;
     let branch_name = head.shorthand().unwrap_or("main").to_string();
+    drop(head);
+
+    if !auto_stash.unwrap_or(false) {
+        return git::pull(&repo, &remote_name, &branch_name).map_err(|e| e.to_string());
+    }
 
-    git::pull(&repo, &remote_name, &branch_name).map_err(|e| e.to_string())
+    let (mut result, outcome) = git::with_auto_stash(&mut repo, "pull", |repo| {
+        git::pull(repo, &remote_name, &branch_name)
+    })
+    .map_err(|e| e.to_structured().to_json())?;
+    result.auto_stash = Some(outcome);
+    Ok(result)
 }
 
 #[tauri::command]
-pub fn push_remote(state: State<AppState>) -> Result<PushResult, String> {
+pub fn push_remote(
+    force: Option<bool>,
+    allow_protected: Option<bool>,
+    app: AppHandle,
+    state: State<AppState>,
+) -> Result<PushResult, String> {
     let repo_path = state.repo_path.lock().unwrap()
         .as_ref()
         .ok_or("No repository open")?
@@ -79,5 +95,17 @@ pub fn push_remote(state: State<AppState>) -> Result<PushResult, String> {
     let head = repo.head().map_err(|e| e.to_string())?;
     let branch_name = head.shorthand().unwrap_or("main").to_string();
 
-    git::push(&repo, &remote_name, &branch_name).map_err(|e| e.to_string())
+    let result = if force.unwrap_or(false) {
+        if !allow_protected.unwrap_or(false) {
+            let protected = git::load_protected_branch_settings().map_err(|e| e.to_string())?;
+            protected.check(&repo, "force_push").map_err(|e| e.to_json())?;
+        }
+        git::force_push(&repo, &remote_name, &branch_name)
+    } else {
+        git::push(&repo, &remote_name, &branch_name)
+    }
+    .map_err(|e| e.to_string())?;
+
+    mirror_after_push(&app, &repo_path);
+    Ok(result)
 }
@@ -1,5 +1,13 @@
-use tauri::State;
-use crate::git::{self, RepoInfo, SyncStatus};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use chrono::{Datelike, Local};
+use tauri::{AppHandle, Emitter, State};
+use crate::git::{
+    self, CommitActivity, RepoHealth, RepoInfo, RepoSyncSummary, SyncStatus, REPO_SCAN_PROGRESS_EVENT,
+};
+use crate::github;
 use crate::commands::state::AppState;
 
 #[tauri::command]
@@ -11,8 +19,46 @@ pub fn open_repository(path: String, state: State<AppState>) -> Result<RepoInfo,
 }
 
 #[tauri::command]
-pub fn init_repository(path: String, state: State<AppState>) -> Result<RepoInfo, String> {
-    let repo = git::init_repo(&path).map_err(|e| e.to_string())?;
+pub async fn init_repository(
+    path: String,
+    gitignore_template: Option<String>,
+    license: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<RepoInfo, String> {
+    let holder = {
+        let repo = git::init_repo(&path).map_err(|e| e.to_string())?;
+        repo.config()
+            .and_then(|c| c.get_string("user.name"))
+            .unwrap_or_default()
+    };
+    let workdir = Path::new(&path);
+
+    if let Some(name) = gitignore_template {
+        let target = workdir.join(".gitignore");
+        if !target.exists() {
+            let template = github::templates::get_gitignore_template(&name)
+                .await
+                .map_err(|e| e.to_string())?;
+            std::fs::write(&target, template.source).map_err(|e| e.to_string())?;
+        }
+    }
+
+    if let Some(key) = license {
+        let target = workdir.join("LICENSE");
+        if !target.exists() {
+            let license = github::templates::get_license(&key)
+                .await
+                .map_err(|e| e.to_string())?;
+            let body = github::templates::fill_license_placeholders(
+                &license.body,
+                Local::now().year(),
+                &holder,
+            );
+            std::fs::write(&target, body).map_err(|e| e.to_string())?;
+        }
+    }
+
+    let repo = git::open_repo(&path).map_err(|e| e.to_string())?;
     let info = git::get_repo_info(&repo).map_err(|e| e.to_string())?;
     *state.repo_path.lock().unwrap() = Some(path);
     Ok(info)
@@ -28,14 +74,70 @@ pub fn get_repository_info(state: State<AppState>) -> Result<RepoInfo, String> {
     git::get_repo_info(&repo).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn get_repo_health(state: State<AppState>) -> Result<RepoHealth, String> {
+    let path = state.repo_path.lock().unwrap()
+        .as_ref()
+        .ok_or("No repository open")?
+        .clone();
+    let repo = git::open_repo(&path).map_err(|e| e.to_string())?;
+    git::get_repo_health(&repo).map_err(|e| e.to_string())
+}
+
+/// Commits per day over the past year, for the activity heatmap
+#[tauri::command]
+pub fn get_commit_activity(author: Option<String>, state: State<AppState>) -> Result<CommitActivity, String> {
+    let path = state.repo_path.lock().unwrap()
+        .as_ref()
+        .ok_or("No repository open")?
+        .clone();
+    let repo = git::open_repo(&path).map_err(|e| e.to_string())?;
+    git::get_commit_activity(&repo, author.as_deref()).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn clone_repository(url: String, path: String) -> Result<RepoInfo, String> {
     git::clone_repository(&url, &path, None).map_err(|e| e.to_string())
 }
 
+/// Scan a directory for repositories in the background
+///
+/// Progress is emitted as `repo-scan-progress` events. A cancelled scan returns
+/// the repositories found so far. Only one scan can be in progress at a time.
+#[tauri::command]
+pub async fn scan_for_repos(
+    app: AppHandle,
+    path: String,
+    max_depth: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<RepoInfo>, String> {
+    let cancel = Arc::new(AtomicBool::new(false));
+    {
+        let mut current = state.repo_scan.lock().unwrap();
+        if current.is_some() {
+            return Err("A repository scan is already in progress".to_string());
+        }
+        *current = Some(cancel.clone());
+    }
+
+    let scan_cancel = cancel.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        git::scan_for_repositories_with(&path, max_depth.unwrap_or(3), &scan_cancel, &|progress| {
+            let _ = app.emit(REPO_SCAN_PROGRESS_EVENT, progress);
+        })
+    })
+    .await;
+
+    *state.repo_scan.lock().unwrap() = None;
+    result.map_err(|e| e.to_string())?.map_err(|e| e.to_string())
+}
+
 #[tauri::command]
-pub fn scan_for_repos(path: String, max_depth: Option<usize>) -> Result<Vec<RepoInfo>, String> {
-    git::scan_for_repositories(&path, max_depth.unwrap_or(3)).map_err(|e| e.to_string())
+pub fn cancel_repo_scan(state: State<AppState>) -> Result<(), String> {
+    if let Some(cancel) = state.repo_scan.lock().unwrap().as_ref() {
+        cancel.store(true, Ordering::Relaxed);
+    }
+    Ok(())
 }
 
 #[tauri::command]
@@ -47,3 +149,11 @@ pub fn get_repo_sync_status(state: State<AppState>) -> Result<SyncStatus, String
     let repo = git::open_repo(&path).map_err(|e| e.to_string())?;
     git::get_sync_status(&repo).map_err(|e| e.to_string())
 }
+
+/// Get the sync status and dirty state of many repositories concurrently
+#[tauri::command]
+pub async fn get_all_repo_sync_statuses(paths: Vec<String>) -> Result<Vec<RepoSyncSummary>, String> {
+    tokio::task::spawn_blocking(move || git::get_all_repo_sync_statuses(&paths))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,87 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use tauri::{AppHandle, Emitter, State};
+
+use crate::commands::state::AppState;
+use crate::git::{self, CommitInfo, SearchIndexProgress, SearchIndexStatus, SEARCH_INDEX_PROGRESS_EVENT};
+
+fn get_repo_path(state: &State<AppState>) -> Result<String, String> {
+    state.repo_path.lock().unwrap()
+        .as_ref()
+        .ok_or("No repository open".to_string())
+        .cloned()
+}
+
+#[tauri::command]
+pub fn search_index_status(state: State<AppState>) -> Result<SearchIndexStatus, String> {
+    let repo_path = get_repo_path(&state)?;
+    let index_path = git::search_index_path(&repo_path).map_err(|e| e.to_string())?;
+    let mut status = git::search_index_status(&index_path).map_err(|e| e.to_string())?;
+    status.building = state.search_index_build.lock().unwrap().is_some();
+    Ok(status)
+}
+
+/// Build or update the commit search index of the open repository in the background
+///
+/// Progress is emitted as `search-index-progress` events. Returns the number of
+/// commits added. Only one build can be in progress at a time.
+#[tauri::command]
+pub async fn build_search_index(app: AppHandle, state: State<'_, AppState>) -> Result<u64, String> {
+    let repo_path = get_repo_path(&state)?;
+    let index_path = git::search_index_path(&repo_path).map_err(|e| e.to_string())?;
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    {
+        let mut current = state.search_index_build.lock().unwrap();
+        if current.is_some() {
+            return Err("The search index is already being built".to_string());
+        }
+        *current = Some(cancel.clone());
+    }
+
+    let build_cancel = cancel.clone();
+    let progress_app = app.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        let repo = git::open_repo(&repo_path)?;
+        git::update_search_index(&repo, &index_path, &build_cancel, |added| {
+            let _ = progress_app.emit(SEARCH_INDEX_PROGRESS_EVENT, SearchIndexProgress { added, done: false });
+        })
+    })
+    .await;
+
+    *state.search_index_build.lock().unwrap() = None;
+    let added = result.map_err(|e| e.to_string())?.map_err(|e| e.to_string())?;
+    let _ = app.emit(SEARCH_INDEX_PROGRESS_EVENT, SearchIndexProgress { added, done: true });
+    Ok(added)
+}
+
+#[tauri::command]
+pub fn cancel_search_index_build(state: State<AppState>) -> Result<(), String> {
+    if let Some(cancel) = state.search_index_build.lock().unwrap().as_ref() {
+        cancel.store(true, Ordering::Relaxed);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn search_indexed_commits(
+    query: String,
+    limit: Option<usize>,
+    state: State<AppState>,
+) -> Result<Vec<CommitInfo>, String> {
+    let repo_path = get_repo_path(&state)?;
+    let index_path = git::search_index_path(&repo_path).map_err(|e| e.to_string())?;
+    let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    git::search_indexed_commits(&repo, &index_path, &query, limit.unwrap_or(50)).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn delete_search_index(state: State<AppState>) -> Result<(), String> {
+    if state.search_index_build.lock().unwrap().is_some() {
+        return Err("The search index is being built".to_string());
+    }
+    let repo_path = get_repo_path(&state)?;
+    let index_path = git::search_index_path(&repo_path).map_err(|e| e.to_string())?;
+    git::delete_search_index(&index_path).map_err(|e| e.to_string())
+}
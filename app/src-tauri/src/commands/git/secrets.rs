@@ -0,0 +1,49 @@
+use tauri::State;
+use crate::git::{self, SecretAllowlist, SecretFinding, SecretScanMode, SecretScanSettings};
+use crate::commands::state::AppState;
+
+/// Scan the staged changes of the open repository for secrets, applying its allowlist
+#[tauri::command]
+pub fn scan_staged_secrets(state: State<AppState>) -> Result<Vec<SecretFinding>, String> {
+    let repo_path = state.repo_path.lock().unwrap()
+        .as_ref()
+        .ok_or("No repository open")?
+        .clone();
+    let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    let settings = git::load_secret_scan_settings().map_err(|e| e.to_string())?;
+    if settings.mode == SecretScanMode::Off {
+        return Ok(Vec::new());
+    }
+    let allowlist = settings.repositories.get(&repo_path).cloned().unwrap_or_default();
+    git::scan_staged_changes(&repo, &allowlist).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_secret_scan_settings() -> Result<SecretScanSettings, String> {
+    git::load_secret_scan_settings().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn set_secret_scan_mode(mode: SecretScanMode) -> Result<(), String> {
+    let mut settings = git::load_secret_scan_settings().map_err(|e| e.to_string())?;
+    settings.mode = mode;
+    git::save_secret_scan_settings(&settings).map_err(|e| e.to_string())
+}
+
+/// Set the allowlist of the open repository, or remove it when `allowlist` is `None`
+#[tauri::command]
+pub fn set_repo_secret_allowlist(
+    allowlist: Option<SecretAllowlist>,
+    state: State<AppState>,
+) -> Result<(), String> {
+    let repo_path = state.repo_path.lock().unwrap()
+        .as_ref()
+        .ok_or("No repository open")?
+        .clone();
+    let mut settings = git::load_secret_scan_settings().map_err(|e| e.to_string())?;
+    match allowlist {
+        Some(allowlist) => settings.repositories.insert(repo_path, allowlist),
+        None => settings.repositories.remove(&repo_path),
+    };
+    git::save_secret_scan_settings(&settings).map_err(|e| e.to_string())
+}
@@ -1,5 +1,5 @@
 use tauri::State;
-use crate::git::{self, StatusInfo};
+use crate::git::{self, LargeFileWarning, StatusInfo};
 use crate::commands::state::AppState;
 
 #[tauri::command]
@@ -13,13 +13,17 @@ pub fn get_status(state: State<AppState>) -> Result<StatusInfo, String> {
 }
 
 #[tauri::command]
-pub fn stage_files(paths: Vec<String>, state: State<AppState>) -> Result<(), String> {
+pub fn stage_files(paths: Vec<String>, state: State<AppState>) -> Result<Option<LargeFileWarning>, String> {
     let repo_path = state.repo_path.lock().unwrap()
         .as_ref()
         .ok_or("No repository open")?
         .clone();
     let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
-    git::stage_files(&repo, &paths).map_err(|e| e.to_string())
+    git::stage_files(&repo, &paths).map_err(|e| e.to_string())?;
+
+    // Staging succeeded; a failing size check only loses the warning
+    let settings = git::load_large_file_settings().unwrap_or_default();
+    Ok(git::check_staged_large_files(&repo, &settings).ok().flatten())
 }
 
 #[tauri::command]
@@ -41,3 +45,17 @@ pub fn discard_changes(paths: Vec<String>, state: State<AppState>) -> Result<(),
     let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
     git::discard_changes(&repo, &paths).map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub fn stage_commit_group(
+    staged_tree: String,
+    files: Vec<String>,
+    state: State<AppState>,
+) -> Result<(), String> {
+    let repo_path = state.repo_path.lock().unwrap()
+        .as_ref()
+        .ok_or("No repository open")?
+        .clone();
+    let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    git::stage_paths_from_tree(&repo, &staged_tree, &files).map_err(|e| e.to_string())
+}
@@ -0,0 +1,19 @@
+use crate::git::{self, FormattedTime, TimeFormatSettings};
+
+#[tauri::command]
+pub fn get_time_format_settings() -> TimeFormatSettings {
+    git::time_format_settings()
+}
+
+/// Save the locale and absolute format used for commit dates
+#[tauri::command]
+pub fn set_time_format_settings(settings: TimeFormatSettings) -> Result<TimeFormatSettings, String> {
+    git::save_time_format_settings(&settings).map_err(|e| e.to_string())?;
+    Ok(settings)
+}
+
+/// Relative and absolute renderings of Unix timestamps with the current settings
+#[tauri::command]
+pub fn format_timestamps(timestamps: Vec<i64>) -> Vec<FormattedTime> {
+    timestamps.into_iter().map(git::format_time).collect()
+}
@@ -1,7 +1,10 @@
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 use walkdir::{DirEntry, WalkDir};
 
+use crate::github::workflow_templates::{self, WorkflowTemplate};
+
 fn is_hidden_or_excluded(entry: &DirEntry) -> bool {
     let name = entry.file_name().to_string_lossy();
     name.starts_with('.')
@@ -67,13 +70,38 @@ pub fn list_workflow_files(repo_path: String) -> Result<Vec<String>, String> {
     Ok(workflows)
 }
 
+/// List the built-in workflow templates
+#[tauri::command]
+pub fn list_workflow_templates() -> Vec<WorkflowTemplate> {
+    workflow_templates::list_workflow_templates().to_vec()
+}
+
+/// Render a workflow template with the given values, for previewing
+#[tauri::command]
+pub fn render_workflow_template(
+    template_id: String,
+    values: HashMap<String, String>,
+) -> Result<String, String> {
+    workflow_templates::render_workflow_template(&template_id, &values).map_err(|e| e.to_string())
+}
+
 /// Create a workflow file in .github/workflows directory
+///
+/// The file is created from `template_id` filled in with `template_values` when
+/// a template is given, otherwise from `content` (empty when missing).
 #[tauri::command]
 pub fn create_workflow_file(
     repo_path: String,
     workflow_path: String,
-    content: String,
+    content: Option<String>,
+    template_id: Option<String>,
+    template_values: Option<HashMap<String, String>>,
 ) -> Result<(), String> {
+    let content = match template_id {
+        Some(id) => workflow_templates::render_workflow_template(&id, &template_values.unwrap_or_default())
+            .map_err(|e| e.to_string())?,
+        None => content.unwrap_or_default(),
+    };
     let full_path = Path::new(&repo_path).join(&workflow_path);
 
     // Create parent directories if they don't exist
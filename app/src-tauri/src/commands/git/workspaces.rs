@@ -0,0 +1,62 @@
+use crate::git::{self, FetchResult, PullResult, RepoSyncSummary, Workspace, WorkspaceBatchResult};
+
+fn find_workspace(name: &str) -> Result<Workspace, String> {
+    let settings = git::load_workspace_settings().map_err(|e| e.to_string())?;
+    settings
+        .find(name)
+        .cloned()
+        .ok_or_else(|| format!("Workspace '{}' not found", name))
+}
+
+#[tauri::command]
+pub fn get_workspaces() -> Result<Vec<Workspace>, String> {
+    git::load_workspace_settings()
+        .map(|settings| settings.workspaces)
+        .map_err(|e| e.to_string())
+}
+
+/// Create a workspace, or replace the one with the same name
+#[tauri::command]
+pub fn save_workspace(workspace: Workspace) -> Result<Vec<Workspace>, String> {
+    let mut settings = git::load_workspace_settings().map_err(|e| e.to_string())?;
+    settings.upsert(workspace).map_err(|e| e.to_string())?;
+    git::save_workspace_settings(&settings).map_err(|e| e.to_string())?;
+    Ok(settings.workspaces)
+}
+
+#[tauri::command]
+pub fn delete_workspace(name: String) -> Result<Vec<Workspace>, String> {
+    let mut settings = git::load_workspace_settings().map_err(|e| e.to_string())?;
+    if !settings.remove(&name) {
+        return Err(format!("Workspace '{}' not found", name));
+    }
+    git::save_workspace_settings(&settings).map_err(|e| e.to_string())?;
+    Ok(settings.workspaces)
+}
+
+#[tauri::command]
+pub async fn fetch_workspace(name: String) -> Result<WorkspaceBatchResult<FetchResult>, String> {
+    let workspace = find_workspace(&name)?;
+    tokio::task::spawn_blocking(move || git::fetch_workspace(&workspace))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn pull_workspace(
+    name: String,
+    auto_stash: Option<bool>,
+) -> Result<WorkspaceBatchResult<PullResult>, String> {
+    let workspace = find_workspace(&name)?;
+    tokio::task::spawn_blocking(move || git::pull_workspace(&workspace, auto_stash.unwrap_or(false)))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_workspace_status(name: String) -> Result<Vec<RepoSyncSummary>, String> {
+    let workspace = find_workspace(&name)?;
+    tokio::task::spawn_blocking(move || git::get_workspace_status(&workspace))
+        .await
+        .map_err(|e| e.to_string())
+}
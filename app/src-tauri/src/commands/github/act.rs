@@ -0,0 +1,148 @@
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tauri::{AppHandle, Emitter, State};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+
+use crate::commands::state::AppState;
+use crate::github::act::{
+    self, ActStatus, LocalWorkflowRunEvent, LocalWorkflowRunOptions, LocalWorkflowRunResult,
+    LOCAL_WORKFLOW_RUN_EVENT,
+};
+
+/// How often a running `act` checks whether it was cancelled
+const CANCEL_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// How long `act` gets to stop its containers after an interrupt before it is killed
+const INTERRUPT_GRACE_PERIOD: Duration = Duration::from_secs(10);
+
+fn emit_run_event(app: &AppHandle, state: Option<&str>, line: Option<String>) {
+    let _ = app.emit(LOCAL_WORKFLOW_RUN_EVENT, LocalWorkflowRunEvent {
+        state: state.map(|s| s.to_string()),
+        line,
+    });
+}
+
+/// Forward each line of an output stream of `act` as a run event
+async fn forward_output(app: AppHandle, stream: impl AsyncRead + Unpin) {
+    let mut lines = BufReader::new(stream).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        emit_run_event(&app, None, Some(line));
+    }
+}
+
+/// Wait for `act` to exit, interrupting it once `cancel` is set
+async fn wait_or_cancel(
+    child: &mut tokio::process::Child,
+    cancel: &AtomicBool,
+) -> std::io::Result<std::process::ExitStatus> {
+    loop {
+        tokio::select! {
+            status = child.wait() => return status,
+            _ = tokio::time::sleep(CANCEL_POLL_INTERVAL) => {
+                if !cancel.load(Ordering::Relaxed) {
+                    continue;
+                }
+                // An interrupt lets act remove its containers; kill it if it doesn't stop
+                if let Some(pid) = child.id() {
+                    let _ = tokio::process::Command::new("kill")
+                        .arg("-INT")
+                        .arg(pid.to_string())
+                        .status()
+                        .await;
+                }
+                return match tokio::time::timeout(INTERRUPT_GRACE_PERIOD, child.wait()).await {
+                    Ok(status) => status,
+                    Err(_) => {
+                        child.kill().await?;
+                        child.wait().await
+                    }
+                };
+            }
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn get_act_status() -> Result<ActStatus, String> {
+    Ok(act::detect_act().await)
+}
+
+/// Run workflows of the open repository locally with `act`
+///
+/// Output lines and state changes are streamed as `local-workflow-run` events.
+/// Only one local run can be in progress at a time.
+#[tauri::command]
+pub async fn run_workflow_locally(
+    app: AppHandle,
+    options: LocalWorkflowRunOptions,
+    state: State<'_, AppState>,
+) -> Result<LocalWorkflowRunResult, String> {
+    let repo_path = state.repo_path.lock().unwrap()
+        .as_ref()
+        .ok_or("No repository open")?
+        .clone();
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    {
+        let mut current = state.local_workflow_run.lock().unwrap();
+        if current.is_some() {
+            return Err("A local workflow run is already in progress".to_string());
+        }
+        *current = Some(cancel.clone());
+    }
+
+    let spawned = tokio::process::Command::new(act::ACT_BINARY)
+        .args(act::act_args(&options))
+        .current_dir(&repo_path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            *state.local_workflow_run.lock().unwrap() = None;
+            return Err(if e.kind() == std::io::ErrorKind::NotFound {
+                "act is not installed; see https://github.com/nektos/act".to_string()
+            } else {
+                format!("Failed to start act: {}", e)
+            });
+        }
+    };
+
+    emit_run_event(&app, Some("running"), None);
+
+    let stdout = child.stdout.take().map(|s| forward_output(app.clone(), s));
+    let stderr = child.stderr.take().map(|s| forward_output(app.clone(), s));
+    let (_, _, exit) = tokio::join!(
+        async { if let Some(f) = stdout { f.await } },
+        async { if let Some(f) = stderr { f.await } },
+        wait_or_cancel(&mut child, &cancel),
+    );
+
+    *state.local_workflow_run.lock().unwrap() = None;
+
+    let cancelled = cancel.load(Ordering::Relaxed);
+    let exit_code = exit.as_ref().ok().and_then(|s| s.code());
+    let success = !cancelled && exit.as_ref().map(|s| s.success()).unwrap_or(false);
+    let final_state = match (cancelled, success) {
+        (true, _) => "cancelled",
+        (false, true) => "success",
+        (false, false) => "failure",
+    };
+    emit_run_event(&app, Some(final_state), None);
+
+    Ok(LocalWorkflowRunResult { success, cancelled, exit_code })
+}
+
+#[tauri::command]
+pub fn cancel_local_workflow_run(state: State<AppState>) -> Result<(), String> {
+    if let Some(cancel) = state.local_workflow_run.lock().unwrap().as_ref() {
+        cancel.store(true, Ordering::Relaxed);
+    }
+    Ok(())
+}
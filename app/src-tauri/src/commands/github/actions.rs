@@ -1,4 +1,15 @@
-use crate::github::actions::{Workflow, WorkflowRun, WorkflowJob, Artifact};
+use crate::github::actions::{
+    Workflow, WorkflowRun, WorkflowJob, Artifact, ActionsCache, ActionsCacheList, ActionsCacheUsage,
+    WorkflowRunUsage, RepoActionsUsage,
+};
+use crate::github::job_logs::JobLog;
+use crate::github::run_watcher::{
+    diff_snapshots, next_poll_interval, RunSnapshot, WorkflowRunUpdate, RUN_COMPLETED_EVENT,
+    RUN_UPDATE_EVENT, RUN_WATCH_ERROR_EVENT,
+};
+use crate::commands::state::AppState;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_notification::NotificationExt;
 
 #[tauri::command]
 pub async fn github_list_workflows(owner: String, repo: String) -> Result<Vec<Workflow>, String> {
@@ -61,6 +72,17 @@ pub async fn github_get_workflow_run_logs(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn github_get_job_log(
+    owner: String,
+    repo: String,
+    job_id: i64,
+) -> Result<JobLog, String> {
+    crate::github::actions::get_job_log(&owner, &repo, job_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn github_trigger_workflow(
     owner: String,
@@ -157,3 +179,202 @@ pub async fn github_delete_workflow_run(
         .await
         .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub async fn github_list_caches(
+    owner: String,
+    repo: String,
+    key: Option<String>,
+    git_ref: Option<String>,
+    sort: Option<String>,
+    per_page: Option<u32>,
+) -> Result<ActionsCacheList, String> {
+    crate::github::actions::list_caches(
+        &owner,
+        &repo,
+        key.as_deref(),
+        git_ref.as_deref(),
+        sort.as_deref(),
+        per_page,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_get_cache_usage(
+    owner: String,
+    repo: String,
+) -> Result<ActionsCacheUsage, String> {
+    crate::github::actions::get_cache_usage(&owner, &repo)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_delete_cache(
+    owner: String,
+    repo: String,
+    cache_id: i64,
+) -> Result<(), String> {
+    crate::github::actions::delete_cache(&owner, &repo, cache_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_delete_caches_by_key(
+    owner: String,
+    repo: String,
+    key: String,
+    git_ref: Option<String>,
+) -> Result<Vec<ActionsCache>, String> {
+    crate::github::actions::delete_caches_by_key(&owner, &repo, &key, git_ref.as_deref())
+        .await
+        .map(|deleted| deleted.actions_caches)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_get_workflow_run_usage(
+    owner: String,
+    repo: String,
+    run_id: i64,
+) -> Result<WorkflowRunUsage, String> {
+    crate::github::actions::get_workflow_run_usage(&owner, &repo, run_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_get_repo_actions_usage(
+    owner: String,
+    repo: String,
+) -> Result<RepoActionsUsage, String> {
+    crate::github::actions::get_repo_actions_usage(&owner, &repo)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Give up watching after this many consecutive failed polls
+const MAX_WATCH_FAILURES: u32 = 5;
+
+async fn fetch_run_snapshot(owner: &str, repo: &str, run_id: i64) -> Result<RunSnapshot, String> {
+    let (run, jobs) = tokio::try_join!(
+        crate::github::actions::get_workflow_run(owner, repo, run_id),
+        crate::github::actions::get_workflow_run_jobs(owner, repo, run_id),
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(RunSnapshot { run, jobs })
+}
+
+fn notify_run_completed(app: &AppHandle, snapshot: &RunSnapshot) {
+    let name = snapshot.run.name.clone().unwrap_or_else(|| "Workflow".to_string());
+    let conclusion = snapshot.run.conclusion.as_deref().unwrap_or("completed");
+    let branch = snapshot.run.head_branch.as_deref().unwrap_or("");
+
+    let _ = app
+        .notification()
+        .builder()
+        .title(format!("{} #{}: {}", name, snapshot.run.run_number, conclusion))
+        .body(format!("Workflow run on {} finished", branch))
+        .show();
+}
+
+async fn watch_run(app: AppHandle, owner: String, repo: String, run_id: i64) {
+    let mut previous: Option<RunSnapshot> = None;
+    let mut unchanged_polls = 0;
+    let mut failures = 0;
+
+    loop {
+        let watching = app.state::<AppState>().watched_runs.lock().unwrap().contains(&run_id);
+        if !watching {
+            return;
+        }
+
+        let snapshot = match fetch_run_snapshot(&owner, &repo, run_id).await {
+            Ok(snapshot) => snapshot,
+            Err(error) => {
+                failures += 1;
+                if failures >= MAX_WATCH_FAILURES {
+                    app.state::<AppState>().watched_runs.lock().unwrap().remove(&run_id);
+                    let _ = app.emit(RUN_WATCH_ERROR_EVENT, WorkflowRunUpdate {
+                        owner,
+                        repo,
+                        run_id,
+                        snapshot: None,
+                        events: Vec::new(),
+                        error: Some(error),
+                    });
+                    return;
+                }
+                tokio::time::sleep(std::time::Duration::from_secs(10 * failures as u64)).await;
+                continue;
+            }
+        };
+        failures = 0;
+
+        let events = diff_snapshots(previous.as_ref(), &snapshot);
+        if events.is_empty() {
+            unchanged_polls += 1;
+        } else {
+            unchanged_polls = 0;
+            let _ = app.emit(RUN_UPDATE_EVENT, WorkflowRunUpdate {
+                owner: owner.clone(),
+                repo: repo.clone(),
+                run_id,
+                snapshot: Some(snapshot.clone()),
+                events,
+                error: None,
+            });
+        }
+
+        if snapshot.is_complete() {
+            app.state::<AppState>().watched_runs.lock().unwrap().remove(&run_id);
+            notify_run_completed(&app, &snapshot);
+            let _ = app.emit(RUN_COMPLETED_EVENT, WorkflowRunUpdate {
+                owner,
+                repo,
+                run_id,
+                snapshot: Some(snapshot),
+                events: Vec::new(),
+                error: None,
+            });
+            return;
+        }
+
+        let interval = next_poll_interval(&snapshot, unchanged_polls);
+        previous = Some(snapshot);
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Start watching a workflow run; progress is reported through `workflow-run-*` events
+#[tauri::command]
+pub fn github_watch_workflow_run(
+    app: AppHandle,
+    owner: String,
+    repo: String,
+    run_id: i64,
+    state: State<AppState>,
+) -> Result<(), String> {
+    // Already being watched
+    if !state.watched_runs.lock().unwrap().insert(run_id) {
+        return Ok(());
+    }
+
+    tauri::async_runtime::spawn(watch_run(app, owner, repo, run_id));
+    Ok(())
+}
+
+#[tauri::command]
+pub fn github_unwatch_workflow_run(run_id: i64, state: State<AppState>) -> Result<(), String> {
+    state.watched_runs.lock().unwrap().remove(&run_id);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn github_list_watched_runs(state: State<AppState>) -> Vec<i64> {
+    state.watched_runs.lock().unwrap().iter().copied().collect()
+}
@@ -0,0 +1,31 @@
+use crate::github::app_auth::{AppAuthConfig, GitHubApp};
+
+#[tauri::command]
+pub fn github_app_get_config() -> Result<AppAuthConfig, String> {
+    crate::github::app_auth::load_config().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn github_app_register(
+    app_id: String,
+    name: String,
+    private_key: String,
+) -> Result<GitHubApp, String> {
+    crate::github::app_auth::register_app(&app_id, &name, &private_key).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn github_app_remove(app_id: String) -> Result<(), String> {
+    crate::github::app_auth::remove_app(&app_id).map_err(|e| e.to_string())
+}
+
+/// Select the GitHub App a repository authenticates with; `None` switches back to the user's token
+#[tauri::command]
+pub fn github_app_set_repo(
+    owner: String,
+    repo: String,
+    app_id: Option<String>,
+) -> Result<(), String> {
+    crate::github::app_auth::set_repo_app(&owner, &repo, app_id.as_deref()).map_err(|e| e.to_string())
+}
+
@@ -1,4 +1,4 @@
-use crate::github::{self, GitHubAuthStatus, GitHubUser, GitHubRepo};
+use crate::github::{self, GitHubAuthStatus, GitHubOrg, GitHubUser, GitHubRepo};
 
 #[tauri::command]
 pub async fn github_login() -> Result<GitHubAuthStatus, String> {
@@ -110,6 +110,41 @@ pub async fn github_get_repos(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn github_get_orgs() -> Result<Vec<GitHubOrg>, String> {
+    let token = github::get_stored_token().map_err(|e| e.to_string())?;
+    github::get_user_orgs(&token)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get a page of an organization's repositories
+///
+/// Archived and language filters are applied to the fetched page, so a page may
+/// hold fewer than `per_page` repositories.
+#[tauri::command]
+pub async fn github_get_org_repos(
+    org: String,
+    repo_type: Option<String>,
+    include_archived: Option<bool>,
+    language: Option<String>,
+    page: Option<u32>,
+    per_page: Option<u32>,
+) -> Result<Vec<GitHubRepo>, String> {
+    let token = github::get_stored_token().map_err(|e| e.to_string())?;
+    let repos = github::get_org_repos(
+        &token,
+        &org,
+        repo_type.as_deref(),
+        page.unwrap_or(1),
+        per_page.unwrap_or(30),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(github::filter_repos(repos, include_archived.unwrap_or(false), language.as_deref()))
+}
+
 #[tauri::command]
 pub fn github_get_token() -> Result<String, String> {
     github::get_stored_token().map_err(|e| e.to_string())
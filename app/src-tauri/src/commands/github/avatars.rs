@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::commands::state::AppState;
+use crate::git::{self, CommitInfo};
+
+/// A page of commits with the avatar URLs of their authors
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitsWithAvatars {
+    pub commits: Vec<CommitInfo>,
+    /// Avatar URL by author email
+    pub avatars: HashMap<String, String>,
+}
+
+/// Resolve author emails to avatar URLs, keyed by email
+#[tauri::command]
+pub async fn resolve_avatars(emails: Vec<String>) -> Result<HashMap<String, String>, String> {
+    Ok(crate::github::avatars::resolve_avatars(&emails).await)
+}
+
+/// Get a page of the commit history along with its authors' avatars
+#[tauri::command]
+pub async fn get_commits_with_avatars(
+    limit: Option<usize>,
+    skip: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<CommitsWithAvatars, String> {
+    let repo_path = state.repo_path.lock().unwrap()
+        .as_ref()
+        .ok_or("No repository open")?
+        .clone();
+    let commits = {
+        let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+        git::get_commit_history(&repo, limit.unwrap_or(100), skip.unwrap_or(0)).map_err(|e| e.to_string())?
+    };
+
+    let emails: Vec<String> = commits.iter().map(|c| c.email.clone()).collect();
+    let avatars = crate::github::avatars::resolve_avatars(&emails).await;
+    Ok(CommitsWithAvatars { commits, avatars })
+}
@@ -0,0 +1,130 @@
+use crate::github::checks::{
+    CheckAnnotation, CheckRun, CheckRunAnnotations, CheckSuite, ChecksRollup, CombinedStatus,
+    CommitStatus,
+};
+
+#[tauri::command]
+pub async fn github_get_pr_checks(
+    owner: String,
+    repo: String,
+    pull_number: i32,
+) -> Result<ChecksRollup, String> {
+    let pull_request = crate::github::pull_requests::get_pull_request(&owner, &repo, pull_number)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    crate::github::checks::get_checks_rollup(&owner, &repo, &pull_request.head.sha)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_list_check_suites(
+    owner: String,
+    repo: String,
+    git_ref: String,
+) -> Result<Vec<CheckSuite>, String> {
+    crate::github::checks::list_check_suites_for_ref(&owner, &repo, &git_ref)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_list_check_runs(
+    owner: String,
+    repo: String,
+    git_ref: String,
+) -> Result<Vec<CheckRun>, String> {
+    crate::github::checks::list_check_runs_for_ref(&owner, &repo, &git_ref)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_list_suite_check_runs(
+    owner: String,
+    repo: String,
+    check_suite_id: i64,
+) -> Result<Vec<CheckRun>, String> {
+    crate::github::checks::list_check_runs_in_suite(&owner, &repo, check_suite_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_list_check_run_annotations(
+    owner: String,
+    repo: String,
+    check_run_id: i64,
+) -> Result<Vec<CheckAnnotation>, String> {
+    crate::github::checks::list_check_run_annotations(&owner, &repo, check_run_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_get_commit_annotations(
+    owner: String,
+    repo: String,
+    sha: String,
+) -> Result<Vec<CheckRunAnnotations>, String> {
+    crate::github::checks::get_commit_annotations(&owner, &repo, &sha)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_rerequest_check_suite(
+    owner: String,
+    repo: String,
+    check_suite_id: i64,
+) -> Result<(), String> {
+    crate::github::checks::rerequest_check_suite(&owner, &repo, check_suite_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_get_combined_status(
+    owner: String,
+    repo: String,
+    git_ref: String,
+) -> Result<CombinedStatus, String> {
+    crate::github::checks::get_combined_status(&owner, &repo, &git_ref)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_list_commit_statuses(
+    owner: String,
+    repo: String,
+    git_ref: String,
+) -> Result<Vec<CommitStatus>, String> {
+    crate::github::checks::list_commit_statuses(&owner, &repo, &git_ref)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_create_commit_status(
+    owner: String,
+    repo: String,
+    sha: String,
+    state: String,
+    context: Option<String>,
+    description: Option<String>,
+    target_url: Option<String>,
+) -> Result<CommitStatus, String> {
+    crate::github::checks::create_commit_status(
+        &owner,
+        &repo,
+        &sha,
+        &state,
+        context.as_deref(),
+        description.as_deref(),
+        target_url.as_deref(),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+
+use crate::github::collaborators::{Collaborator, RepositoryInvitation};
+use crate::github::commits::RecentCommitter;
+
+/// User suggested for @mentions and assignees
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MentionCandidate {
+    pub login: String,
+    pub avatar_url: String,
+    /// `committer` or `collaborator`
+    pub source: String,
+}
+
+#[tauri::command]
+pub async fn github_list_collaborators(
+    owner: String,
+    repo: String,
+    affiliation: Option<String>,
+) -> Result<Vec<Collaborator>, String> {
+    crate::github::collaborators::list_collaborators(&owner, &repo, affiliation.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_add_collaborator(
+    owner: String,
+    repo: String,
+    username: String,
+    permission: Option<String>,
+) -> Result<Option<RepositoryInvitation>, String> {
+    crate::github::collaborators::add_collaborator(&owner, &repo, &username, permission.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_remove_collaborator(
+    owner: String,
+    repo: String,
+    username: String,
+) -> Result<(), String> {
+    crate::github::collaborators::remove_collaborator(&owner, &repo, &username)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_list_repo_invitations(
+    owner: String,
+    repo: String,
+) -> Result<Vec<RepositoryInvitation>, String> {
+    crate::github::collaborators::list_repo_invitations(&owner, &repo)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_cancel_repo_invitation(
+    owner: String,
+    repo: String,
+    invitation_id: i64,
+) -> Result<(), String> {
+    crate::github::collaborators::delete_repo_invitation(&owner, &repo, invitation_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_list_my_invitations() -> Result<Vec<RepositoryInvitation>, String> {
+    crate::github::collaborators::list_user_invitations()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_accept_invitation(invitation_id: i64) -> Result<(), String> {
+    crate::github::collaborators::accept_invitation(invitation_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_decline_invitation(invitation_id: i64) -> Result<(), String> {
+    crate::github::collaborators::decline_invitation(invitation_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_list_recent_committers(
+    owner: String,
+    repo: String,
+    limit: Option<usize>,
+) -> Result<Vec<RecentCommitter>, String> {
+    crate::github::commits::list_recent_committers(&owner, &repo, limit)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Recent committers followed by the remaining collaborators, without duplicates
+#[tauri::command]
+pub async fn github_list_mention_candidates(
+    owner: String,
+    repo: String,
+) -> Result<Vec<MentionCandidate>, String> {
+    let (committers, collaborators) = tokio::join!(
+        crate::github::commits::list_recent_committers(&owner, &repo, None),
+        crate::github::collaborators::list_collaborators(&owner, &repo, None),
+    );
+    // Empty repositories have no commits, and listing collaborators needs push access,
+    // so either source may fail on its own
+    let committers = committers.unwrap_or_default();
+    let collaborators = collaborators.unwrap_or_default();
+
+    let mut candidates: Vec<MentionCandidate> = committers
+        .into_iter()
+        .map(|c| MentionCandidate {
+            login: c.login,
+            avatar_url: c.avatar_url,
+            source: "committer".to_string(),
+        })
+        .collect();
+
+    for collaborator in collaborators {
+        if !candidates.iter().any(|c| c.login == collaborator.login) {
+            candidates.push(MentionCandidate {
+                login: collaborator.login,
+                avatar_url: collaborator.avatar_url,
+                source: "collaborator".to_string(),
+            });
+        }
+    }
+
+    Ok(candidates)
+}
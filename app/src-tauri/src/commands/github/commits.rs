@@ -0,0 +1,45 @@
+use crate::github::commits::{CommitComment, CommitComparison};
+
+#[tauri::command]
+pub async fn github_compare(
+    owner: String,
+    repo: String,
+    base: String,
+    head: String,
+) -> Result<CommitComparison, String> {
+    crate::github::commits::compare_commits(&owner, &repo, &base, &head)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_list_commit_comments(
+    owner: String,
+    repo: String,
+    sha: String,
+) -> Result<Vec<CommitComment>, String> {
+    crate::github::commits::list_commit_comments(&owner, &repo, &sha)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_create_commit_comment(
+    owner: String,
+    repo: String,
+    sha: String,
+    body: String,
+    path: Option<String>,
+    line: Option<i32>,
+) -> Result<CommitComment, String> {
+    crate::github::commits::create_commit_comment(
+        &owner,
+        &repo,
+        &sha,
+        &body,
+        path.as_deref(),
+        line,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,50 @@
+use crate::github::contents::{ContentWriteResult, RepoFile};
+
+#[tauri::command]
+pub async fn github_get_file_content(
+    owner: String,
+    repo: String,
+    path: String,
+    git_ref: Option<String>,
+) -> Result<RepoFile, String> {
+    crate::github::contents::get_file(&owner, &repo, &path, git_ref.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_create_or_update_file(
+    owner: String,
+    repo: String,
+    path: String,
+    content: String,
+    message: String,
+    branch: Option<String>,
+    sha: Option<String>,
+) -> Result<ContentWriteResult, String> {
+    crate::github::contents::put_file(
+        &owner,
+        &repo,
+        &path,
+        &content,
+        &message,
+        branch.as_deref(),
+        sha.as_deref(),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_delete_file(
+    owner: String,
+    repo: String,
+    path: String,
+    message: String,
+    sha: String,
+    branch: Option<String>,
+) -> Result<ContentWriteResult, String> {
+    crate::github::contents::delete_file(&owner, &repo, &path, &message, &sha, branch.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -1,8 +1,39 @@
+use std::process::Stdio;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, State};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+
+use crate::commands::state::AppState;
+use crate::git;
 use crate::github::deployments::{
     Deployment, DeploymentStatus, DeploymentSummary,
     CreateDeploymentRequest, CreateDeploymentStatusRequest,
+    LocalDeploymentEvent, LOCAL_DEPLOYMENT_EVENT,
 };
 
+/// Options for a deployment of the checked-out commit driven by a local script
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalDeploymentOptions {
+    pub environment: String,
+    /// Shell command that performs the deployment; its exit code decides the final status
+    pub script: String,
+    pub remote: Option<String>,
+    pub description: Option<String>,
+    /// URL reported with the `success` status
+    pub environment_url: Option<String>,
+    pub production_environment: Option<bool>,
+    pub transient_environment: Option<bool>,
+}
+
+/// Outcome of a deployment driven by a local script
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalDeploymentResult {
+    pub deployment: Deployment,
+    pub status: DeploymentStatus,
+    pub exit_code: Option<i32>,
+}
+
 #[tauri::command]
 pub async fn github_list_deployments(
     owner: String,
@@ -114,3 +145,145 @@ pub async fn github_get_deployment_summary(
         .await
         .map_err(|e| e.to_string())
 }
+
+/// Forward each line of a script's output stream as a deployment event, returning
+/// the last non-empty line
+async fn forward_output(
+    app: AppHandle,
+    deployment_id: i64,
+    stream: impl AsyncRead + Unpin,
+) -> Option<String> {
+    let mut lines = BufReader::new(stream).lines();
+    let mut last = None;
+    while let Ok(Some(line)) = lines.next_line().await {
+        let _ = app.emit(LOCAL_DEPLOYMENT_EVENT, LocalDeploymentEvent {
+            deployment_id,
+            state: None,
+            line: Some(line.clone()),
+        });
+        if !line.trim().is_empty() {
+            last = Some(line);
+        }
+    }
+    last
+}
+
+/// Create a deployment of the checked-out commit, run a local deploy script, and
+/// post `in_progress` and then `success` or `failure` according to its exit code
+///
+/// The script runs in the repository with `DEPLOYMENT_ID`, `DEPLOY_ENVIRONMENT`,
+/// `DEPLOY_REF`, and `DEPLOY_SHA` set; its output is streamed as
+/// `local-deployment` events.
+#[tauri::command]
+pub async fn github_deploy_local_branch(
+    app: AppHandle,
+    options: LocalDeploymentOptions,
+    state: State<'_, AppState>,
+) -> Result<LocalDeploymentResult, String> {
+    let repo_path = state.repo_path.lock().unwrap()
+        .as_ref()
+        .ok_or("No repository open")?
+        .clone();
+
+    let (owner, repo_name, branch, sha) = {
+        let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+        let remote = match &options.remote {
+            Some(remote) => remote.clone(),
+            None => git::get_default_remote(&repo).map_err(|e| e.to_string())?,
+        };
+        let (owner, repo_name) = git::get_github_remote(&repo, &remote).map_err(|e| e.to_string())?;
+        let head = repo.head().map_err(|e| e.to_string())?;
+        let branch = head.shorthand().filter(|_| head.is_branch()).map(|b| b.to_string());
+        let sha = head.peel_to_commit().map_err(|e| e.to_string())?.id().to_string();
+        (owner, repo_name, branch, sha)
+    };
+
+    // Deploy the exact commit; the branch name is only recorded in the description
+    let description = options.description.clone().unwrap_or_else(|| match &branch {
+        Some(branch) => format!("Deploy {} from {}", &sha[..7], branch),
+        None => format!("Deploy {}", &sha[..7]),
+    });
+    let request = CreateDeploymentRequest {
+        ref_name: sha.clone(),
+        environment: options.environment.clone(),
+        description: Some(crate::github::deployments::status_description(&description)),
+        auto_merge: Some(false),
+        required_contexts: Some(Vec::new()),
+        transient_environment: options.transient_environment,
+        production_environment: options.production_environment,
+    };
+    let deployment = crate::github::deployments::create_deployment(&owner, &repo_name, request)
+        .await
+        .map_err(|e| format!("Failed to create the deployment (is {} pushed?): {}", &sha[..7], e))?;
+
+    let post_status = |new_state: &'static str, description: String, environment_url: Option<String>| {
+        let (owner, repo_name, app) = (owner.clone(), repo_name.clone(), app.clone());
+        async move {
+            let request = CreateDeploymentStatusRequest {
+                state: new_state.to_string(),
+                description: Some(crate::github::deployments::status_description(&description)),
+                environment_url,
+                log_url: None,
+                auto_inactive: Some(new_state == "success"),
+            };
+            let status = crate::github::deployments::create_deployment_status(
+                &owner,
+                &repo_name,
+                deployment.id,
+                request,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+            let _ = app.emit(LOCAL_DEPLOYMENT_EVENT, LocalDeploymentEvent {
+                deployment_id: deployment.id,
+                state: Some(new_state.to_string()),
+                line: None,
+            });
+            Ok::<_, String>(status)
+        }
+    };
+
+    post_status("in_progress", format!("Running {}", options.script), None).await?;
+
+    let spawned = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(&options.script)
+        .current_dir(&repo_path)
+        .env("DEPLOYMENT_ID", deployment.id.to_string())
+        .env("DEPLOY_ENVIRONMENT", &options.environment)
+        .env("DEPLOY_REF", branch.as_deref().unwrap_or(&sha))
+        .env("DEPLOY_SHA", &sha)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            let status = post_status("error", format!("Could not start the deploy script: {}", e), None).await?;
+            return Ok(LocalDeploymentResult { deployment, status, exit_code: None });
+        }
+    };
+
+    let stdout = child.stdout.take().map(|s| forward_output(app.clone(), deployment.id, s));
+    let stderr = child.stderr.take().map(|s| forward_output(app.clone(), deployment.id, s));
+    let (_, last_error, exit) = tokio::join!(
+        async { match stdout { Some(f) => f.await, None => None } },
+        async { match stderr { Some(f) => f.await, None => None } },
+        child.wait(),
+    );
+    let exit_code = exit.as_ref().ok().and_then(|s| s.code());
+
+    let status = if exit.as_ref().map(|s| s.success()).unwrap_or(false) {
+        post_status("success", "Deployed".to_string(), options.environment_url.clone()).await?
+    } else {
+        let reason = last_error.unwrap_or_else(|| match exit_code {
+            Some(code) => format!("Deploy script exited with code {}", code),
+            None => "Deploy script was terminated".to_string(),
+        });
+        post_status("failure", reason, None).await?
+    };
+
+    Ok(LocalDeploymentResult { deployment, status, exit_code })
+}
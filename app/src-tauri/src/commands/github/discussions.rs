@@ -0,0 +1,75 @@
+use crate::github::graphql::{Discussion, DiscussionCategory, DiscussionComment, DiscussionDetail};
+
+#[tauri::command]
+pub async fn github_list_discussion_categories(
+    owner: String,
+    repo: String,
+) -> Result<Vec<DiscussionCategory>, String> {
+    crate::github::graphql::list_discussion_categories(&owner, &repo)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_list_discussions(
+    owner: String,
+    repo: String,
+    category_id: Option<String>,
+    first: Option<u32>,
+) -> Result<Vec<Discussion>, String> {
+    crate::github::graphql::list_discussions(&owner, &repo, category_id.as_deref(), first)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_get_discussion(
+    owner: String,
+    repo: String,
+    number: i32,
+) -> Result<DiscussionDetail, String> {
+    crate::github::graphql::get_discussion(&owner, &repo, number)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_add_discussion_comment(
+    owner: String,
+    repo: String,
+    discussion_id: String,
+    body: String,
+    reply_to_id: Option<String>,
+) -> Result<DiscussionComment, String> {
+    crate::github::graphql::add_discussion_comment(
+        &owner,
+        &repo,
+        &discussion_id,
+        &body,
+        reply_to_id.as_deref(),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_mark_discussion_answer(
+    owner: String,
+    repo: String,
+    comment_id: String,
+) -> Result<bool, String> {
+    crate::github::graphql::mark_discussion_answer(&owner, &repo, &comment_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_unmark_discussion_answer(
+    owner: String,
+    repo: String,
+    comment_id: String,
+) -> Result<bool, String> {
+    crate::github::graphql::unmark_discussion_answer(&owner, &repo, &comment_id)
+        .await
+        .map_err(|e| e.to_string())
+}
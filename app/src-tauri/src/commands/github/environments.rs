@@ -1,8 +1,10 @@
 use crate::github::environments::{
     Environment, EnvironmentsResponse, CreateEnvironmentRequest,
-    EnvironmentSecretsResponse, EnvironmentVariablesResponse,
+    EnvironmentSecretsResponse, EnvironmentVariable, EnvironmentVariablesResponse,
     BranchPolicy, BranchPoliciesResponse, ReviewerRequest, DeploymentBranchPolicy,
+    PendingDeployment,
 };
+use crate::github::deployments::Deployment;
 
 #[tauri::command]
 pub async fn github_list_environments(
@@ -117,6 +119,38 @@ pub async fn github_list_environment_secrets(
         .map_err(|e| e.to_string())
 }
 
+/// Create or update an environment secret (the value is encrypted before it is sent)
+#[tauri::command]
+pub async fn github_set_environment_secret(
+    owner: String,
+    repo: String,
+    environment_name: String,
+    secret_name: String,
+    value: String,
+) -> Result<(), String> {
+    crate::github::environments::set_environment_secret(
+        &owner,
+        &repo,
+        &environment_name,
+        &secret_name,
+        &value,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_delete_environment_secret(
+    owner: String,
+    repo: String,
+    environment_name: String,
+    secret_name: String,
+) -> Result<(), String> {
+    crate::github::environments::delete_environment_secret(&owner, &repo, &environment_name, &secret_name)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn github_list_environment_variables(
     owner: String,
@@ -128,6 +162,78 @@ pub async fn github_list_environment_variables(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn github_get_environment_variable(
+    owner: String,
+    repo: String,
+    environment_name: String,
+    variable_name: String,
+) -> Result<EnvironmentVariable, String> {
+    crate::github::environments::get_environment_variable(
+        &owner,
+        &repo,
+        &environment_name,
+        &variable_name,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_create_environment_variable(
+    owner: String,
+    repo: String,
+    environment_name: String,
+    variable_name: String,
+    value: String,
+) -> Result<(), String> {
+    crate::github::environments::create_environment_variable(
+        &owner,
+        &repo,
+        &environment_name,
+        &variable_name,
+        &value,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_update_environment_variable(
+    owner: String,
+    repo: String,
+    environment_name: String,
+    variable_name: String,
+    value: String,
+) -> Result<(), String> {
+    crate::github::environments::update_environment_variable(
+        &owner,
+        &repo,
+        &environment_name,
+        &variable_name,
+        &value,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_delete_environment_variable(
+    owner: String,
+    repo: String,
+    environment_name: String,
+    variable_name: String,
+) -> Result<(), String> {
+    crate::github::environments::delete_environment_variable(
+        &owner,
+        &repo,
+        &environment_name,
+        &variable_name,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn github_list_branch_policies(
     owner: String,
@@ -174,3 +280,35 @@ pub async fn github_delete_branch_policy(
     .await
     .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub async fn github_get_pending_deployments(
+    owner: String,
+    repo: String,
+    run_id: i64,
+) -> Result<Vec<PendingDeployment>, String> {
+    crate::github::environments::get_pending_deployments(&owner, &repo, run_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_review_pending_deployments(
+    owner: String,
+    repo: String,
+    run_id: i64,
+    environment_ids: Vec<i64>,
+    state: String,
+    comment: Option<String>,
+) -> Result<Vec<Deployment>, String> {
+    crate::github::environments::review_pending_deployments(
+        &owner,
+        &repo,
+        run_id,
+        environment_ids,
+        &state,
+        comment.as_deref().unwrap_or(""),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
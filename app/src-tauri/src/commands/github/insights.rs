@@ -1,7 +1,11 @@
+use tauri::{AppHandle, Emitter};
+
 use crate::github::insights::{
     Contributor, CommitActivity, CodeFrequency, Participation, PunchCard,
     TrafficViews, TrafficClones, Referrer, PopularPath, CommunityProfile, Languages,
 };
+use crate::github::insights_cache::{self, CachedInsights, INSIGHTS_CACHE_UPDATED_EVENT};
+use crate::github::traffic_history::TrafficHistory;
 
 #[tauri::command]
 pub async fn github_get_contributors(owner: String, repo: String) -> Result<Vec<Contributor>, String> {
@@ -88,3 +92,71 @@ pub async fn github_get_languages(owner: String, repo: String) -> Result<Languag
         .await
         .map_err(|e| e.to_string())
 }
+
+/// Snapshot current traffic into the stored history and start tracking the repository
+#[tauri::command]
+pub async fn github_snapshot_traffic(owner: String, repo: String) -> Result<TrafficHistory, String> {
+    crate::github::traffic_history::snapshot_traffic(&owner, &repo)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Get the long-term traffic series, refreshed with the latest 14 days when possible
+#[tauri::command]
+pub async fn github_get_traffic_history(owner: String, repo: String) -> Result<TrafficHistory, String> {
+    let error = match crate::github::traffic_history::snapshot_traffic(&owner, &repo).await {
+        Ok(history) => return Ok(history),
+        Err(e) => e.to_string(),
+    };
+
+    // Fall back to the stored series, e.g. when offline
+    let stored = crate::github::traffic_history::load_history(&owner, &repo).map_err(|e| e.to_string())?;
+    if stored.last_snapshot.is_none() {
+        return Err(error);
+    }
+    Ok(stored)
+}
+
+#[tauri::command]
+pub fn github_untrack_traffic(owner: String, repo: String) -> Result<(), String> {
+    crate::github::traffic_history::delete_history(&owner, &repo).map_err(|e| e.to_string())
+}
+
+/// Get the cached community profile, languages, and contributor stats
+///
+/// Stale or missing entries are refreshed in the background (`force_refresh`
+/// refreshes regardless), and an `insights-cache-updated` event carries the result.
+#[tauri::command]
+pub fn github_get_cached_insights(
+    app: AppHandle,
+    owner: String,
+    repo: String,
+    force_refresh: Option<bool>,
+) -> Result<CachedInsights, String> {
+    let cache = insights_cache::load_cache(&owner, &repo).map_err(|e| e.to_string())?;
+    let stale = cache.is_stale(chrono::Utc::now());
+
+    if (stale || force_refresh.unwrap_or(false)) && insights_cache::begin_refresh(&owner, &repo) {
+        let (owner, repo) = (owner.clone(), repo.clone());
+        tauri::async_runtime::spawn(async move {
+            match insights_cache::refresh_cache(&owner, &repo).await {
+                Ok(cache) => {
+                    let _ = app.emit(INSIGHTS_CACHE_UPDATED_EVENT, &cache);
+                }
+                Err(e) => tracing::warn!("Refreshing insights of {}/{} failed: {}", owner, repo, e),
+            }
+            insights_cache::end_refresh(&owner, &repo);
+        });
+    }
+
+    Ok(CachedInsights {
+        cache,
+        stale,
+        refreshing: insights_cache::is_refreshing(&owner, &repo),
+    })
+}
+
+#[tauri::command]
+pub fn github_clear_insights_cache(owner: String, repo: String) -> Result<(), String> {
+    insights_cache::clear_cache(&owner, &repo).map_err(|e| e.to_string())
+}
@@ -1,4 +1,5 @@
-use crate::github::issues::{Issue, IssueComment, Label as IssueLabel, Milestone};
+use crate::github::issues::{Issue, IssueComment, IssueUser, Label as IssueLabel, Milestone};
+use crate::github::tasklist::TaskListProgress;
 
 #[tauri::command]
 pub async fn github_list_issues(
@@ -108,6 +109,40 @@ pub async fn github_create_issue_comment(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn github_get_issue_comment(
+    owner: String,
+    repo: String,
+    comment_id: i64,
+) -> Result<IssueComment, String> {
+    crate::github::issues::get_issue_comment(&owner, &repo, comment_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_update_issue_comment(
+    owner: String,
+    repo: String,
+    comment_id: i64,
+    body: String,
+) -> Result<IssueComment, String> {
+    crate::github::issues::update_issue_comment(&owner, &repo, comment_id, &body)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_delete_issue_comment(
+    owner: String,
+    repo: String,
+    comment_id: i64,
+) -> Result<(), String> {
+    crate::github::issues::delete_issue_comment(&owner, &repo, comment_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn github_list_labels(
     owner: String,
@@ -165,3 +200,183 @@ pub async fn github_unlock_issue(
         .await
         .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub async fn github_add_assignees(
+    owner: String,
+    repo: String,
+    issue_number: i32,
+    assignees: Vec<String>,
+) -> Result<Issue, String> {
+    crate::github::issues::add_assignees(&owner, &repo, issue_number, assignees)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_remove_assignees(
+    owner: String,
+    repo: String,
+    issue_number: i32,
+    assignees: Vec<String>,
+) -> Result<Issue, String> {
+    crate::github::issues::remove_assignees(&owner, &repo, issue_number, assignees)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_list_assignable_users(
+    owner: String,
+    repo: String,
+    per_page: Option<u32>,
+) -> Result<Vec<IssueUser>, String> {
+    crate::github::issues::list_assignable_users(&owner, &repo, per_page)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_create_label(
+    owner: String,
+    repo: String,
+    name: String,
+    color: String,
+    description: Option<String>,
+) -> Result<IssueLabel, String> {
+    crate::github::issues::create_label(&owner, &repo, &name, &color, description.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_update_label(
+    owner: String,
+    repo: String,
+    name: String,
+    new_name: Option<String>,
+    color: Option<String>,
+    description: Option<String>,
+) -> Result<IssueLabel, String> {
+    crate::github::issues::update_label(
+        &owner,
+        &repo,
+        &name,
+        new_name.as_deref(),
+        color.as_deref(),
+        description.as_deref(),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_delete_label(
+    owner: String,
+    repo: String,
+    name: String,
+) -> Result<(), String> {
+    crate::github::issues::delete_label(&owner, &repo, &name)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_create_milestone(
+    owner: String,
+    repo: String,
+    title: String,
+    description: Option<String>,
+    due_on: Option<String>,
+) -> Result<Milestone, String> {
+    crate::github::issues::create_milestone(
+        &owner,
+        &repo,
+        &title,
+        description.as_deref(),
+        due_on.as_deref(),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_update_milestone(
+    owner: String,
+    repo: String,
+    milestone_number: i32,
+    title: Option<String>,
+    description: Option<String>,
+    due_on: Option<String>,
+    state: Option<String>,
+) -> Result<Milestone, String> {
+    crate::github::issues::update_milestone(
+        &owner,
+        &repo,
+        milestone_number,
+        title.as_deref(),
+        description.as_deref(),
+        due_on.as_deref(),
+        state.as_deref(),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_close_milestone(
+    owner: String,
+    repo: String,
+    milestone_number: i32,
+) -> Result<Milestone, String> {
+    crate::github::issues::close_milestone(&owner, &repo, milestone_number)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_delete_milestone(
+    owner: String,
+    repo: String,
+    milestone_number: i32,
+) -> Result<(), String> {
+    crate::github::issues::delete_milestone(&owner, &repo, milestone_number)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn github_parse_task_list(body: String) -> TaskListProgress {
+    crate::github::tasklist::parse_task_list(&body)
+}
+
+#[tauri::command]
+pub async fn github_toggle_task_list_item(
+    owner: String,
+    repo: String,
+    issue_number: i32,
+    index: usize,
+) -> Result<Issue, String> {
+    let issue = crate::github::issues::get_issue(&owner, &repo, issue_number)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let body = issue.body.unwrap_or_default();
+    let updated = crate::github::tasklist::toggle_task_item(&body, index)
+        .ok_or_else(|| format!("No checklist item at index {}", index))?;
+
+    crate::github::issues::update_issue(
+        &owner,
+        &repo,
+        issue_number,
+        None,
+        Some(&updated),
+        None,
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
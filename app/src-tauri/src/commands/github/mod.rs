@@ -9,6 +9,19 @@ mod issues;
 mod deployments;
 mod environments;
 mod security;
+mod checks;
+mod teams;
+mod reactions;
+mod discussions;
+mod repos;
+mod collaborators;
+mod secrets;
+mod commits;
+mod contents;
+mod search;
+mod app_auth;
+mod act;
+mod avatars;
 
 pub use auth::*;
 pub use actions::*;
@@ -21,3 +34,16 @@ pub use issues::*;
 pub use deployments::*;
 pub use environments::*;
 pub use security::*;
+pub use checks::*;
+pub use teams::*;
+pub use reactions::*;
+pub use discussions::*;
+pub use repos::*;
+pub use collaborators::*;
+pub use secrets::*;
+pub use commits::*;
+pub use contents::*;
+pub use search::*;
+pub use app_auth::*;
+pub use act::*;
+pub use avatars::*;
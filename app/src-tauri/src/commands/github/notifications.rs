@@ -1,4 +1,36 @@
-use crate::github::notifications::{Notification, ThreadSubscription};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::commands::state::AppState;
+use crate::github::notification_poller::{
+    find_new_notifications, seen_keys, should_notify_desktop, NotificationPollerHandle,
+    NotificationsUpdate, NOTIFICATIONS_UPDATE_EVENT,
+};
+use crate::github::notifications::{Notification, NotificationFilter, ThreadSubscription};
+
+/// Source of poller ids
+static NEXT_POLLER_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Outcome of a bulk notification action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkNotificationResult {
+    pub succeeded: Vec<String>,
+    pub failed: Vec<BulkNotificationFailure>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkNotificationFailure {
+    pub thread_id: String,
+    pub error: String,
+}
+
+/// Wait after a failed poll
+const POLL_RETRY_INTERVAL: Duration = Duration::from_secs(120);
 
 #[tauri::command]
 pub async fn github_list_notifications(
@@ -122,3 +154,154 @@ pub async fn github_get_unread_count() -> Result<u32, String> {
         .await
         .map_err(|e| e.to_string())
 }
+
+/// List notifications (all pages) matching a filter
+#[tauri::command]
+pub async fn github_list_filtered_notifications(
+    filter: NotificationFilter,
+    all: Option<bool>,
+    participating: Option<bool>,
+) -> Result<Vec<Notification>, String> {
+    let notifications = crate::github::notifications::list_all_notifications(
+        all.unwrap_or(false),
+        participating.unwrap_or(false),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(notifications.into_iter().filter(|n| filter.matches(n)).collect())
+}
+
+/// Mark every unread notification matching a filter as `read` or `done`
+#[tauri::command]
+pub async fn github_bulk_update_notifications(
+    filter: NotificationFilter,
+    action: String,
+) -> Result<BulkNotificationResult, String> {
+    if action != "read" && action != "done" {
+        return Err(format!("Invalid action '{}': expected 'read' or 'done'", action));
+    }
+
+    let notifications = crate::github::notifications::list_all_unread()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mut tasks = tokio::task::JoinSet::new();
+    for notification in notifications.into_iter().filter(|n| filter.matches(n)) {
+        let done = action == "done";
+        tasks.spawn(async move {
+            let result = if done {
+                crate::github::notifications::mark_thread_done(&notification.id).await
+            } else {
+                crate::github::notifications::mark_thread_read(&notification.id).await
+            };
+            (notification.id, result)
+        });
+    }
+
+    let mut result = BulkNotificationResult { succeeded: Vec::new(), failed: Vec::new() };
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok((thread_id, Ok(()))) => result.succeeded.push(thread_id),
+            Ok((thread_id, Err(e))) => result.failed.push(BulkNotificationFailure {
+                thread_id,
+                error: e.to_string(),
+            }),
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+
+    Ok(result)
+}
+
+fn current_poller(app: &AppHandle) -> Option<NotificationPollerHandle> {
+    *app.state::<AppState>().notification_poller.lock().unwrap()
+}
+
+fn notify_desktop(app: &AppHandle, notification: &Notification) {
+    let reason = match notification.reason.as_str() {
+        "review_requested" => "Review requested",
+        "assign" => "Assigned to you",
+        _ => "Mentioned",
+    };
+
+    let _ = app
+        .notification()
+        .builder()
+        .title(format!("{}: {}", notification.repository.full_name, reason))
+        .body(&notification.subject.title)
+        .show();
+}
+
+async fn poll_notifications_loop(app: AppHandle, poller_id: u64) {
+    let mut last_modified: Option<String> = None;
+    let mut seen: Option<HashSet<(String, String)>> = None;
+
+    loop {
+        let handle = match current_poller(&app) {
+            Some(handle) if handle.id == poller_id => handle,
+            _ => return,
+        };
+
+        let interval = match crate::github::notifications::poll_notifications(last_modified.as_deref()).await {
+            Ok(poll) => {
+                if poll.modified {
+                    // Everything unread at start-up is already known, not new
+                    let new_notifications = match &seen {
+                        Some(seen) => find_new_notifications(seen, &poll.notifications),
+                        None => Vec::new(),
+                    };
+                    seen = Some(seen_keys(&poll.notifications));
+                    last_modified = poll.last_modified;
+
+                    if handle.desktop_notifications {
+                        for notification in new_notifications.iter().filter(|n| should_notify_desktop(n)) {
+                            notify_desktop(&app, notification);
+                        }
+                    }
+
+                    let _ = app.emit(NOTIFICATIONS_UPDATE_EVENT, NotificationsUpdate {
+                        unread_count: poll.notifications.len(),
+                        new_notifications,
+                    });
+                }
+                Duration::from_secs(poll.poll_interval)
+            }
+            Err(e) => {
+                tracing::warn!("Notification poll failed: {}", e);
+                POLL_RETRY_INTERVAL
+            }
+        };
+
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Start polling notifications in the background, emitting `notifications-update` events
+///
+/// Starting an already running poller only updates its desktop notification setting.
+#[tauri::command]
+pub fn github_start_notification_poller(
+    app: AppHandle,
+    desktop_notifications: Option<bool>,
+    state: State<AppState>,
+) -> Result<(), String> {
+    let desktop_notifications = desktop_notifications.unwrap_or(true);
+    let mut poller = state.notification_poller.lock().unwrap();
+
+    if let Some(handle) = poller.as_mut() {
+        handle.desktop_notifications = desktop_notifications;
+        return Ok(());
+    }
+
+    let id = NEXT_POLLER_ID.fetch_add(1, Ordering::Relaxed);
+    *poller = Some(NotificationPollerHandle { id, desktop_notifications });
+    tauri::async_runtime::spawn(poll_notifications_loop(app, id));
+    Ok(())
+}
+
+#[tauri::command]
+pub fn github_stop_notification_poller(state: State<AppState>) -> Result<(), String> {
+    *state.notification_poller.lock().unwrap() = None;
+    Ok(())
+}
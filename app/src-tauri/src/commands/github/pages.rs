@@ -1,4 +1,42 @@
-use crate::github::pages::{PagesInfo, PagesBuild, DeploymentStatus};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::commands::state::AppState;
+use crate::git;
+use crate::github::pages::{
+    PagesInfo, PagesBuild, PagesHealthCheck, DeploymentStatus, PagesDeployProgress,
+    PAGES_DEPLOY_PROGRESS_EVENT,
+};
+use crate::github::pages_artifact::{pages_artifact, ActionsRuntime};
+
+/// How long to wait for GitHub to build or deploy a deployed directory
+const PAGES_BUILD_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// Options for deploying a local directory to Pages
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PagesDirectoryDeployOptions {
+    pub directory: String,
+    /// Branch Pages is published from (defaults to `gh-pages`)
+    pub branch: Option<String>,
+    pub remote: Option<String>,
+    pub message: Option<String>,
+}
+
+/// Outcome of deploying a local directory to Pages
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PagesDirectoryDeployResult {
+    /// Branch the directory was committed to; `None` for workflow deployments
+    pub branch: Option<String>,
+    /// Commit deployed; `None` when the directory matched the deployed content
+    pub commit_sha: Option<String>,
+    pub build: Option<PagesBuild>,
+    pub page_url: Option<String>,
+    /// Pages deployment of a workflow-built site
+    pub deployment_id: Option<String>,
+}
 
 #[tauri::command]
 pub async fn github_get_pages_info(owner: String, repo: String) -> Result<PagesInfo, String> {
@@ -68,6 +106,17 @@ pub async fn github_get_latest_pages_build(owner: String, repo: String) -> Resul
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn github_get_pages_build(
+    owner: String,
+    repo: String,
+    build_id: i64,
+) -> Result<PagesBuild, String> {
+    crate::github::pages::get_pages_build(&owner, &repo, build_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn github_request_pages_build(owner: String, repo: String) -> Result<PagesBuild, String> {
     crate::github::pages::request_pages_build(&owner, &repo)
@@ -85,3 +134,210 @@ pub async fn github_get_deployment_status(
         .await
         .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub async fn github_cancel_pages_deployment(
+    owner: String,
+    repo: String,
+    deployment_id: i64,
+) -> Result<(), String> {
+    crate::github::pages::cancel_deployment(&owner, &repo, deployment_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// DNS diagnostics for the custom domain of a Pages site
+#[tauri::command]
+pub async fn github_get_pages_health_check(
+    owner: String,
+    repo: String,
+) -> Result<PagesHealthCheck, String> {
+    crate::github::pages::get_pages_health_check(&owner, &repo)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Deploy a local directory (e.g. a static site build) to Pages in one step
+///
+/// When Pages is built by a GitHub Actions workflow, the directory is uploaded
+/// as the `github-pages` artifact of the running workflow job, a Pages
+/// deployment is created from it, and the deployment is polled to completion;
+/// this needs the job credentials of the Actions runner. Otherwise the
+/// directory is committed as the whole content of the Pages branch (with a
+/// `.nojekyll` file so it is served as-is) and pushed, Pages is enabled for the
+/// branch if needed, and the build is polled to completion. Progress is
+/// reported with `pages-deploy-progress` events.
+#[tauri::command]
+pub async fn github_deploy_pages_directory(
+    app: AppHandle,
+    options: PagesDirectoryDeployOptions,
+    state: State<'_, AppState>,
+) -> Result<PagesDirectoryDeployResult, String> {
+    let repo_path = state.repo_path.lock().unwrap()
+        .as_ref()
+        .ok_or("No repository open")?
+        .clone();
+    let emit = |stage: &str, message: String, build_status: Option<String>| {
+        let _ = app.emit(PAGES_DEPLOY_PROGRESS_EVENT, PagesDeployProgress {
+            stage: stage.to_string(),
+            message,
+            build_status,
+        });
+    };
+
+    emit("preparing", "Checking the Pages configuration".to_string(), None);
+    let (owner, repo_name, remote) = {
+        let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+        let remote = match &options.remote {
+            Some(remote) => remote.clone(),
+            None => git::get_default_remote(&repo).map_err(|e| e.to_string())?,
+        };
+        let (owner, repo_name) = git::get_github_remote(&repo, &remote).map_err(|e| e.to_string())?;
+        (owner, repo_name, remote)
+    };
+
+    let pages = crate::github::pages::find_pages_info(&owner, &repo_name)
+        .await
+        .map_err(|e| e.to_string())?;
+    if pages.as_ref().and_then(|info| info.build_type.as_deref()) == Some("workflow") {
+        return deploy_directory_artifact(&repo_path, &options.directory, &owner, &repo_name, &emit).await;
+    }
+
+    // Branch-built Pages has to be absent or already published from the branch being deployed
+    let branch = options.branch.unwrap_or_else(|| "gh-pages".to_string());
+    if let Some(source) = pages
+        .as_ref()
+        .and_then(|info| info.source.as_ref())
+        .filter(|s| s.branch != branch || s.path != "/")
+    {
+        return Err(format!(
+            "Pages is published from '{}' in the '{}' branch",
+            source.path, source.branch
+        ));
+    }
+
+    emit("committing", format!("Committing {} to '{}'", options.directory, branch), None);
+    let message = options.message.unwrap_or_else(|| "Deploy to GitHub Pages".to_string());
+    let commit_sha = {
+        let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+        git::fetch(&repo, &remote).map_err(|e| e.to_string())?;
+        let extra_files: &[(&str, &[u8])] = &[(".nojekyll", b"")];
+        let sha = git::publish::commit_directory_to_branch(
+            &repo,
+            Path::new(&options.directory),
+            &branch,
+            &remote,
+            &message,
+            extra_files,
+        )
+        .map_err(|e| e.to_string())?;
+
+        if sha.is_some() {
+            emit("pushing", format!("Pushing '{}' to {}", branch, remote), None);
+            git::push(&repo, &remote, &branch)
+                .map_err(|e| format!("Failed to push '{}': {}", branch, e))?;
+        }
+        sha
+    };
+
+    let Some(sha) = commit_sha else {
+        emit("done", "Nothing changed since the last deployment".to_string(), None);
+        return Ok(PagesDirectoryDeployResult {
+            branch: Some(branch),
+            commit_sha: None,
+            build: None,
+            page_url: None,
+            deployment_id: None,
+        });
+    };
+
+    // Enabling Pages starts the first build; later pushes trigger builds on their own
+    if pages.is_none() {
+        crate::github::pages::enable_pages(&owner, &repo_name, &branch, "/")
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    emit("building", "Waiting for GitHub Pages to build".to_string(), None);
+    let on_progress = |build: &PagesBuild| {
+        emit("building", format!("Pages build is {}", build.status), Some(build.status.clone()));
+    };
+    let build = crate::github::pages::wait_for_pages_build(&owner, &repo_name, &sha, PAGES_BUILD_TIMEOUT, on_progress)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let page_url = crate::github::pages::get_pages_info(&owner, &repo_name)
+        .await
+        .ok()
+        .and_then(|info| info.html_url);
+    emit("done", "Deployed to GitHub Pages".to_string(), Some(build.status.clone()));
+
+    Ok(PagesDirectoryDeployResult {
+        branch: Some(branch),
+        commit_sha: Some(sha),
+        build: Some(build),
+        page_url,
+        deployment_id: None,
+    })
+}
+
+/// Deploy a directory to workflow-built Pages through a `github-pages` artifact,
+/// versioned by the HEAD commit of the repository
+async fn deploy_directory_artifact(
+    repo_path: &str,
+    directory: &str,
+    owner: &str,
+    repo_name: &str,
+    emit: &impl Fn(&str, String, Option<String>),
+) -> Result<PagesDirectoryDeployResult, String> {
+    let runtime = ActionsRuntime::from_env().map_err(|e| e.to_string())?;
+    let build_version = {
+        let repo = git::open_repo(repo_path).map_err(|e| e.to_string())?;
+        let head = repo.head().and_then(|h| h.peel_to_commit()).map_err(|e| e.to_string())?;
+        head.id().to_string()
+    };
+
+    emit("packaging", format!("Packaging {}", directory), None);
+    let dir = PathBuf::from(directory);
+    let artifact = tokio::task::spawn_blocking(move || pages_artifact(&dir))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())?;
+
+    emit("uploading", format!("Uploading the Pages artifact ({} bytes)", artifact.len()), None);
+    let artifact_id = runtime.upload_pages_artifact(artifact).await.map_err(|e| e.to_string())?;
+    let oidc_token = runtime.oidc_token().await.map_err(|e| e.to_string())?;
+
+    emit("deploying", "Creating the Pages deployment".to_string(), None);
+    let deployment = crate::github::pages::create_pages_deployment(
+        owner,
+        repo_name,
+        Some(&artifact_id),
+        &build_version,
+        &oidc_token,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let on_progress = |status: &str| {
+        emit("deploying", format!("Pages deployment is {}", status), Some(status.to_string()));
+    };
+    let status = crate::github::pages::wait_for_pages_deployment(
+        owner,
+        repo_name,
+        &deployment.id,
+        PAGES_BUILD_TIMEOUT,
+        on_progress,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    emit("done", "Deployed to GitHub Pages".to_string(), Some(status));
+
+    Ok(PagesDirectoryDeployResult {
+        branch: None,
+        commit_sha: Some(build_version),
+        build: None,
+        page_url: Some(deployment.page_url),
+        deployment_id: Some(deployment.id),
+    })
+}
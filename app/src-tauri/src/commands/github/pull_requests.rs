@@ -1,4 +1,11 @@
-use crate::github::pull_requests::{PullRequest, PullRequestReview, PullRequestComment};
+use tauri::State;
+use crate::commands::state::AppState;
+use crate::git;
+use crate::github::graphql::ReviewThread;
+use crate::github::pull_requests::{
+    PullRequest, PullRequestReview, PullRequestComment, PullRequestFile, ReviewCommentInput,
+    PullRequestCommit, PullRequestMergeState, UpdateBranchResult, BranchPullRequest,
+};
 
 #[tauri::command]
 pub async fn github_list_pull_requests(
@@ -127,10 +134,36 @@ pub async fn github_request_reviewers(
     repo: String,
     pull_number: i32,
     reviewers: Vec<String>,
+    team_reviewers: Option<Vec<String>>,
 ) -> Result<(), String> {
-    crate::github::pull_requests::request_reviewers(&owner, &repo, pull_number, reviewers)
-        .await
-        .map_err(|e| e.to_string())
+    crate::github::pull_requests::request_reviewers(
+        &owner,
+        &repo,
+        pull_number,
+        reviewers,
+        team_reviewers.unwrap_or_default(),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_remove_requested_reviewers(
+    owner: String,
+    repo: String,
+    pull_number: i32,
+    reviewers: Vec<String>,
+    team_reviewers: Option<Vec<String>>,
+) -> Result<(), String> {
+    crate::github::pull_requests::remove_requested_reviewers(
+        &owner,
+        &repo,
+        pull_number,
+        reviewers,
+        team_reviewers.unwrap_or_default(),
+    )
+    .await
+    .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -139,15 +172,277 @@ pub async fn github_create_review(
     repo: String,
     pull_number: i32,
     body: Option<String>,
-    event: String,
+    event: Option<String>,
+    comments: Option<Vec<ReviewCommentInput>>,
+    commit_id: Option<String>,
 ) -> Result<PullRequestReview, String> {
     crate::github::pull_requests::create_review(
         &owner,
         &repo,
         pull_number,
         body.as_deref(),
+        event.as_deref(),
+        comments.unwrap_or_default(),
+        commit_id.as_deref(),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_submit_review(
+    owner: String,
+    repo: String,
+    pull_number: i32,
+    review_id: i64,
+    body: Option<String>,
+    event: String,
+) -> Result<PullRequestReview, String> {
+    crate::github::pull_requests::submit_review(
+        &owner,
+        &repo,
+        pull_number,
+        review_id,
+        body.as_deref(),
         &event,
     )
     .await
     .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub async fn github_delete_pending_review(
+    owner: String,
+    repo: String,
+    pull_number: i32,
+    review_id: i64,
+) -> Result<(), String> {
+    crate::github::pull_requests::delete_pending_review(&owner, &repo, pull_number, review_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_create_review_comment(
+    owner: String,
+    repo: String,
+    pull_number: i32,
+    commit_id: String,
+    comment: ReviewCommentInput,
+) -> Result<PullRequestComment, String> {
+    crate::github::pull_requests::create_review_comment(
+        &owner,
+        &repo,
+        pull_number,
+        &commit_id,
+        &comment,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_get_review_comment(
+    owner: String,
+    repo: String,
+    comment_id: i64,
+) -> Result<PullRequestComment, String> {
+    crate::github::pull_requests::get_review_comment(&owner, &repo, comment_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_update_review_comment(
+    owner: String,
+    repo: String,
+    comment_id: i64,
+    body: String,
+) -> Result<PullRequestComment, String> {
+    crate::github::pull_requests::update_review_comment(&owner, &repo, comment_id, &body)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_delete_review_comment(
+    owner: String,
+    repo: String,
+    comment_id: i64,
+) -> Result<(), String> {
+    crate::github::pull_requests::delete_review_comment(&owner, &repo, comment_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_list_pr_files(
+    owner: String,
+    repo: String,
+    pull_number: i32,
+) -> Result<Vec<PullRequestFile>, String> {
+    crate::github::pull_requests::list_pr_files(&owner, &repo, pull_number)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_get_pr_diff(
+    owner: String,
+    repo: String,
+    pull_number: i32,
+) -> Result<String, String> {
+    crate::github::pull_requests::get_pr_diff(&owner, &repo, pull_number)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_list_pr_commits(
+    owner: String,
+    repo: String,
+    pull_number: i32,
+) -> Result<Vec<PullRequestCommit>, String> {
+    crate::github::pull_requests::list_pr_commits(&owner, &repo, pull_number)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_mark_pr_ready(
+    owner: String,
+    repo: String,
+    pull_number: i32,
+) -> Result<PullRequest, String> {
+    let pull_request = crate::github::pull_requests::get_pull_request(&owner, &repo, pull_number)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if pull_request.draft {
+        crate::github::graphql::mark_pull_request_ready(&owner, &repo, &pull_request.node_id)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    crate::github::pull_requests::get_pull_request(&owner, &repo, pull_number)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_convert_pr_to_draft(
+    owner: String,
+    repo: String,
+    pull_number: i32,
+) -> Result<PullRequest, String> {
+    let pull_request = crate::github::pull_requests::get_pull_request(&owner, &repo, pull_number)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !pull_request.draft {
+        crate::github::graphql::convert_pull_request_to_draft(&owner, &repo, &pull_request.node_id)
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+
+    crate::github::pull_requests::get_pull_request(&owner, &repo, pull_number)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_get_pr_merge_state(
+    owner: String,
+    repo: String,
+    pull_number: i32,
+) -> Result<PullRequestMergeState, String> {
+    crate::github::pull_requests::get_merge_state(&owner, &repo, pull_number)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_update_pr_branch(
+    owner: String,
+    repo: String,
+    pull_number: i32,
+    expected_head_sha: Option<String>,
+) -> Result<UpdateBranchResult, String> {
+    crate::github::pull_requests::update_pr_branch(
+        &owner,
+        &repo,
+        pull_number,
+        expected_head_sha.as_deref(),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Map the open repository's local branches to their open pull requests on origin
+#[tauri::command]
+pub async fn github_get_branch_pull_requests(
+    state: State<'_, AppState>,
+) -> Result<Vec<BranchPullRequest>, String> {
+    let repo_path = state.repo_path.lock().unwrap()
+        .as_ref()
+        .ok_or("No repository open")?
+        .clone();
+
+    let (owner, repo_name, branches) = {
+        let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+        let remote_name = if repo.find_remote("origin").is_ok() {
+            "origin".to_string()
+        } else {
+            git::get_default_remote(&repo).map_err(|e| e.to_string())?
+        };
+        let (owner, repo_name) = git::get_github_remote(&repo, &remote_name)
+            .map_err(|e| e.to_string())?;
+        let branches: Vec<(String, String)> = git::get_branches(&repo)
+            .map_err(|e| e.to_string())?
+            .into_iter()
+            .filter(|b| !b.is_remote)
+            .map(|b| {
+                let head = git::upstream_branch_name(&repo, &b.name).unwrap_or_else(|| b.name.clone());
+                (b.name, head)
+            })
+            .collect();
+        (owner, repo_name, branches)
+    };
+
+    crate::github::pull_requests::get_branch_pull_requests(&owner, &repo_name, &branches)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_list_review_threads(
+    owner: String,
+    repo: String,
+    pull_number: i32,
+) -> Result<Vec<ReviewThread>, String> {
+    crate::github::graphql::list_review_threads(&owner, &repo, pull_number)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_resolve_review_thread(
+    owner: String,
+    repo: String,
+    thread_id: String,
+) -> Result<ReviewThread, String> {
+    crate::github::graphql::resolve_review_thread(&owner, &repo, &thread_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_unresolve_review_thread(
+    owner: String,
+    repo: String,
+    thread_id: String,
+) -> Result<ReviewThread, String> {
+    crate::github::graphql::unresolve_review_thread(&owner, &repo, &thread_id)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,39 @@
+use crate::github::reactions::{Reaction, ReactionSubject};
+
+#[tauri::command]
+pub async fn github_list_reactions(
+    owner: String,
+    repo: String,
+    subject: ReactionSubject,
+    subject_id: i64,
+) -> Result<Vec<Reaction>, String> {
+    crate::github::reactions::list_reactions(&owner, &repo, subject, subject_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_add_reaction(
+    owner: String,
+    repo: String,
+    subject: ReactionSubject,
+    subject_id: i64,
+    content: String,
+) -> Result<Reaction, String> {
+    crate::github::reactions::add_reaction(&owner, &repo, subject, subject_id, &content)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_remove_reaction(
+    owner: String,
+    repo: String,
+    subject: ReactionSubject,
+    subject_id: i64,
+    reaction_id: i64,
+) -> Result<(), String> {
+    crate::github::reactions::remove_reaction(&owner, &repo, subject, subject_id, reaction_id)
+        .await
+        .map_err(|e| e.to_string())
+}
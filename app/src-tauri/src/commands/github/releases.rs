@@ -1,4 +1,58 @@
-use crate::github::releases::{Release, ReleaseAsset, Tag, CreateReleaseRequest, UpdateReleaseRequest};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, State};
+
+use crate::commands::state::AppState;
+use crate::git::{self, CommitInfo};
+use crate::github::releases::{
+    AssetDownloadProgress, AssetUploadProgress, AssetUploadResult, Release, ReleaseAsset, Tag, TagProtection,
+    CreateReleaseRequest, UpdateReleaseRequest, ASSET_DOWNLOAD_PROGRESS_EVENT,
+    ASSET_UPLOAD_PROGRESS_EVENT,
+};
+
+/// Minimum bytes between progress events of a download
+const PROGRESS_STEP: u64 = 256 * 1024;
+
+/// Options of the guided release workflow
+#[derive(Debug, Clone, Deserialize)]
+pub struct GuidedReleaseOptions {
+    pub tag_name: String,
+    /// Tag of the previous release; defaults to the latest tag reachable from HEAD
+    pub previous_tag: Option<String>,
+    pub name: Option<String>,
+    /// Notes to publish instead of the drafted ones, e.g. after editing the preview
+    pub notes: Option<String>,
+    /// Create `tag_name` at HEAD; otherwise the tag must already exist locally
+    #[serde(default)]
+    pub create_tag: bool,
+    pub remote: Option<String>,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub prerelease: bool,
+    /// Only preview the release without tagging, pushing, or publishing
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Preview or outcome of the guided release workflow
+#[derive(Debug, Clone, Serialize)]
+pub struct GuidedReleasePlan {
+    pub owner: String,
+    pub repo: String,
+    pub remote: String,
+    pub tag_name: String,
+    pub previous_tag: Option<String>,
+    pub target_sha: String,
+    pub commits: Vec<CommitInfo>,
+    pub notes: String,
+    pub tag_created: bool,
+    pub tag_pushed: bool,
+    pub release: Option<Release>,
+}
 
 #[tauri::command]
 pub async fn github_list_releases(
@@ -155,3 +209,299 @@ pub async fn github_list_tags(
         .await
         .map_err(|e| e.to_string())
 }
+
+/// Download a release asset, emitting progress events and resuming any partial download
+#[tauri::command]
+pub async fn github_download_release_asset(
+    app: AppHandle,
+    owner: String,
+    repo: String,
+    asset_id: i64,
+    dest_path: String,
+    state: State<'_, AppState>,
+) -> Result<u64, String> {
+    let cancelled = Arc::new(AtomicBool::new(false));
+    {
+        let mut downloads = state.asset_downloads.lock().unwrap();
+        if downloads.contains_key(&asset_id) {
+            return Err("This asset is already being downloaded".to_string());
+        }
+        downloads.insert(asset_id, cancelled.clone());
+    }
+
+    let mut last_emitted: Option<u64> = None;
+    let result = crate::github::releases::download_release_asset(
+        &owner,
+        &repo,
+        asset_id,
+        Path::new(&dest_path),
+        &cancelled,
+        |downloaded, total| {
+            let due = last_emitted.is_none_or(|last| downloaded >= last + PROGRESS_STEP);
+            if due || Some(downloaded) == total {
+                last_emitted = Some(downloaded);
+                let _ = app.emit(ASSET_DOWNLOAD_PROGRESS_EVENT, AssetDownloadProgress {
+                    asset_id,
+                    downloaded,
+                    total,
+                    done: false,
+                });
+            }
+        },
+    )
+    .await;
+
+    state.asset_downloads.lock().unwrap().remove(&asset_id);
+
+    let size = result.map_err(|e| e.to_string())?;
+    let _ = app.emit(ASSET_DOWNLOAD_PROGRESS_EVENT, AssetDownloadProgress {
+        asset_id,
+        downloaded: size,
+        total: Some(size),
+        done: true,
+    });
+    Ok(size)
+}
+
+/// Cancel an in-progress download; the partial file is kept for resuming
+#[tauri::command]
+pub fn github_cancel_release_asset_download(
+    asset_id: i64,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let downloads = state.asset_downloads.lock().unwrap();
+    let cancelled = downloads
+        .get(&asset_id)
+        .ok_or("This asset is not being downloaded")?;
+    cancelled.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Upload files as assets of a release, one at a time
+///
+/// Each file is read before anything on the release changes. Existing assets
+/// with the same name are replaced when `replace_existing` is set, and fail the
+/// upload otherwise; a replacement is uploaded under a temporary name first,
+/// so the existing asset is only deleted once the new one is in place. A failed
+/// file does not stop the others.
+#[tauri::command]
+pub async fn github_upload_release_assets(
+    app: AppHandle,
+    owner: String,
+    repo: String,
+    release_id: i64,
+    paths: Vec<String>,
+    replace_existing: Option<bool>,
+) -> Result<Vec<AssetUploadResult>, String> {
+    let release = crate::github::releases::get_release(&owner, &repo, release_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    let replace_existing = replace_existing.unwrap_or(false);
+    let total_files = paths.len();
+
+    let emit = |path: &str, index: usize, status: &str, error: Option<String>| {
+        let _ = app.emit(ASSET_UPLOAD_PROGRESS_EVENT, AssetUploadProgress {
+            release_id,
+            path: path.to_string(),
+            index,
+            total_files,
+            status: status.to_string(),
+            error,
+        });
+    };
+
+    let mut results = Vec::with_capacity(total_files);
+    for (index, path) in paths.into_iter().enumerate() {
+        emit(&path, index, "uploading", None);
+
+        let name = Path::new(&path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("asset")
+            .to_string();
+        let existing = release.assets.iter().find(|a| a.name == name);
+
+        let outcome = async {
+            if existing.is_some() && !replace_existing {
+                return Err(format!("An asset named '{}' already exists", name));
+            }
+            let content = tokio::fs::read(&path)
+                .await
+                .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+            if content.is_empty() {
+                return Err(format!("{} is empty", path));
+            }
+
+            let content_type = crate::github::releases::guess_content_type(&name);
+            let upload_name = match existing {
+                Some(_) => format!("{}.uploading", name),
+                None => name.clone(),
+            };
+            let asset = crate::github::releases::upload_release_asset(
+                &owner,
+                &repo,
+                &release.upload_url,
+                &upload_name,
+                content,
+                content_type,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+
+            let Some(old) = existing else {
+                return Ok((asset, false));
+            };
+            crate::github::releases::delete_release_asset(&owner, &repo, old.id)
+                .await
+                .map_err(|e| format!("Uploaded as '{}' but deleting the old asset failed: {}", upload_name, e))?;
+            let asset = crate::github::releases::update_release_asset(&owner, &repo, asset.id, Some(&name), None)
+                .await
+                .map_err(|e| format!("Uploaded as '{}' but renaming it failed: {}", upload_name, e))?;
+            Ok((asset, true))
+        }
+        .await;
+
+        match outcome {
+            Ok((asset, replaced)) => {
+                emit(&path, index, "uploaded", None);
+                results.push(AssetUploadResult { path, asset: Some(asset), replaced, error: None });
+            }
+            Err(e) => {
+                emit(&path, index, "failed", Some(e.clone()));
+                results.push(AssetUploadResult { path, asset: None, replaced: false, error: Some(e) });
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+/// Draft release notes from the commits since the last release tag, then tag,
+/// push, and publish the release
+///
+/// With `dry_run` set only the plan is returned, so it can be previewed and edited.
+#[tauri::command]
+pub async fn github_guided_release(
+    options: GuidedReleaseOptions,
+    state: State<'_, AppState>,
+) -> Result<GuidedReleasePlan, String> {
+    let repo_path = state.repo_path.lock().unwrap()
+        .as_ref()
+        .ok_or("No repository open")?
+        .clone();
+
+    let mut plan = {
+        let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+        let remote = match &options.remote {
+            Some(remote) => remote.clone(),
+            None => git::get_default_remote(&repo).map_err(|e| e.to_string())?,
+        };
+        let (owner, repo_name) = git::get_github_remote(&repo, &remote).map_err(|e| e.to_string())?;
+
+        let tag_ref = format!("refs/tags/{}", options.tag_name);
+        let tag_exists = repo.find_reference(&tag_ref).is_ok();
+        if options.create_tag && tag_exists {
+            return Err(format!("Tag '{}' already exists", options.tag_name));
+        }
+        if !options.create_tag && !tag_exists {
+            return Err(format!("Tag '{}' does not exist locally", options.tag_name));
+        }
+
+        // An existing tag is released at its own commit, a new one at HEAD
+        let target = if options.create_tag { "HEAD" } else { tag_ref.as_str() };
+        let target_sha = repo.revparse_single(target)
+            .and_then(|obj| obj.peel_to_commit())
+            .map_err(|e| e.to_string())?
+            .id()
+            .to_string();
+
+        let previous_tag = match &options.previous_tag {
+            Some(tag) => Some(tag.clone()),
+            None => git::find_latest_tag(&repo, &target_sha, Some(&options.tag_name)).map_err(|e| e.to_string())?,
+        };
+        let commits = git::get_commits_in_range(&repo, previous_tag.as_deref(), &target_sha)
+            .map_err(|e| e.to_string())?;
+
+        let notes = options.notes.clone()
+            .unwrap_or_else(|| git::changelog::draft_release_notes(&commits));
+
+        GuidedReleasePlan {
+            owner,
+            repo: repo_name,
+            remote,
+            tag_name: options.tag_name.clone(),
+            previous_tag,
+            target_sha,
+            commits,
+            notes,
+            tag_created: false,
+            tag_pushed: false,
+            release: None,
+        }
+    };
+
+    if options.dry_run {
+        return Ok(plan);
+    }
+
+    {
+        let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+        if options.create_tag {
+            let message = format!("Release {}", plan.tag_name);
+            git::create_tag(&repo, &plan.target_sha, &plan.tag_name, Some(&message))
+                .map_err(|e| e.to_string())?;
+            plan.tag_created = true;
+        }
+        git::push_tag(&repo, &plan.remote, &plan.tag_name)
+            .map_err(|e| format!("Failed to push tag '{}': {}", plan.tag_name, e))?;
+        plan.tag_pushed = true;
+    }
+
+    let request = CreateReleaseRequest {
+        tag_name: plan.tag_name.clone(),
+        target_commitish: None,
+        name: Some(options.name.unwrap_or_else(|| plan.tag_name.clone())),
+        body: Some(plan.notes.clone()),
+        draft: Some(options.draft),
+        prerelease: Some(options.prerelease),
+        generate_release_notes: Some(false),
+    };
+    let release = crate::github::releases::create_release(&plan.owner, &plan.repo, request)
+        .await
+        .map_err(|e| format!("Tag '{}' was pushed but creating the release failed: {}", plan.tag_name, e))?;
+    plan.release = Some(release);
+
+    Ok(plan)
+}
+
+#[tauri::command]
+pub async fn github_list_tag_protections(
+    owner: String,
+    repo: String,
+) -> Result<Vec<TagProtection>, String> {
+    crate::github::releases::list_tag_protections(&owner, &repo)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_create_tag_protection(
+    owner: String,
+    repo: String,
+    pattern: String,
+) -> Result<TagProtection, String> {
+    crate::github::releases::create_tag_protection(&owner, &repo, &pattern)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_delete_tag_protection(
+    owner: String,
+    repo: String,
+    tag_protection_id: i64,
+) -> Result<(), String> {
+    crate::github::releases::delete_tag_protection(&owner, &repo, tag_protection_id)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,317 @@
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::commands::state::AppState;
+use crate::git;
+use crate::github::templates::{GitignoreTemplate, License, LicenseSummary};
+use crate::github::{
+    self, GitHubBranch, GitHubRepo, RepoSettings, RepoSettingsUpdate, RepoTransfer, WatchLevel,
+};
+
+/// Result of publishing a local repository to GitHub
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishResult {
+    pub repo: GitHubRepo,
+    pub remote: String,
+    pub branch: String,
+}
+
+#[tauri::command]
+pub async fn github_create_repo(
+    name: String,
+    description: Option<String>,
+    private: bool,
+    org: Option<String>,
+    gitignore_template: Option<String>,
+    license_template: Option<String>,
+) -> Result<GitHubRepo, String> {
+    let token = github::get_stored_token().map_err(|e| e.to_string())?;
+    github::create_repo(
+        &token,
+        &name,
+        description.as_deref(),
+        private,
+        org.as_deref(),
+        gitignore_template.as_deref(),
+        license_template.as_deref(),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_publish_repo(
+    name: String,
+    description: Option<String>,
+    private: bool,
+    org: Option<String>,
+    remote_name: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<PublishResult, String> {
+    let repo_path = state.repo_path.lock().unwrap()
+        .as_ref()
+        .ok_or("No repository open")?
+        .clone();
+    let remote_name = remote_name.unwrap_or_else(|| "origin".to_string());
+
+    // Validate the local repository before creating anything on GitHub
+    let branch = {
+        let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+        if repo.find_remote(&remote_name).is_ok() {
+            return Err(format!("Remote '{}' already exists", remote_name));
+        }
+        let head = repo.head()
+            .map_err(|_| "Create a commit before publishing the repository".to_string())?;
+        if !head.is_branch() {
+            return Err("Cannot publish from a detached HEAD".to_string());
+        }
+        head.shorthand().unwrap_or("main").to_string()
+    };
+
+    let token = github::get_stored_token().map_err(|e| e.to_string())?;
+    let created = github::create_repo(&token, &name, description.as_deref(), private, org.as_deref(), None, None)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let repo = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    git::add_remote(&repo, &remote_name, &created.clone_url)
+        .map_err(|e| format!("Created {} but failed to add remote: {}", created.full_name, e))?;
+    git::push_set_upstream(&repo, &remote_name, &branch)
+        .map_err(|e| format!("Created {} but failed to push: {}", created.full_name, e))?;
+
+    Ok(PublishResult {
+        repo: created,
+        remote: remote_name,
+        branch,
+    })
+}
+
+/// Result of forking a repository and adding the fork as a remote
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForkResult {
+    pub repo: GitHubRepo,
+    pub remote: String,
+}
+
+#[tauri::command]
+pub async fn github_fork_repo(
+    owner: String,
+    repo: String,
+    org: Option<String>,
+) -> Result<GitHubRepo, String> {
+    let token = github::get_stored_token().map_err(|e| e.to_string())?;
+    github::fork_repo(&token, &owner, &repo, org.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_fork_and_add_remote(
+    owner: String,
+    repo: String,
+    org: Option<String>,
+    remote_name: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<ForkResult, String> {
+    let repo_path = state.repo_path.lock().unwrap()
+        .as_ref()
+        .ok_or("No repository open")?
+        .clone();
+
+    let token = github::get_stored_token().map_err(|e| e.to_string())?;
+    let fork = github::fork_repo(&token, &owner, &repo, org.as_deref())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let (fork_owner, fork_name) = fork
+        .full_name
+        .split_once('/')
+        .map(|(o, n)| (o.to_string(), n.to_string()))
+        .ok_or_else(|| format!("Unexpected repository name: {}", fork.full_name))?;
+
+    github::wait_for_repo_ready(&token, &fork_owner, &fork_name, 120)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // Name the remote after the fork owner, like `gh repo fork` does for existing clones
+    let remote_name = remote_name.unwrap_or_else(|| fork_owner.clone());
+
+    let local = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+    if let Ok(existing) = local.find_remote(&remote_name) {
+        if existing.url() != Some(fork.clone_url.as_str()) {
+            return Err(format!("Remote '{}' already exists with a different URL", remote_name));
+        }
+    } else {
+        git::add_remote(&local, &remote_name, &fork.clone_url).map_err(|e| e.to_string())?;
+    }
+    git::fetch(&local, &remote_name).map_err(|e| e.to_string())?;
+
+    Ok(ForkResult {
+        repo: fork,
+        remote: remote_name,
+    })
+}
+
+#[tauri::command]
+pub async fn github_star_repo(owner: String, repo: String) -> Result<(), String> {
+    let token = github::get_stored_token().map_err(|e| e.to_string())?;
+    github::star_repo(&token, &owner, &repo)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_unstar_repo(owner: String, repo: String) -> Result<(), String> {
+    let token = github::get_stored_token().map_err(|e| e.to_string())?;
+    github::unstar_repo(&token, &owner, &repo)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_is_repo_starred(owner: String, repo: String) -> Result<bool, String> {
+    let token = github::get_stored_token().map_err(|e| e.to_string())?;
+    github::is_repo_starred(&token, &owner, &repo)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_get_starred_repos(
+    page: Option<u32>,
+    per_page: Option<u32>,
+) -> Result<Vec<GitHubRepo>, String> {
+    let token = github::get_stored_token().map_err(|e| e.to_string())?;
+    github::get_starred_repos(&token, page.unwrap_or(1), per_page.unwrap_or(30))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_get_watch_level(owner: String, repo: String) -> Result<WatchLevel, String> {
+    let token = github::get_stored_token().map_err(|e| e.to_string())?;
+    github::get_watch_level(&token, &owner, &repo)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_set_watch_level(
+    owner: String,
+    repo: String,
+    level: WatchLevel,
+) -> Result<WatchLevel, String> {
+    let token = github::get_stored_token().map_err(|e| e.to_string())?;
+    github::set_watch_level(&token, &owner, &repo, level)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_get_repo(owner: String, repo: String) -> Result<RepoSettings, String> {
+    let token = github::get_stored_token().map_err(|e| e.to_string())?;
+    github::get_repo_settings(&token, &owner, &repo)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_update_repo(
+    owner: String,
+    repo: String,
+    update: RepoSettingsUpdate,
+) -> Result<RepoSettings, String> {
+    let token = github::get_stored_token().map_err(|e| e.to_string())?;
+    github::update_repo_settings(&token, &owner, &repo, &update)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_archive_repo(owner: String, repo: String) -> Result<RepoSettings, String> {
+    let token = github::get_stored_token().map_err(|e| e.to_string())?;
+    github::set_repo_archived(&token, &owner, &repo, true)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_unarchive_repo(owner: String, repo: String) -> Result<RepoSettings, String> {
+    let token = github::get_stored_token().map_err(|e| e.to_string())?;
+    github::set_repo_archived(&token, &owner, &repo, false)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_delete_repo(
+    owner: String,
+    repo: String,
+    confirmation: String,
+) -> Result<(), String> {
+    let token = github::get_stored_token().map_err(|e| e.to_string())?;
+    github::delete_repo(&token, &owner, &repo, &confirmation)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_transfer_repo(
+    owner: String,
+    repo: String,
+    new_owner: String,
+    new_name: Option<String>,
+    team_ids: Option<Vec<i64>>,
+) -> Result<RepoTransfer, String> {
+    let token = github::get_stored_token().map_err(|e| e.to_string())?;
+    github::transfer_repo(
+        &token,
+        &owner,
+        &repo,
+        &new_owner,
+        new_name.as_deref(),
+        team_ids.unwrap_or_default(),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_list_branches(
+    owner: String,
+    repo: String,
+    protected_only: Option<bool>,
+) -> Result<Vec<GitHubBranch>, String> {
+    let token = github::get_stored_token().map_err(|e| e.to_string())?;
+    github::list_branches(&token, &owner, &repo, protected_only.unwrap_or(false))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_list_gitignore_templates() -> Result<Vec<String>, String> {
+    github::templates::list_gitignore_templates()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_get_gitignore_template(name: String) -> Result<GitignoreTemplate, String> {
+    github::templates::get_gitignore_template(&name)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_list_licenses() -> Result<Vec<LicenseSummary>, String> {
+    github::templates::list_licenses()
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_get_license(key: String) -> Result<License, String> {
+    github::templates::get_license(&key)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,50 @@
+use crate::github::GitHubRepo;
+use crate::github::search::{CodeSearchItem, IssueSearchItem, SearchResults, UserSearchItem};
+
+#[tauri::command]
+pub async fn github_search_code(
+    query: String,
+    repo_filter: Option<String>,
+    page: Option<i32>,
+    per_page: Option<i32>,
+) -> Result<SearchResults<CodeSearchItem>, String> {
+    crate::github::search::search_code(&query, repo_filter.as_deref(), page, per_page)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_search_issues(
+    query: String,
+    sort: Option<String>,
+    order: Option<String>,
+    page: Option<i32>,
+    per_page: Option<i32>,
+) -> Result<SearchResults<IssueSearchItem>, String> {
+    crate::github::search::search_issues(&query, sort.as_deref(), order.as_deref(), page, per_page)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_search_repos(
+    query: String,
+    sort: Option<String>,
+    page: Option<i32>,
+    per_page: Option<i32>,
+) -> Result<SearchResults<GitHubRepo>, String> {
+    crate::github::search::search_repos(&query, sort.as_deref(), page, per_page)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_search_users(
+    query: String,
+    page: Option<i32>,
+    per_page: Option<i32>,
+) -> Result<SearchResults<UserSearchItem>, String> {
+    crate::github::search::search_users(&query, page, per_page)
+        .await
+        .map_err(|e| e.to_string())
+}
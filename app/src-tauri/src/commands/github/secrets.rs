@@ -0,0 +1,79 @@
+use crate::github::secrets::{ActionsSecret, ActionsVariable};
+
+#[tauri::command]
+pub async fn github_list_repo_secrets(
+    owner: String,
+    repo: String,
+) -> Result<Vec<ActionsSecret>, String> {
+    crate::github::secrets::list_repo_secrets(&owner, &repo)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_set_repo_secret(
+    owner: String,
+    repo: String,
+    name: String,
+    value: String,
+) -> Result<(), String> {
+    crate::github::secrets::set_repo_secret(&owner, &repo, &name, &value)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_delete_repo_secret(
+    owner: String,
+    repo: String,
+    name: String,
+) -> Result<(), String> {
+    crate::github::secrets::delete_repo_secret(&owner, &repo, &name)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_list_repo_variables(
+    owner: String,
+    repo: String,
+) -> Result<Vec<ActionsVariable>, String> {
+    crate::github::secrets::list_repo_variables(&owner, &repo)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_create_repo_variable(
+    owner: String,
+    repo: String,
+    name: String,
+    value: String,
+) -> Result<(), String> {
+    crate::github::secrets::create_repo_variable(&owner, &repo, &name, &value)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_update_repo_variable(
+    owner: String,
+    repo: String,
+    name: String,
+    value: String,
+) -> Result<(), String> {
+    crate::github::secrets::update_repo_variable(&owner, &repo, &name, &value)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_delete_repo_variable(
+    owner: String,
+    repo: String,
+    name: String,
+) -> Result<(), String> {
+    crate::github::secrets::delete_repo_variable(&owner, &repo, &name)
+        .await
+        .map_err(|e| e.to_string())
+}
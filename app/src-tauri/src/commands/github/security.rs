@@ -1,10 +1,20 @@
+use std::io::Write;
+
+use base64::Engine;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use crate::github::api::GitHubApiError;
-use crate::github::oauth::get_stored_token;
+use tauri::State;
+use crate::commands::state::AppState;
+use crate::git;
+use crate::github::app_auth::token_for_repo;
 
 const GITHUB_API_URL: &str = "https://api.github.com";
 
+/// Largest gzip-compressed SARIF file the upload endpoint accepts
+const MAX_SARIF_UPLOAD_BYTES: usize = 10 * 1024 * 1024;
+
 fn create_client(token: &str) -> Client {
     Client::builder()
         .user_agent("LinuxGit/1.0")
@@ -25,6 +35,13 @@ fn create_client(token: &str) -> Client {
         .unwrap()
 }
 
+/// Client for a repository, authenticating as its GitHub App installation if
+/// one is configured for it
+async fn get_repo_client(owner: &str, repo: &str) -> Result<Client, String> {
+    let token = token_for_repo(owner, repo).await.map_err(|e| e.to_string())?;
+    Ok(create_client(&token))
+}
+
 // Dependabot Alert Types
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DependabotAlert {
@@ -111,6 +128,111 @@ pub struct CodeScanningMessage {
     pub text: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CodeScanningAlertInstance {
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    pub analysis_key: Option<String>,
+    pub environment: Option<String>,
+    pub category: Option<String>,
+    pub state: String,
+    pub commit_sha: Option<String>,
+    pub message: Option<CodeScanningMessage>,
+    pub location: Option<CodeScanningLocation>,
+    #[serde(default)]
+    pub classifications: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SarifUpload {
+    pub id: String,
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SarifUploadStatus {
+    pub processing_status: String,
+    pub analyses_url: Option<String>,
+    #[serde(default)]
+    pub errors: Option<Vec<String>>,
+}
+
+// Repository Security Advisory Types
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AdvisoryPackage {
+    pub ecosystem: String,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AdvisoryVulnerability {
+    pub package: AdvisoryPackage,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vulnerable_version_range: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub patched_versions: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub vulnerable_functions: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AdvisoryCwe {
+    pub cwe_id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RepositoryAdvisory {
+    pub ghsa_id: String,
+    pub cve_id: Option<String>,
+    pub summary: String,
+    pub description: Option<String>,
+    pub severity: Option<String>,
+    pub state: String,
+    pub html_url: String,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub published_at: Option<String>,
+    #[serde(default)]
+    pub vulnerabilities: Vec<AdvisoryVulnerability>,
+    #[serde(default)]
+    pub cwes: Vec<AdvisoryCwe>,
+}
+
+/// Fields of a new draft advisory
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RepositoryAdvisoryInput {
+    pub summary: String,
+    pub description: String,
+    pub vulnerabilities: Vec<AdvisoryVulnerability>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cve_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cwe_ids: Vec<String>,
+}
+
+/// Advisory fields to change; unset fields are left as they are
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RepositoryAdvisoryUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vulnerabilities: Option<Vec<AdvisoryVulnerability>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cve_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cwe_ids: Option<Vec<String>>,
+    /// `draft`, `published`, or `closed`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+}
+
 // Secret Scanning Alert Types
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SecretScanningAlert {
@@ -129,8 +251,7 @@ pub async fn github_list_dependabot_alerts(
     owner: String,
     repo: String,
 ) -> Result<Vec<DependabotAlert>, String> {
-    let token = get_stored_token().map_err(|e| GitHubApiError::ApiError(e.to_string())).map_err(|e| e.to_string())?;
-    let client = create_client(&token);
+    let client = get_repo_client(&owner, &repo).await?;
     let url = format!(
         "{}/repos/{}/{}/dependabot/alerts?state=open&per_page=100",
         GITHUB_API_URL, owner, repo
@@ -164,8 +285,7 @@ pub async fn github_list_code_scanning_alerts(
     owner: String,
     repo: String,
 ) -> Result<Vec<CodeScanningAlert>, String> {
-    let token = get_stored_token().map_err(|e| GitHubApiError::ApiError(e.to_string())).map_err(|e| e.to_string())?;
-    let client = create_client(&token);
+    let client = get_repo_client(&owner, &repo).await?;
     let url = format!(
         "{}/repos/{}/{}/code-scanning/alerts?state=open&per_page=100",
         GITHUB_API_URL, owner, repo
@@ -199,8 +319,7 @@ pub async fn github_list_secret_scanning_alerts(
     owner: String,
     repo: String,
 ) -> Result<Vec<SecretScanningAlert>, String> {
-    let token = get_stored_token().map_err(|e| GitHubApiError::ApiError(e.to_string())).map_err(|e| e.to_string())?;
-    let client = create_client(&token);
+    let client = get_repo_client(&owner, &repo).await?;
     let url = format!(
         "{}/repos/{}/{}/secret-scanning/alerts?state=open&per_page=100",
         GITHUB_API_URL, owner, repo
@@ -237,8 +356,7 @@ pub async fn github_dismiss_dependabot_alert(
     dismissed_reason: String,
     dismissed_comment: Option<String>,
 ) -> Result<DependabotAlert, String> {
-    let token = get_stored_token().map_err(|e| GitHubApiError::ApiError(e.to_string())).map_err(|e| e.to_string())?;
-    let client = create_client(&token);
+    let client = get_repo_client(&owner, &repo).await?;
     let url = format!(
         "{}/repos/{}/{}/dependabot/alerts/{}",
         GITHUB_API_URL, owner, repo, alert_number
@@ -286,8 +404,7 @@ pub async fn github_dismiss_code_scanning_alert(
     dismissed_reason: String,
     dismissed_comment: Option<String>,
 ) -> Result<CodeScanningAlert, String> {
-    let token = get_stored_token().map_err(|e| GitHubApiError::ApiError(e.to_string())).map_err(|e| e.to_string())?;
-    let client = create_client(&token);
+    let client = get_repo_client(&owner, &repo).await?;
     let url = format!(
         "{}/repos/{}/{}/code-scanning/alerts/{}",
         GITHUB_API_URL, owner, repo, alert_number
@@ -335,8 +452,7 @@ pub async fn github_resolve_secret_scanning_alert(
     resolution: String,
     resolution_comment: Option<String>,
 ) -> Result<SecretScanningAlert, String> {
-    let token = get_stored_token().map_err(|e| GitHubApiError::ApiError(e.to_string())).map_err(|e| e.to_string())?;
-    let client = create_client(&token);
+    let client = get_repo_client(&owner, &repo).await?;
     let url = format!(
         "{}/repos/{}/{}/secret-scanning/alerts/{}",
         GITHUB_API_URL, owner, repo, alert_number
@@ -374,3 +490,285 @@ pub async fn github_resolve_secret_scanning_alert(
         .await
         .map_err(|e| format!("Parse error: {}", e))
 }
+
+/// List all instances of a Code Scanning alert, one per branch or analysis
+#[tauri::command]
+pub async fn github_list_code_scanning_alert_instances(
+    owner: String,
+    repo: String,
+    alert_number: i64,
+    git_ref: Option<String>,
+) -> Result<Vec<CodeScanningAlertInstance>, String> {
+    let client = get_repo_client(&owner, &repo).await?;
+    let url = format!(
+        "{}/repos/{}/{}/code-scanning/alerts/{}/instances",
+        GITHUB_API_URL, owner, repo, alert_number
+    );
+
+    let mut query = vec![("per_page", "100".to_string())];
+    if let Some(r) = git_ref {
+        query.push(("ref", r));
+    }
+
+    let response = client
+        .get(&url)
+        .query(&query)
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(format!("API error {}: {}", status, text));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Parse error: {}", e))
+}
+
+/// Gzip and base64-encode a SARIF document for upload
+fn encode_sarif(sarif: &[u8]) -> Result<String, String> {
+    serde_json::from_slice::<serde_json::Value>(sarif)
+        .map_err(|e| format!("Invalid SARIF file: {}", e))?;
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(sarif).map_err(|e| format!("Failed to compress SARIF: {}", e))?;
+    let compressed = encoder.finish().map_err(|e| format!("Failed to compress SARIF: {}", e))?;
+
+    if compressed.len() > MAX_SARIF_UPLOAD_BYTES {
+        return Err("SARIF file is too large to upload (over 10 MB compressed)".to_string());
+    }
+
+    Ok(base64::engine::general_purpose::STANDARD.encode(compressed))
+}
+
+/// Upload a local SARIF file to Code Scanning
+///
+/// The commit and ref default to HEAD of the open repository.
+#[tauri::command]
+pub async fn github_upload_sarif(
+    owner: String,
+    repo: String,
+    sarif_path: String,
+    commit_sha: Option<String>,
+    git_ref: Option<String>,
+    tool_name: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<SarifUpload, String> {
+    let (commit_sha, git_ref) = match (commit_sha, git_ref) {
+        (Some(sha), Some(r)) => (sha, r),
+        (sha, r) => {
+            let repo_path = state.repo_path.lock().unwrap()
+                .as_ref()
+                .ok_or("No repository open")?
+                .clone();
+            let local = git::open_repo(&repo_path).map_err(|e| e.to_string())?;
+            let head = local.head().map_err(|e| e.to_string())?;
+            if r.is_none() && !head.is_branch() {
+                return Err("HEAD is detached; specify the ref the analysis belongs to".to_string());
+            }
+            let head_sha = head.peel_to_commit().map_err(|e| e.to_string())?.id().to_string();
+            let head_ref = head.name().unwrap_or_default().to_string();
+            (sha.unwrap_or(head_sha), r.unwrap_or(head_ref))
+        }
+    };
+
+    let sarif = std::fs::read(&sarif_path).map_err(|e| format!("Failed to read {}: {}", sarif_path, e))?;
+    let encoded = encode_sarif(&sarif)?;
+
+    let client = get_repo_client(&owner, &repo).await?;
+    let url = format!("{}/repos/{}/{}/code-scanning/sarifs", GITHUB_API_URL, owner, repo);
+
+    #[derive(Serialize)]
+    struct SarifBody {
+        commit_sha: String,
+        #[serde(rename = "ref")]
+        git_ref: String,
+        sarif: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        tool_name: Option<String>,
+    }
+
+    let body = SarifBody {
+        commit_sha,
+        git_ref,
+        sarif: encoded,
+        tool_name,
+    };
+
+    let response = client
+        .post(&url)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(format!("API error {}: {}", status, text));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Parse error: {}", e))
+}
+
+/// Get the processing status of a SARIF upload
+#[tauri::command]
+pub async fn github_get_sarif_upload_status(
+    owner: String,
+    repo: String,
+    sarif_id: String,
+) -> Result<SarifUploadStatus, String> {
+    let client = get_repo_client(&owner, &repo).await?;
+    let url = format!(
+        "{}/repos/{}/{}/code-scanning/sarifs/{}",
+        GITHUB_API_URL, owner, repo, sarif_id
+    );
+
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(format!("API error {}: {}", status, text));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Parse error: {}", e))
+}
+
+/// List security advisories of a repository, optionally filtered by state
+#[tauri::command]
+pub async fn github_list_repository_advisories(
+    owner: String,
+    repo: String,
+    state: Option<String>,
+) -> Result<Vec<RepositoryAdvisory>, String> {
+    let client = get_repo_client(&owner, &repo).await?;
+    let url = format!("{}/repos/{}/{}/security-advisories", GITHUB_API_URL, owner, repo);
+
+    let mut query = vec![("per_page", "100".to_string())];
+    if let Some(s) = state {
+        query.push(("state", s));
+    }
+
+    let response = client
+        .get(&url)
+        .query(&query)
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(format!("API error {}: {}", status, text));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Parse error: {}", e))
+}
+
+/// Create a draft security advisory
+#[tauri::command]
+pub async fn github_create_repository_advisory(
+    owner: String,
+    repo: String,
+    advisory: RepositoryAdvisoryInput,
+) -> Result<RepositoryAdvisory, String> {
+    let client = get_repo_client(&owner, &repo).await?;
+    let url = format!("{}/repos/{}/{}/security-advisories", GITHUB_API_URL, owner, repo);
+
+    let response = client
+        .post(&url)
+        .json(&advisory)
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(format!("API error {}: {}", status, text));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Parse error: {}", e))
+}
+
+/// Update a security advisory, including publishing or closing it
+#[tauri::command]
+pub async fn github_update_repository_advisory(
+    owner: String,
+    repo: String,
+    ghsa_id: String,
+    update: RepositoryAdvisoryUpdate,
+) -> Result<RepositoryAdvisory, String> {
+    let client = get_repo_client(&owner, &repo).await?;
+    let url = format!(
+        "{}/repos/{}/{}/security-advisories/{}",
+        GITHUB_API_URL, owner, repo, ghsa_id
+    );
+
+    let response = client
+        .patch(&url)
+        .json(&update)
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(format!("API error {}: {}", status, text));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| format!("Parse error: {}", e))
+}
+
+/// Request a CVE for a draft security advisory
+#[tauri::command]
+pub async fn github_request_advisory_cve(
+    owner: String,
+    repo: String,
+    ghsa_id: String,
+) -> Result<(), String> {
+    let client = get_repo_client(&owner, &repo).await?;
+    let url = format!(
+        "{}/repos/{}/{}/security-advisories/{}/cve",
+        GITHUB_API_URL, owner, repo, ghsa_id
+    );
+
+    let response = client
+        .post(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Network error: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(format!("API error {}: {}", status, text));
+    }
+
+    Ok(())
+}
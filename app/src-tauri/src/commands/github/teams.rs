@@ -0,0 +1,36 @@
+use crate::github::teams::{Team, TeamMember, TeamRepository};
+
+#[tauri::command]
+pub async fn github_list_requestable_teams(owner: String, repo: String) -> Result<Vec<Team>, String> {
+    crate::github::teams::list_repo_teams(&owner, &repo)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_list_org_teams(org: String) -> Result<Vec<Team>, String> {
+    crate::github::teams::list_org_teams(&org)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_list_team_members(
+    org: String,
+    team_slug: String,
+    role: Option<String>,
+) -> Result<Vec<TeamMember>, String> {
+    crate::github::teams::list_team_members(&org, &team_slug, role.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn github_list_team_repos(
+    org: String,
+    team_slug: String,
+) -> Result<Vec<TeamRepository>, String> {
+    crate::github::teams::list_team_repos(&org, &team_slug)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -1,9 +1,25 @@
-use std::sync::Mutex;
-use crate::ai::AiConfig;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+use crate::ai::{AiConfig, GenerationCancel};
+use crate::github::notification_poller::NotificationPollerHandle;
 
 pub struct AppState {
     pub repo_path: Mutex<Option<String>>,
     pub ai_config: Mutex<AiConfig>,
+    pub watched_runs: Mutex<HashSet<i64>>,
+    /// Cancellation flags of in-progress release asset downloads, by asset id
+    pub asset_downloads: Mutex<HashMap<i64, Arc<AtomicBool>>>,
+    /// The running notification poller, if any
+    pub notification_poller: Mutex<Option<NotificationPollerHandle>>,
+    /// Cancellation of the streaming AI generation in progress, if any
+    pub ai_generation: Mutex<Option<Arc<GenerationCancel>>>,
+    /// Cancellation flag of the local workflow run (with `act`) in progress, if any
+    pub local_workflow_run: Mutex<Option<Arc<AtomicBool>>>,
+    /// Cancellation flag of the repository scan in progress, if any
+    pub repo_scan: Mutex<Option<Arc<AtomicBool>>>,
+    /// Cancellation flag of the commit search index build in progress, if any
+    pub search_index_build: Mutex<Option<Arc<AtomicBool>>>,
 }
 
 impl Default for AppState {
@@ -11,6 +27,13 @@ impl Default for AppState {
         Self {
             repo_path: Mutex::new(None),
             ai_config: Mutex::new(AiConfig::default()),
+            watched_runs: Mutex::new(HashSet::new()),
+            asset_downloads: Mutex::new(HashMap::new()),
+            notification_poller: Mutex::new(None),
+            ai_generation: Mutex::new(None),
+            local_workflow_run: Mutex::new(None),
+            repo_scan: Mutex::new(None),
+            search_index_build: Mutex::new(None),
         }
     }
 }
@@ -1,5 +1,5 @@
 use std::fmt;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize)]
 pub enum ErrorCode {
@@ -102,6 +102,44 @@ impl From<serde_json::Error> for AppError {
     }
 }
 
+/// Error returned from a command as a JSON object, so the frontend can tell it
+/// apart from others by `kind` and act on its details
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StructuredError {
+    pub kind: String,
+    pub message: String,
+    /// Fields specific to the kind, flattened into the object
+    #[serde(flatten)]
+    pub details: serde_json::Map<String, serde_json::Value>,
+}
+
+impl StructuredError {
+    pub fn new(kind: &str, message: impl Into<String>, details: serde_json::Value) -> Self {
+        let details = match details {
+            serde_json::Value::Object(map) => map,
+            _ => serde_json::Map::new(),
+        };
+        Self {
+            kind: kind.to_string(),
+            message: message.into(),
+            details,
+        }
+    }
+
+    /// The error as a JSON string, for returning from a command
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_else(|_| self.message.clone())
+    }
+}
+
+impl fmt::Display for StructuredError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for StructuredError {}
+
 pub type AppResult<T> = Result<T, AppError>;
 
 pub trait ResultExt<T> {
@@ -0,0 +1,136 @@
+//! Daily commit counts for an activity heatmap
+//!
+//! Counts are taken from the local branches, so the heatmap works offline.
+//! Commits are dated by their author date in the author's own timezone.
+
+use chrono::{Duration, FixedOffset, Local, NaiveDate, TimeZone};
+use git2::Repository;
+use serde::{Deserialize, Serialize};
+
+use super::GitResult;
+
+/// Number of days shown in the heatmap, ending today
+const HEATMAP_DAYS: i64 = 365;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityDay {
+    /// `YYYY-MM-DD`
+    pub date: String,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitActivity {
+    /// Every day of the period in order, including days without commits
+    pub days: Vec<ActivityDay>,
+    pub total: u32,
+    pub max_count: u32,
+}
+
+/// Author date of a commit in the author's timezone
+fn author_date(commit: &git2::Commit) -> Option<NaiveDate> {
+    let when = commit.author().when();
+    let offset = FixedOffset::east_opt(when.offset_minutes() * 60)?;
+    offset
+        .timestamp_opt(when.seconds(), 0)
+        .single()
+        .map(|date| date.date_naive())
+}
+
+/// Count the commits of each day of the year up to `today`, optionally only those
+/// whose author name or email contains `author`
+pub fn get_commit_activity_until(
+    repo: &Repository,
+    author: Option<&str>,
+    today: NaiveDate,
+) -> GitResult<CommitActivity> {
+    let start = today - Duration::days(HEATMAP_DAYS - 1);
+    let mut counts = vec![0u32; HEATMAP_DAYS as usize];
+    let author = author.map(|a| a.trim().to_lowercase()).filter(|a| !a.is_empty());
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.set_sorting(git2::Sort::TIME)?;
+    revwalk.push_glob("refs/heads")?;
+    if repo.head().is_ok_and(|h| !h.is_branch()) {
+        revwalk.push_head()?;
+    }
+    // Commits are walked newest first; allow a day for timezones before stopping
+    let oldest = start.and_hms_opt(0, 0, 0).map(|d| d.and_utc().timestamp() - 86400).unwrap_or(i64::MIN);
+
+    for oid in revwalk {
+        let commit = repo.find_commit(oid?)?;
+        if commit.time().seconds() < oldest {
+            break;
+        }
+
+        if let Some(author) = &author {
+            let signature = commit.author();
+            let matches = [signature.name(), signature.email()]
+                .into_iter()
+                .flatten()
+                .any(|value| value.to_lowercase().contains(author.as_str()));
+            if !matches {
+                continue;
+            }
+        }
+
+        let Some(date) = author_date(&commit) else {
+            continue;
+        };
+        let index = (date - start).num_days();
+        if (0..HEATMAP_DAYS).contains(&index) {
+            counts[index as usize] += 1;
+        }
+    }
+
+    let days = counts
+        .iter()
+        .enumerate()
+        .map(|(i, &count)| ActivityDay {
+            date: (start + Duration::days(i as i64)).format("%Y-%m-%d").to_string(),
+            count,
+        })
+        .collect();
+
+    Ok(CommitActivity {
+        days,
+        total: counts.iter().sum(),
+        max_count: counts.iter().copied().max().unwrap_or(0),
+    })
+}
+
+/// Count the commits of each day of the past year
+pub fn get_commit_activity(repo: &Repository, author: Option<&str>) -> GitResult<CommitActivity> {
+    get_commit_activity_until(repo, author, Local::now().date_naive())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::{commit_index, signature_at};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_commit_activity() {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let today = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+        let noon = |date: NaiveDate| date.and_hms_opt(12, 0, 0).unwrap().and_utc().timestamp();
+
+        commit_index(&repo, "commit", &signature_at("Old", noon(today - Duration::days(400))));
+        commit_index(&repo, "commit", &signature_at("Ada", noon(today - Duration::days(364))));
+        commit_index(&repo, "commit", &signature_at("Ada", noon(today)));
+        commit_index(&repo, "commit", &signature_at("Bob", noon(today)));
+
+        let activity = get_commit_activity_until(&repo, None, today).unwrap();
+        assert_eq!(activity.days.len(), 365);
+        assert_eq!(activity.days[0].date, "2023-07-02");
+        assert_eq!(activity.days[0].count, 1);
+        assert_eq!(activity.days[364].date, "2024-06-30");
+        assert_eq!(activity.days[364].count, 2);
+        assert_eq!((activity.total, activity.max_count), (3, 2));
+
+        let activity = get_commit_activity_until(&repo, Some("ada"), today).unwrap();
+        assert_eq!(activity.total, 2);
+    }
+}
@@ -0,0 +1,307 @@
+//! Line-by-line blame of a file
+//!
+//! Each line of a file is attributed to the commit that last changed it.
+//! Without a revision the working directory version is blamed, so lines that
+//! are not committed yet show up as uncommitted. Renames are followed by
+//! default; when they are not, lines older than the file's current path are
+//! attributed to the commit that introduced the path.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::path::Path;
+
+use git2::{BlameOptions, Oid, Repository};
+use serde::{Deserialize, Serialize};
+
+use super::files::worktree_path;
+use super::{format_time, GitError, GitResult};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileBlameOptions {
+    /// Ignore changes that only touch whitespace
+    #[serde(default)]
+    pub ignore_whitespace: bool,
+    /// Follow the file through renames
+    #[serde(default = "default_follow_renames")]
+    pub follow_renames: bool,
+}
+
+fn default_follow_renames() -> bool {
+    true
+}
+
+impl Default for FileBlameOptions {
+    fn default() -> Self {
+        Self {
+            ignore_whitespace: false,
+            follow_renames: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlameLine {
+    /// 1-based line number in the blamed version
+    pub line_number: usize,
+    pub content: String,
+    /// Commit that last changed the line; all zeros when it is uncommitted
+    pub sha: String,
+    pub short_sha: String,
+    pub author: String,
+    pub email: String,
+    pub timestamp: i64,
+    /// Relative date, such as "2 days ago"
+    pub date: String,
+    /// Absolute local date in the configured format
+    pub absolute_date: String,
+    /// First line of the commit message
+    pub summary: String,
+    /// Path of the file in that commit, if it differs because of a rename
+    pub original_path: Option<String>,
+    /// Line number in that commit
+    pub original_line: usize,
+    /// Whether the commit is the oldest one blamed rather than the line's origin
+    pub boundary: bool,
+    pub uncommitted: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileBlame {
+    pub path: String,
+    /// Revision blamed, or none for the working directory
+    pub rev: Option<String>,
+    pub lines: Vec<BlameLine>,
+}
+
+/// Author and summary of a commit, looked up once per commit
+struct CommitSummary {
+    author: String,
+    email: String,
+    summary: String,
+}
+
+/// The commit on the first-parent history of `newest` that introduced `path`
+/// under that name
+fn path_introduced_in(repo: &Repository, newest: Oid, path: &Path) -> GitResult<Oid> {
+    let mut commit = repo.find_commit(newest)?;
+    loop {
+        let parent = match commit.parent(0) {
+            Ok(parent) => parent,
+            Err(_) => return Ok(commit.id()),
+        };
+        if parent.tree()?.get_path(path).is_err() {
+            return Ok(commit.id());
+        }
+        commit = parent;
+    }
+}
+
+fn uncommitted_line(line_number: usize, content: &str) -> BlameLine {
+    BlameLine {
+        line_number,
+        content: content.to_string(),
+        sha: Oid::zero().to_string(),
+        short_sha: String::new(),
+        author: "Not Committed Yet".to_string(),
+        email: String::new(),
+        timestamp: 0,
+        date: String::new(),
+        absolute_date: String::new(),
+        summary: String::new(),
+        original_path: None,
+        original_line: line_number,
+        boundary: false,
+        uncommitted: true,
+    }
+}
+
+/// Blame every line of `path` at `rev`, or in the working directory
+pub fn get_file_blame(
+    repo: &Repository,
+    path: &str,
+    rev: Option<&str>,
+    options: &FileBlameOptions,
+) -> GitResult<FileBlame> {
+    let file_path = Path::new(path);
+    let newest = match rev {
+        Some(rev) => Some(
+            repo.revparse_single(rev)
+                .and_then(|obj| obj.peel_to_commit())
+                .map_err(|_| GitError::CommitNotFound(rev.to_string()))?
+                .id(),
+        ),
+        None => repo.head().ok().and_then(|head| head.target()),
+    };
+
+    let content = match (rev, newest) {
+        (Some(_), Some(newest)) => {
+            let entry = repo
+                .find_commit(newest)?
+                .tree()?
+                .get_path(file_path)
+                .map_err(|_| GitError::FileNotFound(path.to_string()))?;
+            repo.find_blob(entry.id())?.content().to_vec()
+        }
+        _ => std::fs::read(worktree_path(repo, path)?).map_err(|_| GitError::FileNotFound(path.to_string()))?,
+    };
+    if content.contains(&0) {
+        return Err(GitError::Generic(format!("Cannot blame binary file {}", path)));
+    }
+    let text = String::from_utf8_lossy(&content);
+    let file_lines: Vec<&str> = text.lines().collect();
+
+    let committed = newest.filter(|&id| {
+        repo.find_commit(id)
+            .and_then(|c| c.tree())
+            .is_ok_and(|tree| tree.get_path(file_path).is_ok())
+    });
+    let Some(newest) = committed else {
+        // A file that was never committed is entirely uncommitted
+        return Ok(FileBlame {
+            path: path.to_string(),
+            rev: rev.map(|r| r.to_string()),
+            lines: file_lines
+                .iter()
+                .enumerate()
+                .map(|(i, line)| uncommitted_line(i + 1, line))
+                .collect(),
+        });
+    };
+
+    let mut blame_opts = BlameOptions::new();
+    blame_opts.newest_commit(newest).ignore_whitespace(options.ignore_whitespace);
+    if !options.follow_renames {
+        blame_opts.oldest_commit(path_introduced_in(repo, newest, file_path)?);
+    }
+    let committed_blame = repo.blame_file(file_path, Some(&mut blame_opts))?;
+    let blame = match rev {
+        Some(_) => committed_blame,
+        None => committed_blame.blame_buffer(&content)?,
+    };
+
+    let mut commits: HashMap<Oid, CommitSummary> = HashMap::new();
+    let mut lines = Vec::with_capacity(file_lines.len());
+    for (i, line) in file_lines.iter().enumerate() {
+        let line_number = i + 1;
+        let Some(hunk) = blame.get_line(line_number) else {
+            lines.push(uncommitted_line(line_number, line));
+            continue;
+        };
+        let id = hunk.final_commit_id();
+        if id.is_zero() {
+            lines.push(uncommitted_line(line_number, line));
+            continue;
+        }
+
+        let summary = match commits.entry(id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let commit = repo.find_commit(id)?;
+                let author = commit.author();
+                entry.insert(CommitSummary {
+                    author: author.name().unwrap_or("Unknown").to_string(),
+                    email: author.email().unwrap_or("").to_string(),
+                    summary: commit.summary().unwrap_or("").to_string(),
+                })
+            }
+        };
+        let timestamp = hunk.final_signature().when().seconds();
+        let time = format_time(timestamp);
+        let sha = id.to_string();
+
+        lines.push(BlameLine {
+            line_number,
+            content: line.to_string(),
+            short_sha: sha.chars().take(7).collect(),
+            sha,
+            author: summary.author.clone(),
+            email: summary.email.clone(),
+            timestamp,
+            date: time.relative,
+            absolute_date: time.absolute,
+            summary: summary.summary.clone(),
+            original_path: hunk
+                .path()
+                .filter(|p| *p != file_path)
+                .map(|p| p.to_string_lossy().to_string()),
+            original_line: hunk.orig_start_line() + (line_number - hunk.final_start_line()),
+            boundary: hunk.is_boundary(),
+            uncommitted: false,
+        });
+    }
+
+    Ok(FileBlame {
+        path: path.to_string(),
+        rev: rev.map(|r| r.to_string()),
+        lines,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::commit_all;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn shas(blame: &FileBlame) -> Vec<String> {
+        blame.lines.iter().map(|l| l.sha.clone()).collect()
+    }
+
+    #[test]
+    fn test_blame_lines_and_working_directory() {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        fs::write(dir.path().join("f.txt"), "one\ntwo\n").unwrap();
+        let first = commit_all(&repo, "first");
+        fs::write(dir.path().join("f.txt"), "one\n2\nthree\n").unwrap();
+        let second = commit_all(&repo, "second");
+        fs::write(dir.path().join("f.txt"), "one\n2\nthree\nwip\n").unwrap();
+
+        let blame = get_file_blame(&repo, "f.txt", Some("HEAD"), &FileBlameOptions::default()).unwrap();
+        assert_eq!(shas(&blame), [first.to_string(), second.to_string(), second.to_string()]);
+        assert_eq!(blame.lines[1].content, "2");
+        assert_eq!(blame.lines[1].summary, "second");
+
+        let at_first =
+            get_file_blame(&repo, "f.txt", Some(&first.to_string()), &FileBlameOptions::default()).unwrap();
+        assert_eq!(at_first.lines.len(), 2);
+
+        let workdir = get_file_blame(&repo, "f.txt", None, &FileBlameOptions::default()).unwrap();
+        assert_eq!(workdir.lines.len(), 4);
+        assert!(workdir.lines[3].uncommitted);
+        assert_eq!(workdir.lines[0].sha, first.to_string());
+
+        // Working directory paths may not leave the repository
+        assert!(get_file_blame(&repo, "../f.txt", None, &FileBlameOptions::default()).is_err());
+        assert!(get_file_blame(&repo, ".git/config", None, &FileBlameOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_blame_ignore_whitespace_and_renames() {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        fs::write(dir.path().join("old.txt"), "alpha\nbeta\ngamma\ndelta\n").unwrap();
+        let first = commit_all(&repo, "first");
+        fs::write(dir.path().join("old.txt"), "alpha\n  beta\ngamma\ndelta\n").unwrap();
+        let indent = commit_all(&repo, "indent");
+
+        let plain = get_file_blame(&repo, "old.txt", Some("HEAD"), &FileBlameOptions::default()).unwrap();
+        assert_eq!(plain.lines[1].sha, indent.to_string());
+        let options = FileBlameOptions { ignore_whitespace: true, follow_renames: true };
+        let ignoring = get_file_blame(&repo, "old.txt", Some("HEAD"), &options).unwrap();
+        assert_eq!(ignoring.lines[1].sha, first.to_string());
+
+        fs::rename(dir.path().join("old.txt"), dir.path().join("new.txt")).unwrap();
+        let rename = commit_all(&repo, "rename");
+
+        let followed = get_file_blame(&repo, "new.txt", Some("HEAD"), &FileBlameOptions::default()).unwrap();
+        assert_eq!(followed.lines[0].sha, first.to_string());
+        assert_eq!(followed.lines[0].original_path.as_deref(), Some("old.txt"));
+
+        let options = FileBlameOptions { ignore_whitespace: false, follow_renames: false };
+        let unfollowed = get_file_blame(&repo, "new.txt", Some("HEAD"), &options).unwrap();
+        assert_eq!(unfollowed.lines[0].sha, rename.to_string());
+        assert!(unfollowed.lines[0].boundary);
+    }
+}
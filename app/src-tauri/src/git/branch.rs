@@ -1,6 +1,53 @@
 use git2::{BranchType, Repository};
 
 use super::{BranchInfo, GitError, GitResult};
+use crate::error::StructuredError;
+
+/// Error returned when checking out `target` would overwrite local changes to
+/// `paths`, with kind `checkout_conflict`. Its `choices` are the ways to go
+/// ahead: `force` discards the local changes, `stash` auto-stashes them
+pub fn checkout_conflict_error(target: &str, paths: Vec<String>) -> StructuredError {
+    StructuredError::new(
+        "checkout_conflict",
+        format!(
+            "Checking out '{}' would overwrite local changes to: {}",
+            target,
+            paths.join(", ")
+        ),
+        serde_json::json!({
+            "target": target,
+            "paths": paths,
+            "choices": ["force", "stash"]
+        }),
+    )
+}
+
+/// Safely check out a tree, listing the paths with local changes in the way
+/// instead of failing with libgit2's generic conflict error
+pub(crate) fn checkout_tree_safely(repo: &Repository, tree: &git2::Object) -> GitResult<()> {
+    let mut conflicts = Vec::new();
+    let result = {
+        let mut checkout = git2::build::CheckoutBuilder::new();
+        checkout
+            .notify_on(git2::CheckoutNotificationType::CONFLICT)
+            .notify(|_, path, _, _, _| {
+                if let Some(path) = path {
+                    conflicts.push(path.to_string_lossy().to_string());
+                }
+                true
+            });
+        repo.checkout_tree(tree, Some(&mut checkout))
+    };
+
+    match result {
+        Err(e) if e.code() == git2::ErrorCode::Conflict && !conflicts.is_empty() => {
+            conflicts.sort();
+            conflicts.dedup();
+            Err(GitError::CheckoutConflict(conflicts))
+        }
+        result => Ok(result?),
+    }
+}
 
 /// Gets all branches (local and remote)
 pub fn get_branches(repo: &Repository) -> GitResult<Vec<BranchInfo>> {
@@ -86,6 +133,18 @@ fn get_tracking_info(branch: &git2::Branch) -> (Option<String>, u32, u32) {
     (upstream, 0, 0)
 }
 
+/// Name on its remote of the branch that local branch `name` tracks, such as
+/// `feature` for an upstream of `origin/feature`
+pub fn upstream_branch_name(repo: &Repository, name: &str) -> Option<String> {
+    let branch = repo.find_branch(name, BranchType::Local).ok()?;
+    let upstream = branch.upstream().ok()?;
+    let upstream_ref = upstream.get().name()?;
+    let remote = repo.branch_remote_name(upstream_ref).ok()?;
+    upstream_ref
+        .strip_prefix(&format!("refs/remotes/{}/", remote.as_str()?))
+        .map(str::to_string)
+}
+
 /// Creates a new branch from HEAD or a specific commit
 pub fn create_branch(repo: &Repository, name: &str, from_sha: Option<&str>) -> GitResult<BranchInfo> {
     let commit = match from_sha {
@@ -122,7 +181,22 @@ pub fn checkout_branch(repo: &Repository, name: &str) -> GitResult<()> {
     let reference = branch.into_reference();
     let tree = reference.peel_to_tree()?;
 
-    repo.checkout_tree(tree.as_object(), None)?;
+    checkout_tree_safely(repo, tree.as_object())?;
+    repo.set_head(reference.name().unwrap_or(""))?;
+
+    Ok(())
+}
+
+/// Checks out a branch, discarding local changes to the files it touches
+pub fn force_checkout_branch(repo: &Repository, name: &str) -> GitResult<()> {
+    let branch = repo
+        .find_branch(name, BranchType::Local)
+        .map_err(|_| GitError::BranchNotFound(name.to_string()))?;
+
+    let reference = branch.into_reference();
+    let tree = reference.peel_to_tree()?;
+
+    repo.checkout_tree(tree.as_object(), Some(git2::build::CheckoutBuilder::new().force()))?;
     repo.set_head(reference.name().unwrap_or(""))?;
 
     Ok(())
@@ -228,6 +302,7 @@ pub fn merge_branch(repo: &Repository, name: &str) -> GitResult<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::git::test_support::commit_file;
     use std::fs;
     use tempfile::tempdir;
 
@@ -252,4 +327,41 @@ mod tests {
         assert_eq!(branch.name, "test-branch");
         assert!(!branch.is_remote);
     }
+
+    #[test]
+    fn test_checkout_conflict_lists_paths() {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        commit_file(&repo, "test.txt", "hello", "commit");
+        create_branch(&repo, "other", None).unwrap();
+        commit_file(&repo, "test.txt", "hello again", "commit");
+        fs::write(dir.path().join("test.txt"), "local change").unwrap();
+
+        match checkout_branch(&repo, "other") {
+            Err(GitError::CheckoutConflict(paths)) => assert_eq!(paths, vec!["test.txt"]),
+            other => panic!("expected a checkout conflict, got {:?}", other),
+        }
+        assert!(repo.head().unwrap().shorthand() != Some("other"));
+
+        force_checkout_branch(&repo, "other").unwrap();
+        assert_eq!(repo.head().unwrap().shorthand(), Some("other"));
+        assert_eq!(fs::read_to_string(dir.path().join("test.txt")).unwrap(), "hello");
+    }
+
+    #[test]
+    fn test_upstream_branch_name() {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let oid = commit_file(&repo, "test.txt", "hello", "commit");
+        repo.remote("my/fork", "https://github.com/octocat/fork.git").unwrap();
+        repo.reference("refs/remotes/my/fork/feature-on-remote", oid, false, "test").unwrap();
+
+        create_branch(&repo, "feature", None).unwrap();
+        let mut branch = repo.find_branch("feature", BranchType::Local).unwrap();
+        branch.set_upstream(Some("my/fork/feature-on-remote")).unwrap();
+
+        assert_eq!(upstream_branch_name(&repo, "feature").as_deref(), Some("feature-on-remote"));
+        create_branch(&repo, "untracked", None).unwrap();
+        assert_eq!(upstream_branch_name(&repo, "untracked"), None);
+    }
 }
@@ -0,0 +1,161 @@
+//! Release notes from commit messages
+//!
+//! Groups commits by their Conventional Commits type and renders the groups
+//! as markdown release notes. Messages that do not follow the convention are
+//! listed under "Other Changes".
+
+use serde::{Deserialize, Serialize};
+
+use super::CommitInfo;
+
+/// A commit message parsed as a Conventional Commit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConventionalCommit {
+    pub commit_type: Option<String>,
+    pub scope: Option<String>,
+    pub breaking: bool,
+    pub description: String,
+}
+
+/// Parse the subject (and `BREAKING CHANGE` footer) of a commit message
+pub fn parse_conventional_commit(message: &str) -> ConventionalCommit {
+    let subject = message.lines().next().unwrap_or("").trim();
+    let breaking_footer = message
+        .lines()
+        .any(|l| l.starts_with("BREAKING CHANGE:") || l.starts_with("BREAKING-CHANGE:"));
+
+    let parsed = subject.split_once(": ").and_then(|(prefix, description)| {
+        let (prefix, bang) = match prefix.strip_suffix('!') {
+            Some(p) => (p, true),
+            None => (prefix, false),
+        };
+        let (commit_type, scope) = match prefix.split_once('(') {
+            Some((t, rest)) => (t, Some(rest.strip_suffix(')')?.to_string())),
+            None => (prefix, None),
+        };
+        if commit_type.is_empty() || !commit_type.chars().all(|c| c.is_ascii_alphabetic()) {
+            return None;
+        }
+        Some(ConventionalCommit {
+            commit_type: Some(commit_type.to_ascii_lowercase()),
+            scope,
+            breaking: bang || breaking_footer,
+            description: description.trim().to_string(),
+        })
+    });
+
+    parsed.unwrap_or_else(|| ConventionalCommit {
+        commit_type: None,
+        scope: None,
+        breaking: breaking_footer,
+        description: subject.to_string(),
+    })
+}
+
+/// Section heading of a commit type, in the order sections are rendered
+const SECTIONS: &[(&str, &[&str])] = &[
+    ("Features", &["feat"]),
+    ("Bug Fixes", &["fix"]),
+    ("Performance", &["perf"]),
+    ("Documentation", &["docs"]),
+];
+
+/// Draft markdown release notes for a list of commits
+///
+/// Merge commits are skipped, and breaking changes are repeated in their own section.
+pub fn draft_release_notes(commits: &[CommitInfo]) -> String {
+    let mut breaking = Vec::new();
+    let mut sections: Vec<Vec<String>> = vec![Vec::new(); SECTIONS.len() + 1];
+
+    for commit in commits.iter().filter(|c| c.parents.len() <= 1) {
+        let parsed = parse_conventional_commit(&commit.message);
+        let entry = match &parsed.scope {
+            Some(scope) => format!("- **{}:** {} ({})", scope, parsed.description, commit.short_sha),
+            None => format!("- {} ({})", parsed.description, commit.short_sha),
+        };
+
+        if parsed.breaking {
+            breaking.push(entry.clone());
+        }
+
+        let index = parsed
+            .commit_type
+            .as_deref()
+            .and_then(|t| SECTIONS.iter().position(|(_, types)| types.contains(&t)))
+            .unwrap_or(SECTIONS.len());
+        sections[index].push(entry);
+    }
+
+    let mut notes = String::new();
+    let mut push_section = |title: &str, entries: &[String]| {
+        if entries.is_empty() {
+            return;
+        }
+        if !notes.is_empty() {
+            notes.push('\n');
+        }
+        notes.push_str(&format!("## {}\n\n", title));
+        for entry in entries {
+            notes.push_str(entry);
+            notes.push('\n');
+        }
+    };
+
+    push_section("Breaking Changes", &breaking);
+    for (i, (title, _)) in SECTIONS.iter().enumerate() {
+        push_section(title, &sections[i]);
+    }
+    push_section("Other Changes", &sections[SECTIONS.len()]);
+
+    notes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(sha: &str, message: &str) -> CommitInfo {
+        CommitInfo {
+            sha: sha.to_string(),
+            short_sha: sha.to_string(),
+            message: message.to_string(),
+            author: "Dev".to_string(),
+            email: "dev@example.com".to_string(),
+            date: String::new(),
+            absolute_date: String::new(),
+            timestamp: 0,
+            parents: vec!["p".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_parse_conventional_commit() {
+        let parsed = parse_conventional_commit("feat(ui)!: add dark mode\n\nBody");
+        assert_eq!(parsed.commit_type.as_deref(), Some("feat"));
+        assert_eq!(parsed.scope.as_deref(), Some("ui"));
+        assert!(parsed.breaking);
+        assert_eq!(parsed.description, "add dark mode");
+
+        let plain = parse_conventional_commit("Update README: typo");
+        assert_eq!(plain.commit_type, None);
+        assert_eq!(plain.description, "Update README: typo");
+    }
+
+    #[test]
+    fn test_draft_release_notes() {
+        let commits = vec![
+            commit("a1", "fix: handle empty repos"),
+            commit("b2", "feat(sync): pull on open\n\nBREAKING CHANGE: new default"),
+            commit("c3", "Tidy up"),
+        ];
+
+        let notes = draft_release_notes(&commits);
+        assert_eq!(
+            notes,
+            "## Breaking Changes\n\n- **sync:** pull on open (b2)\n\n\
+             ## Features\n\n- **sync:** pull on open (b2)\n\n\
+             ## Bug Fixes\n\n- handle empty repos (a1)\n\n\
+             ## Other Changes\n\n- Tidy up (c3)\n"
+        );
+    }
+}
@@ -2,6 +2,7 @@ use git2::{build::RepoBuilder, FetchOptions, RemoteCallbacks, Progress};
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 
+use super::credentials::HelperCredentials;
 use super::{GitError, GitResult, RepoInfo};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -95,16 +96,16 @@ pub fn clone_repository(
         });
     }
 
-    // Set up SSH authentication
-    callbacks.credentials(|_url, username_from_url, allowed_types| {
+    // Set up SSH authentication, and HTTPS through the git credential helper
+    let helper = HelperCredentials::global();
+    let callback_helper = helper.clone();
+    callbacks.credentials(move |url, username_from_url, allowed_types| {
         if allowed_types.is_ssh_key() {
             // Try to use SSH key from the default location
             let username = username_from_url.unwrap_or("git");
             git2::Cred::ssh_key_from_agent(username)
         } else if allowed_types.is_user_pass_plaintext() {
-            // For HTTPS, we might need to prompt for credentials
-            // For now, just fail - in the future, we can integrate with credential storage
-            Err(git2::Error::from_str("HTTPS authentication not supported yet"))
+            callback_helper.get(url, username_from_url)
         } else {
             Err(git2::Error::from_str("Unsupported credential type"))
         }
@@ -118,6 +119,7 @@ pub fn clone_repository(
     let repo = RepoBuilder::new()
         .fetch_options(fetch_options)
         .clone(url, target_path)?;
+    helper.approve();
 
     // Get repo info
     let name = target_path
@@ -141,79 +143,6 @@ pub fn clone_repository(
     })
 }
 
-/// Scan a directory for Git repositories
-pub fn scan_for_repositories(path: &str, max_depth: usize) -> GitResult<Vec<RepoInfo>> {
-    let root_path = Path::new(path);
-    let mut repos = Vec::new();
-
-    if !root_path.exists() || !root_path.is_dir() {
-        return Err(GitError::OperationFailed(format!(
-            "Path '{}' is not a valid directory",
-            path
-        )));
-    }
-
-    scan_directory(root_path, &mut repos, 0, max_depth)?;
-
-    Ok(repos)
-}
-
-fn scan_directory(
-    path: &Path,
-    repos: &mut Vec<RepoInfo>,
-    current_depth: usize,
-    max_depth: usize,
-) -> GitResult<()> {
-    if current_depth > max_depth {
-        return Ok(());
-    }
-
-    // Check if this directory is a Git repository
-    let git_dir = path.join(".git");
-    if git_dir.exists() {
-        if let Ok(repo) = git2::Repository::open(path) {
-            let name = path
-                .file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("unknown")
-                .to_string();
-
-            let head = repo.head().ok();
-            let head_branch = head.as_ref().and_then(|h| h.shorthand()).map(String::from);
-            let head_sha = head.as_ref().and_then(|h| h.target()).map(|oid| oid.to_string());
-            let is_detached = repo.head_detached().unwrap_or(false);
-
-            repos.push(RepoInfo {
-                path: path.to_string_lossy().to_string(),
-                name,
-                is_bare: repo.is_bare(),
-                head_branch,
-                head_sha,
-                is_detached,
-            });
-        }
-        // Don't recurse into git repositories
-        return Ok(());
-    }
-
-    // Scan subdirectories
-    if let Ok(entries) = std::fs::read_dir(path) {
-        for entry in entries.flatten() {
-            let entry_path = entry.path();
-            if entry_path.is_dir() {
-                // Skip hidden directories (except .git which we already checked)
-                if let Some(name) = entry_path.file_name().and_then(|n| n.to_str()) {
-                    if !name.starts_with('.') {
-                        scan_directory(&entry_path, repos, current_depth + 1, max_depth)?;
-                    }
-                }
-            }
-        }
-    }
-
-    Ok(())
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SyncStatus {
     pub ahead: u32,
@@ -1,7 +1,13 @@
+use std::collections::HashMap;
+
 use chrono::Utc;
 use git2::{Oid, Repository};
 
-use super::{CommitInfo, GitError, GitResult};
+use super::rebase::rebase_single_commit;
+use super::{
+    format_absolute_time, format_relative_time_at, time_format_settings, CommitInfo, GitError, GitResult,
+    RebaseAction, RebaseProgress, TimeFormatSettings,
+};
 
 /// Creates a new commit with the staged changes
 pub fn create_commit(repo: &Repository, message: &str) -> GitResult<CommitInfo> {
@@ -28,7 +34,7 @@ pub fn create_commit(repo: &Repository, message: &str) -> GitResult<CommitInfo>
     )?;
 
     let commit = repo.find_commit(oid)?;
-    Ok(commit_to_info(&commit))
+    Ok(commit_to_info(&commit, &time_format_settings()))
 }
 
 /// Gets the commit history
@@ -37,26 +43,105 @@ pub fn get_commit_history(repo: &Repository, limit: usize, skip: usize) -> GitRe
     revwalk.push_head()?;
     revwalk.set_sorting(git2::Sort::TIME)?;
 
+    let settings = time_format_settings();
     let commits: Vec<CommitInfo> = revwalk
         .skip(skip)
         .take(limit)
         .filter_map(|oid| oid.ok())
         .filter_map(|oid| repo.find_commit(oid).ok())
-        .map(|commit| commit_to_info(&commit))
+        .map(|commit| commit_to_info(&commit, &settings))
+        .collect();
+
+    Ok(commits)
+}
+
+/// Gets the commits reachable from HEAD but not from `since` (a tag, branch, or sha)
+///
+/// With no `since`, the whole history of HEAD is returned.
+pub fn get_commits_since(repo: &Repository, since: Option<&str>) -> GitResult<Vec<CommitInfo>> {
+    get_commits_in_range(repo, since, "HEAD")
+}
+
+/// Gets the commits reachable from `to` but not from `from` (tags, branches, or shas)
+///
+/// With no `from`, the whole history of `to` is returned.
+pub fn get_commits_in_range(repo: &Repository, from: Option<&str>, to: &str) -> GitResult<Vec<CommitInfo>> {
+    let head = repo
+        .revparse_single(to)
+        .and_then(|obj| obj.peel_to_commit())
+        .map_err(|_| GitError::CommitNotFound(to.to_string()))?;
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(head.id())?;
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+
+    if let Some(since) = from {
+        let base = repo
+            .revparse_single(since)
+            .and_then(|obj| obj.peel_to_commit())
+            .map_err(|_| GitError::CommitNotFound(since.to_string()))?;
+        revwalk.hide(base.id())?;
+    }
+
+    let settings = time_format_settings();
+    let commits = revwalk
+        .filter_map(|oid| oid.ok())
+        .filter_map(|oid| repo.find_commit(oid).ok())
+        .map(|commit| commit_to_info(&commit, &settings))
         .collect();
 
     Ok(commits)
 }
 
+/// Finds the most recent tag reachable from `from`, ignoring the tag named `exclude`
+///
+/// Of several tags on the same commit, the greatest name is used.
+pub fn find_latest_tag(repo: &Repository, from: &str, exclude: Option<&str>) -> GitResult<Option<String>> {
+    let start = repo
+        .revparse_single(from)
+        .and_then(|obj| obj.peel_to_commit())
+        .map_err(|_| GitError::CommitNotFound(from.to_string()))?;
+
+    let mut tags: HashMap<Oid, String> = HashMap::new();
+    for name in repo.tag_names(None)?.iter().flatten() {
+        if Some(name) == exclude {
+            continue;
+        }
+        let Ok(commit) = repo
+            .revparse_single(&format!("refs/tags/{}", name))
+            .and_then(|obj| obj.peel_to_commit())
+        else {
+            continue;
+        };
+        let tag = tags.entry(commit.id()).or_insert_with(|| name.to_string());
+        if name > tag.as_str() {
+            *tag = name.to_string();
+        }
+    }
+    if tags.is_empty() {
+        return Ok(None);
+    }
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(start.id())?;
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+    for oid in revwalk {
+        if let Some(name) = tags.remove(&oid?) {
+            return Ok(Some(name));
+        }
+    }
+    Ok(None)
+}
+
 /// Gets details for a specific commit
 pub fn get_commit_detail(repo: &Repository, sha: &str) -> GitResult<CommitInfo> {
     let oid = Oid::from_str(sha).map_err(|_| GitError::CommitNotFound(sha.to_string()))?;
     let commit = repo.find_commit(oid).map_err(|_| GitError::CommitNotFound(sha.to_string()))?;
-    Ok(commit_to_info(&commit))
+    Ok(commit_to_info(&commit, &time_format_settings()))
 }
 
-/// Converts a git2::Commit to our CommitInfo struct
-fn commit_to_info(commit: &git2::Commit) -> CommitInfo {
+/// Converts a git2::Commit to our CommitInfo struct, formatting its date with `settings`
+fn commit_to_info(commit: &git2::Commit, settings: &TimeFormatSettings) -> CommitInfo {
     let sha = commit.id().to_string();
     let short_sha = sha.chars().take(7).collect();
 
@@ -66,7 +151,8 @@ fn commit_to_info(commit: &git2::Commit) -> CommitInfo {
     let email = author.email().unwrap_or("").to_string();
 
     let timestamp = commit.time().seconds();
-    let date = format_relative_time(timestamp);
+    let date = format_relative_time(timestamp, &settings.locale);
+    let absolute_date = format_absolute_time(timestamp, &settings.absolute_format);
 
     let parents: Vec<String> = commit
         .parent_ids()
@@ -80,6 +166,7 @@ fn commit_to_info(commit: &git2::Commit) -> CommitInfo {
         author: author_name,
         email,
         date,
+        absolute_date,
         timestamp,
         parents,
     }
@@ -121,7 +208,7 @@ pub fn cherry_pick_commit(repo: &Repository, sha: &str) -> GitResult<CommitInfo>
     repo.cleanup_state()?;
 
     let new_commit = repo.find_commit(new_oid)?;
-    Ok(commit_to_info(&new_commit))
+    Ok(commit_to_info(&new_commit, &time_format_settings()))
 }
 
 /// Reverts a commit by creating a new commit that undoes its changes
@@ -165,7 +252,7 @@ pub fn revert_commit(repo: &Repository, sha: &str) -> GitResult<CommitInfo> {
     repo.cleanup_state()?;
 
     let new_commit = repo.find_commit(new_oid)?;
-    Ok(commit_to_info(&new_commit))
+    Ok(commit_to_info(&new_commit, &time_format_settings()))
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -197,8 +284,8 @@ pub fn checkout_commit(repo: &Repository, sha: &str) -> GitResult<()> {
     let commit = repo.find_commit(oid).map_err(|_| GitError::CommitNotFound(sha.to_string()))?;
     let obj = commit.as_object();
 
+    super::branch::checkout_tree_safely(repo, obj)?;
     repo.set_head_detached(oid)?;
-    repo.checkout_tree(obj, None)?;
 
     Ok(())
 }
@@ -231,7 +318,7 @@ pub fn merge_commit(repo: &Repository, sha: &str) -> GitResult<CommitInfo> {
     let (analysis, _preference) = repo.merge_analysis(&[&annotated_commit])?;
 
     if analysis.is_up_to_date() {
-        return Ok(commit_to_info(&commit));
+        return Ok(commit_to_info(&commit, &time_format_settings()));
     }
 
     if analysis.is_fast_forward() {
@@ -240,7 +327,7 @@ pub fn merge_commit(repo: &Repository, sha: &str) -> GitResult<CommitInfo> {
         let mut reference = repo.find_reference(refname)?;
         reference.set_target(oid, &format!("Fast-forward to {}", sha))?;
         repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))?;
-        return Ok(commit_to_info(&commit));
+        return Ok(commit_to_info(&commit, &time_format_settings()));
     }
 
     // Normal merge
@@ -278,7 +365,7 @@ pub fn merge_commit(repo: &Repository, sha: &str) -> GitResult<CommitInfo> {
     repo.cleanup_state()?;
 
     let new_commit = repo.find_commit(new_oid)?;
-    Ok(commit_to_info(&new_commit))
+    Ok(commit_to_info(&new_commit, &time_format_settings()))
 }
 
 /// Rebases the current branch onto a specific commit
@@ -299,30 +386,6 @@ pub fn rebase_onto(repo_path: &str, sha: &str) -> GitResult<()> {
     Ok(())
 }
 
-/// Starts an interactive rebase from a specific commit
-pub fn interactive_rebase(repo_path: &str, sha: &str) -> GitResult<()> {
-    use std::process::Command;
-
-    // For interactive rebase, we need to use git command with editor
-    // This will open the default editor for the user
-    let output = Command::new("git")
-        .args(["rebase", "-i", &format!("{}^", sha)])
-        .current_dir(repo_path)
-        .env("GIT_SEQUENCE_EDITOR", "true") // Non-interactive for now, just mark as started
-        .output()
-        .map_err(|e| GitError::Generic(format!("Failed to execute git rebase -i: {}", e)))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        // If it's just that we can't do interactive in non-tty, that's expected
-        if !stderr.contains("terminal") && !stderr.contains("tty") {
-            return Err(GitError::Generic(format!("Interactive rebase failed: {}", stderr)));
-        }
-    }
-
-    Ok(())
-}
-
 /// Deletes a tag
 pub fn delete_tag(repo: &Repository, tag_name: &str) -> GitResult<()> {
     // First try to delete as lightweight tag
@@ -336,29 +399,8 @@ pub fn delete_tag(repo: &Repository, tag_name: &str) -> GitResult<()> {
     Err(GitError::Generic(format!("Tag '{}' not found", tag_name)))
 }
 
-/// Squashes a commit with its parent
-pub fn squash_commits(repo_path: &str, sha: &str) -> GitResult<()> {
-    use std::process::Command;
-
-    // Use git reset and commit to squash
-    // First, we need to do an interactive rebase with squash
-    let output = Command::new("git")
-        .args(["rebase", "-i", &format!("{}^^", sha)])
-        .current_dir(repo_path)
-        .env("GIT_SEQUENCE_EDITOR", &format!("sed -i '2s/pick/squash/'"))
-        .output()
-        .map_err(|e| GitError::Generic(format!("Failed to squash: {}", e)))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(GitError::Generic(format!("Squash failed: {}", stderr)));
-    }
-
-    Ok(())
-}
-
 /// Amends the message of the most recent commit (or specified commit via rebase)
-pub fn amend_commit_message(repo: &Repository, repo_path: &str, sha: &str, new_message: &str) -> GitResult<CommitInfo> {
+pub fn amend_commit_message(repo: &Repository, sha: &str, new_message: &str) -> GitResult<CommitInfo> {
     let head = repo.head()?.peel_to_commit()?;
     let head_sha = head.id().to_string();
 
@@ -380,46 +422,27 @@ pub fn amend_commit_message(repo: &Repository, repo_path: &str, sha: &str, new_m
         )?;
 
         let new_commit = repo.find_commit(new_oid)?;
-        return Ok(commit_to_info(&new_commit));
+        return Ok(commit_to_info(&new_commit, &time_format_settings()));
     }
 
-    // For non-HEAD commits, use git rebase with reword
-    use std::process::Command;
-
-    let output = Command::new("git")
-        .args(["rebase", "-i", &format!("{}^", sha)])
-        .current_dir(repo_path)
-        .env("GIT_SEQUENCE_EDITOR", &format!("sed -i '1s/pick/reword/'"))
-        .env("GIT_EDITOR", &format!("echo '{}' >", new_message.replace("'", "'\\''")))
-        .output()
-        .map_err(|e| GitError::Generic(format!("Failed to amend message: {}", e)))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(GitError::Generic(format!("Amend failed: {}", stderr)));
+    // For non-HEAD commits, rebase the commits after it with a reword step
+    let progress = rebase_single_commit(repo, sha, RebaseAction::Reword, Some(new_message.to_string()))?;
+    if !progress.done {
+        return Err(GitError::Generic(
+            "Rewording stopped on conflicts; resolve them and continue the rebase".to_string(),
+        ));
     }
 
     // Return the current HEAD as the result
     let new_head = repo.head()?.peel_to_commit()?;
-    Ok(commit_to_info(&new_head))
+    Ok(commit_to_info(&new_head, &time_format_settings()))
 }
 
 /// Drops a commit from history using rebase
-pub fn drop_commit(repo_path: &str, sha: &str) -> GitResult<()> {
-    use std::process::Command;
-
-    let output = Command::new("git")
-        .args(["rebase", "--onto", &format!("{}^", sha), sha])
-        .current_dir(repo_path)
-        .output()
-        .map_err(|e| GitError::Generic(format!("Failed to drop commit: {}", e)))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(GitError::Generic(format!("Drop commit failed: {}", stderr)));
-    }
-
-    Ok(())
+///
+/// The rebase stops if later commits conflict without the dropped one.
+pub fn drop_commit(repo: &Repository, sha: &str) -> GitResult<RebaseProgress> {
+    rebase_single_commit(repo, sha, RebaseAction::Drop, None)
 }
 
 /// Gets the diff for a specific commit
@@ -462,6 +485,8 @@ pub fn get_commit_diff(repo: &Repository, sha: &str) -> GitResult<Vec<super::Fil
         };
 
         let is_binary = delta.new_file().is_binary() || delta.old_file().is_binary();
+        let submodule = super::is_submodule_delta(&delta)
+            .then(|| super::submodule_change(repo, &delta, false));
 
         file_diffs.push(super::FileDiff {
             path,
@@ -471,6 +496,7 @@ pub fn get_commit_diff(repo: &Repository, sha: &str) -> GitResult<Vec<super::Fil
             is_binary,
             additions: 0,
             deletions: 0,
+            submodule,
         });
     }
 
@@ -491,69 +517,43 @@ pub fn get_commit_diff(repo: &Repository, sha: &str) -> GitResult<Vec<super::Fil
     Ok(file_diffs)
 }
 
-/// Formats a Unix timestamp as a relative time string
-fn format_relative_time(timestamp: i64) -> String {
-    let now = Utc::now().timestamp();
-    let diff = now - timestamp;
-
-    if diff < 60 {
-        "just now".to_string()
-    } else if diff < 3600 {
-        let minutes = diff / 60;
-        if minutes == 1 {
-            "1 minute ago".to_string()
-        } else {
-            format!("{} minutes ago", minutes)
-        }
-    } else if diff < 86400 {
-        let hours = diff / 3600;
-        if hours == 1 {
-            "1 hour ago".to_string()
-        } else {
-            format!("{} hours ago", hours)
-        }
-    } else if diff < 604800 {
-        let days = diff / 86400;
-        if days == 1 {
-            "1 day ago".to_string()
-        } else {
-            format!("{} days ago", days)
-        }
-    } else if diff < 2629746 {
-        let weeks = diff / 604800;
-        if weeks == 1 {
-            "1 week ago".to_string()
-        } else {
-            format!("{} weeks ago", weeks)
-        }
-    } else if diff < 31556952 {
-        let months = diff / 2629746;
-        if months == 1 {
-            "1 month ago".to_string()
-        } else {
-            format!("{} months ago", months)
-        }
-    } else {
-        let years = diff / 31556952;
-        if years == 1 {
-            "1 year ago".to_string()
-        } else {
-            format!("{} years ago", years)
-        }
-    }
+/// Formats a Unix timestamp as a relative time string in `locale`
+fn format_relative_time(timestamp: i64, locale: &str) -> String {
+    format_relative_time_at(timestamp, Utc::now().timestamp(), locale)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::git::test_support::commit_file;
 
     #[test]
     fn test_format_relative_time() {
         let now = Utc::now().timestamp();
 
-        assert_eq!(format_relative_time(now), "just now");
-        assert_eq!(format_relative_time(now - 120), "2 minutes ago");
-        assert_eq!(format_relative_time(now - 7200), "2 hours ago");
-        assert_eq!(format_relative_time(now - 172800), "2 days ago");
+        assert_eq!(format_relative_time(now, "en"), "just now");
+        assert_eq!(format_relative_time(now - 120, "en"), "2 minutes ago");
+        assert_eq!(format_relative_time(now - 7200, "en"), "2 hours ago");
+        assert_eq!(format_relative_time(now - 172800, "en"), "2 days ago");
+    }
+
+    #[test]
+    fn test_find_latest_tag() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let first = commit_file(&repo, "a.txt", "1", "first");
+        let second = commit_file(&repo, "a.txt", "2", "second");
+        commit_file(&repo, "a.txt", "3", "third");
+        assert_eq!(find_latest_tag(&repo, "HEAD", None).unwrap(), None);
+
+        let sig = git2::Signature::now("Test", "test@test.com").unwrap();
+        repo.tag_lightweight("v1.0", &repo.find_object(first, None).unwrap(), false).unwrap();
+        repo.tag("v2.0", &repo.find_object(second, None).unwrap(), &sig, "Release v2.0", false)
+            .unwrap();
+
+        assert_eq!(find_latest_tag(&repo, "HEAD", None).unwrap().as_deref(), Some("v2.0"));
+        // The tag being released is not its own previous tag
+        assert_eq!(find_latest_tag(&repo, "v2.0", Some("v2.0")).unwrap().as_deref(), Some("v1.0"));
+        assert_eq!(find_latest_tag(&repo, &first.to_string(), None).unwrap().as_deref(), Some("v1.0"));
     }
 }
@@ -0,0 +1,237 @@
+//! Linting commit messages before they are committed
+//!
+//! Checks the Conventional Commits format, the subject length, and that the
+//! subject is written in the imperative mood ("add", not "added" or "adds").
+//! Violations are structured so the UI can show them next to the message.
+
+use serde::{Deserialize, Serialize};
+
+use super::changelog::parse_conventional_commit;
+use super::GitResult;
+use crate::error::StructuredError;
+use crate::settings::{load_json_settings, save_json_settings};
+
+/// Verbs commonly starting a subject, used to recognize non-imperative forms
+const COMMON_VERBS: &[&str] = &[
+    "add", "adjust", "allow", "avoid", "bump", "change", "check", "clean", "convert", "correct",
+    "create", "delete", "disable", "document", "drop", "enable", "ensure", "expose", "extract",
+    "fix", "handle", "implement", "improve", "include", "increase", "introduce", "merge", "move",
+    "optimize", "prevent", "reduce", "refactor", "remove", "rename", "replace", "restore",
+    "return", "revert", "set", "show", "simplify", "support", "test", "update", "upgrade", "use",
+];
+
+/// Rules applied to commit messages
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitLintSettings {
+    /// Whether messages are checked when committing
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_true")]
+    pub conventional: bool,
+    #[serde(default = "default_max_subject_length")]
+    pub max_subject_length: usize,
+    #[serde(default = "default_true")]
+    pub imperative_mood: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_max_subject_length() -> usize {
+    72
+}
+
+impl Default for CommitLintSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            conventional: true,
+            max_subject_length: default_max_subject_length(),
+            imperative_mood: true,
+        }
+    }
+}
+
+/// A way in which a commit message breaks the lint rules
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintViolation {
+    /// `empty-subject`, `conventional-format`, `subject-length`,
+    /// `subject-period`, `blank-line`, or `imperative-mood`
+    pub rule: String,
+    pub message: String,
+    /// 1-based line of the message the violation is on
+    pub line: usize,
+}
+
+/// Error returned when a commit is refused because its message breaks the lint
+/// rules, with kind `commit_lint`
+pub fn commit_lint_error(violations: Vec<LintViolation>) -> StructuredError {
+    let messages: Vec<&str> = violations.iter().map(|v| v.message.as_str()).collect();
+    StructuredError::new(
+        "commit_lint",
+        format!("Commit message does not follow the rules: {}", messages.join("; ")),
+        serde_json::json!({ "violations": violations }),
+    )
+}
+
+fn violation(rule: &str, message: String, line: usize) -> LintViolation {
+    LintViolation {
+        rule: rule.to_string(),
+        message,
+        line,
+    }
+}
+
+/// The imperative form of a word if it looks like a past tense, third person, or
+/// gerund form of a common verb
+fn non_imperative_stem(word: &str) -> Option<&'static str> {
+    let word = word.to_lowercase();
+    let candidates = [
+        word.strip_suffix("ed").map(|s| s.to_string()),
+        word.strip_suffix('d').map(|s| s.to_string()),
+        word.strip_suffix("es").map(|s| s.to_string()),
+        word.strip_suffix('s').map(|s| s.to_string()),
+        word.strip_suffix("ing").map(|s| s.to_string()),
+        word.strip_suffix("ing").map(|s| format!("{}e", s)),
+        // Doubled final consonant: "dropped", "setting"
+        word.strip_suffix("ed").or_else(|| word.strip_suffix("ing")).and_then(|s| {
+            let mut chars = s.chars();
+            let last = chars.next_back()?;
+            (chars.next_back()? == last).then(|| s[..s.len() - last.len_utf8()].to_string())
+        }),
+    ];
+
+    candidates
+        .into_iter()
+        .flatten()
+        .find_map(|stem| COMMON_VERBS.iter().copied().find(|verb| *verb == stem))
+}
+
+/// Check a commit message against the rules
+pub fn lint_commit_message(message: &str, settings: &CommitLintSettings) -> Vec<LintViolation> {
+    let mut violations = Vec::new();
+    let lines: Vec<&str> = message.trim_end().lines().collect();
+    let subject = lines.first().map(|l| l.trim()).unwrap_or("");
+
+    if subject.is_empty() {
+        violations.push(violation("empty-subject", "The subject line is empty".to_string(), 1));
+        return violations;
+    }
+
+    let length = subject.chars().count();
+    if length > settings.max_subject_length {
+        violations.push(violation(
+            "subject-length",
+            format!(
+                "The subject is {} characters, more than {}",
+                length, settings.max_subject_length
+            ),
+            1,
+        ));
+    }
+
+    if subject.ends_with('.') {
+        violations.push(violation(
+            "subject-period",
+            "The subject should not end with a period".to_string(),
+            1,
+        ));
+    }
+
+    if lines.len() > 1 && !lines[1].trim().is_empty() {
+        violations.push(violation(
+            "blank-line",
+            "Separate the subject from the body with a blank line".to_string(),
+            2,
+        ));
+    }
+
+    let parsed = parse_conventional_commit(subject);
+    if settings.conventional && parsed.commit_type.is_none() {
+        violations.push(violation(
+            "conventional-format",
+            "The subject is not in the form `type(scope): description`".to_string(),
+            1,
+        ));
+    }
+
+    if settings.imperative_mood {
+        let first_word = parsed.description.split_whitespace().next().unwrap_or("");
+        if let Some(verb) = non_imperative_stem(first_word) {
+            violations.push(violation(
+                "imperative-mood",
+                format!("Use the imperative mood: \"{}\" instead of \"{}\"", verb, first_word),
+                1,
+            ));
+        }
+    }
+
+    violations
+}
+
+/// Name of the settings file in the app config directory
+const SETTINGS_FILE: &str = "commit_lint.json";
+
+/// Load the commit lint settings, or the defaults if none were saved
+pub fn load_commit_lint_settings() -> GitResult<CommitLintSettings> {
+    Ok(load_json_settings(SETTINGS_FILE)?)
+}
+
+/// Save the commit lint settings
+pub fn save_commit_lint_settings(settings: &CommitLintSettings) -> GitResult<()> {
+    Ok(save_json_settings(SETTINGS_FILE, settings)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(message: &str) -> Vec<String> {
+        lint_commit_message(message, &CommitLintSettings::default())
+            .into_iter()
+            .map(|v| v.rule)
+            .collect()
+    }
+
+    #[test]
+    fn test_lint_valid_messages() {
+        assert!(rules("feat(ui): add dark mode").is_empty());
+        assert!(rules("fix: speed up status refresh\n\nCache the index.").is_empty());
+    }
+
+    #[test]
+    fn test_lint_violations() {
+        assert_eq!(rules(""), vec!["empty-subject"]);
+        assert_eq!(rules("Add dark mode"), vec!["conventional-format"]);
+        assert_eq!(rules("feat: add dark mode."), vec!["subject-period"]);
+        assert_eq!(rules("feat: add dark mode\nMore details"), vec!["blank-line"]);
+        assert_eq!(rules(&format!("feat: {}", "a".repeat(80))), vec!["subject-length"]);
+    }
+
+    #[test]
+    fn test_imperative_mood() {
+        for word in ["Added", "adds", "Fixed", "fixes", "updating", "dropped", "setting", "Removed"] {
+            assert_eq!(rules(&format!("fix: {} things", word)), vec!["imperative-mood"], "{}", word);
+        }
+        for word in ["add", "fix", "speed", "need", "process", "address"] {
+            assert!(rules(&format!("fix: {} things", word)).is_empty(), "{}", word);
+        }
+        assert_eq!(
+            lint_commit_message("fix: Updated docs", &CommitLintSettings::default())[0].message,
+            "Use the imperative mood: \"update\" instead of \"Updated\""
+        );
+    }
+
+    #[test]
+    fn test_commit_lint_error_json() {
+        let violations = lint_commit_message("Added docs.", &CommitLintSettings::default());
+        let json = commit_lint_error(violations.clone()).to_json();
+        let error: StructuredError = serde_json::from_str(&json).unwrap();
+        assert_eq!(error.kind, "commit_lint");
+        let parsed: Vec<LintViolation> =
+            serde_json::from_value(error.details["violations"].clone()).unwrap();
+        assert_eq!(parsed, violations);
+        assert!(error.message.starts_with("Commit message does not follow the rules: "));
+    }
+}
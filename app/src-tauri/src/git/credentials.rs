@@ -0,0 +1,198 @@
+//! HTTPS credentials from git credential helpers
+//!
+//! Remotes other than GitHub authenticate with the `credential.helper` the
+//! user configured for git (such as libsecret or Git Credential Manager), using
+//! the git credential protocol, so GitLab, Bitbucket, and self-hosted HTTPS
+//! remotes work without any setup in the app.
+
+use std::cell::RefCell;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::rc::Rc;
+
+use git2::{Config, Cred, CredentialHelper, Repository};
+
+/// Whether a remote URL points at github.com, which uses the stored GitHub token
+pub fn is_github_url(url: &str) -> bool {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', ':']).next().unwrap_or("");
+    let host = authority.rsplit('@').next().unwrap_or("");
+    host.eq_ignore_ascii_case("github.com") || host.to_ascii_lowercase().ends_with(".github.com")
+}
+
+/// Username and password for `url` from the configured credential helpers
+pub fn helper_credentials(config: &Config, url: &str, username: Option<&str>) -> Option<(String, String)> {
+    CredentialHelper::new(url)
+        .config(config)
+        .username(username)
+        .execute()
+}
+
+/// Credential helper lookups for one remote operation
+///
+/// libgit2 calls the credentials callback again when the server rejects what
+/// it was given, so the helper is only asked once; asking again would return
+/// the same rejected credentials forever. Instead the helper is told to erase
+/// them, and credentials that worked are passed back to it to store once the
+/// operation has succeeded, like git does. Clones share the same state, so one
+/// can be moved into the callbacks while the other approves the credentials.
+#[derive(Clone)]
+pub(crate) struct HelperCredentials {
+    state: Rc<RefCell<HelperState>>,
+}
+
+struct HelperState {
+    config: Option<Config>,
+    /// Directory `git credential` runs in, so the repository configuration applies
+    git_dir: Option<PathBuf>,
+    /// Credentials returned by the helper: URL, username, and password
+    given: Option<(String, String, String)>,
+    asked: bool,
+}
+
+impl HelperCredentials {
+    fn new(config: Option<Config>, git_dir: Option<PathBuf>) -> Self {
+        Self {
+            state: Rc::new(RefCell::new(HelperState {
+                config,
+                git_dir,
+                given: None,
+                asked: false,
+            })),
+        }
+    }
+
+    /// Use the configuration of a repository, including the global and system files
+    pub(crate) fn for_repo(repo: &Repository) -> Self {
+        let config = repo.config().or_else(|_| Config::open_default()).ok();
+        Self::new(config, Some(repo.path().to_path_buf()))
+    }
+
+    /// Use the global and system configuration, for remotes without a repository yet
+    pub(crate) fn global() -> Self {
+        Self::new(Config::open_default().ok(), None)
+    }
+
+    pub(crate) fn get(&self, url: &str, username_from_url: Option<&str>) -> Result<Cred, git2::Error> {
+        let mut state = self.state.borrow_mut();
+        if state.asked {
+            if let Some((url, username, password)) = state.given.take() {
+                run_credential_helpers(state.git_dir.as_deref(), "reject", &url, &username, &password);
+            }
+            return Err(git2::Error::from_str(&format!(
+                "Authentication failed for {}: the saved credentials were rejected",
+                url
+            )));
+        }
+        state.asked = true;
+
+        let config = state
+            .config
+            .as_ref()
+            .ok_or_else(|| git2::Error::from_str("Could not read the git configuration"))?;
+        match helper_credentials(config, url, username_from_url) {
+            Some((username, password)) => {
+                let cred = Cred::userpass_plaintext(&username, &password);
+                state.given = Some((url.to_string(), username, password));
+                cred
+            }
+            None => Err(git2::Error::from_str(&format!(
+                "No credentials for {}; configure a git credential.helper to authenticate",
+                url
+            ))),
+        }
+    }
+
+    /// Let the helpers store the credentials they gave, once the operation succeeded
+    pub(crate) fn approve(&self) {
+        let mut state = self.state.borrow_mut();
+        if let Some((url, username, password)) = state.given.take() {
+            run_credential_helpers(state.git_dir.as_deref(), "approve", &url, &username, &password);
+        }
+    }
+}
+
+/// Run `git credential approve` or `git credential reject`, which pass the
+/// credentials to the `store` or `erase` action of every configured helper
+fn run_credential_helpers(git_dir: Option<&Path>, action: &str, url: &str, username: &str, password: &str) {
+    let mut command = Command::new("git");
+    command
+        .args(["credential", action])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    if let Some(dir) = git_dir {
+        command.current_dir(dir);
+    }
+
+    let Ok(mut child) = command.spawn() else {
+        return;
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = write!(stdin, "url={}\nusername={}\npassword={}\n\n", url, username, password);
+    }
+    let _ = child.wait();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_is_github_url() {
+        assert!(is_github_url("https://github.com/octocat/hello-world.git"));
+        assert!(is_github_url("https://user@GitHub.com/octocat/hello-world"));
+        assert!(is_github_url("git@github.com:octocat/hello-world.git"));
+        assert!(!is_github_url("https://gitlab.com/group/project.git"));
+        assert!(!is_github_url("https://git.example.com/github.com/project.git"));
+        assert!(!is_github_url("https://notgithub.com/octocat/hello-world"));
+    }
+
+    #[test]
+    fn test_helper_credentials() {
+        let dir = tempdir().unwrap();
+        let mut config = Config::open(&dir.path().join("config")).unwrap();
+        config
+            .set_str("credential.helper", "!f() { echo username=alice; echo password=s3cret; }; f")
+            .unwrap();
+
+        let credentials = helper_credentials(&config, "https://gitlab.example.com/team/app.git", None);
+        assert_eq!(credentials, Some(("alice".to_string(), "s3cret".to_string())));
+
+        let helper = HelperCredentials::new(Some(config), None);
+        assert!(helper.get("https://gitlab.example.com/team/app.git", None).is_ok());
+        // A second request means the credentials were rejected
+        assert!(helper.get("https://gitlab.example.com/team/app.git", None).is_err());
+    }
+
+    #[test]
+    fn test_helper_credentials_store_and_erase() {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let log = dir.path().join("helper.log");
+        let helper_command = format!(
+            "!f() {{ if [ \"$1\" = get ]; then echo username=alice; echo password=s3cret; \
+             else echo \"$1 $(grep password)\" >> '{}'; fi; }}; f",
+            log.display()
+        );
+        repo.config().unwrap().set_str("credential.helper", &helper_command).unwrap();
+        let url = "https://gitlab.example.com/team/app.git";
+
+        let helper = HelperCredentials::for_repo(&repo);
+        assert!(helper.get(url, None).is_ok());
+        helper.approve();
+        assert_eq!(std::fs::read_to_string(&log).unwrap(), "store password=s3cret\n");
+
+        let helper = HelperCredentials::for_repo(&repo);
+        assert!(helper.get(url, None).is_ok());
+        assert!(helper.get(url, None).is_err());
+        // Rejected credentials are erased, and never stored afterwards
+        helper.approve();
+        assert_eq!(
+            std::fs::read_to_string(&log).unwrap(),
+            "store password=s3cret\nerase password=s3cret\n"
+        );
+    }
+}
@@ -1,7 +1,10 @@
 use std::cell::RefCell;
 use git2::{DiffOptions, Repository};
 
-use super::{DiffHunk, DiffLine, DiffLineType, FileDiff, FileStatusType, GitResult};
+use super::{
+    DiffHunk, DiffLine, DiffLineType, DiffSummary, FileChangeSummary, FileDiff, FileStatusType, GitError,
+    GitResult,
+};
 
 /// Gets the diff for a specific file
 pub fn get_file_diff(repo: &Repository, path: &str, staged: bool) -> GitResult<FileDiff> {
@@ -18,6 +21,25 @@ pub fn get_file_diff(repo: &Repository, path: &str, staged: bool) -> GitResult<F
         repo.diff_index_to_workdir(None, Some(&mut diff_opts))?
     };
 
+    // A submodule's change is its commit pointer, not file contents
+    if let Some(delta) = diff.deltas().find(super::is_submodule_delta) {
+        let change = super::submodule_change(repo, &delta, !staged);
+        return Ok(FileDiff {
+            path: path.to_string(),
+            old_path: None,
+            status: match delta.status() {
+                git2::Delta::Added => FileStatusType::Added,
+                git2::Delta::Deleted => FileStatusType::Deleted,
+                _ => FileStatusType::Modified,
+            },
+            hunks: vec![change.to_hunk()],
+            is_binary: false,
+            additions: 0,
+            deletions: 0,
+            submodule: Some(change),
+        });
+    }
+
     let file_diff = RefCell::new(FileDiff {
         path: path.to_string(),
         old_path: None,
@@ -26,6 +48,7 @@ pub fn get_file_diff(repo: &Repository, path: &str, staged: bool) -> GitResult<F
         is_binary: false,
         additions: 0,
         deletions: 0,
+        submodule: None,
     });
 
     // Process the diff using print which uses a single callback
@@ -98,11 +121,100 @@ pub fn get_file_diff(repo: &Repository, path: &str, staged: bool) -> GitResult<F
     Ok(file_diff.into_inner())
 }
 
+/// Gets the changed files with their line counts, without building hunks
+///
+/// Staged changes compare HEAD to the index; unstaged changes compare the index
+/// to the working directory, including untracked files.
+pub fn get_diff_summary(repo: &Repository, staged: bool) -> GitResult<DiffSummary> {
+    let mut diff_opts = DiffOptions::new();
+    let diff = if staged {
+        let head_tree = repo.head().ok().and_then(|h| h.peel_to_tree().ok());
+        let mut diff = repo.diff_tree_to_index(head_tree.as_ref(), None, Some(&mut diff_opts))?;
+        diff.find_similar(None)?;
+        diff
+    } else {
+        diff_opts
+            .include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .show_untracked_content(true);
+        repo.diff_index_to_workdir(None, Some(&mut diff_opts))?
+    };
+
+    let files = RefCell::new(Vec::new());
+    diff.foreach(
+        &mut |delta, _| {
+            let path = delta.new_file().path().or_else(|| delta.old_file().path());
+            let old_path = delta.old_file().path().filter(|old| Some(*old) != path);
+            files.borrow_mut().push(FileChangeSummary {
+                path: path.map(|p| p.to_string_lossy().to_string()).unwrap_or_default(),
+                old_path: old_path.map(|p| p.to_string_lossy().to_string()),
+                status: match delta.status() {
+                    git2::Delta::Added | git2::Delta::Untracked => FileStatusType::Added,
+                    git2::Delta::Deleted => FileStatusType::Deleted,
+                    git2::Delta::Renamed | git2::Delta::Copied => FileStatusType::Renamed,
+                    git2::Delta::Conflicted => FileStatusType::Conflict,
+                    _ => FileStatusType::Modified,
+                },
+                is_binary: delta.flags().is_binary(),
+                additions: 0,
+                deletions: 0,
+            });
+            true
+        },
+        Some(&mut |delta, _| {
+            // Binary status is only known once the content has been loaded
+            if let Some(file) = files.borrow_mut().last_mut() {
+                file.is_binary = delta.flags().is_binary();
+            }
+            true
+        }),
+        None,
+        Some(&mut |_, _, line| {
+            if let Some(file) = files.borrow_mut().last_mut() {
+                match line.origin() {
+                    '+' => file.additions += 1,
+                    '-' => file.deletions += 1,
+                    _ => {}
+                }
+            }
+            true
+        }),
+    )?;
+
+    let files = files.into_inner();
+    Ok(DiffSummary {
+        additions: files.iter().map(|f| f.additions).sum(),
+        deletions: files.iter().map(|f| f.deletions).sum(),
+        files,
+    })
+}
+
 /// Gets the full diff text for staged changes (for AI commit message generation)
 pub fn get_staged_diff_text(repo: &Repository) -> GitResult<String> {
     let head_tree = repo.head().ok().and_then(|h| h.peel_to_tree().ok());
     let diff = repo.diff_tree_to_index(head_tree.as_ref(), None, None)?;
 
+    diff_to_text(&diff)
+}
+
+/// Gets the full diff text of a commit against its first parent
+pub fn get_commit_diff_text(repo: &Repository, sha: &str) -> GitResult<String> {
+    let commit = repo
+        .revparse_single(sha)
+        .and_then(|obj| obj.peel_to_commit())
+        .map_err(|_| GitError::CommitNotFound(sha.to_string()))?;
+
+    let parent_tree = if commit.parent_count() > 0 {
+        Some(commit.parent(0)?.tree()?)
+    } else {
+        None
+    };
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+
+    diff_to_text(&diff)
+}
+
+fn diff_to_text(diff: &git2::Diff) -> GitResult<String> {
     let mut diff_text = String::new();
 
     diff.print(git2::DiffFormat::Patch, |_delta, _hunk, line| {
@@ -124,6 +236,98 @@ pub fn get_staged_diff_text(repo: &Repository) -> GitResult<String> {
     Ok(diff_text)
 }
 
+/// Rewrite the line counts of every hunk header to match its lines, since
+/// editing a hunk by hand usually leaves them stale
+pub fn recount_hunks(patch: &str) -> String {
+    let lines: Vec<&str> = patch.lines().collect();
+    let mut out = String::with_capacity(patch.len());
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        i += 1;
+        let Some((old_start, new_start, section)) = parse_hunk_header(line) else {
+            out.push_str(line);
+            out.push('\n');
+            continue;
+        };
+
+        let body_start = i;
+        let (mut old_lines, mut new_lines) = (0, 0);
+        while i < lines.len() && !lines[i].starts_with("@@") && !lines[i].starts_with("diff --git") {
+            match lines[i].chars().next() {
+                Some('+') => new_lines += 1,
+                Some('-') => old_lines += 1,
+                Some('\\') => {}
+                _ => {
+                    old_lines += 1;
+                    new_lines += 1;
+                }
+            }
+            i += 1;
+        }
+        out.push_str(&format!(
+            "@@ -{},{} +{},{} @@{}\n",
+            old_start, old_lines, new_start, new_lines, section
+        ));
+        for body_line in &lines[body_start..i] {
+            // Editors strip the space of empty context lines
+            out.push_str(if body_line.is_empty() { " " } else { body_line });
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Parse `@@ -a,b +c,d @@ section` into its start lines and section text
+fn parse_hunk_header(line: &str) -> Option<(u32, u32, &str)> {
+    let rest = line.strip_prefix("@@ -")?;
+    let (ranges, section) = rest.split_once(" @@")?;
+    let (old, new) = ranges.split_once(" +")?;
+    let start = |range: &str| range.split(',').next()?.parse::<u32>().ok();
+    Some((start(old)?, start(new)?, section))
+}
+
+/// Apply a unified diff of one file, such as a hunk edited in the UI, to the
+/// index or the working directory
+///
+/// Hunks without file headers are accepted, and hunk line counts are
+/// recomputed. The patch is checked before anything is changed, so a patch
+/// that no longer applies leaves the index and working directory untouched.
+pub fn apply_edited_patch(repo: &Repository, path: &str, patch_text: &str, to_index: bool) -> GitResult<()> {
+    if !patch_text.lines().any(|line| line.starts_with("@@")) {
+        return Err(GitError::Generic("The patch has no hunks".to_string()));
+    }
+
+    let mut patch = String::new();
+    if !patch_text.starts_with("diff --git") && !patch_text.starts_with("--- ") {
+        patch.push_str(&format!("diff --git a/{0} b/{0}\n--- a/{0}\n+++ b/{0}\n", path));
+    }
+    // Trailing blank lines would otherwise be counted as empty context lines
+    patch.push_str(&recount_hunks(patch_text.trim_end()));
+
+    let diff = git2::Diff::from_buffer(patch.as_bytes())
+        .map_err(|e| GitError::Generic(format!("Invalid patch: {}", e.message())))?;
+    for delta in diff.deltas() {
+        let touched = [delta.old_file().path(), delta.new_file().path()];
+        if touched.iter().flatten().any(|p| *p != std::path::Path::new(path)) {
+            return Err(GitError::Generic(format!("The patch changes files other than {}", path)));
+        }
+    }
+
+    let location = if to_index {
+        git2::ApplyLocation::Index
+    } else {
+        git2::ApplyLocation::WorkDir
+    };
+    let mut check = git2::ApplyOptions::new();
+    check.check(true);
+    repo.apply(&diff, location, Some(&mut check)).map_err(|e| {
+        GitError::Generic(format!("The patch does not apply to {}: {}", path, e.message()))
+    })?;
+    repo.apply(&diff, location, None)?;
+    Ok(())
+}
+
 /// Gets diff statistics for staged changes
 pub fn get_staged_diff_stats(repo: &Repository) -> GitResult<(u32, u32, u32)> {
     let head_tree = repo.head().ok().and_then(|h| h.peel_to_tree().ok());
@@ -141,6 +345,7 @@ pub fn get_staged_diff_stats(repo: &Repository) -> GitResult<(u32, u32, u32)> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::git::test_support::commit_file;
     use std::fs;
     use tempfile::tempdir;
 
@@ -170,4 +375,63 @@ mod tests {
         assert!(!diff.is_binary);
         assert!(diff.additions > 0 || diff.deletions > 0);
     }
+
+    #[test]
+    fn test_get_diff_summary() {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        commit_file(&repo, "a.txt", "one\ntwo\n", "Initial");
+
+        fs::write(dir.path().join("a.txt"), "one\n2\nthree\n").unwrap();
+        fs::write(dir.path().join("new.txt"), "hello\n").unwrap();
+
+        let summary = get_diff_summary(&repo, false).unwrap();
+        let files: Vec<(&str, u32, u32)> = summary
+            .files
+            .iter()
+            .map(|f| (f.path.as_str(), f.additions, f.deletions))
+            .collect();
+        assert_eq!(files, vec![("a.txt", 2, 1), ("new.txt", 1, 0)]);
+        assert_eq!((summary.additions, summary.deletions), (3, 1));
+
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("new.txt")).unwrap();
+        index.write().unwrap();
+        let staged = get_diff_summary(&repo, true).unwrap();
+        assert_eq!(staged.files.len(), 1);
+        assert!(matches!(staged.files[0].status, FileStatusType::Added));
+    }
+
+    #[test]
+    fn test_apply_edited_patch() {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        commit_file(&repo, "a.txt", "one\ntwo\nthree\n", "Initial");
+        fs::write(dir.path().join("a.txt"), "one\n2\nthree\nfour\n").unwrap();
+
+        // The user kept the rewrite of line two but dropped the new line four,
+        // leaving the header counts stale
+        let edited = "@@ -1,3 +1,4 @@\n one\n-two\n+2\n three\n";
+        apply_edited_patch(&repo, "a.txt", edited, true).unwrap();
+
+        let index = repo.index().unwrap();
+        let blob = repo.find_blob(index.get_path(std::path::Path::new("a.txt"), 0).unwrap().id).unwrap();
+        assert_eq!(blob.content(), b"one\n2\nthree\n");
+        assert_eq!(fs::read_to_string(dir.path().join("a.txt")).unwrap(), "one\n2\nthree\nfour\n");
+
+        // The same change no longer applies to the updated index
+        assert!(apply_edited_patch(&repo, "a.txt", edited, true).is_err());
+        assert!(apply_edited_patch(&repo, "a.txt", "not a patch", true).is_err());
+        let other = "diff --git a/b.txt b/b.txt\n--- a/b.txt\n+++ b/b.txt\n@@ -1 +1 @@\n-x\n+y\n";
+        assert!(apply_edited_patch(&repo, "a.txt", other, true).is_err());
+    }
+
+    #[test]
+    fn test_recount_hunks() {
+        let patch = "@@ -3,9 +3,9 @@ fn main\n a\n-b\n+c\n+d\n\n\\ No newline at end of file\n";
+        assert_eq!(
+            recount_hunks(patch),
+            "@@ -3,3 +3,4 @@ fn main\n a\n-b\n+c\n+d\n \n\\ No newline at end of file\n"
+        );
+    }
 }
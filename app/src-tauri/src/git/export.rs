@@ -0,0 +1,271 @@
+//! Exporting the commit history to JSON or CSV
+//!
+//! Commits are written one at a time while the history is walked, so large
+//! repositories are never held in memory.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use chrono::{TimeZone, Utc};
+use git2::{Commit, Repository};
+use serde::{Deserialize, Serialize};
+
+use super::{GitError, GitResult};
+
+const CSV_HEADER: &str = "sha,author,email,date,timestamp,parents,files_changed,insertions,deletions,subject";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryExportFormat {
+    Json,
+    Csv,
+}
+
+/// Which commits to export
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HistoryFilters {
+    /// Revision to start from, HEAD when missing
+    pub rev: Option<String>,
+    /// Text the author name or email must contain (case-insensitive)
+    pub author: Option<String>,
+    /// Text the message must contain (case-insensitive)
+    pub message: Option<String>,
+    /// Only commits changing this file or directory
+    pub path: Option<String>,
+    /// Only commits at or after this time (Unix seconds)
+    pub since: Option<i64>,
+    /// Only commits at or before this time (Unix seconds)
+    pub until: Option<i64>,
+    #[serde(default)]
+    pub no_merges: bool,
+    pub max_count: Option<usize>,
+}
+
+/// A commit with its change statistics, as written to an export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedCommit {
+    pub sha: String,
+    pub author: String,
+    pub email: String,
+    /// RFC 3339 date in UTC
+    pub date: String,
+    pub timestamp: i64,
+    pub parents: Vec<String>,
+    pub subject: String,
+    pub message: String,
+    pub files_changed: usize,
+    pub insertions: usize,
+    pub deletions: usize,
+}
+
+fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
+    haystack.to_lowercase().contains(&needle.to_lowercase())
+}
+
+fn matches_path(path: &std::path::Path, filter: &str) -> bool {
+    let filter = filter.trim_end_matches('/');
+    path.starts_with(filter)
+}
+
+/// Build the export record of a commit, or `None` if the filters exclude it
+fn export_commit(repo: &Repository, commit: &Commit, filters: &HistoryFilters) -> GitResult<Option<ExportedCommit>> {
+    let timestamp = commit.time().seconds();
+    if filters.since.is_some_and(|since| timestamp < since) || filters.until.is_some_and(|until| timestamp > until) {
+        return Ok(None);
+    }
+    if filters.no_merges && commit.parent_count() > 1 {
+        return Ok(None);
+    }
+
+    let author = commit.author();
+    let name = author.name().unwrap_or("Unknown").to_string();
+    let email = author.email().unwrap_or("").to_string();
+    if let Some(filter) = filters.author.as_deref().filter(|f| !f.is_empty()) {
+        if !contains_ignore_case(&name, filter) && !contains_ignore_case(&email, filter) {
+            return Ok(None);
+        }
+    }
+
+    let message = commit.message().unwrap_or("").to_string();
+    if let Some(filter) = filters.message.as_deref().filter(|f| !f.is_empty()) {
+        if !contains_ignore_case(&message, filter) {
+            return Ok(None);
+        }
+    }
+
+    // Changes relative to the first parent, like `git log --stat --first-parent`
+    let parent_tree = commit.parents().next().map(|p| p.tree()).transpose()?;
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+    if let Some(filter) = filters.path.as_deref().filter(|f| !f.is_empty()) {
+        let touches_path = diff.deltas().any(|delta| {
+            [delta.new_file().path(), delta.old_file().path()]
+                .into_iter()
+                .flatten()
+                .any(|path| matches_path(path, filter))
+        });
+        if !touches_path {
+            return Ok(None);
+        }
+    }
+    let stats = diff.stats()?;
+
+    Ok(Some(ExportedCommit {
+        sha: commit.id().to_string(),
+        author: name,
+        email,
+        date: Utc
+            .timestamp_opt(timestamp, 0)
+            .single()
+            .map(|date| date.to_rfc3339())
+            .unwrap_or_default(),
+        timestamp,
+        parents: commit.parent_ids().map(|oid| oid.to_string()).collect(),
+        subject: message.lines().next().unwrap_or("").to_string(),
+        message,
+        files_changed: stats.files_changed(),
+        insertions: stats.insertions(),
+        deletions: stats.deletions(),
+    }))
+}
+
+/// Quote a CSV field if needed
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn csv_row(commit: &ExportedCommit) -> String {
+    [
+        csv_field(&commit.sha),
+        csv_field(&commit.author),
+        csv_field(&commit.email),
+        csv_field(&commit.date),
+        commit.timestamp.to_string(),
+        csv_field(&commit.parents.join(" ")),
+        commit.files_changed.to_string(),
+        commit.insertions.to_string(),
+        commit.deletions.to_string(),
+        csv_field(&commit.subject),
+    ]
+    .join(",")
+}
+
+/// Write the filtered history to `out`, returning the number of commits written
+pub fn export_history<W: Write>(
+    repo: &Repository,
+    format: HistoryExportFormat,
+    filters: &HistoryFilters,
+    mut out: W,
+) -> GitResult<usize> {
+    let start = repo.revparse_single(filters.rev.as_deref().unwrap_or("HEAD"))?.peel_to_commit()?;
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(start.id())?;
+    revwalk.set_sorting(git2::Sort::TIME)?;
+
+    let write_error = |e: serde_json::Error| GitError::Generic(format!("Failed to write commit: {}", e));
+    match format {
+        HistoryExportFormat::Json => out.write_all(b"[")?,
+        HistoryExportFormat::Csv => writeln!(out, "{}", CSV_HEADER)?,
+    }
+
+    let mut count = 0;
+    for oid in revwalk {
+        if filters.max_count.is_some_and(|max| count >= max) {
+            break;
+        }
+        let commit = repo.find_commit(oid?)?;
+        let Some(exported) = export_commit(repo, &commit, filters)? else {
+            continue;
+        };
+
+        match format {
+            HistoryExportFormat::Json => {
+                out.write_all(if count == 0 { b"\n  " } else { b",\n  " })?;
+                serde_json::to_writer(&mut out, &exported).map_err(write_error)?;
+            }
+            HistoryExportFormat::Csv => writeln!(out, "{}", csv_row(&exported))?,
+        }
+        count += 1;
+    }
+
+    if format == HistoryExportFormat::Json {
+        out.write_all(if count == 0 { b"]\n" } else { b"\n]\n" })?;
+    }
+    out.flush()?;
+    Ok(count)
+}
+
+/// Write the filtered history to a file, returning the number of commits written
+pub fn export_history_to_file(
+    repo: &Repository,
+    format: HistoryExportFormat,
+    filters: &HistoryFilters,
+    path: &str,
+) -> GitResult<usize> {
+    let file = File::create(path)?;
+    export_history(repo, format, filters, BufWriter::new(file))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::commit_file_as;
+    use tempfile::tempdir;
+
+    fn test_repo() -> (tempfile::TempDir, Repository) {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let ada = git2::Signature::now("Ada, Jr.", "ada@example.com").unwrap();
+        commit_file_as(&repo, "README.md", "hello\n", "docs: add readme", &ada);
+        commit_file_as(&repo, "src/lib.rs", "pub fn a() {}\npub fn b() {}\n", "feat: add \"lib\"", &ada);
+        (dir, repo)
+    }
+
+    #[test]
+    fn test_export_json_with_filters() {
+        let (_dir, repo) = test_repo();
+
+        let mut out = Vec::new();
+        let count = export_history(&repo, HistoryExportFormat::Json, &HistoryFilters::default(), &mut out).unwrap();
+        assert_eq!(count, 2);
+        let commits: Vec<ExportedCommit> = serde_json::from_slice(&out).unwrap();
+        assert_eq!(commits[0].subject, "feat: add \"lib\"");
+        assert_eq!((commits[0].files_changed, commits[0].insertions), (1, 2));
+
+        let filters = HistoryFilters {
+            path: Some("src/".to_string()),
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        assert_eq!(export_history(&repo, HistoryExportFormat::Json, &filters, &mut out).unwrap(), 1);
+
+        let filters = HistoryFilters {
+            author: Some("nobody".to_string()),
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        assert_eq!(export_history(&repo, HistoryExportFormat::Json, &filters, &mut out).unwrap(), 0);
+        assert!(serde_json::from_slice::<Vec<ExportedCommit>>(&out).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_export_csv() {
+        let (_dir, repo) = test_repo();
+        let filters = HistoryFilters {
+            max_count: Some(1),
+            ..Default::default()
+        };
+
+        let mut out = Vec::new();
+        export_history(&repo, HistoryExportFormat::Csv, &filters, &mut out).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0], CSV_HEADER);
+        assert!(lines[1].contains(",\"Ada, Jr.\",ada@example.com,"));
+        assert!(lines[1].ends_with(",1,2,0,\"feat: add \"\"lib\"\"\""));
+    }
+}
@@ -0,0 +1,201 @@
+//! Reading and editing files in the working directory
+//!
+//! Paths are relative to the repository root and may not leave the working
+//! directory, through `..` or symlinks, or point into `.git`.
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use git2::Repository;
+use serde::{Deserialize, Serialize};
+
+use super::{GitError, GitResult};
+
+/// Largest file (in bytes) that can be opened or saved in the editor
+pub const MAX_EDITABLE_FILE_SIZE: u64 = 1024 * 1024;
+
+/// Number of leading bytes searched for a NUL byte, as Git does, to detect binary files
+const BINARY_CHECK_BYTES: usize = 8000;
+
+/// A file of the working directory opened for editing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorktreeFile {
+    pub path: String,
+    pub size: u64,
+    pub is_binary: bool,
+    /// Whether the file is larger than [`MAX_EDITABLE_FILE_SIZE`]
+    pub too_large: bool,
+    /// Text content; missing for binary and too large files
+    pub content: Option<String>,
+}
+
+/// Whether content looks binary rather than text
+pub fn is_binary_content(content: &[u8]) -> bool {
+    content[..content.len().min(BINARY_CHECK_BYTES)].contains(&0)
+}
+
+/// Resolve a repository-relative path to a path in the working directory
+///
+/// The deepest existing directory of the path must resolve inside the working
+/// directory, so symlinked directories cannot lead out of it, and the path
+/// itself may not be a symlink.
+pub(crate) fn worktree_path(repo: &Repository, path: &str) -> GitResult<PathBuf> {
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| GitError::OperationFailed("Repository has no working directory".to_string()))?
+        .canonicalize()?;
+
+    let relative = Path::new(path);
+    let mut components = relative.components().peekable();
+    if components.peek().is_none() {
+        return Err(GitError::Generic("File path is empty".to_string()));
+    }
+    for component in components {
+        match component {
+            Component::Normal(name) if name.eq_ignore_ascii_case(".git") => {
+                return Err(GitError::Generic(format!("Cannot edit files in .git: {}", path)));
+            }
+            Component::Normal(_) | Component::CurDir => {}
+            _ => {
+                return Err(GitError::Generic(format!(
+                    "File path must be relative to the repository: {}",
+                    path
+                )))
+            }
+        }
+    }
+
+    let full_path = workdir.join(relative);
+    let mut existing = full_path.parent().unwrap_or(&workdir);
+    while !existing.exists() {
+        existing = existing.parent().unwrap_or(&workdir);
+    }
+    if !existing.canonicalize()?.starts_with(&workdir) {
+        return Err(GitError::Generic(format!("File path leaves the repository: {}", path)));
+    }
+    if fs::symlink_metadata(&full_path).is_ok_and(|metadata| metadata.file_type().is_symlink()) {
+        return Err(GitError::Generic(format!("Cannot edit symlinks: {}", path)));
+    }
+
+    Ok(full_path)
+}
+
+/// Read a file of the working directory
+pub fn read_worktree_file(repo: &Repository, path: &str) -> GitResult<WorktreeFile> {
+    let full_path = worktree_path(repo, path)?;
+    let metadata = fs::metadata(&full_path).map_err(|_| GitError::FileNotFound(path.to_string()))?;
+    if !metadata.is_file() {
+        return Err(GitError::FileNotFound(path.to_string()));
+    }
+
+    let size = metadata.len();
+    if size > MAX_EDITABLE_FILE_SIZE {
+        return Ok(WorktreeFile {
+            path: path.to_string(),
+            size,
+            is_binary: false,
+            too_large: true,
+            content: None,
+        });
+    }
+
+    let bytes = fs::read(&full_path)?;
+    let content = if is_binary_content(&bytes) {
+        None
+    } else {
+        String::from_utf8(bytes).ok()
+    };
+
+    Ok(WorktreeFile {
+        path: path.to_string(),
+        size,
+        is_binary: content.is_none(),
+        too_large: false,
+        content,
+    })
+}
+
+/// Write a file of the working directory, creating it and its directories if needed
+pub fn write_worktree_file(repo: &Repository, path: &str, content: &str) -> GitResult<()> {
+    if content.len() as u64 > MAX_EDITABLE_FILE_SIZE {
+        return Err(GitError::Generic(format!(
+            "File is too large to save ({} bytes, at most {})",
+            content.len(),
+            MAX_EDITABLE_FILE_SIZE
+        )));
+    }
+
+    let full_path = worktree_path(repo, path)?;
+    if full_path.is_dir() {
+        return Err(GitError::Generic(format!("{} is a directory", path)));
+    }
+    if let Some(parent) = full_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&full_path, content)?;
+    Ok(())
+}
+
+/// Delete a file of the working directory
+pub fn delete_worktree_file(repo: &Repository, path: &str) -> GitResult<()> {
+    let full_path = worktree_path(repo, path)?;
+    if !full_path.is_file() {
+        return Err(GitError::FileNotFound(path.to_string()));
+    }
+    fs::remove_file(&full_path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_write_read_and_delete_worktree_file() {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+
+        write_worktree_file(&repo, "src/main.rs", "fn main() {}\n").unwrap();
+        let file = read_worktree_file(&repo, "src/main.rs").unwrap();
+        assert_eq!(file.content.as_deref(), Some("fn main() {}\n"));
+        assert!(!file.is_binary && !file.too_large);
+
+        fs::write(dir.path().join("image.png"), [0x89, b'P', b'N', b'G', 0, 1]).unwrap();
+        let file = read_worktree_file(&repo, "image.png").unwrap();
+        assert!(file.is_binary);
+        assert!(file.content.is_none());
+
+        delete_worktree_file(&repo, "src/main.rs").unwrap();
+        assert!(read_worktree_file(&repo, "src/main.rs").is_err());
+    }
+
+    #[test]
+    fn test_paths_outside_the_worktree_are_rejected() {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+
+        assert!(write_worktree_file(&repo, "../outside.txt", "x").is_err());
+        assert!(write_worktree_file(&repo, "/tmp/outside.txt", "x").is_err());
+        assert!(write_worktree_file(&repo, ".git/config", "x").is_err());
+        assert!(write_worktree_file(&repo, ".GIT/config", "x").is_err());
+        assert!(write_worktree_file(&repo, "", "x").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinks_are_rejected() {
+        let dir = tempdir().unwrap();
+        let outside = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        fs::write(outside.path().join("secret.txt"), "secret").unwrap();
+        std::os::unix::fs::symlink(outside.path(), dir.path().join("linked")).unwrap();
+        std::os::unix::fs::symlink(outside.path().join("secret.txt"), dir.path().join("secret.txt")).unwrap();
+
+        assert!(read_worktree_file(&repo, "linked/secret.txt").is_err());
+        assert!(write_worktree_file(&repo, "linked/new/file.txt", "x").is_err());
+        assert!(!outside.path().join("new").exists());
+        assert!(read_worktree_file(&repo, "secret.txt").is_err());
+        assert!(delete_worktree_file(&repo, "secret.txt").is_err());
+    }
+}
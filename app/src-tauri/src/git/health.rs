@@ -0,0 +1,240 @@
+//! Repository health report
+//!
+//! Collects local signals that usually need attention — stale or unpushed
+//! branches, large files, missing README or LICENSE, and unresolved conflicts —
+//! into one report for the dashboard.
+
+use chrono::Utc;
+use git2::{BranchType, ObjectType, Repository, TreeWalkMode, TreeWalkResult};
+use serde::{Deserialize, Serialize};
+
+use super::{get_repo_status, GitResult};
+
+/// Days without commits after which a branch counts as stale
+pub const STALE_BRANCH_DAYS: i64 = 90;
+
+/// Size (in bytes) from which a tracked file counts as large
+pub const LARGE_FILE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Largest number of large files listed in a report
+const MAX_LARGE_FILES: usize = 20;
+
+/// Most unpushed commits counted per branch
+const MAX_UNPUSHED_COUNT: usize = 1000;
+
+/// Local branch without recent commits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaleBranch {
+    pub name: String,
+    pub last_commit_timestamp: i64,
+    pub days_inactive: i64,
+}
+
+/// Local branch with commits that are on no remote
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnpushedBranch {
+    pub name: String,
+    pub upstream: Option<String>,
+    pub unpushed_commits: usize,
+}
+
+/// File in HEAD of at least [`LARGE_FILE_BYTES`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LargeFile {
+    pub path: String,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoHealth {
+    pub stale_branches: Vec<StaleBranch>,
+    pub unpushed_branches: Vec<UnpushedBranch>,
+    /// Largest files first
+    pub large_files: Vec<LargeFile>,
+    pub has_readme: bool,
+    pub has_license: bool,
+    /// Paths with unresolved merge conflicts
+    pub conflicts: Vec<String>,
+}
+
+impl RepoHealth {
+    /// Number of problems found
+    pub fn issue_count(&self) -> usize {
+        self.stale_branches.len()
+            + self.unpushed_branches.len()
+            + self.large_files.len()
+            + usize::from(!self.has_readme)
+            + usize::from(!self.has_license)
+            + self.conflicts.len()
+    }
+}
+
+fn is_readme(name: &str) -> bool {
+    name.to_lowercase().starts_with("readme")
+}
+
+fn is_license(name: &str) -> bool {
+    let name = name.to_lowercase();
+    name.starts_with("license") || name.starts_with("licence") || name.starts_with("copying")
+}
+
+/// Names of the files at the root of the repository, from the working directory
+/// when there is one and from HEAD otherwise
+fn root_file_names(repo: &Repository) -> Vec<String> {
+    if let Some(workdir) = repo.workdir() {
+        return std::fs::read_dir(workdir)
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .filter(|e| e.path().is_file())
+                    .map(|e| e.file_name().to_string_lossy().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+    }
+
+    repo.head()
+        .ok()
+        .and_then(|head| head.peel_to_tree().ok())
+        .map(|tree| {
+            tree.iter()
+                .filter(|entry| entry.kind() == Some(ObjectType::Blob))
+                .filter_map(|entry| entry.name().map(|n| n.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn find_large_files(repo: &Repository) -> GitResult<Vec<LargeFile>> {
+    let Some(tree) = repo.head().ok().and_then(|head| head.peel_to_tree().ok()) else {
+        return Ok(Vec::new());
+    };
+    let odb = repo.odb()?;
+
+    let mut files = Vec::new();
+    tree.walk(TreeWalkMode::PreOrder, |dir, entry| {
+        if entry.kind() == Some(ObjectType::Blob) {
+            if let Ok((size, _)) = odb.read_header(entry.id()) {
+                if size as u64 >= LARGE_FILE_BYTES {
+                    files.push(LargeFile {
+                        path: format!("{}{}", dir, entry.name().unwrap_or_default()),
+                        size: size as u64,
+                    });
+                }
+            }
+        }
+        TreeWalkResult::Ok
+    })?;
+
+    files.sort_by_key(|f| std::cmp::Reverse(f.size));
+    files.truncate(MAX_LARGE_FILES);
+    Ok(files)
+}
+
+/// Collect the health report of a repository at the time `now` (Unix seconds)
+pub fn get_repo_health_at(repo: &Repository, now: i64) -> GitResult<RepoHealth> {
+    let current = repo.head().ok().filter(|h| h.is_branch()).and_then(|h| h.shorthand().map(|s| s.to_string()));
+    let remote_tips: Vec<git2::Oid> = repo
+        .branches(Some(BranchType::Remote))?
+        .filter_map(|b| b.ok())
+        .filter_map(|(branch, _)| branch.get().target())
+        .collect();
+
+    let mut stale_branches = Vec::new();
+    let mut unpushed_branches = Vec::new();
+    for branch in repo.branches(Some(BranchType::Local))? {
+        let (branch, _) = branch?;
+        let Some(name) = branch.name()?.map(|n| n.to_string()) else {
+            continue;
+        };
+        let Ok(tip) = branch.get().peel_to_commit() else {
+            continue;
+        };
+
+        let last_commit_timestamp = tip.time().seconds();
+        let days_inactive = (now - last_commit_timestamp) / 86400;
+        if days_inactive >= STALE_BRANCH_DAYS && current.as_ref() != Some(&name) {
+            stale_branches.push(StaleBranch {
+                name: name.clone(),
+                last_commit_timestamp,
+                days_inactive,
+            });
+        }
+
+        let mut walk = repo.revwalk()?;
+        walk.push(tip.id())?;
+        for oid in &remote_tips {
+            walk.hide(*oid)?;
+        }
+        let unpushed_commits = walk.take(MAX_UNPUSHED_COUNT).filter(|c| c.is_ok()).count();
+        if unpushed_commits > 0 {
+            let upstream = branch
+                .upstream()
+                .ok()
+                .and_then(|u| u.name().ok().flatten().map(|n| n.to_string()));
+            unpushed_branches.push(UnpushedBranch {
+                name,
+                upstream,
+                unpushed_commits,
+            });
+        }
+    }
+    stale_branches.sort_by_key(|b| std::cmp::Reverse(b.days_inactive));
+
+    let root_files = root_file_names(repo);
+    let conflicts = if repo.workdir().is_some() {
+        get_repo_status(repo)?.conflicts.into_iter().map(|f| f.path).collect()
+    } else {
+        Vec::new()
+    };
+
+    Ok(RepoHealth {
+        stale_branches,
+        unpushed_branches,
+        large_files: find_large_files(repo)?,
+        has_readme: root_files.iter().any(|n| is_readme(n)),
+        has_license: root_files.iter().any(|n| is_license(n)),
+        conflicts,
+    })
+}
+
+/// Collect the health report of a repository
+pub fn get_repo_health(repo: &Repository) -> GitResult<RepoHealth> {
+    get_repo_health_at(repo, Utc::now().timestamp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::{commit_file_as, signature_at};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_repo_health() {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let day = 86400;
+        commit_file_as(&repo, "README.md", b"# Test", "commit", &signature_at("Test", 0));
+        commit_file_as(&repo, "big.bin", vec![0u8; LARGE_FILE_BYTES as usize], "commit", &signature_at("Test", day));
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("old-feature", &head, false).unwrap();
+
+        let health = get_repo_health_at(&repo, 200 * day).unwrap();
+        assert!(health.has_readme);
+        assert!(!health.has_license);
+        assert!(health.conflicts.is_empty());
+        assert_eq!(health.large_files.len(), 1);
+        assert_eq!(health.large_files[0].path, "big.bin");
+
+        // The current branch is never reported as stale
+        let stale: Vec<&str> = health.stale_branches.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(stale, vec!["old-feature"]);
+        assert_eq!(health.stale_branches[0].days_inactive, 199);
+
+        // Without remotes every commit is unpushed
+        assert_eq!(health.unpushed_branches.len(), 2);
+        assert!(health.unpushed_branches.iter().all(|b| b.unpushed_commits == 2));
+        assert_eq!(health.issue_count(), 1 + 2 + 1 + 1);
+    }
+}
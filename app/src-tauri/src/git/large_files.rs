@@ -0,0 +1,210 @@
+//! Warning about large staged files before they are committed
+//!
+//! GitHub rejects pushes containing files over 100 MB, and large binaries bloat
+//! the history for good, so staged blobs above a configurable size are
+//! reported together with the Git LFS command that would track them instead.
+
+use std::path::Path;
+
+use git2::Repository;
+use serde::{Deserialize, Serialize};
+
+use super::{GitResult, LargeFile};
+use crate::error::StructuredError;
+use crate::settings::{load_json_settings, save_json_settings};
+
+/// Default size (in bytes) from which a staged file is reported: GitHub warns from 50 MB
+pub const DEFAULT_LARGE_FILE_THRESHOLD: u64 = 50 * 1024 * 1024;
+
+/// Settings of the large file check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LargeFileCheckSettings {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_threshold")]
+    pub threshold_bytes: u64,
+    /// Refuse to commit large files instead of only warning
+    #[serde(default)]
+    pub block: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_threshold() -> u64 {
+    DEFAULT_LARGE_FILE_THRESHOLD
+}
+
+impl Default for LargeFileCheckSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            threshold_bytes: default_threshold(),
+            block: false,
+        }
+    }
+}
+
+/// Large files about to be committed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LargeFileWarning {
+    pub files: Vec<LargeFile>,
+    pub threshold_bytes: u64,
+    /// Commands that would move the files to Git LFS
+    pub lfs_commands: Vec<String>,
+}
+
+impl LargeFileWarning {
+    pub fn message(&self) -> String {
+        let files: Vec<String> = self
+            .files
+            .iter()
+            .map(|f| format!("{} ({:.1} MB)", f.path, f.size as f64 / (1024.0 * 1024.0)))
+            .collect();
+        format!(
+            "Staged files are larger than {} MB: {}. Consider tracking them with Git LFS",
+            self.threshold_bytes / (1024 * 1024),
+            files.join(", ")
+        )
+    }
+}
+
+/// Error returned when a commit is refused because it adds large files, with
+/// kind `large_files` and the warning's fields
+pub fn large_files_error(warning: LargeFileWarning) -> StructuredError {
+    StructuredError::new(
+        "large_files",
+        warning.message(),
+        serde_json::to_value(&warning).unwrap_or_default(),
+    )
+}
+
+/// The `git lfs track` pattern for a path: every file with its extension, or the
+/// path itself when it has none
+pub fn lfs_track_pattern(path: &str) -> String {
+    match Path::new(path).extension() {
+        Some(extension) => format!("*.{}", extension.to_string_lossy()),
+        None => path.to_string(),
+    }
+}
+
+/// Find staged files of at least `threshold` bytes that are not yet in HEAD
+pub fn find_large_staged_files(repo: &Repository, threshold: u64) -> GitResult<Vec<LargeFile>> {
+    let head_tree = repo.head().ok().and_then(|h| h.peel_to_tree().ok());
+    let diff = repo.diff_tree_to_index(head_tree.as_ref(), None, None)?;
+    let odb = repo.odb()?;
+
+    let mut files = Vec::new();
+    for delta in diff.deltas() {
+        let new_file = delta.new_file();
+        if delta.status() == git2::Delta::Deleted || new_file.id().is_zero() {
+            continue;
+        }
+        let Some(path) = new_file.path() else {
+            continue;
+        };
+        let (size, _) = odb.read_header(new_file.id())?;
+        if size as u64 >= threshold {
+            files.push(LargeFile {
+                path: path.to_string_lossy().to_string(),
+                size: size as u64,
+            });
+        }
+    }
+
+    files.sort_by_key(|f| std::cmp::Reverse(f.size));
+    Ok(files)
+}
+
+/// Check the staged files against the settings, returning a warning if any are too large
+pub fn check_staged_large_files(
+    repo: &Repository,
+    settings: &LargeFileCheckSettings,
+) -> GitResult<Option<LargeFileWarning>> {
+    if !settings.enabled {
+        return Ok(None);
+    }
+
+    let files = find_large_staged_files(repo, settings.threshold_bytes)?;
+    if files.is_empty() {
+        return Ok(None);
+    }
+
+    let mut lfs_commands: Vec<String> = Vec::new();
+    for file in &files {
+        let command = format!("git lfs track \"{}\"", lfs_track_pattern(&file.path));
+        if !lfs_commands.contains(&command) {
+            lfs_commands.push(command);
+        }
+    }
+
+    Ok(Some(LargeFileWarning {
+        files,
+        threshold_bytes: settings.threshold_bytes,
+        lfs_commands,
+    }))
+}
+
+/// Name of the settings file in the app config directory
+const SETTINGS_FILE: &str = "large_file_check.json";
+
+/// Load the large file check settings, or the defaults if none were saved
+pub fn load_large_file_settings() -> GitResult<LargeFileCheckSettings> {
+    Ok(load_json_settings(SETTINGS_FILE)?)
+}
+
+/// Save the large file check settings
+pub fn save_large_file_settings(settings: &LargeFileCheckSettings) -> GitResult<()> {
+    Ok(save_json_settings(SETTINGS_FILE, settings)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_check_staged_large_files() {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        fs::write(dir.path().join("small.txt"), "hello").unwrap();
+        fs::write(dir.path().join("video.mp4"), vec![0u8; 2048]).unwrap();
+        fs::write(dir.path().join("clip.mp4"), vec![0u8; 4096]).unwrap();
+
+        let mut index = repo.index().unwrap();
+        for path in ["small.txt", "video.mp4", "clip.mp4"] {
+            index.add_path(Path::new(path)).unwrap();
+        }
+        index.write().unwrap();
+
+        let settings = LargeFileCheckSettings {
+            threshold_bytes: 1024,
+            ..Default::default()
+        };
+        let warning = check_staged_large_files(&repo, &settings).unwrap().unwrap();
+        let paths: Vec<&str> = warning.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["clip.mp4", "video.mp4"]);
+        assert_eq!(warning.lfs_commands, vec!["git lfs track \"*.mp4\""]);
+
+        let json = large_files_error(warning).to_json();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["kind"], "large_files");
+        assert_eq!(value["threshold_bytes"], 1024);
+        assert_eq!(value["files"][0]["path"], "clip.mp4");
+        assert_eq!(value["lfs_commands"][0], "git lfs track \"*.mp4\"");
+
+        let disabled = LargeFileCheckSettings {
+            enabled: false,
+            ..settings
+        };
+        assert!(check_staged_large_files(&repo, &disabled).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_lfs_track_pattern() {
+        assert_eq!(lfs_track_pattern("assets/logo.psd"), "*.psd");
+        assert_eq!(lfs_track_pattern("bin/tool"), "bin/tool");
+    }
+}
@@ -0,0 +1,368 @@
+//! Mirror pushes to a backup remote
+//!
+//! A repository can name a secondary remote that receives every branch and
+//! tag, either on a schedule or after each successful push, as a simple
+//! off-site backup. Refs deleted locally are only deleted from the mirror when
+//! pruning is turned on, and the remote the repository pushes to can never be
+//! used as its mirror. Mirror configurations and the outcome of their last
+//! push are stored in the app config directory.
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+use git2::{Direction, Repository};
+use serde::{Deserialize, Serialize};
+
+use super::credentials::HelperCredentials;
+use super::remote::{create_callbacks, get_default_remote, push_refspecs};
+use super::{GitError, GitResult};
+use crate::settings::{load_json_settings, save_json_settings};
+
+/// Name of the event emitted after each mirror push, with the updated `MirrorConfig`
+pub const MIRROR_STATUS_EVENT: &str = "mirror-status";
+
+/// Ref namespaces copied to the mirror
+const MIRRORED_REF_PREFIXES: &[&str] = &["refs/heads/", "refs/tags/"];
+
+/// Outcome of the last mirror push of a repository
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MirrorStatus {
+    /// Unix timestamp of the last push attempt
+    pub last_attempt: Option<i64>,
+    /// Unix timestamp of the last successful push
+    pub last_success: Option<i64>,
+    /// Error of the last attempt, if it failed
+    pub last_error: Option<String>,
+    /// Refs updated and deleted by the last successful push
+    pub pushed_refs: usize,
+    pub deleted_refs: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MirrorConfig {
+    pub repo_path: String,
+    /// Remote receiving the mirror pushes
+    pub remote: String,
+    /// Minutes between scheduled pushes, or none to only push after pushes
+    #[serde(default)]
+    pub interval_minutes: Option<u64>,
+    /// Whether to mirror after each successful push to the default remote
+    #[serde(default)]
+    pub after_push: bool,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Whether to delete branches and tags from the mirror that no longer exist locally
+    #[serde(default)]
+    pub prune: bool,
+    #[serde(default)]
+    pub status: MirrorStatus,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl MirrorConfig {
+    /// Whether a scheduled push is due at `now`
+    pub fn is_due(&self, now: i64) -> bool {
+        let Some(interval) = self.interval_minutes.filter(|&m| m > 0) else {
+            return false;
+        };
+        self.enabled
+            && self
+                .status
+                .last_attempt
+                .is_none_or(|last| now - last >= interval as i64 * 60)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MirrorSettings {
+    #[serde(default)]
+    pub mirrors: Vec<MirrorConfig>,
+}
+
+impl MirrorSettings {
+    pub fn find(&self, repo_path: &str) -> Option<&MirrorConfig> {
+        self.mirrors.iter().find(|m| m.repo_path == repo_path)
+    }
+
+    /// Add the mirror of a repository, replacing its previous one but keeping
+    /// the status if the remote is unchanged
+    pub fn upsert(&mut self, mut mirror: MirrorConfig) -> GitResult<()> {
+        mirror.remote = mirror.remote.trim().to_string();
+        if mirror.remote.is_empty() {
+            return Err(GitError::Generic("Mirror remote cannot be empty".to_string()));
+        }
+
+        match self.mirrors.iter_mut().find(|m| m.repo_path == mirror.repo_path) {
+            Some(existing) => {
+                if existing.remote == mirror.remote {
+                    mirror.status = existing.status.clone();
+                }
+                *existing = mirror;
+            }
+            None => self.mirrors.push(mirror),
+        }
+        Ok(())
+    }
+
+    /// Remove the mirror of a repository, returning whether it existed
+    pub fn remove(&mut self, repo_path: &str) -> bool {
+        let count = self.mirrors.len();
+        self.mirrors.retain(|m| m.repo_path != repo_path);
+        self.mirrors.len() != count
+    }
+}
+
+/// Refs updated and deleted by a mirror push
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MirrorPushResult {
+    pub remote: String,
+    pub pushed_refs: Vec<String>,
+    pub deleted_refs: Vec<String>,
+}
+
+/// Check that `remote_name` exists and is not a remote the repository pushes
+/// to, whose branches a forced mirror push would overwrite
+pub fn validate_mirror_remote(repo: &Repository, remote_name: &str) -> GitResult<()> {
+    repo.find_remote(remote_name)
+        .map_err(|_| GitError::OperationFailed(format!("Remote '{}' not found", remote_name)))?;
+
+    let mut push_remotes = vec![get_default_remote(repo)?];
+    let config = repo.config()?;
+    let mut entries = config.entries(Some(r"^(remote\.pushdefault|branch\..*\.(remote|pushremote))$"))?;
+    while let Some(entry) = entries.next() {
+        if let Some(value) = entry?.value() {
+            push_remotes.push(value.to_string());
+        }
+    }
+
+    if push_remotes.iter().any(|name| name == remote_name) {
+        return Err(GitError::OperationFailed(format!(
+            "Remote '{}' is pushed to by this repository and cannot be used as a mirror",
+            remote_name
+        )));
+    }
+    Ok(())
+}
+
+/// Push every branch and tag to `remote_name`, forcing updates, and with
+/// `prune` delete the branches and tags that no longer exist locally
+pub fn mirror_push(repo: &Repository, remote_name: &str, prune: bool) -> GitResult<MirrorPushResult> {
+    validate_mirror_remote(repo, remote_name)?;
+    let mut remote = repo.find_remote(remote_name)?;
+
+    let mut local_refs = Vec::new();
+    for reference in repo.references()? {
+        let reference = reference?;
+        if let Some(name) = reference.name() {
+            if MIRRORED_REF_PREFIXES.iter().any(|prefix| name.starts_with(prefix)) {
+                local_refs.push(name.to_string());
+            }
+        }
+    }
+    if local_refs.is_empty() {
+        return Err(GitError::Generic("The repository has no branches or tags to mirror".to_string()));
+    }
+
+    let refspecs: Vec<String> = local_refs.iter().map(|name| format!("+{}:{}", name, name)).collect();
+    push_refspecs(repo, &mut remote, &refspecs)?;
+    if !prune {
+        return Ok(MirrorPushResult {
+            remote: remote_name.to_string(),
+            pushed_refs: local_refs,
+            deleted_refs: Vec::new(),
+        });
+    }
+
+    // Listing the refs of an empty remote is not supported by git2, so stale refs
+    // are looked up once the remote has received the local ones
+    let local: HashSet<&str> = local_refs.iter().map(|s| s.as_str()).collect();
+    let helper = HelperCredentials::for_repo(repo);
+    let connection = remote.connect_auth(Direction::Push, Some(create_callbacks(&helper)), None)?;
+    let deleted_refs: Vec<String> = connection
+        .list()?
+        .iter()
+        .map(|head| head.name().to_string())
+        .filter(|name| {
+            MIRRORED_REF_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+                && !name.ends_with("^{}")
+                && !local.contains(name.as_str())
+        })
+        .collect();
+    drop(connection);
+    helper.approve();
+
+    if !deleted_refs.is_empty() {
+        let refspecs: Vec<String> = deleted_refs.iter().map(|name| format!(":{}", name)).collect();
+        push_refspecs(repo, &mut remote, &refspecs)?;
+    }
+
+    Ok(MirrorPushResult {
+        remote: remote_name.to_string(),
+        pushed_refs: local_refs,
+        deleted_refs,
+    })
+}
+
+/// Held for a whole mirror run, so the scheduler, pushes, and the user never
+/// push the same mirror at once
+static MIRROR_RUN_LOCK: Mutex<()> = Mutex::new(());
+
+/// Held while mirrors.json is loaded, modified, and saved
+static MIRROR_SETTINGS_LOCK: Mutex<()> = Mutex::new(());
+
+/// Load the mirror configurations, change them, and save them, without losing
+/// concurrent updates
+pub fn update_mirror_settings<T>(update: impl FnOnce(&mut MirrorSettings) -> GitResult<T>) -> GitResult<T> {
+    let _guard = MIRROR_SETTINGS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut settings = load_mirror_settings()?;
+    let value = update(&mut settings)?;
+    save_mirror_settings(&settings)?;
+    Ok(value)
+}
+
+/// Mirror a repository to its configured remote and record the outcome
+///
+/// Returns the updated configuration; the push error, if any, is in its status.
+pub fn run_mirror(repo_path: &str) -> GitResult<MirrorConfig> {
+    let _guard = MIRROR_RUN_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let (remote, prune) = load_mirror_settings()?
+        .find(repo_path)
+        .map(|m| (m.remote.clone(), m.prune))
+        .ok_or_else(|| GitError::Generic(format!("No mirror configured for {}", repo_path)))?;
+
+    let result = Repository::open(repo_path)
+        .map_err(GitError::from)
+        .and_then(|repo| mirror_push(&repo, &remote, prune));
+
+    // Reload in case the configuration changed during the push
+    update_mirror_settings(|settings| {
+        let mirror = settings
+            .mirrors
+            .iter_mut()
+            .find(|m| m.repo_path == repo_path)
+            .ok_or_else(|| GitError::Generic(format!("No mirror configured for {}", repo_path)))?;
+        let now = chrono::Utc::now().timestamp();
+        mirror.status.last_attempt = Some(now);
+        match result {
+            Ok(result) => {
+                mirror.status.last_success = Some(now);
+                mirror.status.last_error = None;
+                mirror.status.pushed_refs = result.pushed_refs.len();
+                mirror.status.deleted_refs = result.deleted_refs.len();
+            }
+            Err(e) => mirror.status.last_error = Some(e.to_string()),
+        }
+        Ok(mirror.clone())
+    })
+}
+
+/// Name of the settings file in the app config directory
+const SETTINGS_FILE: &str = "mirrors.json";
+
+/// Load the mirror configurations, or none if none were saved
+pub fn load_mirror_settings() -> GitResult<MirrorSettings> {
+    Ok(load_json_settings(SETTINGS_FILE)?)
+}
+
+/// Save the mirror configurations
+pub fn save_mirror_settings(settings: &MirrorSettings) -> GitResult<()> {
+    Ok(save_json_settings(SETTINGS_FILE, settings)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn mirror(repo_path: &str, remote: &str) -> MirrorConfig {
+        MirrorConfig {
+            repo_path: repo_path.to_string(),
+            remote: remote.to_string(),
+            interval_minutes: Some(30),
+            after_push: false,
+            enabled: true,
+            prune: false,
+            status: MirrorStatus::default(),
+        }
+    }
+
+    #[test]
+    fn test_mirror_is_due() {
+        let mut config = mirror("/repo", "backup");
+        assert!(config.is_due(1_000));
+        config.status.last_attempt = Some(1_000);
+        assert!(!config.is_due(1_000 + 29 * 60));
+        assert!(config.is_due(1_000 + 30 * 60));
+
+        config.enabled = false;
+        assert!(!config.is_due(1_000 + 30 * 60));
+        config.enabled = true;
+        config.interval_minutes = None;
+        assert!(!config.is_due(1_000 + 30 * 60));
+    }
+
+    #[test]
+    fn test_mirror_settings_upsert_keeps_status() {
+        let mut settings = MirrorSettings::default();
+        let mut config = mirror("/repo", "backup");
+        config.status.last_success = Some(42);
+        settings.upsert(config).unwrap();
+
+        settings.upsert(mirror("/repo", " backup ")).unwrap();
+        assert_eq!(settings.find("/repo").unwrap().status.last_success, Some(42));
+        settings.upsert(mirror("/repo", "offsite")).unwrap();
+        assert_eq!(settings.mirrors.len(), 1);
+        assert_eq!(settings.find("/repo").unwrap().status.last_success, None);
+
+        assert!(settings.upsert(mirror("/other", "")).is_err());
+        assert!(settings.remove("/repo"));
+        assert!(!settings.remove("/repo"));
+    }
+
+    #[test]
+    fn test_mirror_push() {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path().join("repo")).unwrap();
+        let backup = Repository::init_bare(dir.path().join("backup.git")).unwrap();
+        Repository::init_bare(dir.path().join("origin.git")).unwrap();
+        repo.remote("origin", &dir.path().join("origin.git").to_string_lossy()).unwrap();
+        repo.remote("backup", &dir.path().join("backup.git").to_string_lossy()).unwrap();
+
+        fs::write(dir.path().join("repo/a.txt"), "a").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("a.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = git2::Signature::now("Test", "test@test.com").unwrap();
+        let oid = repo.commit(Some("HEAD"), &sig, &sig, "Initial", &tree, &[]).unwrap();
+        let commit = repo.find_commit(oid).unwrap();
+        repo.branch("feature", &commit, false).unwrap();
+        repo.tag_lightweight("v1.0", commit.as_object(), false).unwrap();
+
+        // The remote the repository pushes to is never mirrored over
+        assert!(mirror_push(&repo, "origin", false).is_err());
+        let mut config = repo.config().unwrap();
+        config.set_str("remote.pushDefault", "backup").unwrap();
+        assert!(mirror_push(&repo, "backup", false).is_err());
+        config.remove("remote.pushDefault").unwrap();
+
+        let result = mirror_push(&repo, "backup", false).unwrap();
+        assert_eq!(result.pushed_refs.len(), 3);
+        assert!(backup.find_reference("refs/heads/feature").is_ok());
+        assert!(backup.find_reference("refs/tags/v1.0").is_ok());
+
+        // Refs deleted locally are only deleted from the mirror when pruning
+        repo.find_branch("feature", git2::BranchType::Local).unwrap().delete().unwrap();
+        let result = mirror_push(&repo, "backup", false).unwrap();
+        assert!(result.deleted_refs.is_empty());
+        assert!(backup.find_reference("refs/heads/feature").is_ok());
+        let result = mirror_push(&repo, "backup", true).unwrap();
+        assert_eq!(result.deleted_refs, vec!["refs/heads/feature"]);
+        assert!(backup.find_reference("refs/heads/feature").is_err());
+        assert!(backup.find_reference("refs/tags/v1.0").is_ok());
+    }
+}
@@ -5,6 +5,28 @@ pub mod branch;
 pub mod diff;
 pub mod remote;
 pub mod clone;
+pub mod changelog;
+pub mod publish;
+pub mod files;
+pub mod secrets;
+pub mod health;
+pub mod export;
+pub mod activity;
+pub mod large_files;
+pub mod commit_lint;
+pub mod protected;
+pub mod stash;
+pub mod scan;
+pub mod workspaces;
+pub mod submodules;
+pub mod search_index;
+pub mod time_format;
+pub mod credentials;
+pub mod mirror;
+pub mod rebase;
+pub mod blame;
+#[cfg(test)]
+pub mod test_support;
 
 pub use repository::*;
 pub use status::*;
@@ -13,13 +35,32 @@ pub use commit::{
     cherry_pick_commit, revert_commit, reset_to_commit, checkout_commit,
     create_tag, get_commit_diff, ResetType,
     // New commit operations
-    merge_commit, rebase_onto, interactive_rebase, delete_tag,
-    squash_commits, amend_commit_message, drop_commit,
+    merge_commit, rebase_onto, delete_tag,
+    amend_commit_message, drop_commit,
+    get_commits_since, get_commits_in_range, find_latest_tag,
 };
 pub use branch::*;
 pub use diff::*;
 pub use remote::*;
 pub use clone::*;
+pub use files::*;
+pub use secrets::*;
+pub use health::*;
+pub use export::*;
+pub use activity::*;
+pub use large_files::*;
+pub use commit_lint::*;
+pub use protected::*;
+pub use stash::*;
+pub use scan::*;
+pub use workspaces::*;
+pub use submodules::*;
+pub use search_index::*;
+pub use time_format::*;
+pub use credentials::*;
+pub use mirror::*;
+pub use rebase::*;
+pub use blame::*;
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -47,6 +88,9 @@ pub enum GitError {
     #[error("Merge conflict detected")]
     MergeConflict,
 
+    #[error("Checkout would overwrite local changes to: {}", .0.join(", "))]
+    CheckoutConflict(Vec<String>),
+
     #[error("{0}")]
     Generic(String),
 
@@ -55,6 +99,12 @@ pub enum GitError {
 
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
+
+    #[error("Search index error: {0}")]
+    SearchIndex(#[from] rusqlite::Error),
+
+    #[error("{0}")]
+    Settings(#[from] crate::settings::SettingsError),
 }
 
 impl Serialize for GitError {
@@ -103,6 +153,8 @@ pub struct StatusInfo {
     pub unstaged: Vec<FileStatus>,
     pub untracked: Vec<FileStatus>,
     pub conflicts: Vec<FileStatus>,
+    #[serde(default)]
+    pub submodules: Vec<SubmoduleStatus>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -112,7 +164,11 @@ pub struct CommitInfo {
     pub message: String,
     pub author: String,
     pub email: String,
+    /// Relative date, such as "2 days ago"
     pub date: String,
+    /// Absolute local date in the configured format, such as "2024-05-12 14:03"
+    #[serde(default)]
+    pub absolute_date: String,
     pub timestamp: i64,
     pub parents: Vec<String>,
 }
@@ -164,4 +220,25 @@ pub struct FileDiff {
     pub is_binary: bool,
     pub additions: u32,
     pub deletions: u32,
+    /// The changed commit pointer, if the file is a submodule
+    #[serde(default)]
+    pub submodule: Option<SubmoduleChange>,
+}
+
+/// Line counts of one changed file, without its hunks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileChangeSummary {
+    pub path: String,
+    pub old_path: Option<String>,
+    pub status: FileStatusType,
+    pub is_binary: bool,
+    pub additions: u32,
+    pub deletions: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiffSummary {
+    pub files: Vec<FileChangeSummary>,
+    pub additions: u32,
+    pub deletions: u32,
 }
@@ -0,0 +1,130 @@
+//! Locally protected branches
+//!
+//! Branches matching the configured patterns (such as `main` or `release/*`)
+//! refuse commits, hard resets, and force pushes unless the caller explicitly
+//! overrides the protection. Settings are stored in the app config directory.
+
+use git2::Repository;
+use serde::{Deserialize, Serialize};
+
+use super::GitResult;
+use crate::error::StructuredError;
+use crate::settings::{load_json_settings, save_json_settings};
+
+/// Patterns of the branches that are protected
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProtectedBranchSettings {
+    /// Branch names, where `*` matches any sequence of characters
+    #[serde(default)]
+    pub patterns: Vec<String>,
+}
+
+/// Error returned when `operation` (`commit`, `hard_reset`, or `force_push`) is
+/// refused on a protected branch, with kind `protected_branch`
+pub fn protected_branch_error(branch: &str, operation: &str) -> StructuredError {
+    StructuredError::new(
+        "protected_branch",
+        format!(
+            "Branch '{}' is protected; {} needs an explicit override",
+            branch,
+            operation.replace('_', " ")
+        ),
+        serde_json::json!({ "branch": branch, "operation": operation }),
+    )
+}
+
+/// Whether `name` matches `pattern`, where `*` matches any sequence of characters
+pub fn matches_branch_pattern(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No wildcard: the whole name must match
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+impl ProtectedBranchSettings {
+    pub fn is_protected(&self, branch: &str) -> bool {
+        self.patterns
+            .iter()
+            .map(|p| p.trim())
+            .filter(|p| !p.is_empty())
+            .any(|p| matches_branch_pattern(p, branch))
+    }
+
+    /// The checked-out branch, if it is protected
+    pub fn protected_head(&self, repo: &Repository) -> Option<String> {
+        let head = repo.head().ok().filter(|h| h.is_branch())?;
+        let branch = head.shorthand()?;
+        self.is_protected(branch).then(|| branch.to_string())
+    }
+
+    /// Refuse `operation` if the checked-out branch is protected
+    pub fn check(&self, repo: &Repository, operation: &str) -> Result<(), StructuredError> {
+        match self.protected_head(repo) {
+            Some(branch) => Err(protected_branch_error(&branch, operation)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Name of the settings file in the app config directory
+const SETTINGS_FILE: &str = "protected_branches.json";
+
+/// Load the protected branch settings, or the defaults (nothing protected) if none were saved
+pub fn load_protected_branch_settings() -> GitResult<ProtectedBranchSettings> {
+    Ok(load_json_settings(SETTINGS_FILE)?)
+}
+
+/// Save the protected branch settings
+pub fn save_protected_branch_settings(settings: &ProtectedBranchSettings) -> GitResult<()> {
+    Ok(save_json_settings(SETTINGS_FILE, settings)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_branch_pattern() {
+        assert!(matches_branch_pattern("main", "main"));
+        assert!(!matches_branch_pattern("main", "main-old"));
+        assert!(matches_branch_pattern("release/*", "release/1.0"));
+        assert!(!matches_branch_pattern("release/*", "releases/1.0"));
+        assert!(matches_branch_pattern("*-stable", "2.x-stable"));
+        assert!(matches_branch_pattern("hotfix/*/*", "hotfix/app/crash"));
+        assert!(!matches_branch_pattern("a*a", "a"));
+        assert!(matches_branch_pattern("*", "anything"));
+    }
+
+    #[test]
+    fn test_is_protected() {
+        let settings = ProtectedBranchSettings {
+            patterns: vec!["main".to_string(), " release/* ".to_string(), String::new()],
+        };
+        assert!(settings.is_protected("main"));
+        assert!(settings.is_protected("release/2.0"));
+        assert!(!settings.is_protected("feature/login"));
+    }
+
+    #[test]
+    fn test_protected_branch_error_json() {
+        let json = protected_branch_error("main", "force_push").to_json();
+        let error: StructuredError = serde_json::from_str(&json).unwrap();
+        assert_eq!(error.kind, "protected_branch");
+        assert_eq!(error.details["branch"], "main");
+        assert_eq!(error.message, "Branch 'main' is protected; force push needs an explicit override");
+    }
+}
@@ -0,0 +1,153 @@
+//! Publishing a directory to a branch
+//!
+//! Commits the contents of an arbitrary directory (e.g. a static site build)
+//! as the whole tree of a branch, without touching the working tree or index.
+
+use std::path::Path;
+
+use git2::{FileMode, Oid, Repository};
+
+use super::{GitError, GitResult};
+
+/// Write a directory into the object database and return its tree id
+///
+/// `.git` directories are skipped; executable files keep their mode and
+/// symlinks are stored as links.
+fn write_tree(repo: &Repository, dir: &Path) -> GitResult<Oid> {
+    let mut builder = repo.treebuilder(None)?;
+
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let name = name
+            .to_str()
+            .ok_or_else(|| GitError::OperationFailed(format!("Invalid file name: {:?}", name)))?;
+        if name == ".git" {
+            continue;
+        }
+
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            let tree = write_tree(repo, &path)?;
+            builder.insert(name, tree, FileMode::Tree.into())?;
+        } else if file_type.is_symlink() {
+            let target = std::fs::read_link(&path)?;
+            let blob = repo.blob(target.to_string_lossy().as_bytes())?;
+            builder.insert(name, blob, FileMode::Link.into())?;
+        } else {
+            let blob = repo.blob_path(&path)?;
+            builder.insert(name, blob, file_mode(&entry.metadata()?).into())?;
+        }
+    }
+
+    Ok(builder.write()?)
+}
+
+#[cfg(unix)]
+fn file_mode(metadata: &std::fs::Metadata) -> FileMode {
+    use std::os::unix::fs::PermissionsExt;
+    if metadata.permissions().mode() & 0o111 != 0 {
+        FileMode::BlobExecutable
+    } else {
+        FileMode::Blob
+    }
+}
+
+#[cfg(not(unix))]
+fn file_mode(_metadata: &std::fs::Metadata) -> FileMode {
+    FileMode::Blob
+}
+
+/// Commit the contents of `dir` as the tree of `branch`
+///
+/// `extra_files` are added at the root unless the directory already has a file
+/// of that name. The commit's parent is the remote-tracking branch of `remote_name` if it
+/// exists (so the push fast-forwards), otherwise the local branch. The local
+/// branch is moved to the new commit. Returns `None` when the directory matches
+/// the parent's tree and nothing was committed.
+pub fn commit_directory_to_branch(
+    repo: &Repository,
+    dir: &Path,
+    branch: &str,
+    remote_name: &str,
+    message: &str,
+    extra_files: &[(&str, &[u8])],
+) -> GitResult<Option<String>> {
+    if !dir.is_dir() {
+        return Err(GitError::FileNotFound(dir.display().to_string()));
+    }
+
+    let mut tree_id = write_tree(repo, dir)?;
+    if !extra_files.is_empty() {
+        let mut builder = repo.treebuilder(Some(&repo.find_tree(tree_id)?))?;
+        for (name, content) in extra_files {
+            if builder.get(name)?.is_none() {
+                builder.insert(name, repo.blob(content)?, FileMode::Blob.into())?;
+            }
+        }
+        tree_id = builder.write()?;
+    }
+    let tree = repo.find_tree(tree_id)?;
+
+    let parent = repo
+        .find_reference(&format!("refs/remotes/{}/{}", remote_name, branch))
+        .or_else(|_| repo.find_reference(&format!("refs/heads/{}", branch)))
+        .ok()
+        .and_then(|r| r.peel_to_commit().ok());
+
+    if let Some(parent) = &parent {
+        if parent.tree_id() == tree_id {
+            return Ok(None);
+        }
+    }
+
+    let sig = repo.signature()?;
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    let oid = repo.commit(None, &sig, &sig, message, &tree, &parents)?;
+
+    repo.reference(
+        &format!("refs/heads/{}", branch),
+        oid,
+        true,
+        &format!("publish: {}", message),
+    )?;
+
+    Ok(Some(oid.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_commit_directory_to_branch() {
+        let repo_dir = tempdir().unwrap();
+        let repo = Repository::init(repo_dir.path()).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test").unwrap();
+        config.set_str("user.email", "test@test.com").unwrap();
+
+        let site = tempdir().unwrap();
+        fs::write(site.path().join("index.html"), "<h1>Hi</h1>").unwrap();
+        fs::create_dir(site.path().join("css")).unwrap();
+        fs::write(site.path().join("css").join("site.css"), "body {}").unwrap();
+
+        let extra: &[(&str, &[u8])] = &[(".nojekyll", b"")];
+        let sha = commit_directory_to_branch(&repo, site.path(), "gh-pages", "origin", "Deploy", extra)
+            .unwrap()
+            .unwrap();
+        let branch = repo.find_branch("gh-pages", git2::BranchType::Local).unwrap();
+        let commit = branch.get().peel_to_commit().unwrap();
+        assert_eq!(commit.id().to_string(), sha);
+        let tree = commit.tree().unwrap();
+        assert!(tree.get_path(Path::new("css/site.css")).is_ok());
+        assert!(tree.get_name(".nojekyll").is_some());
+
+        // Unchanged content produces no commit
+        let again = commit_directory_to_branch(&repo, site.path(), "gh-pages", "origin", "Deploy", extra);
+        assert_eq!(again.unwrap(), None);
+    }
+}
@@ -0,0 +1,628 @@
+//! Interactive rebase built on libgit2's rebase
+//!
+//! A rebase is described by a plan: the commits after a base, oldest first,
+//! each with an action (pick, reword, squash, fixup, or drop), in the order
+//! they should be replayed. The plan is carried out with `git2::Rebase`, so the
+//! rebase state lives in `.git/rebase-merge` where `git status` sees it and
+//! `git rebase --abort` can undo it. Each libgit2 operation is one slot of the
+//! plan: rewording uses the message of the commit, squash and fixup meld the
+//! result into the previous commit, drop discards it, and a slot whose planned
+//! commit differs from the operation's (the plan was reordered) has the
+//! planned commit applied instead. When a step has conflicts the rebase stops
+//! until they are resolved and it is continued, or it is aborted.
+
+use std::path::PathBuf;
+
+use git2::{
+    build::CheckoutBuilder, Commit, ErrorCode, Index, Oid, Rebase, Repository, RepositoryState, Signature,
+    StatusOptions,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{GitError, GitResult};
+
+/// File in `.git/rebase-merge` keeping the plan of a rebase started here,
+/// removed along with the rest of the rebase state
+const REBASE_PLAN_FILE: &str = "linuxgit-plan.json";
+
+/// What to do with a commit of the plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RebaseAction {
+    Pick,
+    /// Pick with the step's `message`
+    Reword,
+    /// Meld into the previous commit, joining the messages
+    Squash,
+    /// Meld into the previous commit, keeping its message
+    Fixup,
+    Drop,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RebaseStep {
+    pub action: RebaseAction,
+    pub sha: String,
+    #[serde(default)]
+    pub summary: String,
+    /// New message of a reworded commit, or of the commit a squash results in
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RebasePlan {
+    /// Commit the steps are replayed onto
+    pub onto: String,
+    /// Commit HEAD pointed at when the plan was made
+    pub orig_head: String,
+    /// Branch being rebased, or none for a detached HEAD
+    pub branch: Option<String>,
+    pub steps: Vec<RebaseStep>,
+}
+
+/// Where a rebase stands after starting or continuing it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RebaseProgress {
+    pub plan: RebasePlan,
+    /// Number of steps done
+    pub completed: usize,
+    /// Step waiting for its conflicts to be resolved, if the rebase stopped
+    pub stopped_at: Option<usize>,
+    pub conflicts: Vec<String>,
+    pub done: bool,
+    /// Tip of the rewritten history so far
+    pub head: String,
+}
+
+fn plan_path(repo: &Repository) -> PathBuf {
+    repo.path().join("rebase-merge").join(REBASE_PLAN_FILE)
+}
+
+fn save_plan(repo: &Repository, plan: &RebasePlan) -> GitResult<()> {
+    let data = serde_json::to_string_pretty(plan)
+        .map_err(|e| GitError::Generic(format!("Failed to serialize rebase plan: {}", e)))?;
+    std::fs::write(plan_path(repo), data)?;
+    Ok(())
+}
+
+/// The plan of the rebase in progress
+///
+/// A rebase started elsewhere has no stored plan; it is read as picking each
+/// of its commits.
+fn load_plan(repo: &Repository, rebase: &mut Rebase) -> GitResult<RebasePlan> {
+    let path = plan_path(repo);
+    if path.exists() {
+        let data = std::fs::read_to_string(&path)?;
+        return serde_json::from_str(&data)
+            .map_err(|e| GitError::Generic(format!("Failed to parse rebase plan: {}", e)));
+    }
+
+    let onto = std::fs::read_to_string(repo.path().join("rebase-merge").join("onto"))?;
+    let mut steps = Vec::new();
+    for n in 0..rebase.len() {
+        if let Some(operation) = rebase.nth(n) {
+            let commit = repo.find_commit(operation.id())?;
+            steps.push(RebaseStep {
+                action: RebaseAction::Pick,
+                sha: commit.id().to_string(),
+                summary: commit.summary().unwrap_or("").to_string(),
+                message: None,
+            });
+        }
+    }
+    Ok(RebasePlan {
+        onto: onto.trim().to_string(),
+        orig_head: rebase.orig_head_id().map(|id| id.to_string()).unwrap_or_default(),
+        branch: rebase.orig_head_name().filter(|name| name.starts_with("refs/")).map(str::to_string),
+        steps,
+    })
+}
+
+fn is_rebasing(repo: &Repository) -> bool {
+    matches!(repo.state(), RepositoryState::RebaseMerge | RepositoryState::RebaseInteractive)
+}
+
+/// Whether tracked files have staged or unstaged changes
+fn has_tracked_changes(repo: &Repository) -> GitResult<bool> {
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(false).include_ignored(false);
+    Ok(!repo.statuses(Some(&mut opts))?.is_empty())
+}
+
+fn conflicted_paths(index: &Index) -> GitResult<Vec<String>> {
+    let mut paths = Vec::new();
+    for conflict in index.conflicts()? {
+        let conflict = conflict?;
+        let entry = conflict.our.or(conflict.their).or(conflict.ancestor);
+        if let Some(entry) = entry {
+            paths.push(String::from_utf8_lossy(&entry.path).to_string());
+        }
+    }
+    paths.sort();
+    paths.dedup();
+    Ok(paths)
+}
+
+fn find_commit<'r>(repo: &'r Repository, sha: &str) -> GitResult<Commit<'r>> {
+    repo.revparse_single(sha)
+        .and_then(|obj| obj.peel_to_commit())
+        .map_err(|_| GitError::CommitNotFound(sha.to_string()))
+}
+
+fn head_commit(repo: &Repository) -> GitResult<Commit<'_>> {
+    Ok(repo.head()?.peel_to_commit()?)
+}
+
+/// The default plan for rebasing the commits after `base` onto it: every
+/// commit up to HEAD picked, oldest first
+pub fn get_rebase_plan(repo: &Repository, base: &str) -> GitResult<RebasePlan> {
+    let head = repo.head()?;
+    let head_commit = head.peel_to_commit()?;
+    let base_commit = find_commit(repo, base)?;
+    if !repo.graph_descendant_of(head_commit.id(), base_commit.id())? {
+        return Err(GitError::Generic(format!("{} is not an ancestor of HEAD", base)));
+    }
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(head_commit.id())?;
+    revwalk.hide(base_commit.id())?;
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)?;
+
+    let mut steps = Vec::new();
+    for oid in revwalk {
+        let commit = repo.find_commit(oid?)?;
+        if commit.parent_count() > 1 {
+            return Err(GitError::Generic(format!(
+                "Cannot rebase merge commit {}; choose a base after it",
+                &commit.id().to_string()[..7]
+            )));
+        }
+        steps.push(RebaseStep {
+            action: RebaseAction::Pick,
+            sha: commit.id().to_string(),
+            summary: commit.summary().unwrap_or("").to_string(),
+            message: None,
+        });
+    }
+
+    Ok(RebasePlan {
+        onto: base_commit.id().to_string(),
+        orig_head: head_commit.id().to_string(),
+        branch: head.is_branch().then(|| head.name().map(|n| n.to_string())).flatten(),
+        steps,
+    })
+}
+
+/// Check that a plan edited in the UI still matches the repository
+fn validate_plan(repo: &Repository, plan: &RebasePlan) -> GitResult<()> {
+    let expected = get_rebase_plan(repo, &plan.onto)?;
+    if expected.orig_head != plan.orig_head || expected.branch != plan.branch {
+        return Err(GitError::Generic("HEAD has moved since the rebase plan was made".to_string()));
+    }
+
+    let mut planned: Vec<&str> = plan.steps.iter().map(|s| s.sha.as_str()).collect();
+    let mut commits: Vec<&str> = expected.steps.iter().map(|s| s.sha.as_str()).collect();
+    planned.sort_unstable();
+    commits.sort_unstable();
+    if planned != commits {
+        return Err(GitError::Generic(
+            "The rebase plan must list every commit after the base exactly once".to_string(),
+        ));
+    }
+
+    let mut has_previous = false;
+    for step in &plan.steps {
+        match step.action {
+            RebaseAction::Squash | RebaseAction::Fixup if !has_previous => {
+                return Err(GitError::Generic(format!(
+                    "Commit {} has no earlier commit to be melded into",
+                    &step.sha[..7.min(step.sha.len())]
+                )));
+            }
+            RebaseAction::Reword if step.message.as_deref().is_none_or(|m| m.trim().is_empty()) => {
+                return Err(GitError::Generic(format!(
+                    "Reworded commit {} needs a message",
+                    &step.sha[..7.min(step.sha.len())]
+                )));
+            }
+            RebaseAction::Drop => {}
+            _ => has_previous = true,
+        }
+    }
+    Ok(())
+}
+
+/// Discard the changes libgit2 applied for the current operation
+///
+/// A hard reset would also clean up the rebase state, so HEAD is checked out
+/// over the changes instead.
+fn reset_to_head(repo: &Repository) -> GitResult<()> {
+    let head = head_commit(repo)?;
+    repo.checkout_head(Some(CheckoutBuilder::new().force()))?;
+    let mut index = repo.index()?;
+    index.read_tree(&head.tree()?)?;
+    index.write()?;
+    Ok(())
+}
+
+/// Apply `commit` on top of HEAD in place of the commit of the current
+/// operation, leaving conflict markers where it does not apply cleanly
+fn apply_planned_commit(repo: &Repository, commit: &Commit) -> GitResult<()> {
+    reset_to_head(repo)?;
+    let head = head_commit(repo)?;
+    let mut index = repo.cherrypick_commit(commit, &head, 0, None)?;
+    repo.checkout_index(Some(&mut index), Some(CheckoutBuilder::new().allow_conflicts(true)))?;
+    Ok(())
+}
+
+/// Commit the index as the result of `step`, on top of or melded into HEAD
+fn commit_step(repo: &Repository, rebase: &mut Rebase, step: &RebaseStep, committer: &Signature) -> GitResult<()> {
+    let original = find_commit(repo, &step.sha)?;
+    let original_message = original.message().unwrap_or("");
+
+    match step.action {
+        RebaseAction::Squash | RebaseAction::Fixup => {
+            let current = head_commit(repo)?;
+            let message = match (&step.message, step.action) {
+                (Some(message), _) => message.clone(),
+                (None, RebaseAction::Squash) => format!(
+                    "{}\n\n{}",
+                    current.message().unwrap_or("").trim_end(),
+                    original_message
+                ),
+                (None, _) => current.message().unwrap_or("").to_string(),
+            };
+            let tree = repo.find_tree(repo.index()?.write_tree()?)?;
+            let parents: Vec<Commit> = current.parents().collect();
+            let parent_refs: Vec<&Commit> = parents.iter().collect();
+            let melded = repo.commit(None, &current.author(), committer, &message, &tree, &parent_refs)?;
+            repo.set_head_detached(melded)?;
+        }
+        _ => {
+            // The author and message are passed on since a reordered slot
+            // holds another commit than the operation's own
+            let message = match step.action {
+                RebaseAction::Reword => step.message.as_deref().unwrap_or(original_message),
+                _ => original_message,
+            };
+            match rebase.commit(Some(&original.author()), committer, Some(message)) {
+                // Like git, leave out commits whose changes are already there
+                Err(e) if e.code() == ErrorCode::Applied => {}
+                result => {
+                    result?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn progress(repo: &Repository, plan: RebasePlan, completed: usize, conflicts: Vec<String>, done: bool) -> GitResult<RebaseProgress> {
+    Ok(RebaseProgress {
+        plan,
+        completed,
+        stopped_at: (!done).then_some(completed),
+        conflicts,
+        done,
+        head: head_commit(repo)?.id().to_string(),
+    })
+}
+
+/// Carry out the remaining operations, stopping at the first one with conflicts
+fn run_steps(repo: &Repository, mut rebase: Rebase, plan: RebasePlan) -> GitResult<RebaseProgress> {
+    let committer = repo.signature()?;
+    while let Some(operation) = rebase.next() {
+        let operation_id = operation?.id();
+        let current = rebase.operation_current().unwrap_or(0);
+        let step = plan.steps.get(current).cloned().ok_or_else(|| {
+            GitError::Generic("The rebase has more commits than its plan".to_string())
+        })?;
+
+        if step.action == RebaseAction::Drop {
+            reset_to_head(repo)?;
+            continue;
+        }
+        let commit = find_commit(repo, &step.sha)?;
+        if commit.id() != operation_id {
+            apply_planned_commit(repo, &commit)?;
+        }
+
+        let conflicts = conflicted_paths(&repo.index()?)?;
+        if !conflicts.is_empty() {
+            return progress(repo, plan, current, conflicts, false);
+        }
+        commit_step(repo, &mut rebase, &step, &committer)?;
+    }
+
+    let completed = plan.steps.len();
+    rebase.finish(Some(&committer))?;
+    progress(repo, plan, completed, Vec::new(), true)
+}
+
+/// Start a rebase with an edited plan
+///
+/// Tracked files must have no changes, since every step is checked out in
+/// the working directory.
+pub fn set_rebase_plan(repo: &Repository, plan: RebasePlan) -> GitResult<RebaseProgress> {
+    if repo.state() != RepositoryState::Clean {
+        return Err(GitError::Generic(format!(
+            "Cannot rebase while another operation is in progress ({:?})",
+            repo.state()
+        )));
+    }
+    if has_tracked_changes(repo)? {
+        return Err(GitError::Generic(
+            "Commit or stash your changes before rebasing".to_string(),
+        ));
+    }
+    validate_plan(repo, &plan)?;
+
+    let head = repo.head()?;
+    // Rebasing from a reference moves the branch when the rebase finishes
+    let branch = match head.is_branch() {
+        true => repo.reference_to_annotated_commit(&head)?,
+        false => repo.find_annotated_commit(head_commit(repo)?.id())?,
+    };
+    let upstream = repo.find_annotated_commit(Oid::from_str(&plan.onto)?)?;
+    let rebase = repo.rebase(Some(&branch), Some(&upstream), None, None)?;
+    save_plan(repo, &plan)?;
+    run_steps(repo, rebase, plan)
+}
+
+/// Where the rebase in progress stands, if there is one
+pub fn get_rebase_progress(repo: &Repository) -> GitResult<Option<RebaseProgress>> {
+    if !is_rebasing(repo) {
+        return Ok(None);
+    }
+    let mut rebase = repo.open_rebase(None)?;
+    let plan = load_plan(repo, &mut rebase)?;
+    let completed = rebase.operation_current().unwrap_or(0);
+    let conflicts = conflicted_paths(&repo.index()?)?;
+    Ok(Some(progress(repo, plan, completed, conflicts, false)?))
+}
+
+/// Commit the resolved step the rebase stopped at and carry out the rest
+pub fn rebase_continue(repo: &Repository) -> GitResult<RebaseProgress> {
+    if !is_rebasing(repo) {
+        return Err(GitError::Generic("No rebase in progress".to_string()));
+    }
+    let mut rebase = repo.open_rebase(None)?;
+    let plan = load_plan(repo, &mut rebase)?;
+
+    if let Some(current) = rebase.operation_current() {
+        let conflicts = conflicted_paths(&repo.index()?)?;
+        if !conflicts.is_empty() {
+            return Err(GitError::Generic(format!(
+                "Resolve and stage the conflicts in {} before continuing",
+                conflicts.join(", ")
+            )));
+        }
+        if let Some(step) = plan.steps.get(current).filter(|s| s.action != RebaseAction::Drop) {
+            commit_step(repo, &mut rebase, step, &repo.signature()?)?;
+        }
+    }
+
+    run_steps(repo, rebase, plan)
+}
+
+/// Stop the rebase in progress and return to where it started
+pub fn rebase_abort(repo: &Repository) -> GitResult<()> {
+    if !is_rebasing(repo) {
+        return Err(GitError::Generic("No rebase in progress".to_string()));
+    }
+    repo.open_rebase(None)?.abort()?;
+    Ok(())
+}
+
+/// Squash a commit into its parent, keeping both messages
+pub fn squash_commits(repo: &Repository, sha: &str) -> GitResult<RebaseProgress> {
+    let commit = find_commit(repo, sha)?;
+    let parent = commit
+        .parent(0)
+        .map_err(|_| GitError::Generic("Cannot squash the first commit".to_string()))?;
+    let base = parent
+        .parent(0)
+        .map_err(|_| GitError::Generic("Cannot squash into the first commit".to_string()))?;
+
+    let mut plan = get_rebase_plan(repo, &base.id().to_string())?;
+    let target = commit.id().to_string();
+    for step in &mut plan.steps {
+        if step.sha == target {
+            step.action = RebaseAction::Squash;
+        }
+    }
+    set_rebase_plan(repo, plan)
+}
+
+/// Rebase the commits after the parent of `sha` with `action` for it and
+/// every other commit picked
+pub(crate) fn rebase_single_commit(repo: &Repository, sha: &str, action: RebaseAction, message: Option<String>) -> GitResult<RebaseProgress> {
+    let commit = find_commit(repo, sha)?;
+    let parent = commit
+        .parent(0)
+        .map_err(|_| GitError::Generic("Cannot rebase the first commit".to_string()))?;
+
+    let mut plan = get_rebase_plan(repo, &parent.id().to_string())?;
+    let target = commit.id().to_string();
+    for step in &mut plan.steps {
+        if step.sha == target {
+            step.action = action;
+            step.message = message.clone();
+        }
+    }
+    set_rebase_plan(repo, plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::commit_file;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn setup() -> (tempfile::TempDir, Repository) {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test").unwrap();
+        config.set_str("user.email", "test@test.com").unwrap();
+        (dir, repo)
+    }
+
+    fn history(repo: &Repository) -> Vec<String> {
+        let mut revwalk = repo.revwalk().unwrap();
+        revwalk.push_head().unwrap();
+        revwalk
+            .map(|oid| repo.find_commit(oid.unwrap()).unwrap().message().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_rebase_plan_reorder_reword_drop_and_squash() {
+        let (dir, repo) = setup();
+        let base = commit_file(&repo, "base.txt", "base", "base");
+        commit_file(&repo, "a.txt", "a", "add a");
+        commit_file(&repo, "b.txt", "b", "add b");
+        commit_file(&repo, "c.txt", "c", "add c");
+        commit_file(&repo, "a.txt", "a2", "fix a");
+
+        let mut plan = get_rebase_plan(&repo, &base.to_string()).unwrap();
+        let summaries: Vec<&str> = plan.steps.iter().map(|s| s.summary.as_str()).collect();
+        assert_eq!(summaries, ["add a", "add b", "add c", "fix a"]);
+        assert!(plan.branch.is_some());
+
+        // add c (reworded), add a + fix a (fixup), add b dropped
+        let steps = plan.steps.clone();
+        plan.steps = vec![
+            RebaseStep { action: RebaseAction::Reword, message: Some("add c!".to_string()), ..steps[2].clone() },
+            steps[0].clone(),
+            RebaseStep { action: RebaseAction::Fixup, ..steps[3].clone() },
+            RebaseStep { action: RebaseAction::Drop, ..steps[1].clone() },
+        ];
+        let progress = set_rebase_plan(&repo, plan).unwrap();
+        assert!(progress.done);
+
+        assert_eq!(history(&repo), ["add a", "add c!", "base"]);
+        assert_eq!(fs::read_to_string(dir.path().join("a.txt")).unwrap(), "a2");
+        assert!(!dir.path().join("b.txt").exists());
+        assert!(get_rebase_progress(&repo).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_rebase_stops_on_conflict_and_continues() {
+        let (dir, repo) = setup();
+        let base = commit_file(&repo, "f.txt", "one\n", "base");
+        commit_file(&repo, "f.txt", "two\n", "two");
+        commit_file(&repo, "f.txt", "three\n", "three");
+
+        // Swapping the edits of the same line conflicts
+        let mut plan = get_rebase_plan(&repo, &base.to_string()).unwrap();
+        plan.steps.reverse();
+        let progress = set_rebase_plan(&repo, plan).unwrap();
+        assert!(!progress.done);
+        assert_eq!(progress.stopped_at, Some(0));
+        assert_eq!(progress.conflicts, ["f.txt"]);
+        assert!(rebase_continue(&repo).is_err());
+
+        fs::write(dir.path().join("f.txt"), "three\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("f.txt")).unwrap();
+        index.write().unwrap();
+        let progress = rebase_continue(&repo).unwrap();
+
+        // The second step conflicts as well
+        assert_eq!(progress.stopped_at, Some(1));
+        fs::write(dir.path().join("f.txt"), "two\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("f.txt")).unwrap();
+        index.write().unwrap();
+        assert!(rebase_continue(&repo).unwrap().done);
+
+        assert_eq!(history(&repo), ["two", "three", "base"]);
+        assert!(repo.head().unwrap().is_branch());
+        assert_eq!(repo.state(), RepositoryState::Clean);
+    }
+
+    #[test]
+    fn test_rebase_abort_restores_branch() {
+        let (dir, repo) = setup();
+        let base = commit_file(&repo, "f.txt", "one\n", "base");
+        commit_file(&repo, "f.txt", "two\n", "two");
+        let head = commit_file(&repo, "f.txt", "three\n", "three");
+
+        let mut plan = get_rebase_plan(&repo, &base.to_string()).unwrap();
+        plan.steps.reverse();
+        assert!(!set_rebase_plan(&repo, plan).unwrap().done);
+        assert!(get_rebase_progress(&repo).unwrap().is_some());
+
+        rebase_abort(&repo).unwrap();
+        assert_eq!(repo.head().unwrap().target(), Some(head));
+        assert!(repo.head().unwrap().is_branch());
+        assert_eq!(fs::read_to_string(dir.path().join("f.txt")).unwrap(), "three\n");
+        assert!(get_rebase_progress(&repo).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_stopped_rebase_is_visible_to_git() {
+        let (dir, repo) = setup();
+        let base = commit_file(&repo, "f.txt", "one\n", "base");
+        commit_file(&repo, "f.txt", "two\n", "two");
+        let head = commit_file(&repo, "f.txt", "three\n", "three");
+
+        let mut plan = get_rebase_plan(&repo, &base.to_string()).unwrap();
+        plan.steps.reverse();
+        assert!(!set_rebase_plan(&repo, plan).unwrap().done);
+        assert_eq!(repo.state(), RepositoryState::RebaseMerge);
+
+        let output = std::process::Command::new("git")
+            .args(["rebase", "--abort"])
+            .current_dir(dir.path())
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+        assert_eq!(repo.head().unwrap().target(), Some(head));
+        assert!(get_rebase_progress(&repo).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_reword_and_drop_single_commit() {
+        let (dir, repo) = setup();
+        commit_file(&repo, "a.txt", "a", "base");
+        let middle = commit_file(&repo, "b.txt", "b", "add b");
+        commit_file(&repo, "c.txt", "c", "add c");
+
+        let message = "add b\n\nIt's a \\ 'quoted' message";
+        assert!(rebase_single_commit(&repo, &middle.to_string(), RebaseAction::Reword, Some(message.to_string()))
+            .unwrap()
+            .done);
+        assert_eq!(history(&repo), ["add c", message, "base"]);
+
+        let reworded = repo.revparse_single("HEAD~1").unwrap().id().to_string();
+        assert!(rebase_single_commit(&repo, &reworded, RebaseAction::Drop, None).unwrap().done);
+        assert_eq!(history(&repo), ["add c", "base"]);
+        assert!(!dir.path().join("b.txt").exists());
+        assert!(repo.head().unwrap().is_branch());
+    }
+
+    #[test]
+    fn test_squash_commits_and_plan_validation() {
+        let (_dir, repo) = setup();
+        let base = commit_file(&repo, "a.txt", "a", "base");
+        commit_file(&repo, "b.txt", "b", "add b");
+        let second = commit_file(&repo, "c.txt", "c", "add c");
+        commit_file(&repo, "d.txt", "d", "add d");
+
+        let mut plan = get_rebase_plan(&repo, &base.to_string()).unwrap();
+        plan.steps[0].action = RebaseAction::Squash;
+        assert!(set_rebase_plan(&repo, plan.clone()).is_err());
+        plan.steps[0].action = RebaseAction::Reword;
+        assert!(set_rebase_plan(&repo, plan.clone()).is_err());
+        plan.steps[0].action = RebaseAction::Pick;
+        plan.steps.pop();
+        assert!(set_rebase_plan(&repo, plan).is_err());
+
+        assert!(squash_commits(&repo, &second.to_string()).unwrap().done);
+        assert_eq!(history(&repo), ["add d", "add b\n\nadd c", "base"]);
+    }
+}
@@ -1,7 +1,10 @@
-use git2::{Repository, FetchOptions, PushOptions, RemoteCallbacks, Cred, CredentialType};
+use std::cell::RefCell;
+
+use git2::{Repository, Remote, FetchOptions, PushOptions, RemoteCallbacks, Cred, CredentialType};
 use serde::{Deserialize, Serialize};
 
-use super::{GitError, GitResult};
+use super::credentials::HelperCredentials;
+use super::{is_github_url, AutoStashOutcome, GitError, GitResult};
 use crate::github;
 
 /// Get GitHub token if available
@@ -27,6 +30,9 @@ pub struct PullResult {
     pub fast_forward: bool,
     pub conflicts: bool,
     pub updated_files: u32,
+    /// Local changes stashed around the pull, if auto-stash was requested
+    #[serde(default)]
+    pub auto_stash: Option<AutoStashOutcome>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -69,20 +75,68 @@ pub fn remove_remote(repo: &Repository, name: &str) -> GitResult<()> {
     Ok(())
 }
 
+/// Parse a GitHub remote URL (HTTPS or SSH) into `(owner, repo)`
+pub fn parse_github_url(url: &str) -> Option<(String, String)> {
+    let path = url
+        .strip_prefix("git@github.com:")
+        .or_else(|| url.strip_prefix("ssh://git@github.com/"))
+        .or_else(|| url.strip_prefix("https://github.com/"))
+        .or_else(|| url.strip_prefix("http://github.com/"))
+        .or_else(|| {
+            // https://user@github.com/owner/repo.git
+            url.strip_prefix("https://")
+                .and_then(|rest| rest.split_once("@github.com/"))
+                .map(|(_, path)| path)
+        })?;
+
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let mut parts = path.splitn(2, '/');
+    let owner = parts.next().filter(|s| !s.is_empty())?;
+    let repo = parts.next().filter(|s| !s.is_empty() && !s.contains('/'))?;
+
+    Some((owner.to_string(), repo.to_string()))
+}
+
+/// Get the GitHub `(owner, repo)` of a remote, if it points at github.com
+pub fn get_github_remote(repo: &Repository, remote_name: &str) -> GitResult<(String, String)> {
+    let remote = repo.find_remote(remote_name)
+        .map_err(|_| GitError::OperationFailed(format!("Remote '{}' not found", remote_name)))?;
+    let url = remote.url().unwrap_or("");
+
+    parse_github_url(url).ok_or_else(|| {
+        GitError::OperationFailed(format!("Remote '{}' is not a GitHub repository", remote_name))
+    })
+}
+
 /// Create callbacks for authentication
-fn create_callbacks<'a>() -> RemoteCallbacks<'a> {
+///
+/// Credentials from the git credential helpers are looked up through `helper`,
+/// which should be approved once the operation has succeeded.
+pub(crate) fn create_callbacks<'a>(helper: &HelperCredentials) -> RemoteCallbacks<'a> {
     let mut callbacks = RemoteCallbacks::new();
+    let helper = helper.clone();
+    let mut helper_error = None;
 
-    callbacks.credentials(|url, username_from_url, allowed_types| {
+    callbacks.credentials(move |url, username_from_url, allowed_types| {
         // For HTTPS URLs, try GitHub token first
         if allowed_types.contains(CredentialType::USER_PASS_PLAINTEXT) {
             // Check if this is a GitHub URL
-            if url.contains("github.com") {
-                if let Some(token) = get_github_token() {
+            if is_github_url(url) {
+                // Repositories configured for a GitHub App use its installation token
+                let app_token = parse_github_url(url)
+                    .and_then(|(owner, repo)| github::app_auth::installation_token_blocking(&owner, &repo));
+                if let Some(token) = app_token.or_else(get_github_token) {
                     // Use token as password with "x-access-token" as username
                     return Cred::userpass_plaintext("x-access-token", &token);
                 }
             }
+
+            // Other hosts, and GitHub without a token, use the git credential helper
+            match helper.get(url, username_from_url) {
+                Ok(cred) => return Ok(cred),
+                Err(e) => helper_error = Some(e),
+            }
         }
 
         // Try SSH agent first
@@ -118,17 +172,37 @@ fn create_callbacks<'a>() -> RemoteCallbacks<'a> {
             return Cred::default();
         }
 
-        Err(git2::Error::from_str("No valid credentials found"))
+        Err(helper_error
+            .take()
+            .unwrap_or_else(|| git2::Error::from_str("No valid credentials found")))
     });
 
-    callbacks.push_update_reference(|_refname, status| {
+    callbacks
+}
+
+/// Push `refspecs` to a remote, failing if the remote rejects any of the refs
+pub(crate) fn push_refspecs(repo: &Repository, remote: &mut Remote, refspecs: &[String]) -> GitResult<()> {
+    let rejected = RefCell::new(Vec::new());
+    let helper = HelperCredentials::for_repo(repo);
+    let mut callbacks = create_callbacks(&helper);
+    callbacks.push_update_reference(|refname, status| {
         if let Some(msg) = status {
-            eprintln!("Push rejected: {}", msg);
+            rejected.borrow_mut().push(format!("{} ({})", refname, msg));
         }
         Ok(())
     });
 
-    callbacks
+    let mut push_options = PushOptions::new();
+    push_options.remote_callbacks(callbacks);
+    remote.push(refspecs, Some(&mut push_options))?;
+    drop(push_options);
+    helper.approve();
+
+    let rejected = rejected.into_inner();
+    if !rejected.is_empty() {
+        return Err(GitError::OperationFailed(format!("Push rejected: {}", rejected.join(", "))));
+    }
+    Ok(())
 }
 
 /// Fetch from a remote
@@ -136,9 +210,9 @@ pub fn fetch(repo: &Repository, remote_name: &str) -> GitResult<FetchResult> {
     let mut remote = repo.find_remote(remote_name)
         .map_err(|_| GitError::OperationFailed(format!("Remote '{}' not found", remote_name)))?;
 
-    let callbacks = create_callbacks();
+    let helper = HelperCredentials::for_repo(repo);
     let mut fetch_options = FetchOptions::new();
-    fetch_options.remote_callbacks(callbacks);
+    fetch_options.remote_callbacks(create_callbacks(&helper));
 
     // Fetch all branches
     let refspecs: Vec<String> = remote.fetch_refspecs()?
@@ -154,6 +228,7 @@ pub fn fetch(repo: &Repository, remote_name: &str) -> GitResult<FetchResult> {
     };
 
     remote.fetch(&refspec_strs, Some(&mut fetch_options), None)?;
+    helper.approve();
 
     Ok(FetchResult {
         remote: remote_name.to_string(),
@@ -193,6 +268,7 @@ pub fn pull(repo: &Repository, remote_name: &str, branch_name: &str) -> GitResul
             fast_forward: false,
             conflicts: false,
             updated_files: 0,
+            auto_stash: None,
         });
     }
 
@@ -208,6 +284,7 @@ pub fn pull(repo: &Repository, remote_name: &str, branch_name: &str) -> GitResul
             fast_forward: true,
             conflicts: false,
             updated_files: 1, // Simplified - actual count would require diff
+            auto_stash: None,
         });
     }
 
@@ -222,6 +299,7 @@ pub fn pull(repo: &Repository, remote_name: &str, branch_name: &str) -> GitResul
                 fast_forward: false,
                 conflicts: true,
                 updated_files: 0,
+                auto_stash: None,
             });
         }
 
@@ -248,6 +326,7 @@ pub fn pull(repo: &Repository, remote_name: &str, branch_name: &str) -> GitResul
             fast_forward: false,
             conflicts: false,
             updated_files: 1,
+            auto_stash: None,
         });
     }
 
@@ -256,16 +335,26 @@ pub fn pull(repo: &Repository, remote_name: &str, branch_name: &str) -> GitResul
 
 /// Push to remote
 pub fn push(repo: &Repository, remote_name: &str, branch_name: &str) -> GitResult<PushResult> {
+    push_branch(repo, remote_name, branch_name, false)
+}
+
+/// Force push to remote, overwriting the remote branch even if it has diverged
+pub fn force_push(repo: &Repository, remote_name: &str, branch_name: &str) -> GitResult<PushResult> {
+    push_branch(repo, remote_name, branch_name, true)
+}
+
+fn push_branch(repo: &Repository, remote_name: &str, branch_name: &str, force: bool) -> GitResult<PushResult> {
     let mut remote = repo.find_remote(remote_name)
         .map_err(|_| GitError::OperationFailed(format!("Remote '{}' not found", remote_name)))?;
 
-    let callbacks = create_callbacks();
-    let mut push_options = PushOptions::new();
-    push_options.remote_callbacks(callbacks);
-
-    let refspec = format!("refs/heads/{}:refs/heads/{}", branch_name, branch_name);
+    let refspec = format!(
+        "{}refs/heads/{}:refs/heads/{}",
+        if force { "+" } else { "" },
+        branch_name,
+        branch_name
+    );
 
-    remote.push(&[&refspec], Some(&mut push_options))?;
+    push_refspecs(repo, &mut remote, &[refspec])?;
 
     Ok(PushResult {
         remote: remote_name.to_string(),
@@ -273,6 +362,32 @@ pub fn push(repo: &Repository, remote_name: &str, branch_name: &str) -> GitResul
     })
 }
 
+/// Push a branch and set the pushed remote branch as its upstream
+pub fn push_set_upstream(repo: &Repository, remote_name: &str, branch_name: &str) -> GitResult<PushResult> {
+    let result = push(repo, remote_name, branch_name)?;
+
+    let mut branch = repo.find_branch(branch_name, git2::BranchType::Local)
+        .map_err(|_| GitError::BranchNotFound(branch_name.to_string()))?;
+    branch.set_upstream(Some(&format!("{}/{}", remote_name, branch_name)))?;
+
+    Ok(result)
+}
+
+/// Push a tag to a remote
+pub fn push_tag(repo: &Repository, remote_name: &str, tag_name: &str) -> GitResult<PushResult> {
+    let mut remote = repo.find_remote(remote_name)
+        .map_err(|_| GitError::OperationFailed(format!("Remote '{}' not found", remote_name)))?;
+
+    let refspec = format!("refs/tags/{}:refs/tags/{}", tag_name, tag_name);
+
+    push_refspecs(repo, &mut remote, &[refspec])?;
+
+    Ok(PushResult {
+        remote: remote_name.to_string(),
+        pushed_refs: vec![tag_name.to_string()],
+    })
+}
+
 /// Get the default remote for a branch (usually "origin")
 pub fn get_default_remote(repo: &Repository) -> GitResult<String> {
     // Try to get the upstream remote for the current branch
@@ -304,3 +419,18 @@ pub fn get_default_remote(repo: &Repository) -> GitResult<String> {
         .map(|s| s.to_string())
         .ok_or_else(|| GitError::OperationFailed("No remotes configured".to_string()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_github_url() {
+        let expected = Some(("octocat".to_string(), "hello-world".to_string()));
+        assert_eq!(parse_github_url("https://github.com/octocat/hello-world.git"), expected);
+        assert_eq!(parse_github_url("https://github.com/octocat/hello-world"), expected);
+        assert_eq!(parse_github_url("git@github.com:octocat/hello-world.git"), expected);
+        assert_eq!(parse_github_url("ssh://git@github.com/octocat/hello-world.git"), expected);
+        assert_eq!(parse_github_url("https://gitlab.com/octocat/hello-world.git"), None);
+    }
+}
@@ -0,0 +1,340 @@
+//! Scanning directories for Git repositories
+//!
+//! Directories are walked in parallel by a small pool of threads, each with its
+//! own queue of directories; idle threads steal work from the others. Hidden
+//! directories and well-known dependency or build directories are skipped, and
+//! the scan can be cancelled, keeping the repositories found so far.
+//!
+//! The sync status of the scanned repositories can then be computed in one
+//! batch, also in parallel.
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use git2::Repository;
+use serde::{Deserialize, Serialize};
+
+use super::{get_sync_status, has_local_changes, GitError, GitResult, RepoInfo, SyncStatus};
+
+/// Name of the event emitted while scanning for repositories
+pub const REPO_SCAN_PROGRESS_EVENT: &str = "repo-scan-progress";
+
+/// Directories that never contain repositories worth listing but can be huge
+pub const SKIPPED_SCAN_DIRS: &[&str] = &[
+    "node_modules",
+    "target",
+    "vendor",
+    "__pycache__",
+    "venv",
+    "site-packages",
+    "bower_components",
+    "Library",
+    "snap",
+];
+
+/// Number of directories scanned between two progress reports
+const PROGRESS_INTERVAL: usize = 200;
+
+/// Maximum number of threads walking directories
+const MAX_SCAN_THREADS: usize = 8;
+
+/// Progress of a repository scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoScanProgress {
+    pub scanned_dirs: usize,
+    pub found: usize,
+    /// Directory being scanned when the progress was reported
+    pub current: String,
+}
+
+/// Sync state of one repository in a batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoSyncSummary {
+    pub path: String,
+    /// Ahead/behind counts of the checked-out branch, if they could be computed
+    pub sync: Option<SyncStatus>,
+    /// Whether the working directory or index has changes, including untracked files
+    pub dirty: bool,
+    /// Why the repository could not be read
+    pub error: Option<String>,
+}
+
+/// Whether a directory is skipped when scanning
+fn is_skipped_dir(name: &str) -> bool {
+    name.starts_with('.') || SKIPPED_SCAN_DIRS.contains(&name)
+}
+
+fn repo_info(path: &Path, repo: &Repository) -> RepoInfo {
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("unknown")
+        .to_string();
+
+    let head = repo.head().ok();
+    let head_branch = head.as_ref().and_then(|h| h.shorthand()).map(String::from);
+    let head_sha = head.as_ref().and_then(|h| h.target()).map(|oid| oid.to_string());
+
+    RepoInfo {
+        path: path.to_string_lossy().to_string(),
+        name,
+        is_bare: repo.is_bare(),
+        head_branch,
+        head_sha,
+        is_detached: repo.head_detached().unwrap_or(false),
+    }
+}
+
+struct Walker<'a> {
+    /// Directories left to scan, with their depth, one queue per thread
+    queues: Vec<Mutex<VecDeque<(PathBuf, usize)>>>,
+    /// Directories queued or being scanned; the walk is over when it drops to zero
+    pending: AtomicUsize,
+    scanned: AtomicUsize,
+    repos: Mutex<Vec<RepoInfo>>,
+    max_depth: usize,
+    cancel: &'a AtomicBool,
+    on_progress: &'a (dyn Fn(RepoScanProgress) + Sync),
+}
+
+impl Walker<'_> {
+    /// Take a directory from the thread's own queue, or steal one from another
+    fn next(&self, worker: usize) -> Option<(PathBuf, usize)> {
+        if let Some(task) = self.queues[worker].lock().unwrap().pop_back() {
+            return Some(task);
+        }
+        (1..self.queues.len())
+            .map(|offset| (worker + offset) % self.queues.len())
+            .find_map(|other| self.queues[other].lock().unwrap().pop_front())
+    }
+
+    fn push(&self, worker: usize, path: PathBuf, depth: usize) {
+        self.pending.fetch_add(1, Ordering::SeqCst);
+        self.queues[worker].lock().unwrap().push_back((path, depth));
+    }
+
+    fn report(&self, current: &Path) {
+        let found = self.repos.lock().unwrap().len();
+        (self.on_progress)(RepoScanProgress {
+            scanned_dirs: self.scanned.load(Ordering::Relaxed),
+            found,
+            current: current.to_string_lossy().to_string(),
+        });
+    }
+
+    fn scan(&self, worker: usize, path: &Path, depth: usize) {
+        let scanned = self.scanned.fetch_add(1, Ordering::Relaxed) + 1;
+
+        if path.join(".git").exists() {
+            if let Ok(repo) = Repository::open(path) {
+                self.repos.lock().unwrap().push(repo_info(path, &repo));
+                self.report(path);
+            }
+            // Don't recurse into git repositories
+            return;
+        }
+        if scanned % PROGRESS_INTERVAL == 0 {
+            self.report(path);
+        }
+        if depth >= self.max_depth {
+            return;
+        }
+
+        let Ok(entries) = std::fs::read_dir(path) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            // Symlinks are not followed, so links back up the tree can't loop
+            if !entry.file_type().is_ok_and(|t| t.is_dir()) {
+                continue;
+            }
+            let name = entry.file_name();
+            if !is_skipped_dir(&name.to_string_lossy()) {
+                self.push(worker, entry.path(), depth + 1);
+            }
+        }
+    }
+
+    fn run(&self, worker: usize) {
+        while !self.cancel.load(Ordering::Relaxed) {
+            match self.next(worker) {
+                Some((path, depth)) => {
+                    self.scan(worker, &path, depth);
+                    self.pending.fetch_sub(1, Ordering::SeqCst);
+                }
+                None if self.pending.load(Ordering::SeqCst) == 0 => break,
+                // Others are still scanning and may queue more directories
+                None => std::thread::sleep(Duration::from_millis(1)),
+            }
+        }
+    }
+}
+
+/// Scan a directory for Git repositories up to `max_depth` levels deep
+///
+/// `on_progress` is called from the scanning threads whenever a repository is
+/// found and every few hundred directories. Once `cancel` is set the scan stops
+/// and returns the repositories found so far.
+pub fn scan_for_repositories_with(
+    path: &str,
+    max_depth: usize,
+    cancel: &AtomicBool,
+    on_progress: &(dyn Fn(RepoScanProgress) + Sync),
+) -> GitResult<Vec<RepoInfo>> {
+    let root_path = Path::new(path);
+    if !root_path.is_dir() {
+        return Err(GitError::OperationFailed(format!(
+            "Path '{}' is not a valid directory",
+            path
+        )));
+    }
+
+    let threads = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4)
+        .min(MAX_SCAN_THREADS);
+    let walker = Walker {
+        queues: (0..threads).map(|_| Mutex::new(VecDeque::new())).collect(),
+        pending: AtomicUsize::new(0),
+        scanned: AtomicUsize::new(0),
+        repos: Mutex::new(Vec::new()),
+        max_depth,
+        cancel,
+        on_progress,
+    };
+    walker.push(0, root_path.to_path_buf(), 0);
+
+    std::thread::scope(|scope| {
+        for worker in 0..threads {
+            let walker = &walker;
+            scope.spawn(move || walker.run(worker));
+        }
+    });
+
+    let mut repos = walker.repos.into_inner().unwrap();
+    repos.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(repos)
+}
+
+/// Scan a directory for Git repositories
+pub fn scan_for_repositories(path: &str, max_depth: usize) -> GitResult<Vec<RepoInfo>> {
+    scan_for_repositories_with(path, max_depth, &AtomicBool::new(false), &|_| {})
+}
+
+fn repo_sync_summary(path: &str) -> RepoSyncSummary {
+    let mut summary = RepoSyncSummary {
+        path: path.to_string(),
+        sync: None,
+        dirty: false,
+        error: None,
+    };
+    let repo = match Repository::open(path) {
+        Ok(repo) => repo,
+        Err(e) => {
+            summary.error = Some(e.message().to_string());
+            return summary;
+        }
+    };
+
+    // An unborn HEAD has nothing to sync yet, which is not an error
+    summary.sync = get_sync_status(&repo).ok();
+    match has_local_changes(&repo) {
+        Ok(dirty) => summary.dirty = dirty,
+        Err(e) => summary.error = Some(e.to_string()),
+    }
+    summary
+}
+
+/// Apply `f` to each item on a small pool of threads, keeping the order of `items`
+pub(crate) fn parallel_map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let threads = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4)
+        .min(MAX_SCAN_THREADS)
+        .min(items.len());
+    let next = AtomicUsize::new(0);
+    let results: Vec<Mutex<Option<R>>> = items.iter().map(|_| Mutex::new(None)).collect();
+
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    break;
+                };
+                *results[index].lock().unwrap() = Some(f(item));
+            });
+        }
+    });
+
+    results
+        .into_iter()
+        .zip(items)
+        .map(|(result, item)| result.into_inner().unwrap().unwrap_or_else(|| f(item)))
+        .collect()
+}
+
+/// Get the sync status and dirty state of many repositories at once, in the
+/// order of `paths`
+pub fn get_all_repo_sync_statuses(paths: &[String]) -> Vec<RepoSyncSummary> {
+    parallel_map(paths, |path| repo_sync_summary(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_scan_for_repositories() {
+        let dir = tempdir().unwrap();
+        for path in ["a", "nested/b", "nested/deeper/still/c", "node_modules/dep", ".hidden/d"] {
+            let repo_dir = dir.path().join(path);
+            fs::create_dir_all(&repo_dir).unwrap();
+            Repository::init(&repo_dir).unwrap();
+        }
+        // Repositories inside repositories are not listed
+        Repository::init(dir.path().join("a/inner")).unwrap();
+
+        let root = dir.path().to_str().unwrap();
+        let names = |repos: Vec<RepoInfo>| repos.into_iter().map(|r| r.name).collect::<Vec<_>>();
+        assert_eq!(names(scan_for_repositories(root, 3).unwrap()), vec!["a", "b"]);
+        assert_eq!(names(scan_for_repositories(root, 4).unwrap()), vec!["a", "b", "c"]);
+
+        let cancelled = AtomicBool::new(true);
+        assert!(scan_for_repositories_with(root, 4, &cancelled, &|_| {}).unwrap().is_empty());
+
+        let reported = AtomicUsize::new(0);
+        scan_for_repositories_with(root, 4, &AtomicBool::new(false), &|progress| {
+            reported.fetch_max(progress.found, Ordering::SeqCst);
+        })
+        .unwrap();
+        assert_eq!(reported.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_get_all_repo_sync_statuses() {
+        let dir = tempdir().unwrap();
+        let clean = dir.path().join("clean");
+        let dirty = dir.path().join("dirty");
+        Repository::init(&clean).unwrap();
+        Repository::init(&dirty).unwrap();
+        fs::write(dirty.join("new.txt"), "hello").unwrap();
+
+        let paths: Vec<String> = [&clean, &dirty, &dir.path().join("missing")]
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect();
+        let summaries = get_all_repo_sync_statuses(&paths);
+
+        assert_eq!(summaries.len(), 3);
+        assert_eq!(summaries[0].path, paths[0]);
+        assert!(!summaries[0].dirty && summaries[0].error.is_none());
+        assert!(summaries[1].dirty);
+        assert!(summaries[2].error.is_some());
+    }
+}
@@ -0,0 +1,292 @@
+//! Full-text commit search index
+//!
+//! Commit messages, authors, and changed paths are stored in a local SQLite
+//! FTS5 table, so searching a large history is instant instead of walking every
+//! commit. The index is optional: it only exists once it has been built, and
+//! later builds only add the commits made since. Indexes are kept per
+//! repository in the app cache directory.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use git2::{Oid, Repository};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+
+use super::{get_commit_detail, CommitInfo, GitError, GitResult};
+
+/// Name of the event emitted while the index is being built
+pub const SEARCH_INDEX_PROGRESS_EVENT: &str = "search-index-progress";
+
+/// Number of commits written per transaction while building the index
+const INDEX_BATCH_SIZE: usize = 500;
+
+/// Changed paths stored per commit; huge commits are still found by the first ones
+const MAX_INDEXED_PATHS: usize = 200;
+
+/// Progress of an index build
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchIndexProgress {
+    /// Commits added so far by this build
+    pub added: u64,
+    pub done: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchIndexStatus {
+    /// Whether an index has been built for the repository
+    pub exists: bool,
+    pub indexed_commits: u64,
+    /// Unix timestamp of the last completed build
+    pub last_updated: Option<i64>,
+    /// Whether a build is in progress
+    pub building: bool,
+}
+
+/// Location of the index of a repository, named after a hash of its path
+pub fn search_index_path(repo_path: &str) -> GitResult<PathBuf> {
+    let canonical = std::fs::canonicalize(repo_path).unwrap_or_else(|_| PathBuf::from(repo_path));
+    let id = Oid::hash_object(git2::ObjectType::Blob, canonical.to_string_lossy().as_bytes())?;
+    dirs::cache_dir()
+        .map(|dir| dir.join("linuxgit").join("search").join(format!("{}.sqlite", id)))
+        .ok_or_else(|| GitError::Generic("Could not determine the cache directory".to_string()))
+}
+
+fn open_index(path: &Path) -> GitResult<Connection> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let conn = Connection::open(path)?;
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS meta (key TEXT PRIMARY KEY, value TEXT NOT NULL);
+         CREATE TABLE IF NOT EXISTS indexed (sha TEXT PRIMARY KEY);
+         CREATE VIRTUAL TABLE IF NOT EXISTS commits USING fts5(
+             sha UNINDEXED, message, author, paths, timestamp UNINDEXED
+         );",
+    )?;
+    Ok(conn)
+}
+
+fn get_meta(conn: &Connection, key: &str) -> GitResult<Option<String>> {
+    Ok(conn
+        .query_row("SELECT value FROM meta WHERE key = ?1", [key], |row| row.get(0))
+        .optional()?)
+}
+
+fn set_meta(conn: &Connection, key: &str, value: &str) -> GitResult<()> {
+    conn.execute(
+        "INSERT INTO meta (key, value) VALUES (?1, ?2)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        params![key, value],
+    )?;
+    Ok(())
+}
+
+/// Paths changed by a commit relative to its first parent
+fn changed_paths(repo: &Repository, commit: &git2::Commit) -> GitResult<String> {
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree()?),
+        Err(_) => None,
+    };
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), None)?;
+    let paths: Vec<String> = diff
+        .deltas()
+        .take(MAX_INDEXED_PATHS)
+        .filter_map(|d| d.new_file().path().or_else(|| d.old_file().path()))
+        .map(|p| p.to_string_lossy().to_string())
+        .collect();
+    Ok(paths.join("\n"))
+}
+
+/// Add the commits reachable from the branches and HEAD that are not indexed yet
+///
+/// Branch tips from the previous build bound the walk, so only new commits are
+/// visited. Returns the number of commits added; a cancelled build keeps what
+/// was added so far and continues from there next time.
+pub fn update_search_index(
+    repo: &Repository,
+    index_path: &Path,
+    cancel: &AtomicBool,
+    mut on_progress: impl FnMut(u64),
+) -> GitResult<u64> {
+    let mut conn = open_index(index_path)?;
+
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push_glob("refs/heads")?;
+    if repo.head().is_ok() {
+        revwalk.push_head()?;
+    }
+    let previous_tips: Vec<String> = get_meta(&conn, "tips")?
+        .and_then(|tips| serde_json::from_str(&tips).ok())
+        .unwrap_or_default();
+    for tip in &previous_tips {
+        // Tips that no longer exist (e.g. after a rebase) simply don't bound the walk
+        if let Ok(oid) = Oid::from_str(tip) {
+            let _ = revwalk.hide(oid);
+        }
+    }
+
+    let mut added = 0u64;
+    let mut walk = revwalk.peekable();
+    while walk.peek().is_some() && !cancel.load(Ordering::Relaxed) {
+        let tx = conn.transaction()?;
+        for oid in walk.by_ref().take(INDEX_BATCH_SIZE) {
+            let commit = repo.find_commit(oid?)?;
+            let sha = commit.id().to_string();
+            if tx.execute("INSERT OR IGNORE INTO indexed (sha) VALUES (?1)", [&sha])? == 0 {
+                continue;
+            }
+
+            let author = commit.author();
+            tx.execute(
+                "INSERT INTO commits (sha, message, author, paths, timestamp) VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    sha,
+                    commit.message().unwrap_or(""),
+                    format!("{} {}", author.name().unwrap_or(""), author.email().unwrap_or("")),
+                    changed_paths(repo, &commit)?,
+                    commit.time().seconds(),
+                ],
+            )?;
+            added += 1;
+        }
+        tx.commit()?;
+        on_progress(added);
+    }
+
+    if !cancel.load(Ordering::Relaxed) {
+        let mut tips: Vec<String> = repo
+            .branches(Some(git2::BranchType::Local))?
+            .filter_map(|b| b.ok())
+            .filter_map(|(b, _)| b.get().target())
+            .map(|oid| oid.to_string())
+            .collect();
+        if let Some(head) = repo.head().ok().and_then(|h| h.target()) {
+            tips.push(head.to_string());
+        }
+        let tips = serde_json::to_string(&tips)
+            .map_err(|e| GitError::Generic(format!("Failed to serialize branch tips: {}", e)))?;
+        set_meta(&conn, "tips", &tips)?;
+        set_meta(&conn, "last_updated", &chrono::Utc::now().timestamp().to_string())?;
+    }
+    Ok(added)
+}
+
+/// Status of the index at `index_path`; `building` is filled in by the caller
+pub fn search_index_status(index_path: &Path) -> GitResult<SearchIndexStatus> {
+    if !index_path.exists() {
+        return Ok(SearchIndexStatus {
+            exists: false,
+            indexed_commits: 0,
+            last_updated: None,
+            building: false,
+        });
+    }
+
+    let conn = open_index(index_path)?;
+    let indexed_commits: i64 = conn.query_row("SELECT COUNT(*) FROM indexed", [], |row| row.get(0))?;
+    Ok(SearchIndexStatus {
+        exists: true,
+        indexed_commits: indexed_commits as u64,
+        last_updated: get_meta(&conn, "last_updated")?.and_then(|v| v.parse().ok()),
+        building: false,
+    })
+}
+
+/// Turn what the user typed into an FTS5 query: every word must match, and the
+/// last one may be incomplete
+pub fn fts_query(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect();
+    if terms.is_empty() {
+        return None;
+    }
+    Some(format!("{}*", terms.join(" ")))
+}
+
+/// Search the indexed commits, best matches first
+///
+/// Commits that no longer exist in the repository are left out.
+pub fn search_indexed_commits(
+    repo: &Repository,
+    index_path: &Path,
+    query: &str,
+    limit: usize,
+) -> GitResult<Vec<CommitInfo>> {
+    let Some(query) = fts_query(query) else {
+        return Ok(Vec::new());
+    };
+    if !index_path.exists() {
+        return Err(GitError::Generic("The search index has not been built".to_string()));
+    }
+
+    let conn = open_index(index_path)?;
+    let mut statement = conn.prepare(
+        "SELECT sha FROM commits WHERE commits MATCH ?1 ORDER BY rank, timestamp DESC LIMIT ?2",
+    )?;
+    let shas = statement
+        .query_map(params![query, limit as i64], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(shas
+        .iter()
+        .filter_map(|sha| get_commit_detail(repo, sha).ok())
+        .collect())
+}
+
+/// Delete the index of a repository
+pub fn delete_search_index(index_path: &Path) -> GitResult<()> {
+    if index_path.exists() {
+        std::fs::remove_file(index_path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::commit_file_as;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_fts_query() {
+        assert_eq!(fts_query("fix login").as_deref(), Some("\"fix\" \"login\"*"));
+        assert_eq!(fts_query("say \"hi\"").as_deref(), Some("\"say\" \"\"\"hi\"\"\"*"));
+        assert_eq!(fts_query("   "), None);
+    }
+
+    #[test]
+    fn test_search_index() {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path().join("repo")).unwrap();
+        let index_path = dir.path().join("index.sqlite");
+        let cancel = AtomicBool::new(false);
+        let ada = git2::Signature::now("Ada Lovelace", "ada@example.com").unwrap();
+
+        commit_file_as(&repo, "login.rs", "Fix login redirect", "Fix login redirect", &ada);
+        commit_file_as(&repo, "README.md", "Document setup", "Document setup", &ada);
+        assert_eq!(update_search_index(&repo, &index_path, &cancel, |_| {}).unwrap(), 2);
+
+        let search = |query: &str| -> Vec<String> {
+            search_indexed_commits(&repo, &index_path, query, 10)
+                .unwrap()
+                .into_iter()
+                .map(|c| c.message)
+                .collect()
+        };
+        assert_eq!(search("logi"), vec!["Fix login redirect"]);
+        assert_eq!(search("readme"), vec!["Document setup"]);
+        assert_eq!(search("lovelace").len(), 2);
+
+        // Only new commits are added by later builds
+        commit_file_as(&repo, "api.rs", "Add login API", "Add login API", &ada);
+        assert_eq!(update_search_index(&repo, &index_path, &cancel, |_| {}).unwrap(), 1);
+        assert_eq!(search("login").len(), 2);
+
+        let status = search_index_status(&index_path).unwrap();
+        assert!(status.exists && status.last_updated.is_some());
+        assert_eq!(status.indexed_commits, 3);
+    }
+}
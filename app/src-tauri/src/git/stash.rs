@@ -0,0 +1,291 @@
+//! Stashing local changes around operations that need a clean working directory
+//!
+//! Local changes (including untracked files) are stashed before the operation
+//! and re-applied afterwards. If re-applying conflicts, the conflicts are left
+//! in the working directory and the stash is kept so nothing is lost.
+
+use git2::{Oid, Repository, Signature, StashApplyOptions, StashFlags, StatusOptions};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::{GitError, GitResult};
+use crate::error::StructuredError;
+
+/// What happened to the local changes around an operation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AutoStashOutcome {
+    /// Whether there were local changes to stash
+    pub stashed: bool,
+    /// Whether the changes were re-applied and the stash dropped
+    pub restored: bool,
+    /// Paths left with conflicts by re-applying the changes
+    pub conflicts: Vec<String>,
+    /// Why the changes could not be re-applied; they remain in the stash
+    pub error: Option<String>,
+}
+
+/// An operation run by [`with_auto_stash`] that failed, with what happened to
+/// the local changes around it
+#[derive(Debug, Error)]
+#[error("{message}")]
+pub struct AutoStashError {
+    pub message: String,
+    pub outcome: AutoStashOutcome,
+    pub error: GitError,
+}
+
+impl AutoStashError {
+    pub fn new(error: GitError, outcome: AutoStashOutcome) -> Self {
+        let message = match &outcome.error {
+            Some(stash_error) => format!("{}. {}", error, stash_error),
+            None if !outcome.conflicts.is_empty() => format!(
+                "{}. Re-applying local changes left conflicts in: {}",
+                error,
+                outcome.conflicts.join(", ")
+            ),
+            None => error.to_string(),
+        };
+        Self {
+            message,
+            outcome,
+            error,
+        }
+    }
+
+    /// The error as a structured error of kind `auto_stash_failed`, for
+    /// returning from a command
+    pub fn to_structured(&self) -> StructuredError {
+        StructuredError::new(
+            "auto_stash_failed",
+            self.message.clone(),
+            serde_json::json!({ "outcome": self.outcome }),
+        )
+    }
+}
+
+impl From<Box<AutoStashError>> for GitError {
+    fn from(e: Box<AutoStashError>) -> Self {
+        if e.outcome.error.is_none() && e.outcome.conflicts.is_empty() {
+            return e.error;
+        }
+        GitError::Generic(e.message)
+    }
+}
+
+/// Whether the working directory or index has changes, including untracked files
+pub fn has_local_changes(repo: &Repository) -> GitResult<bool> {
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(true).include_ignored(false);
+    Ok(!repo.statuses(Some(&mut opts))?.is_empty())
+}
+
+fn stash_signature(repo: &Repository) -> GitResult<Signature<'static>> {
+    match repo.signature() {
+        Ok(signature) => Ok(signature.to_owned()),
+        Err(_) => Ok(Signature::now("LinuxGit", "linuxgit@localhost")?),
+    }
+}
+
+fn stash_index(repo: &mut Repository, stash_id: Oid) -> GitResult<Option<usize>> {
+    let mut found = None;
+    repo.stash_foreach(|index, _, id| {
+        if *id == stash_id {
+            found = Some(index);
+            false
+        } else {
+            true
+        }
+    })?;
+    Ok(found)
+}
+
+fn apply_stash(repo: &mut Repository, index: usize, reinstate_index: bool) -> Result<(), git2::Error> {
+    let mut checkout = git2::build::CheckoutBuilder::new();
+    checkout.allow_conflicts(true).conflict_style_merge(true);
+    let mut options = StashApplyOptions::new();
+    options.checkout_options(checkout);
+    if reinstate_index {
+        options.reinstantiate_index();
+    }
+    repo.stash_apply(index, Some(&mut options))
+}
+
+/// Re-apply a stash made by [`with_auto_stash`], dropping it if that succeeds cleanly
+///
+/// Failures are reported in the outcome, so they never hide the result of the
+/// operation itself.
+fn restore_stash(repo: &mut Repository, stash_id: Oid) -> AutoStashOutcome {
+    try_restore_stash(repo, stash_id).unwrap_or_else(|e| AutoStashOutcome {
+        stashed: true,
+        error: Some(format!("Local changes were kept in the stash: {}", e)),
+        ..Default::default()
+    })
+}
+
+fn try_restore_stash(repo: &mut Repository, stash_id: Oid) -> GitResult<AutoStashOutcome> {
+    let mut outcome = AutoStashOutcome {
+        stashed: true,
+        ..Default::default()
+    };
+    let Some(index) = stash_index(repo, stash_id)? else {
+        outcome.error = Some("The auto-stash could not be found".to_string());
+        return Ok(outcome);
+    };
+
+    // Staged changes are restored as staged unless that conflicts with the new HEAD
+    let applied = match apply_stash(repo, index, true) {
+        Err(e) if e.code() == git2::ErrorCode::Conflict => apply_stash(repo, index, false),
+        result => result,
+    };
+    if let Err(e) = applied {
+        outcome.error = Some(format!("Local changes were kept in the stash: {}", e.message()));
+        return Ok(outcome);
+    }
+
+    let repo_index = repo.index()?;
+    if repo_index.has_conflicts() {
+        outcome.conflicts = repo_index
+            .conflicts()?
+            .filter_map(|c| c.ok())
+            .filter_map(|c| c.our.or(c.their).or(c.ancestor))
+            .map(|entry| String::from_utf8_lossy(&entry.path).to_string())
+            .collect();
+        return Ok(outcome);
+    }
+
+    repo.stash_drop(index)?;
+    outcome.restored = true;
+    Ok(outcome)
+}
+
+/// Run `operation` with local changes stashed, re-applying them afterwards
+///
+/// The changes are re-applied even if the operation fails, in which case its
+/// error is returned along with what happened to the changes.
+pub fn with_auto_stash<T>(
+    repo: &mut Repository,
+    description: &str,
+    operation: impl FnOnce(&Repository) -> GitResult<T>,
+) -> Result<(T, AutoStashOutcome), Box<AutoStashError>> {
+    let not_stashed = |e: GitError| Box::new(AutoStashError::new(e, AutoStashOutcome::default()));
+    if !has_local_changes(repo).map_err(not_stashed)? {
+        let result = operation(repo).map_err(not_stashed)?;
+        return Ok((result, AutoStashOutcome::default()));
+    }
+
+    let signature = stash_signature(repo).map_err(not_stashed)?;
+    let stash_id = repo
+        .stash_save(
+            &signature,
+            &format!("LinuxGit auto-stash before {}", description),
+            Some(StashFlags::INCLUDE_UNTRACKED),
+        )
+        .map_err(|e| not_stashed(e.into()))?;
+
+    let result = operation(repo);
+    let outcome = restore_stash(repo, stash_id);
+    match result {
+        Ok(value) => Ok((value, outcome)),
+        Err(e) => Err(Box::new(AutoStashError::new(e, outcome))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::commit_file;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn stash_count(repo: &mut Repository) -> usize {
+        let mut count = 0;
+        repo.stash_foreach(|_, _, _| {
+            count += 1;
+            true
+        })
+        .unwrap();
+        count
+    }
+
+    #[test]
+    fn test_with_auto_stash_restores_changes() {
+        let dir = tempdir().unwrap();
+        let mut repo = Repository::init(dir.path()).unwrap();
+        commit_file(&repo, "a.txt", "one\n", "commit");
+        fs::write(dir.path().join("a.txt"), "changed\n").unwrap();
+        fs::write(dir.path().join("new.txt"), "untracked\n").unwrap();
+
+        let (clean, outcome) = with_auto_stash(&mut repo, "test", has_local_changes).unwrap();
+        assert!(!clean);
+        assert!(outcome.stashed && outcome.restored);
+        assert_eq!(fs::read_to_string(dir.path().join("a.txt")).unwrap(), "changed\n");
+        assert!(dir.path().join("new.txt").exists());
+        assert_eq!(stash_count(&mut repo), 0);
+    }
+
+    #[test]
+    fn test_with_auto_stash_reports_conflicts() {
+        let dir = tempdir().unwrap();
+        let mut repo = Repository::init(dir.path()).unwrap();
+        commit_file(&repo, "a.txt", "one\n", "commit");
+        fs::write(dir.path().join("a.txt"), "local\n").unwrap();
+
+        let (_, outcome) = with_auto_stash(&mut repo, "test", |repo| {
+            commit_file(repo, "a.txt", "upstream\n", "commit");
+            Ok(())
+        })
+        .unwrap();
+        assert!(outcome.stashed);
+        assert!(!outcome.restored);
+        assert_eq!(outcome.conflicts, vec!["a.txt"]);
+
+        // The stash is kept so the local changes can be recovered
+        assert_eq!(stash_count(&mut repo), 1);
+    }
+
+    #[test]
+    fn test_with_auto_stash_restores_changes_when_operation_fails() {
+        let dir = tempdir().unwrap();
+        let mut repo = Repository::init(dir.path()).unwrap();
+        commit_file(&repo, "a.txt", "one\n", "commit");
+        fs::write(dir.path().join("a.txt"), "changed\n").unwrap();
+
+        let error = with_auto_stash(&mut repo, "test", |_| -> GitResult<()> {
+            Err(GitError::OperationFailed("pull failed".to_string()))
+        })
+        .unwrap_err();
+        assert_eq!(error.to_structured().kind, "auto_stash_failed");
+        assert!(error.outcome.stashed && error.outcome.restored);
+        assert!(matches!(error.error, GitError::OperationFailed(_)));
+        assert_eq!(fs::read_to_string(dir.path().join("a.txt")).unwrap(), "changed\n");
+        assert_eq!(stash_count(&mut repo), 0);
+    }
+
+    #[test]
+    fn test_with_auto_stash_reports_lost_stash() {
+        let dir = tempdir().unwrap();
+        let mut repo = Repository::init(dir.path()).unwrap();
+        commit_file(&repo, "a.txt", "one\n", "commit");
+        fs::write(dir.path().join("a.txt"), "changed\n").unwrap();
+
+        // The operation's result is kept even though the changes can't be restored
+        let (value, outcome) = with_auto_stash(&mut repo, "test", |repo| {
+            git2::Repository::open(repo.path())?.stash_drop(0)?;
+            Ok(42)
+        })
+        .unwrap();
+        assert_eq!(value, 42);
+        assert!(outcome.stashed && !outcome.restored);
+        assert!(outcome.error.is_some());
+    }
+
+    #[test]
+    fn test_with_auto_stash_without_changes() {
+        let dir = tempdir().unwrap();
+        let mut repo = Repository::init(dir.path()).unwrap();
+        commit_file(&repo, "a.txt", "one\n", "commit");
+
+        let (_, outcome) = with_auto_stash(&mut repo, "test", |_| Ok(())).unwrap();
+        assert!(!outcome.stashed);
+    }
+}
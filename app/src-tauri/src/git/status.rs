@@ -1,6 +1,6 @@
 use git2::{Repository, StatusOptions};
 
-use super::{FileStatus, FileStatusType, GitResult, StatusInfo};
+use super::{FileStatus, FileStatusType, GitError, GitResult, StatusInfo};
 
 /// Gets the current status of the repository
 pub fn get_repo_status(repo: &Repository) -> GitResult<StatusInfo> {
@@ -100,6 +100,8 @@ pub fn get_repo_status(repo: &Repository) -> GitResult<StatusInfo> {
         unstaged,
         untracked,
         conflicts,
+        // A broken .gitmodules should not prevent showing the status
+        submodules: super::get_submodule_statuses(repo).unwrap_or_default(),
     })
 }
 
@@ -108,7 +110,13 @@ pub fn stage_files(repo: &Repository, paths: &[String]) -> GitResult<()> {
     let mut index = repo.index()?;
 
     for path in paths {
-        index.add_path(std::path::Path::new(path))?;
+        let path = std::path::Path::new(path);
+        // A file missing from the working directory is staged as deleted
+        if repo.workdir().is_some_and(|workdir| !workdir.join(path).exists()) {
+            index.remove_path(path)?;
+        } else {
+            index.add_path(path)?;
+        }
     }
 
     index.write()?;
@@ -139,6 +147,52 @@ pub fn unstage_files(repo: &Repository, paths: &[String]) -> GitResult<()> {
     Ok(())
 }
 
+/// Writes the index to a tree, so its staged changes can be restored later
+pub fn snapshot_index(repo: &Repository) -> GitResult<String> {
+    let mut index = repo.index()?;
+    Ok(index.write_tree()?.to_string())
+}
+
+/// Stages only `paths`, with their content taken from a tree written by
+/// [`snapshot_index`]; every other path is reset to HEAD
+///
+/// Each path is staged whole, with all of its staged hunks. Paths missing from
+/// the tree are staged as deleted. The working directory is not touched.
+pub fn stage_paths_from_tree(repo: &Repository, tree_sha: &str, paths: &[String]) -> GitResult<()> {
+    let oid = git2::Oid::from_str(tree_sha)
+        .map_err(|_| GitError::Generic(format!("Invalid tree id: {}", tree_sha)))?;
+    let tree = repo.find_tree(oid)?;
+    let mut index = repo.index()?;
+
+    match repo.head().ok().and_then(|head| head.peel_to_tree().ok()) {
+        Some(head_tree) => index.read_tree(&head_tree)?,
+        None => index.clear()?,
+    }
+
+    for path in paths {
+        match tree.get_path(std::path::Path::new(path)) {
+            Ok(entry) => index.add(&git2::IndexEntry {
+                ctime: git2::IndexTime::new(0, 0),
+                mtime: git2::IndexTime::new(0, 0),
+                dev: 0,
+                ino: 0,
+                mode: entry.filemode() as u32,
+                uid: 0,
+                gid: 0,
+                file_size: 0,
+                id: entry.id(),
+                flags: 0,
+                flags_extended: 0,
+                path: path.as_bytes().to_vec(),
+            })?,
+            Err(_) => index.remove_path(std::path::Path::new(path))?,
+        }
+    }
+
+    index.write()?;
+    Ok(())
+}
+
 /// Discards changes in the working directory
 pub fn discard_changes(repo: &Repository, paths: &[String]) -> GitResult<()> {
     let mut checkout_builder = git2::build::CheckoutBuilder::new();
@@ -183,4 +237,31 @@ mod tests {
         let status = get_repo_status(&repo).unwrap();
         assert_eq!(status.staged.len(), 1);
     }
+
+    #[test]
+    fn test_stage_paths_from_tree() {
+        let dir = tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+
+        fs::write(dir.path().join("a.txt"), "staged a").unwrap();
+        fs::write(dir.path().join("b.txt"), "staged b").unwrap();
+        stage_files(&repo, &["a.txt".to_string(), "b.txt".to_string()]).unwrap();
+        let snapshot = snapshot_index(&repo).unwrap();
+
+        // Unstaged edits must not leak into the staged content
+        fs::write(dir.path().join("a.txt"), "edited a").unwrap();
+
+        stage_paths_from_tree(&repo, &snapshot, &["a.txt".to_string()]).unwrap();
+        let status = get_repo_status(&repo).unwrap();
+        let staged: Vec<&str> = status.staged.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(staged, vec!["a.txt"]);
+
+        let index = repo.index().unwrap();
+        let entry = index.get_path(std::path::Path::new("a.txt"), 0).unwrap();
+        let blob = repo.find_blob(entry.id).unwrap();
+        assert_eq!(blob.content(), b"staged a");
+
+        stage_paths_from_tree(&repo, &snapshot, &["a.txt".to_string(), "b.txt".to_string()]).unwrap();
+        assert_eq!(snapshot_index(&repo).unwrap(), snapshot);
+    }
 }
@@ -0,0 +1,188 @@
+//! Submodule state for status and diffs
+//!
+//! A submodule shows up in a diff as a changed commit pointer rather than as
+//! file contents, so its changes are described by the commits on either side
+//! ("Subproject commit A → B") and whether its own working directory is dirty.
+
+use git2::{DiffDelta, FileMode, Oid, Repository, SubmoduleIgnore, SubmoduleStatus as Sm};
+use serde::{Deserialize, Serialize};
+
+use super::{DiffHunk, DiffLine, DiffLineType, GitResult};
+
+/// State of a submodule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmoduleStatus {
+    pub name: String,
+    pub path: String,
+    pub url: Option<String>,
+    /// Commit recorded in HEAD
+    pub head_sha: Option<String>,
+    /// Commit recorded in the index
+    pub index_sha: Option<String>,
+    /// Commit checked out in the submodule
+    pub workdir_sha: Option<String>,
+    /// Whether the submodule is checked out
+    pub initialized: bool,
+    /// Whether the submodule has uncommitted changes or untracked files
+    pub dirty: bool,
+    /// Whether a new commit pointer is staged
+    pub staged_change: bool,
+    /// Whether the checked-out commit differs from the index
+    pub unstaged_change: bool,
+}
+
+/// A changed submodule pointer in a diff
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmoduleChange {
+    /// Commit before the change, if the submodule existed
+    pub old_sha: Option<String>,
+    /// Commit after the change, if the submodule still exists
+    pub new_sha: Option<String>,
+    /// Whether the submodule's working directory has uncommitted changes
+    pub dirty: bool,
+}
+
+fn sha(id: Option<Oid>) -> Option<String> {
+    id.filter(|id| !id.is_zero()).map(|id| id.to_string())
+}
+
+impl SubmoduleChange {
+    /// "Subproject commit A → B", with abbreviated commits
+    pub fn summary(&self) -> String {
+        let short = |sha: &Option<String>| match sha {
+            Some(sha) => sha.chars().take(7).collect(),
+            None => "(none)".to_string(),
+        };
+        let mut summary = format!("Subproject commit {} → {}", short(&self.old_sha), short(&self.new_sha));
+        if self.dirty {
+            summary.push_str(" (dirty)");
+        }
+        summary
+    }
+
+    /// The change rendered as a diff hunk with a single line
+    pub fn to_hunk(&self) -> DiffHunk {
+        DiffHunk {
+            header: "Submodule".to_string(),
+            old_start: 0,
+            old_lines: 0,
+            new_start: 0,
+            new_lines: 0,
+            lines: vec![DiffLine {
+                line_type: DiffLineType::Header,
+                content: self.summary(),
+                old_line: None,
+                new_line: None,
+            }],
+        }
+    }
+}
+
+/// Whether a diff delta is a submodule pointer change
+pub fn is_submodule_delta(delta: &DiffDelta) -> bool {
+    delta.old_file().mode() == FileMode::Commit || delta.new_file().mode() == FileMode::Commit
+}
+
+/// The pointer change of a submodule delta; `check_dirty` looks at the
+/// submodule's working directory, which only matters for unstaged changes
+pub fn submodule_change(repo: &Repository, delta: &DiffDelta, check_dirty: bool) -> SubmoduleChange {
+    let path = delta
+        .new_file()
+        .path()
+        .or_else(|| delta.old_file().path())
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let status = check_dirty
+        .then(|| repo.submodule_status(&path, SubmoduleIgnore::None).ok())
+        .flatten();
+
+    let mut new_sha = sha(Some(delta.new_file().id()));
+    if new_sha.is_none() && check_dirty && delta.new_file().mode() == FileMode::Commit {
+        // The checked-out commit is not always filled in for the working directory
+        new_sha = repo.find_submodule(&path).ok().and_then(|sm| sha(sm.workdir_id()));
+    }
+
+    SubmoduleChange {
+        old_sha: sha(Some(delta.old_file().id())),
+        new_sha,
+        dirty: status.is_some_and(|s| {
+            s.intersects(Sm::WD_INDEX_MODIFIED | Sm::WD_WD_MODIFIED | Sm::WD_UNTRACKED)
+        }),
+    }
+}
+
+/// Get the state of every submodule of the repository
+pub fn get_submodule_statuses(repo: &Repository) -> GitResult<Vec<SubmoduleStatus>> {
+    let mut submodules = Vec::new();
+    for submodule in repo.submodules()? {
+        let name = submodule.name().unwrap_or("").to_string();
+        let status = repo.submodule_status(&name, SubmoduleIgnore::None)?;
+
+        submodules.push(SubmoduleStatus {
+            path: submodule.path().to_string_lossy().to_string(),
+            url: submodule.url().map(|u| u.to_string()),
+            head_sha: sha(submodule.head_id()),
+            index_sha: sha(submodule.index_id()),
+            workdir_sha: sha(submodule.workdir_id()),
+            initialized: !status.contains(Sm::WD_UNINITIALIZED),
+            dirty: status.intersects(Sm::WD_INDEX_MODIFIED | Sm::WD_WD_MODIFIED | Sm::WD_UNTRACKED),
+            staged_change: status.intersects(Sm::INDEX_ADDED | Sm::INDEX_DELETED | Sm::INDEX_MODIFIED),
+            unstaged_change: status.contains(Sm::WD_MODIFIED),
+            name,
+        });
+    }
+    Ok(submodules)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_support::commit_all;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_submodule_statuses() {
+        let dir = tempdir().unwrap();
+        let upstream = Repository::init(dir.path().join("upstream")).unwrap();
+        fs::write(dir.path().join("upstream/lib.txt"), "v1").unwrap();
+        let first = commit_all(&upstream, "v1");
+
+        let repo = Repository::init(dir.path().join("main")).unwrap();
+        let url = dir.path().join("upstream").to_string_lossy().to_string();
+        let mut submodule = repo.submodule(&url, Path::new("libs/dep"), true).unwrap();
+        submodule.clone(None).unwrap();
+        submodule.add_finalize().unwrap();
+        commit_all(&repo, "add submodule");
+
+        // Move the submodule to a new commit and leave a change in it
+        let sub_repo = submodule.open().unwrap();
+        fs::write(dir.path().join("main/libs/dep/lib.txt"), "v2").unwrap();
+        let second = commit_all(&sub_repo, "v2");
+        fs::write(dir.path().join("main/libs/dep/wip.txt"), "wip").unwrap();
+
+        let statuses = get_submodule_statuses(&repo).unwrap();
+        assert_eq!(statuses.len(), 1);
+        let status = &statuses[0];
+        assert_eq!(status.path, "libs/dep");
+        assert_eq!(status.head_sha, Some(first.to_string()));
+        assert_eq!(status.workdir_sha, Some(second.to_string()));
+        assert!(status.initialized && status.dirty && status.unstaged_change);
+        assert!(!status.staged_change);
+
+        let diff = repo.diff_index_to_workdir(None, None).unwrap();
+        let delta = diff.deltas().find(is_submodule_delta).unwrap();
+        let change = submodule_change(&repo, &delta, true);
+        assert_eq!(change.old_sha, Some(first.to_string()));
+        assert_eq!(change.new_sha, Some(second.to_string()));
+        assert_eq!(
+            change.summary(),
+            format!("Subproject commit {} → {} (dirty)", &first.to_string()[..7], &second.to_string()[..7])
+        );
+
+        let file_diff = super::super::get_file_diff(&repo, "libs/dep", false).unwrap();
+        assert_eq!(file_diff.hunks[0].lines[0].content, change.summary());
+        assert!(file_diff.submodule.is_some());
+    }
+}
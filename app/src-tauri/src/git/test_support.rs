@@ -0,0 +1,52 @@
+//! Repository fixtures shared by the tests of the git modules
+
+use std::fs;
+use std::path::Path;
+
+use git2::{Oid, Repository, Signature, Time};
+
+/// Signature of `name` at a fixed Unix timestamp
+pub fn signature_at(name: &str, time: i64) -> Signature<'static> {
+    Signature::new(name, "test@test.com", &Time::new(time, 0)).unwrap()
+}
+
+/// Commit the index as it is on top of HEAD
+pub fn commit_index(repo: &Repository, message: &str, signature: &Signature) -> Oid {
+    let mut index = repo.index().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    repo.commit(Some("HEAD"), signature, signature, message, &tree, &parents).unwrap()
+}
+
+/// Write `path`, creating its directories, and commit it on top of HEAD
+pub fn commit_file_as(
+    repo: &Repository,
+    path: &str,
+    content: impl AsRef<[u8]>,
+    message: &str,
+    signature: &Signature,
+) -> Oid {
+    let full_path = repo.workdir().unwrap().join(path);
+    fs::create_dir_all(full_path.parent().unwrap()).unwrap();
+    fs::write(full_path, content).unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new(path)).unwrap();
+    index.write().unwrap();
+    commit_index(repo, message, signature)
+}
+
+/// Write `path` and commit it on top of HEAD as the test user
+pub fn commit_file(repo: &Repository, path: &str, content: impl AsRef<[u8]>, message: &str) -> Oid {
+    let signature = Signature::now("Test", "test@test.com").unwrap();
+    commit_file_as(repo, path, content, message, &signature)
+}
+
+/// Stage every change of the working directory, deletions included, and commit it
+pub fn commit_all(repo: &Repository, message: &str) -> Oid {
+    let mut index = repo.index().unwrap();
+    index.add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None).unwrap();
+    index.update_all(["*"].iter(), None).unwrap();
+    index.write().unwrap();
+    commit_index(repo, message, &Signature::now("Test", "test@test.com").unwrap())
+}
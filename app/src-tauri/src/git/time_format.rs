@@ -0,0 +1,241 @@
+//! Formatting commit timestamps
+//!
+//! Timestamps are shown both relative ("2 days ago") and absolute
+//! ("2024-05-12 14:03"), so the UI can switch between the two. Relative times
+//! are translated for a few locales, falling back to English, and the absolute
+//! format is a configurable strftime pattern. Settings are stored in the app
+//! config directory and kept in memory once loaded.
+
+use std::sync::{OnceLock, RwLock};
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use super::{GitError, GitResult};
+use crate::settings::{load_json_settings, save_json_settings};
+
+/// Locales with translated relative times
+pub const SUPPORTED_TIME_LOCALES: &[&str] = &["en", "de", "fr", "es"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeFormatSettings {
+    /// Language of relative times, such as `en` or `de-DE`
+    #[serde(default = "default_locale")]
+    pub locale: String,
+    /// strftime pattern of absolute times, in local time
+    #[serde(default = "default_absolute_format")]
+    pub absolute_format: String,
+}
+
+fn default_locale() -> String {
+    "en".to_string()
+}
+
+fn default_absolute_format() -> String {
+    "%Y-%m-%d %H:%M".to_string()
+}
+
+impl Default for TimeFormatSettings {
+    fn default() -> Self {
+        Self {
+            locale: default_locale(),
+            absolute_format: default_absolute_format(),
+        }
+    }
+}
+
+/// A timestamp with its relative and absolute renderings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FormattedTime {
+    pub timestamp: i64,
+    pub relative: String,
+    pub absolute: String,
+}
+
+/// Words of relative times in one language
+struct RelativeWords {
+    just_now: &'static str,
+    /// Template with `{}` for the amount and unit
+    ago: &'static str,
+    /// Singular and plural of minute, hour, day, week, month, and year
+    units: [(&'static str, &'static str); 6],
+}
+
+fn relative_words(locale: &str) -> &'static RelativeWords {
+    const EN: RelativeWords = RelativeWords {
+        just_now: "just now",
+        ago: "{} ago",
+        units: [
+            ("minute", "minutes"),
+            ("hour", "hours"),
+            ("day", "days"),
+            ("week", "weeks"),
+            ("month", "months"),
+            ("year", "years"),
+        ],
+    };
+    const DE: RelativeWords = RelativeWords {
+        just_now: "gerade eben",
+        ago: "vor {}",
+        units: [
+            ("Minute", "Minuten"),
+            ("Stunde", "Stunden"),
+            ("Tag", "Tagen"),
+            ("Woche", "Wochen"),
+            ("Monat", "Monaten"),
+            ("Jahr", "Jahren"),
+        ],
+    };
+    const FR: RelativeWords = RelativeWords {
+        just_now: "à l'instant",
+        ago: "il y a {}",
+        units: [
+            ("minute", "minutes"),
+            ("heure", "heures"),
+            ("jour", "jours"),
+            ("semaine", "semaines"),
+            ("mois", "mois"),
+            ("an", "ans"),
+        ],
+    };
+    const ES: RelativeWords = RelativeWords {
+        just_now: "justo ahora",
+        ago: "hace {}",
+        units: [
+            ("minuto", "minutos"),
+            ("hora", "horas"),
+            ("día", "días"),
+            ("semana", "semanas"),
+            ("mes", "meses"),
+            ("año", "años"),
+        ],
+    };
+
+    // `de-DE` and `de_AT` use the `de` words
+    let language = locale.split(['-', '_']).next().unwrap_or("").to_lowercase();
+    match language.as_str() {
+        "de" => &DE,
+        "fr" => &FR,
+        "es" => &ES,
+        _ => &EN,
+    }
+}
+
+/// Relative time of `timestamp` as seen at `now`, in the language of `locale`
+pub fn format_relative_time_at(timestamp: i64, now: i64, locale: &str) -> String {
+    let words = relative_words(locale);
+    let diff = now - timestamp;
+    if diff < 60 {
+        return words.just_now.to_string();
+    }
+
+    // Average month and year lengths, in seconds
+    let (amount, unit) = if diff < 3600 {
+        (diff / 60, 0)
+    } else if diff < 86400 {
+        (diff / 3600, 1)
+    } else if diff < 604800 {
+        (diff / 86400, 2)
+    } else if diff < 2629746 {
+        (diff / 604800, 3)
+    } else if diff < 31556952 {
+        (diff / 2629746, 4)
+    } else {
+        (diff / 31556952, 5)
+    };
+
+    let (singular, plural) = words.units[unit];
+    let unit = if amount == 1 { singular } else { plural };
+    words.ago.replace("{}", &format!("{} {}", amount, unit))
+}
+
+/// Whether `format` is a valid strftime pattern
+pub fn is_valid_time_format(format: &str) -> bool {
+    !format.trim().is_empty() && !StrftimeItems::new(format).any(|item| matches!(item, Item::Error))
+}
+
+/// Absolute local time of `timestamp` using a strftime pattern
+pub fn format_absolute_time(timestamp: i64, format: &str) -> String {
+    let format = if is_valid_time_format(format) { format } else { "%Y-%m-%d %H:%M" };
+    Local
+        .timestamp_opt(timestamp, 0)
+        .single()
+        .map(|time| time.format(format).to_string())
+        .unwrap_or_default()
+}
+
+/// Relative and absolute renderings of `timestamp` with the given settings
+pub fn format_time_with(timestamp: i64, now: i64, settings: &TimeFormatSettings) -> FormattedTime {
+    FormattedTime {
+        timestamp,
+        relative: format_relative_time_at(timestamp, now, &settings.locale),
+        absolute: format_absolute_time(timestamp, &settings.absolute_format),
+    }
+}
+
+static TIME_FORMAT_SETTINGS: OnceLock<RwLock<TimeFormatSettings>> = OnceLock::new();
+
+fn current_settings() -> &'static RwLock<TimeFormatSettings> {
+    TIME_FORMAT_SETTINGS.get_or_init(|| RwLock::new(load_time_format_settings().unwrap_or_default()))
+}
+
+/// The time format settings in use
+pub fn time_format_settings() -> TimeFormatSettings {
+    current_settings().read().unwrap().clone()
+}
+
+/// Relative and absolute renderings of `timestamp` with the current settings
+pub fn format_time(timestamp: i64) -> FormattedTime {
+    format_time_with(timestamp, chrono::Utc::now().timestamp(), &time_format_settings())
+}
+
+/// Name of the settings file in the app config directory
+const SETTINGS_FILE: &str = "time_format.json";
+
+/// Load the time format settings, or the defaults if none were saved
+pub fn load_time_format_settings() -> GitResult<TimeFormatSettings> {
+    Ok(load_json_settings(SETTINGS_FILE)?)
+}
+
+/// Save the time format settings and use them from now on
+pub fn save_time_format_settings(settings: &TimeFormatSettings) -> GitResult<()> {
+    if !is_valid_time_format(&settings.absolute_format) {
+        return Err(GitError::Generic(format!(
+            "Invalid time format: {}",
+            settings.absolute_format
+        )));
+    }
+
+    save_json_settings(SETTINGS_FILE, settings)?;
+
+    *current_settings().write().unwrap() = settings.clone();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_relative_time_locales() {
+        let now = 1_700_000_000;
+        assert_eq!(format_relative_time_at(now - 10, now, "en"), "just now");
+        assert_eq!(format_relative_time_at(now - 60, now, "en"), "1 minute ago");
+        assert_eq!(format_relative_time_at(now - 172800, now, "de-DE"), "vor 2 Tagen");
+        assert_eq!(format_relative_time_at(now - 3600, now, "fr"), "il y a 1 heure");
+        assert_eq!(format_relative_time_at(now - 2 * 31556952, now, "es_MX"), "hace 2 años");
+        assert_eq!(format_relative_time_at(now - 7200, now, "xx"), "2 hours ago");
+    }
+
+    #[test]
+    fn test_format_absolute_time() {
+        let timestamp = Local.with_ymd_and_hms(2024, 5, 12, 14, 3, 0).unwrap().timestamp();
+        assert_eq!(format_absolute_time(timestamp, "%Y-%m-%d %H:%M"), "2024-05-12 14:03");
+        assert_eq!(format_absolute_time(timestamp, "%d.%m.%Y"), "12.05.2024");
+        // An invalid pattern falls back to the default instead of panicking
+        assert_eq!(format_absolute_time(timestamp, "%Q"), "2024-05-12 14:03");
+        assert!(!is_valid_time_format("%Q"));
+        assert!(!is_valid_time_format(" "));
+    }
+}
@@ -0,0 +1,201 @@
+//! Workspaces: named groups of repositories
+//!
+//! A workspace lets related repositories (such as the services of one product)
+//! be fetched, pulled, or checked together. Operations run on all repositories
+//! in parallel and report each repository's outcome, so one failing repository
+//! does not stop the others. Workspaces are stored in the app config directory.
+
+use git2::Repository;
+use serde::{Deserialize, Serialize};
+
+use super::scan::parallel_map;
+use super::{
+    fetch, get_all_repo_sync_statuses, get_default_remote, pull, with_auto_stash, FetchResult,
+    GitError, GitResult, PullResult, RepoSyncSummary,
+};
+use crate::settings::{load_json_settings, save_json_settings};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workspace {
+    pub name: String,
+    /// Paths of the repositories in the workspace
+    #[serde(default)]
+    pub paths: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkspaceSettings {
+    #[serde(default)]
+    pub workspaces: Vec<Workspace>,
+}
+
+impl WorkspaceSettings {
+    pub fn find(&self, name: &str) -> Option<&Workspace> {
+        self.workspaces.iter().find(|w| w.name == name)
+    }
+
+    /// Add a workspace, replacing the one with the same name
+    pub fn upsert(&mut self, mut workspace: Workspace) -> GitResult<()> {
+        workspace.name = workspace.name.trim().to_string();
+        if workspace.name.is_empty() {
+            return Err(GitError::Generic("Workspace name cannot be empty".to_string()));
+        }
+        let mut paths: Vec<String> = Vec::new();
+        for path in workspace.paths.drain(..) {
+            let path = path.trim().to_string();
+            if !path.is_empty() && !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+        workspace.paths = paths;
+
+        match self.workspaces.iter_mut().find(|w| w.name == workspace.name) {
+            Some(existing) => *existing = workspace,
+            None => self.workspaces.push(workspace),
+        }
+        Ok(())
+    }
+
+    /// Remove a workspace, returning whether it existed
+    pub fn remove(&mut self, name: &str) -> bool {
+        let count = self.workspaces.len();
+        self.workspaces.retain(|w| w.name != name);
+        self.workspaces.len() != count
+    }
+}
+
+/// Outcome of an operation on one repository of a workspace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceRepoResult<T> {
+    pub path: String,
+    pub result: Option<T>,
+    pub error: Option<String>,
+}
+
+/// Outcome of an operation on every repository of a workspace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceBatchResult<T> {
+    pub workspace: String,
+    pub repos: Vec<WorkspaceRepoResult<T>>,
+    pub succeeded: usize,
+    pub failed: usize,
+}
+
+/// Run `operation` on every repository of a workspace in parallel
+pub fn run_workspace_batch<T: Send>(
+    workspace: &Workspace,
+    operation: impl Fn(&str) -> GitResult<T> + Sync,
+) -> WorkspaceBatchResult<T> {
+    let repos: Vec<WorkspaceRepoResult<T>> = parallel_map(&workspace.paths, |path| match operation(path) {
+        Ok(result) => WorkspaceRepoResult {
+            path: path.clone(),
+            result: Some(result),
+            error: None,
+        },
+        Err(e) => WorkspaceRepoResult {
+            path: path.clone(),
+            result: None,
+            error: Some(e.to_string()),
+        },
+    });
+
+    let failed = repos.iter().filter(|r| r.error.is_some()).count();
+    WorkspaceBatchResult {
+        workspace: workspace.name.clone(),
+        succeeded: repos.len() - failed,
+        failed,
+        repos,
+    }
+}
+
+/// Fetch the default remote of every repository of a workspace
+pub fn fetch_workspace(workspace: &Workspace) -> WorkspaceBatchResult<FetchResult> {
+    run_workspace_batch(workspace, |path| {
+        let repo = Repository::open(path)?;
+        let remote_name = get_default_remote(&repo)?;
+        fetch(&repo, &remote_name)
+    })
+}
+
+/// Pull the checked-out branch of every repository of a workspace, optionally
+/// stashing local changes around the pull
+pub fn pull_workspace(workspace: &Workspace, auto_stash: bool) -> WorkspaceBatchResult<PullResult> {
+    run_workspace_batch(workspace, |path| {
+        let mut repo = Repository::open(path)?;
+        let remote_name = get_default_remote(&repo)?;
+        let branch_name = repo.head()?.shorthand().unwrap_or("main").to_string();
+
+        if !auto_stash {
+            return pull(&repo, &remote_name, &branch_name);
+        }
+        let (mut result, outcome) =
+            with_auto_stash(&mut repo, "pull", |repo| pull(repo, &remote_name, &branch_name))?;
+        result.auto_stash = Some(outcome);
+        Ok(result)
+    })
+}
+
+/// Get the sync status and dirty state of every repository of a workspace
+pub fn get_workspace_status(workspace: &Workspace) -> Vec<RepoSyncSummary> {
+    get_all_repo_sync_statuses(&workspace.paths)
+}
+
+/// Name of the settings file in the app config directory
+const SETTINGS_FILE: &str = "workspaces.json";
+
+/// Load the saved workspaces, or none if none were saved
+pub fn load_workspace_settings() -> GitResult<WorkspaceSettings> {
+    Ok(load_json_settings(SETTINGS_FILE)?)
+}
+
+/// Save the workspaces
+pub fn save_workspace_settings(settings: &WorkspaceSettings) -> GitResult<()> {
+    Ok(save_json_settings(SETTINGS_FILE, settings)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn workspace(name: &str, paths: &[&str]) -> Workspace {
+        Workspace {
+            name: name.to_string(),
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_workspace_settings_upsert_and_remove() {
+        let mut settings = WorkspaceSettings::default();
+        settings.upsert(workspace(" services ", &["/a", " /b", "/a", ""])).unwrap();
+        assert_eq!(settings.find("services").unwrap().paths, vec!["/a", "/b"]);
+
+        settings.upsert(workspace("services", &["/c"])).unwrap();
+        assert_eq!(settings.workspaces.len(), 1);
+        assert_eq!(settings.find("services").unwrap().paths, vec!["/c"]);
+
+        assert!(settings.upsert(workspace("  ", &[])).is_err());
+        assert!(settings.remove("services"));
+        assert!(!settings.remove("services"));
+    }
+
+    #[test]
+    fn test_run_workspace_batch() {
+        let dir = tempdir().unwrap();
+        let repo_path = dir.path().join("repo");
+        Repository::init(&repo_path).unwrap();
+        let missing = dir.path().join("missing");
+
+        let ws = Workspace {
+            name: "fleet".to_string(),
+            paths: vec![repo_path.to_string_lossy().to_string(), missing.to_string_lossy().to_string()],
+        };
+        let batch = run_workspace_batch(&ws, |path| Ok(Repository::open(path)?.is_bare()));
+
+        assert_eq!(batch.workspace, "fleet");
+        assert_eq!((batch.succeeded, batch.failed), (1, 1));
+        assert_eq!(batch.repos[0].result, Some(false));
+        assert!(batch.repos[1].error.is_some());
+    }
+}
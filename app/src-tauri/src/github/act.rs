@@ -0,0 +1,132 @@
+//! Running GitHub Actions workflows locally with `act`
+//!
+//! [`act`](https://github.com/nektos/act) runs workflow jobs in Docker
+//! containers on this machine, so CI failures can be debugged before pushing.
+//! It has to be installed separately and found on `PATH`.
+
+use serde::{Deserialize, Serialize};
+
+/// Event streaming the output and state changes of a local workflow run
+pub const LOCAL_WORKFLOW_RUN_EVENT: &str = "local-workflow-run";
+
+/// Name of the `act` executable
+pub const ACT_BINARY: &str = "act";
+
+/// Whether `act` is available
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActStatus {
+    pub installed: bool,
+    pub version: Option<String>,
+}
+
+/// What to run with `act`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LocalWorkflowRunOptions {
+    /// Workflow file name in `.github/workflows`; all workflows when missing
+    pub workflow: Option<String>,
+    /// Job id to run; all jobs triggered by the event when missing
+    pub job: Option<String>,
+    /// Event that triggers the workflows, `push` when missing
+    pub event: Option<String>,
+    /// Docker image for `runs-on: ubuntu-latest` jobs, instead of act's default
+    pub ubuntu_image: Option<String>,
+    /// Show only what would run without starting any containers
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Output line or state change of a local workflow run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalWorkflowRunEvent {
+    /// `running`, `success`, `failure`, or `cancelled`
+    pub state: Option<String>,
+    pub line: Option<String>,
+}
+
+/// Outcome of a local workflow run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalWorkflowRunResult {
+    pub success: bool,
+    pub cancelled: bool,
+    pub exit_code: Option<i32>,
+}
+
+/// Arguments passed to `act` for a run
+pub fn act_args(options: &LocalWorkflowRunOptions) -> Vec<String> {
+    let non_empty = |value: &Option<String>| value.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(str::to_string);
+
+    let mut args = vec![non_empty(&options.event).unwrap_or_else(|| "push".to_string())];
+    if let Some(workflow) = non_empty(&options.workflow) {
+        args.push("--workflows".to_string());
+        args.push(format!(".github/workflows/{}", workflow));
+    }
+    if let Some(job) = non_empty(&options.job) {
+        args.push("--job".to_string());
+        args.push(job);
+    }
+    if let Some(image) = non_empty(&options.ubuntu_image) {
+        args.push("--platform".to_string());
+        args.push(format!("ubuntu-latest={}", image));
+    }
+    if options.dry_run {
+        args.push("--dryrun".to_string());
+    }
+    args
+}
+
+/// Read the version number from the output of `act --version`
+pub fn parse_act_version(output: &str) -> Option<String> {
+    output
+        .split_whitespace()
+        .last()
+        .filter(|v| v.starts_with(|c: char| c.is_ascii_digit()))
+        .map(str::to_string)
+}
+
+/// Check whether `act` is installed
+pub async fn detect_act() -> ActStatus {
+    let output = tokio::process::Command::new(ACT_BINARY)
+        .arg("--version")
+        .stdin(std::process::Stdio::null())
+        .output()
+        .await;
+
+    match output {
+        Ok(output) if output.status.success() => ActStatus {
+            installed: true,
+            version: parse_act_version(&String::from_utf8_lossy(&output.stdout)),
+        },
+        _ => ActStatus {
+            installed: false,
+            version: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_act_args() {
+        assert_eq!(act_args(&LocalWorkflowRunOptions::default()), vec!["push"]);
+
+        let options = LocalWorkflowRunOptions {
+            workflow: Some("ci.yml".to_string()),
+            job: Some("test".to_string()),
+            event: Some("pull_request".to_string()),
+            ubuntu_image: Some(" ".to_string()),
+            dry_run: true,
+        };
+        assert_eq!(
+            act_args(&options),
+            vec!["pull_request", "--workflows", ".github/workflows/ci.yml", "--job", "test", "--dryrun"]
+        );
+    }
+
+    #[test]
+    fn test_parse_act_version() {
+        assert_eq!(parse_act_version("act version 0.2.61\n"), Some("0.2.61".to_string()));
+        assert_eq!(parse_act_version("usage: act"), None);
+    }
+}
@@ -2,10 +2,13 @@
 //!
 //! Provides access to GitHub Actions workflows, runs, and artifacts.
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 use reqwest::Client;
 
-use super::get_stored_token;
+use super::app_auth::token_for_repo;
+use super::job_logs::{parse_job_log, JobLog};
 
 /// GitHub Workflow
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -106,6 +109,83 @@ struct ArtifactsResponse {
     artifacts: Vec<Artifact>,
 }
 
+/// GitHub Actions cache entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionsCache {
+    pub id: i64,
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    pub key: String,
+    pub version: String,
+    pub last_accessed_at: String,
+    pub created_at: String,
+    pub size_in_bytes: i64,
+}
+
+/// A page of Actions caches with the total number of matches
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionsCacheList {
+    pub total_count: i32,
+    pub actions_caches: Vec<ActionsCache>,
+}
+
+/// Actions cache usage of a repository
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionsCacheUsage {
+    pub full_name: String,
+    pub active_caches_size_in_bytes: i64,
+    pub active_caches_count: i32,
+}
+
+/// Billable time of a single job run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobRunTiming {
+    pub job_id: i64,
+    pub duration_ms: i64,
+}
+
+/// Billable time on one runner OS (`UBUNTU`, `MACOS`, `WINDOWS`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunnerBillable {
+    pub total_ms: i64,
+    #[serde(default)]
+    pub jobs: i32,
+    #[serde(default)]
+    pub job_runs: Vec<JobRunTiming>,
+}
+
+/// Billable usage of a workflow run, keyed by runner OS
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowRunUsage {
+    #[serde(default)]
+    pub billable: BTreeMap<String, RunnerBillable>,
+    pub run_duration_ms: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WorkflowTiming {
+    #[serde(default)]
+    billable: BTreeMap<String, RunnerBillable>,
+}
+
+/// Billable time of a workflow in the current billing cycle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowUsage {
+    pub workflow_id: i64,
+    pub name: String,
+    pub path: String,
+    pub billable_ms: BTreeMap<String, i64>,
+    pub total_ms: i64,
+}
+
+/// Billable Actions time of a repository, per workflow and per runner OS
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoActionsUsage {
+    pub workflows: Vec<WorkflowUsage>,
+    pub billable_ms_by_os: BTreeMap<String, i64>,
+    pub total_ms: i64,
+}
+
 /// Error type for actions API
 #[derive(Debug)]
 pub struct ActionsError(pub String);
@@ -120,15 +200,16 @@ impl std::error::Error for ActionsError {}
 
 pub type ActionsResult<T> = Result<T, ActionsError>;
 
-fn get_client() -> ActionsResult<(Client, String)> {
-    let token = get_stored_token().map_err(|e| ActionsError(e.to_string()))?;
-    let client = Client::new();
-    Ok((client, token))
+/// Client and token for a repository, authenticating as its GitHub App
+/// installation if one is configured for it
+async fn get_repo_client(owner: &str, repo: &str) -> ActionsResult<(Client, String)> {
+    let token = token_for_repo(owner, repo).await.map_err(|e| ActionsError(e.to_string()))?;
+    Ok((Client::new(), token))
 }
 
 /// List all workflows for a repository
 pub async fn list_workflows(owner: &str, repo: &str) -> ActionsResult<Vec<Workflow>> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!("https://api.github.com/repos/{}/{}/actions/workflows", owner, repo);
 
@@ -165,7 +246,7 @@ pub async fn list_workflow_runs(
     status: Option<&str>,
     per_page: Option<u32>,
 ) -> ActionsResult<Vec<WorkflowRun>> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = if let Some(wid) = workflow_id {
         format!(
@@ -214,7 +295,7 @@ pub async fn list_workflow_runs(
 
 /// Get a specific workflow run
 pub async fn get_workflow_run(owner: &str, repo: &str, run_id: i64) -> ActionsResult<WorkflowRun> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/actions/runs/{}",
@@ -249,7 +330,7 @@ pub async fn get_workflow_run_jobs(
     repo: &str,
     run_id: i64,
 ) -> ActionsResult<Vec<WorkflowJob>> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/actions/runs/{}/jobs",
@@ -282,7 +363,7 @@ pub async fn get_workflow_run_jobs(
 
 /// Get logs for a workflow run (returns download URL)
 pub async fn get_workflow_run_logs(owner: &str, repo: &str, run_id: i64) -> ActionsResult<String> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/actions/runs/{}/logs",
@@ -313,6 +394,79 @@ pub async fn get_workflow_run_logs(owner: &str, repo: &str, run_id: i64) -> Acti
     Ok(response.url().to_string())
 }
 
+/// Get a single workflow job
+pub async fn get_job(owner: &str, repo: &str, job_id: i64) -> ActionsResult<WorkflowJob> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/actions/jobs/{}",
+        owner, repo, job_id
+    );
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| ActionsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(ActionsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| ActionsError(format!("Failed to parse response: {}", e)))
+}
+
+/// Download the plain-text log of a workflow job
+pub async fn download_job_log(owner: &str, repo: &str, job_id: i64) -> ActionsResult<String> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/actions/jobs/{}/logs",
+        owner, repo, job_id
+    );
+
+    // GitHub redirects to a short-lived download URL, which reqwest follows
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| ActionsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(ActionsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .text()
+        .await
+        .map_err(|e| ActionsError(format!("Failed to read log: {}", e)))
+}
+
+/// Download a job log and split it into per-step sections
+pub async fn get_job_log(owner: &str, repo: &str, job_id: i64) -> ActionsResult<JobLog> {
+    let (job, log) = tokio::try_join!(
+        get_job(owner, repo, job_id),
+        download_job_log(owner, repo, job_id),
+    )?;
+
+    Ok(parse_job_log(job_id, &log, job.steps.as_deref().unwrap_or_default()))
+}
+
 /// Trigger a workflow dispatch event
 pub async fn trigger_workflow(
     owner: &str,
@@ -321,7 +475,7 @@ pub async fn trigger_workflow(
     ref_name: &str,
     inputs: Option<serde_json::Value>,
 ) -> ActionsResult<()> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/actions/workflows/{}/dispatches",
@@ -358,7 +512,7 @@ pub async fn trigger_workflow(
 
 /// Cancel a workflow run
 pub async fn cancel_workflow_run(owner: &str, repo: &str, run_id: i64) -> ActionsResult<()> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/actions/runs/{}/cancel",
@@ -386,7 +540,7 @@ pub async fn cancel_workflow_run(owner: &str, repo: &str, run_id: i64) -> Action
 
 /// Re-run a workflow
 pub async fn rerun_workflow(owner: &str, repo: &str, run_id: i64) -> ActionsResult<()> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/actions/runs/{}/rerun",
@@ -414,7 +568,7 @@ pub async fn rerun_workflow(owner: &str, repo: &str, run_id: i64) -> ActionsResu
 
 /// Re-run failed jobs only
 pub async fn rerun_failed_jobs(owner: &str, repo: &str, run_id: i64) -> ActionsResult<()> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/actions/runs/{}/rerun-failed-jobs",
@@ -446,7 +600,7 @@ pub async fn list_run_artifacts(
     repo: &str,
     run_id: i64,
 ) -> ActionsResult<Vec<Artifact>> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/actions/runs/{}/artifacts",
@@ -479,7 +633,7 @@ pub async fn list_run_artifacts(
 
 /// List all artifacts for a repository
 pub async fn list_repo_artifacts(owner: &str, repo: &str) -> ActionsResult<Vec<Artifact>> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/actions/artifacts",
@@ -516,7 +670,7 @@ pub async fn get_artifact_download_url(
     repo: &str,
     artifact_id: i64,
 ) -> ActionsResult<String> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/actions/artifacts/{}/zip",
@@ -539,7 +693,7 @@ pub async fn get_artifact_download_url(
 
 /// Delete an artifact
 pub async fn delete_artifact(owner: &str, repo: &str, artifact_id: i64) -> ActionsResult<()> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/actions/artifacts/{}",
@@ -567,7 +721,7 @@ pub async fn delete_artifact(owner: &str, repo: &str, artifact_id: i64) -> Actio
 
 /// Delete a workflow run
 pub async fn delete_workflow_run(owner: &str, repo: &str, run_id: i64) -> ActionsResult<()> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/actions/runs/{}",
@@ -592,3 +746,304 @@ pub async fn delete_workflow_run(owner: &str, repo: &str, run_id: i64) -> Action
 
     Ok(())
 }
+
+/// List Actions caches, optionally filtered by key prefix and git ref
+pub async fn list_caches(
+    owner: &str,
+    repo: &str,
+    key: Option<&str>,
+    git_ref: Option<&str>,
+    sort: Option<&str>,
+    per_page: Option<u32>,
+) -> ActionsResult<ActionsCacheList> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!("https://api.github.com/repos/{}/{}/actions/caches", owner, repo);
+
+    let mut query: Vec<(&str, String)> = vec![("per_page", per_page.unwrap_or(100).to_string())];
+    if let Some(k) = key {
+        query.push(("key", k.to_string()));
+    }
+
+    if let Some(r) = git_ref {
+        query.push(("ref", r.to_string()));
+    }
+
+    if let Some(s) = sort {
+        query.push(("sort", s.to_string()));
+    }
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .query(&query)
+        .send()
+        .await
+        .map_err(|e| ActionsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(ActionsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| ActionsError(format!("Failed to parse response: {}", e)))
+}
+
+/// Get the total Actions cache usage of a repository
+pub async fn get_cache_usage(owner: &str, repo: &str) -> ActionsResult<ActionsCacheUsage> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/actions/cache/usage",
+        owner, repo
+    );
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| ActionsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(ActionsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| ActionsError(format!("Failed to parse response: {}", e)))
+}
+
+/// Delete an Actions cache by id
+pub async fn delete_cache(owner: &str, repo: &str, cache_id: i64) -> ActionsResult<()> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/actions/caches/{}",
+        owner, repo, cache_id
+    );
+
+    let response = client
+        .delete(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| ActionsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(ActionsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    Ok(())
+}
+
+/// Delete all Actions caches with an exact key, optionally restricted to a git ref
+pub async fn delete_caches_by_key(
+    owner: &str,
+    repo: &str,
+    key: &str,
+    git_ref: Option<&str>,
+) -> ActionsResult<ActionsCacheList> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!("https://api.github.com/repos/{}/{}/actions/caches", owner, repo);
+
+    let mut query = vec![("key", key)];
+    if let Some(r) = git_ref {
+        query.push(("ref", r));
+    }
+
+    let response = client
+        .delete(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .query(&query)
+        .send()
+        .await
+        .map_err(|e| ActionsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(ActionsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| ActionsError(format!("Failed to parse response: {}", e)))
+}
+
+/// Get the billable time of a workflow run
+pub async fn get_workflow_run_usage(
+    owner: &str,
+    repo: &str,
+    run_id: i64,
+) -> ActionsResult<WorkflowRunUsage> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/actions/runs/{}/timing",
+        owner, repo, run_id
+    );
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| ActionsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(ActionsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| ActionsError(format!("Failed to parse response: {}", e)))
+}
+
+/// Get the billable time of a workflow in the current billing cycle
+async fn get_workflow_timing(
+    owner: &str,
+    repo: &str,
+    workflow_id: i64,
+) -> ActionsResult<WorkflowTiming> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/actions/workflows/{}/timing",
+        owner, repo, workflow_id
+    );
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| ActionsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(ActionsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| ActionsError(format!("Failed to parse response: {}", e)))
+}
+
+/// Aggregate per-workflow usage into per-OS totals, most expensive workflows first
+pub fn summarize_usage(mut workflows: Vec<WorkflowUsage>) -> RepoActionsUsage {
+    let mut billable_ms_by_os = BTreeMap::new();
+    for workflow in &workflows {
+        for (os, ms) in &workflow.billable_ms {
+            *billable_ms_by_os.entry(os.clone()).or_insert(0) += ms;
+        }
+    }
+
+    workflows.sort_by(|a, b| b.total_ms.cmp(&a.total_ms).then_with(|| a.name.cmp(&b.name)));
+
+    RepoActionsUsage {
+        total_ms: billable_ms_by_os.values().sum(),
+        workflows,
+        billable_ms_by_os,
+    }
+}
+
+/// Get the billable Actions time of every workflow in a repository
+pub async fn get_repo_actions_usage(owner: &str, repo: &str) -> ActionsResult<RepoActionsUsage> {
+    let workflows = list_workflows(owner, repo).await?;
+    let mut usage = Vec::with_capacity(workflows.len());
+
+    for workflow in workflows {
+        let timing = get_workflow_timing(owner, repo, workflow.id).await?;
+        let billable_ms: BTreeMap<String, i64> = timing
+            .billable
+            .into_iter()
+            .map(|(os, billable)| (os, billable.total_ms))
+            .collect();
+
+        usage.push(WorkflowUsage {
+            workflow_id: workflow.id,
+            name: workflow.name,
+            path: workflow.path,
+            total_ms: billable_ms.values().sum(),
+            billable_ms,
+        });
+    }
+
+    Ok(summarize_usage(usage))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(name: &str, billable: &[(&str, i64)]) -> WorkflowUsage {
+        let billable_ms: BTreeMap<String, i64> =
+            billable.iter().map(|(os, ms)| (os.to_string(), *ms)).collect();
+        WorkflowUsage {
+            workflow_id: 1,
+            name: name.to_string(),
+            path: format!(".github/workflows/{}.yml", name),
+            total_ms: billable_ms.values().sum(),
+            billable_ms,
+        }
+    }
+
+    #[test]
+    fn test_summarize_usage() {
+        let summary = summarize_usage(vec![
+            usage("lint", &[("UBUNTU", 1_000)]),
+            usage("release", &[("UBUNTU", 2_000), ("MACOS", 5_000)]),
+        ]);
+
+        assert_eq!(summary.total_ms, 8_000);
+        assert_eq!(summary.billable_ms_by_os["UBUNTU"], 3_000);
+        assert_eq!(summary.workflows[0].name, "release");
+    }
+
+    #[test]
+    fn test_run_usage_parses_timing() {
+        let run: WorkflowRunUsage = serde_json::from_str(
+            r#"{"billable": {"UBUNTU": {"total_ms": 180000, "jobs": 1,
+                "job_runs": [{"job_id": 1, "duration_ms": 180000}]}},
+                "run_duration_ms": 500000}"#,
+        )
+        .unwrap();
+
+        assert_eq!(run.billable["UBUNTU"].job_runs[0].duration_ms, 180_000);
+    }
+}
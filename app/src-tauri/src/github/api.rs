@@ -54,6 +54,118 @@ pub struct GitHubRepo {
     pub stargazers_count: u32,
     pub forks_count: u32,
     pub updated_at: String,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+/// Organization the authenticated user belongs to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubOrg {
+    pub login: String,
+    pub id: u64,
+    pub avatar_url: String,
+    pub description: Option<String>,
+}
+
+/// Branch of a repository on GitHub
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubBranch {
+    pub name: String,
+    pub commit: GitHubBranchCommit,
+    #[serde(default)]
+    pub protected: bool,
+}
+
+/// Head commit of a branch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubBranchCommit {
+    pub sha: String,
+}
+
+/// Editable settings of a repository
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoSettings {
+    pub name: String,
+    pub full_name: String,
+    pub description: Option<String>,
+    pub homepage: Option<String>,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    pub default_branch: String,
+    pub private: bool,
+    pub visibility: Option<String>,
+    pub archived: bool,
+    pub has_issues: bool,
+    pub has_wiki: bool,
+    pub has_projects: bool,
+    #[serde(default)]
+    pub has_discussions: bool,
+    pub allow_squash_merge: Option<bool>,
+    pub allow_merge_commit: Option<bool>,
+    pub allow_rebase_merge: Option<bool>,
+    pub delete_branch_on_merge: Option<bool>,
+}
+
+/// Changes to apply to a repository's settings (unset fields are left unchanged)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RepoSettingsUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+    #[serde(skip_serializing)]
+    pub topics: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_branch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_issues: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_wiki: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_projects: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_discussions: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_squash_merge: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_merge_commit: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_rebase_merge: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete_branch_on_merge: Option<bool>,
+}
+
+/// Outcome of a repository transfer request
+///
+/// Transfers are processed asynchronously: `completed` is false while GitHub
+/// is still moving the repository, or while a user owner has yet to accept it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoTransfer {
+    pub repo: GitHubRepo,
+    pub new_full_name: String,
+    pub completed: bool,
+}
+
+/// Watch level of a repository for the authenticated user
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchLevel {
+    /// Notified of all activity
+    Watching,
+    /// Notified only when participating or mentioned
+    NotWatching,
+    /// Never notified
+    Ignoring,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RepoSubscription {
+    subscribed: bool,
+    ignored: bool,
 }
 
 /// Create a configured HTTP client with auth token
@@ -80,10 +192,8 @@ fn create_client(token: &str) -> Client {
         .unwrap()
 }
 
-/// Handle API response errors
-async fn handle_response<T: for<'de> Deserialize<'de>>(
-    response: reqwest::Response,
-) -> Result<T, GitHubApiError> {
+/// Check an API response for errors, returning it if successful
+async fn check_response(response: reqwest::Response) -> Result<reqwest::Response, GitHubApiError> {
     let status = response.status();
 
     if status == reqwest::StatusCode::UNAUTHORIZED {
@@ -115,12 +225,25 @@ async fn handle_response<T: for<'de> Deserialize<'de>>(
         )));
     }
 
-    response
+    Ok(response)
+}
+
+/// Handle API response errors
+async fn handle_response<T: for<'de> Deserialize<'de>>(
+    response: reqwest::Response,
+) -> Result<T, GitHubApiError> {
+    check_response(response)
+        .await?
         .json()
         .await
         .map_err(|e| GitHubApiError::ApiError(e.to_string()))
 }
 
+/// Handle API responses without a body (e.g. 204 No Content)
+async fn handle_empty_response(response: reqwest::Response) -> Result<(), GitHubApiError> {
+    check_response(response).await.map(|_| ())
+}
+
 /// Get the authenticated user's profile
 pub async fn get_current_user(token: &str) -> Result<GitHubUser, GitHubApiError> {
     let client = create_client(token);
@@ -179,6 +302,70 @@ pub async fn get_user_repos(
     handle_response(response).await
 }
 
+/// Get the organizations the authenticated user is a member of
+pub async fn get_user_orgs(token: &str) -> Result<Vec<GitHubOrg>, GitHubApiError> {
+    let client = create_client(token);
+    let url = format!("{}/user/orgs?per_page=100", GITHUB_API_URL);
+
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Get a page of an organization's repositories
+///
+/// `repo_type` is one of `all`, `public`, `private`, `forks`, `sources`, or `member`.
+pub async fn get_org_repos(
+    token: &str,
+    org: &str,
+    repo_type: Option<&str>,
+    page: u32,
+    per_page: u32,
+) -> Result<Vec<GitHubRepo>, GitHubApiError> {
+    let client = create_client(token);
+    let url = format!(
+        "{}/orgs/{}/repos?type={}&page={}&per_page={}&sort=updated&direction=desc",
+        GITHUB_API_URL,
+        org,
+        repo_type.unwrap_or("all"),
+        page,
+        per_page
+    );
+
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Keep repositories matching the archived and language filters
+///
+/// Languages are compared case-insensitively.
+pub fn filter_repos(
+    repos: Vec<GitHubRepo>,
+    include_archived: bool,
+    language: Option<&str>,
+) -> Vec<GitHubRepo> {
+    repos
+        .into_iter()
+        .filter(|r| include_archived || !r.archived)
+        .filter(|r| match language {
+            Some(lang) => r
+                .language
+                .as_deref()
+                .is_some_and(|l| l.eq_ignore_ascii_case(lang)),
+            None => true,
+        })
+        .collect()
+}
+
 /// Validate that a token is still valid
 pub async fn validate_token(token: &str) -> bool {
     get_current_user(token).await.is_ok()
@@ -202,6 +389,432 @@ pub async fn get_repo(
     handle_response(response).await
 }
 
+/// Create a repository for the authenticated user, or in an organization
+pub async fn create_repo(
+    token: &str,
+    name: &str,
+    description: Option<&str>,
+    private: bool,
+    org: Option<&str>,
+    gitignore_template: Option<&str>,
+    license_template: Option<&str>,
+) -> Result<GitHubRepo, GitHubApiError> {
+    let client = create_client(token);
+    let url = match org {
+        Some(org) => format!("{}/orgs/{}/repos", GITHUB_API_URL, org),
+        None => format!("{}/user/repos", GITHUB_API_URL),
+    };
+
+    let mut payload = serde_json::json!({
+        "name": name,
+        "private": private,
+    });
+    if let Some(d) = description {
+        payload["description"] = serde_json::json!(d);
+    }
+    // GitHub only applies templates to repositories created with an initial commit
+    if gitignore_template.is_some() || license_template.is_some() {
+        payload["auto_init"] = serde_json::json!(true);
+    }
+    if let Some(t) = gitignore_template {
+        payload["gitignore_template"] = serde_json::json!(t);
+    }
+    if let Some(l) = license_template {
+        payload["license_template"] = serde_json::json!(l);
+    }
+
+    let response = client
+        .post(&url)
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Fork a repository into the authenticated user's account, or an organization
+///
+/// GitHub creates forks asynchronously; use [`wait_for_repo_ready`] before relying on its contents.
+pub async fn fork_repo(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    org: Option<&str>,
+) -> Result<GitHubRepo, GitHubApiError> {
+    let client = create_client(token);
+    let url = format!("{}/repos/{}/{}/forks", GITHUB_API_URL, owner, repo);
+
+    let mut payload = serde_json::json!({});
+    if let Some(org) = org {
+        payload["organization"] = serde_json::json!(org);
+    }
+
+    let response = client
+        .post(&url)
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Poll a repository until it has at least one branch, e.g. after forking
+pub async fn wait_for_repo_ready(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    timeout_secs: u64,
+) -> Result<(), GitHubApiError> {
+    let client = create_client(token);
+    let url = format!("{}/repos/{}/{}/branches?per_page=1", GITHUB_API_URL, owner, repo);
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(timeout_secs);
+
+    loop {
+        let response = client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+        // 404 (not created yet) and 409 (empty repository) mean the fork is still being prepared
+        let status = response.status();
+        if status != reqwest::StatusCode::NOT_FOUND && status != reqwest::StatusCode::CONFLICT {
+            let branches: Vec<serde_json::Value> = handle_response(response).await?;
+            if !branches.is_empty() {
+                return Ok(());
+            }
+        }
+
+        if std::time::Instant::now() >= deadline {
+            return Err(GitHubApiError::ApiError(format!(
+                "Timed out waiting for {}/{} to become ready",
+                owner, repo
+            )));
+        }
+
+        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+    }
+}
+
+/// List all branches of a repository, optionally only the protected ones
+pub async fn list_branches(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    protected_only: bool,
+) -> Result<Vec<GitHubBranch>, GitHubApiError> {
+    let client = create_client(token);
+    let mut branches = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let mut url = format!(
+            "{}/repos/{}/{}/branches?per_page=100&page={}",
+            GITHUB_API_URL, owner, repo, page
+        );
+        if protected_only {
+            url.push_str("&protected=true");
+        }
+
+        let response = client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+        let batch: Vec<GitHubBranch> = handle_response(response).await?;
+        let done = batch.len() < 100;
+        branches.extend(batch);
+        if done {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(branches)
+}
+
+/// Get the editable settings of a repository
+pub async fn get_repo_settings(
+    token: &str,
+    owner: &str,
+    repo: &str,
+) -> Result<RepoSettings, GitHubApiError> {
+    let client = create_client(token);
+    let url = format!("{}/repos/{}/{}", GITHUB_API_URL, owner, repo);
+
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Replace the topics of a repository
+pub async fn replace_repo_topics(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    topics: &[String],
+) -> Result<Vec<String>, GitHubApiError> {
+    #[derive(Deserialize)]
+    struct Topics {
+        names: Vec<String>,
+    }
+
+    let client = create_client(token);
+    let url = format!("{}/repos/{}/{}/topics", GITHUB_API_URL, owner, repo);
+
+    let response = client
+        .put(&url)
+        .json(&serde_json::json!({ "names": topics }))
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    let data: Topics = handle_response(response).await?;
+    Ok(data.names)
+}
+
+/// Update the settings of a repository
+pub async fn update_repo_settings(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    update: &RepoSettingsUpdate,
+) -> Result<RepoSettings, GitHubApiError> {
+    let client = create_client(token);
+    let url = format!("{}/repos/{}/{}", GITHUB_API_URL, owner, repo);
+
+    let response = client
+        .patch(&url)
+        .json(update)
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    let mut settings: RepoSettings = handle_response(response).await?;
+
+    // Topics have their own endpoint
+    if let Some(topics) = &update.topics {
+        settings.topics = replace_repo_topics(token, owner, repo, topics).await?;
+    }
+
+    Ok(settings)
+}
+
+/// Archive or unarchive a repository
+pub async fn set_repo_archived(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    archived: bool,
+) -> Result<RepoSettings, GitHubApiError> {
+    let client = create_client(token);
+    let url = format!("{}/repos/{}/{}", GITHUB_API_URL, owner, repo);
+
+    let response = client
+        .patch(&url)
+        .json(&serde_json::json!({ "archived": archived }))
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Permanently delete a repository
+///
+/// `confirmation` must be the repository's full name (`owner/repo`), mirroring
+/// the confirmation GitHub asks for in its own UI.
+pub async fn delete_repo(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    confirmation: &str,
+) -> Result<(), GitHubApiError> {
+    let full_name = format!("{}/{}", owner, repo);
+    if confirmation.trim() != full_name {
+        return Err(GitHubApiError::ApiError(format!(
+            "Confirmation does not match repository name '{}'",
+            full_name
+        )));
+    }
+
+    let client = create_client(token);
+    let url = format!("{}/repos/{}", GITHUB_API_URL, full_name);
+
+    let response = client
+        .delete(&url)
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    handle_empty_response(response).await
+}
+
+/// Transfer a repository to another user or organization
+pub async fn transfer_repo(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    new_owner: &str,
+    new_name: Option<&str>,
+    team_ids: Vec<i64>,
+) -> Result<RepoTransfer, GitHubApiError> {
+    let client = create_client(token);
+    let url = format!("{}/repos/{}/{}/transfer", GITHUB_API_URL, owner, repo);
+
+    let mut payload = serde_json::json!({ "new_owner": new_owner });
+    if let Some(n) = new_name {
+        payload["new_name"] = serde_json::json!(n);
+    }
+    if !team_ids.is_empty() {
+        payload["team_ids"] = serde_json::json!(team_ids);
+    }
+
+    let response = client
+        .post(&url)
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    let moved: GitHubRepo = handle_response(response).await?;
+    let new_full_name = format!("{}/{}", new_owner, new_name.unwrap_or(repo));
+    let completed = moved.full_name.eq_ignore_ascii_case(&new_full_name);
+
+    Ok(RepoTransfer {
+        repo: moved,
+        new_full_name,
+        completed,
+    })
+}
+
+/// Star a repository
+pub async fn star_repo(token: &str, owner: &str, repo: &str) -> Result<(), GitHubApiError> {
+    let client = create_client(token);
+    let url = format!("{}/user/starred/{}/{}", GITHUB_API_URL, owner, repo);
+
+    let response = client
+        .put(&url)
+        .header(reqwest::header::CONTENT_LENGTH, 0)
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    handle_empty_response(response).await
+}
+
+/// Unstar a repository
+pub async fn unstar_repo(token: &str, owner: &str, repo: &str) -> Result<(), GitHubApiError> {
+    let client = create_client(token);
+    let url = format!("{}/user/starred/{}/{}", GITHUB_API_URL, owner, repo);
+
+    let response = client
+        .delete(&url)
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    handle_empty_response(response).await
+}
+
+/// Check whether the authenticated user has starred a repository
+pub async fn is_repo_starred(token: &str, owner: &str, repo: &str) -> Result<bool, GitHubApiError> {
+    let client = create_client(token);
+    let url = format!("{}/user/starred/{}/{}", GITHUB_API_URL, owner, repo);
+
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(false);
+    }
+
+    handle_empty_response(response).await.map(|_| true)
+}
+
+/// Get repositories starred by the authenticated user
+pub async fn get_starred_repos(
+    token: &str,
+    page: u32,
+    per_page: u32,
+) -> Result<Vec<GitHubRepo>, GitHubApiError> {
+    let client = create_client(token);
+    let url = format!(
+        "{}/user/starred?page={}&per_page={}&sort=updated&direction=desc",
+        GITHUB_API_URL, page, per_page
+    );
+
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    handle_response(response).await
+}
+
+/// Get the authenticated user's watch level for a repository
+pub async fn get_watch_level(token: &str, owner: &str, repo: &str) -> Result<WatchLevel, GitHubApiError> {
+    let client = create_client(token);
+    let url = format!("{}/repos/{}/{}/subscription", GITHUB_API_URL, owner, repo);
+
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    // No subscription means the default "participating and @mentions" level
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(WatchLevel::NotWatching);
+    }
+
+    let subscription: RepoSubscription = handle_response(response).await?;
+    Ok(if subscription.ignored {
+        WatchLevel::Ignoring
+    } else if subscription.subscribed {
+        WatchLevel::Watching
+    } else {
+        WatchLevel::NotWatching
+    })
+}
+
+/// Set the authenticated user's watch level for a repository
+pub async fn set_watch_level(
+    token: &str,
+    owner: &str,
+    repo: &str,
+    level: WatchLevel,
+) -> Result<WatchLevel, GitHubApiError> {
+    let client = create_client(token);
+    let url = format!("{}/repos/{}/{}/subscription", GITHUB_API_URL, owner, repo);
+
+    let response = match level {
+        WatchLevel::NotWatching => client.delete(&url).send().await,
+        WatchLevel::Watching | WatchLevel::Ignoring => {
+            let payload = RepoSubscription {
+                subscribed: level == WatchLevel::Watching,
+                ignored: level == WatchLevel::Ignoring,
+            };
+            client.put(&url).json(&payload).send().await
+        }
+    }
+    .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    handle_empty_response(response).await?;
+    Ok(level)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -212,4 +825,43 @@ mod tests {
         // Just verify it doesn't panic
         assert!(true);
     }
+
+    #[test]
+    fn test_filter_repos() {
+        let repo = |name: &str, archived: bool, language: Option<&str>| -> GitHubRepo {
+            serde_json::from_value(serde_json::json!({
+                "id": 1, "name": name, "full_name": format!("org/{}", name), "description": null,
+                "private": false, "html_url": "", "clone_url": "", "ssh_url": "",
+                "default_branch": "main", "stargazers_count": 0, "forks_count": 0,
+                "updated_at": "", "archived": archived, "language": language
+            }))
+            .unwrap()
+        };
+        let repos = vec![
+            repo("app", false, Some("Rust")),
+            repo("old", true, Some("Rust")),
+            repo("web", false, Some("TypeScript")),
+        ];
+
+        let names = |repos: Vec<GitHubRepo>| repos.into_iter().map(|r| r.name).collect::<Vec<_>>();
+        assert_eq!(names(filter_repos(repos.clone(), false, None)), ["app", "web"]);
+        assert_eq!(names(filter_repos(repos.clone(), true, Some("rust"))), ["app", "old"]);
+        assert_eq!(names(filter_repos(repos, false, Some("Go"))), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_settings_update_omits_unset_fields() {
+        let update = RepoSettingsUpdate {
+            description: Some("A git client".to_string()),
+            topics: Some(vec!["git".to_string()]),
+            has_wiki: Some(false),
+            ..Default::default()
+        };
+
+        let value = serde_json::to_value(&update).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({ "description": "A git client", "has_wiki": false })
+        );
+    }
 }
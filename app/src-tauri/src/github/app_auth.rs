@@ -0,0 +1,299 @@
+//! GitHub App installation authentication
+//!
+//! Authenticates as a GitHub App installation: the app's private key signs a
+//! short-lived JWT, which is exchanged for an installation access token.
+//! Repositories can be configured to use an app instead of the user's OAuth token.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Mutex, OnceLock};
+
+use chrono::{DateTime, Utc};
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use super::get_stored_token;
+use crate::settings::{load_json_settings, save_json_settings};
+
+const KEYRING_SERVICE: &str = "linuxgit";
+
+/// Installation tokens are renewed this long before they expire
+const TOKEN_REFRESH_MARGIN_SECS: i64 = 300;
+
+/// A registered GitHub App (its private key is kept in the system keyring)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitHubApp {
+    pub app_id: String,
+    pub name: String,
+}
+
+/// Registered apps and the repositories that authenticate with them
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AppAuthConfig {
+    #[serde(default)]
+    pub apps: Vec<GitHubApp>,
+    /// `owner/repo` (lowercase) to app id
+    #[serde(default)]
+    pub repositories: BTreeMap<String, String>,
+}
+
+/// Installation access token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallationToken {
+    pub token: String,
+    pub expires_at: String,
+}
+
+#[derive(Debug, Serialize)]
+struct AppClaims {
+    iat: i64,
+    exp: i64,
+    iss: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Installation {
+    id: i64,
+}
+
+/// Error type for GitHub App authentication
+#[derive(Debug)]
+pub struct AppAuthError(pub String);
+
+impl std::fmt::Display for AppAuthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for AppAuthError {}
+
+impl From<crate::settings::SettingsError> for AppAuthError {
+    fn from(e: crate::settings::SettingsError) -> Self {
+        AppAuthError(e.0)
+    }
+}
+
+pub type AppAuthResult<T> = Result<T, AppAuthError>;
+
+/// Installation tokens by app id and `owner/repo`
+static TOKEN_CACHE: OnceLock<Mutex<HashMap<(String, String), InstallationToken>>> = OnceLock::new();
+
+fn token_cache() -> &'static Mutex<HashMap<(String, String), InstallationToken>> {
+    TOKEN_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+impl AppAuthConfig {
+    /// App id configured for a repository
+    pub fn app_for_repo(&self, owner: &str, repo: &str) -> Option<&str> {
+        self.repositories.get(&repo_key(owner, repo)).map(|id| id.as_str())
+    }
+}
+
+fn repo_key(owner: &str, repo: &str) -> String {
+    format!("{}/{}", owner, repo).to_lowercase()
+}
+
+fn keyring_username(app_id: &str) -> String {
+    format!("github_app_{}", app_id)
+}
+
+/// Name of the settings file in the app config directory
+const SETTINGS_FILE: &str = "github_apps.json";
+
+/// Load the app configuration (empty if none has been saved)
+pub fn load_config() -> AppAuthResult<AppAuthConfig> {
+    Ok(load_json_settings(SETTINGS_FILE)?)
+}
+
+fn save_config(config: &AppAuthConfig) -> AppAuthResult<()> {
+    Ok(save_json_settings(SETTINGS_FILE, config)?)
+}
+
+fn get_private_key(app_id: &str) -> AppAuthResult<String> {
+    keyring::Entry::new(KEYRING_SERVICE, &keyring_username(app_id))
+        .and_then(|entry| entry.get_password())
+        .map_err(|_| AppAuthError(format!("No private key stored for GitHub App {}", app_id)))
+}
+
+/// Create the JWT that authenticates as the app itself (valid for 9 minutes)
+pub fn create_app_jwt(app_id: &str, private_key_pem: &str, now: i64) -> AppAuthResult<String> {
+    let key = EncodingKey::from_rsa_pem(private_key_pem.as_bytes())
+        .map_err(|e| AppAuthError(format!("Invalid private key: {}", e)))?;
+
+    // Backdate the issue time to allow for clock drift
+    let claims = AppClaims {
+        iat: now - 60,
+        exp: now + 9 * 60,
+        iss: app_id.to_string(),
+    };
+
+    encode(&Header::new(Algorithm::RS256), &claims, &key)
+        .map_err(|e| AppAuthError(format!("Failed to sign JWT: {}", e)))
+}
+
+/// Register an app, storing its private key in the system keyring
+pub fn register_app(app_id: &str, name: &str, private_key_pem: &str) -> AppAuthResult<GitHubApp> {
+    // Fail early on keys that cannot sign
+    create_app_jwt(app_id, private_key_pem, Utc::now().timestamp())?;
+
+    keyring::Entry::new(KEYRING_SERVICE, &keyring_username(app_id))
+        .and_then(|entry| entry.set_password(private_key_pem))
+        .map_err(|e| AppAuthError(format!("Failed to store private key: {}", e)))?;
+
+    let app = GitHubApp {
+        app_id: app_id.to_string(),
+        name: name.to_string(),
+    };
+
+    let mut config = load_config()?;
+    config.apps.retain(|a| a.app_id != app_id);
+    config.apps.push(app.clone());
+    save_config(&config)?;
+
+    Ok(app)
+}
+
+/// Remove an app, its private key, and the repositories using it
+pub fn remove_app(app_id: &str) -> AppAuthResult<()> {
+    if let Ok(entry) = keyring::Entry::new(KEYRING_SERVICE, &keyring_username(app_id)) {
+        // Ignore error if the key doesn't exist
+        let _ = entry.delete_password();
+    }
+
+    let mut config = load_config()?;
+    config.apps.retain(|a| a.app_id != app_id);
+    config.repositories.retain(|_, id| id != app_id);
+    save_config(&config)?;
+
+    token_cache().lock().unwrap().retain(|(id, _), _| id != app_id);
+    Ok(())
+}
+
+/// Select the app a repository authenticates with, or the user's token with `None`
+pub fn set_repo_app(owner: &str, repo: &str, app_id: Option<&str>) -> AppAuthResult<()> {
+    let mut config = load_config()?;
+    let key = repo_key(owner, repo);
+
+    match app_id {
+        Some(id) => {
+            if !config.apps.iter().any(|a| a.app_id == id) {
+                return Err(AppAuthError(format!("GitHub App {} is not registered", id)));
+            }
+            config.repositories.insert(key.clone(), id.to_string());
+        }
+        None => {
+            config.repositories.remove(&key);
+        }
+    }
+    save_config(&config)?;
+
+    token_cache().lock().unwrap().retain(|(_, repo), _| *repo != key);
+    Ok(())
+}
+
+/// Whether a token is still usable at `now`, leaving a margin before expiry
+fn is_token_fresh(expires_at: &str, now: DateTime<Utc>) -> bool {
+    DateTime::parse_from_rfc3339(expires_at)
+        .map(|exp| exp.timestamp() - now.timestamp() > TOKEN_REFRESH_MARGIN_SECS)
+        .unwrap_or(false)
+}
+
+async fn send_app_request(request: reqwest::RequestBuilder, jwt: &str) -> AppAuthResult<reqwest::Response> {
+    let response = request
+        .header("Authorization", format!("Bearer {}", jwt))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| AppAuthError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(AppAuthError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    Ok(response)
+}
+
+/// Get an installation token of `app_id` for a repository, reusing a cached one while it is fresh
+pub async fn installation_token(owner: &str, repo: &str, app_id: &str) -> AppAuthResult<InstallationToken> {
+    let key = (app_id.to_string(), repo_key(owner, repo));
+    if let Some(cached) = token_cache().lock().unwrap().get(&key) {
+        if is_token_fresh(&cached.expires_at, Utc::now()) {
+            return Ok(cached.clone());
+        }
+    }
+
+    let private_key = get_private_key(app_id)?;
+    let jwt = create_app_jwt(app_id, &private_key, Utc::now().timestamp())?;
+    let client = Client::new();
+
+    let url = format!("https://api.github.com/repos/{}/{}/installation", owner, repo);
+    let installation: Installation = send_app_request(client.get(&url), &jwt)
+        .await?
+        .json()
+        .await
+        .map_err(|e| AppAuthError(format!("Failed to parse response: {}", e)))?;
+
+    let url = format!(
+        "https://api.github.com/app/installations/{}/access_tokens",
+        installation.id
+    );
+    let token: InstallationToken = send_app_request(client.post(&url), &jwt)
+        .await?
+        .json()
+        .await
+        .map_err(|e| AppAuthError(format!("Failed to parse response: {}", e)))?;
+
+    token_cache().lock().unwrap().insert(key, token.clone());
+    Ok(token)
+}
+
+/// Token to use for a repository: its app's installation token if one is
+/// configured, otherwise the user's OAuth token
+pub async fn token_for_repo(owner: &str, repo: &str) -> AppAuthResult<String> {
+    let config = load_config()?;
+    match config.app_for_repo(owner, repo) {
+        Some(app_id) => Ok(installation_token(owner, repo, app_id).await?.token),
+        None => get_stored_token().map_err(|e| AppAuthError(e.to_string())),
+    }
+}
+
+/// Installation token for a repository configured to use an app, for synchronous callers
+/// such as git credential callbacks
+pub fn installation_token_blocking(owner: &str, repo: &str) -> Option<String> {
+    let config = load_config().ok()?;
+    let app_id = config.app_for_repo(owner, repo)?;
+
+    let future = installation_token(owner, repo, app_id);
+    let token = match tokio::runtime::Handle::try_current() {
+        Ok(handle) => tokio::task::block_in_place(|| handle.block_on(future)),
+        Err(_) => tauri::async_runtime::block_on(future),
+    };
+    token.ok().map(|t| t.token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_app_for_repo_is_case_insensitive() {
+        let mut config = AppAuthConfig::default();
+        config.repositories.insert(repo_key("Acme", "Deploy"), "42".to_string());
+
+        assert_eq!(config.app_for_repo("acme", "deploy"), Some("42"));
+        assert_eq!(config.app_for_repo("acme", "other"), None);
+    }
+
+    #[test]
+    fn test_is_token_fresh() {
+        let now = DateTime::parse_from_rfc3339("2024-01-01T12:00:00Z").unwrap().with_timezone(&Utc);
+        assert!(is_token_fresh("2024-01-01T13:00:00Z", now));
+        assert!(!is_token_fresh("2024-01-01T12:04:00Z", now));
+        assert!(!is_token_fresh("not a date", now));
+    }
+}
@@ -0,0 +1,205 @@
+//! Avatars of commit authors
+//!
+//! Author emails are resolved to avatar URLs without an API call when they are
+//! GitHub noreply addresses, otherwise by looking the email up with the user
+//! search API, falling back to Gravatar. Results are cached on disk so the
+//! history view doesn't repeat lookups, and the number of API lookups per batch
+//! is capped to stay well within the search rate limit.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::search::{search_users, SearchError, SearchResult};
+
+/// How long a resolved avatar is reused before it is looked up again
+const AVATAR_CACHE_TTL_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// API lookups made per batch; the other emails get a Gravatar URL until a later batch
+const MAX_LOOKUPS_PER_BATCH: usize = 10;
+
+/// Where an avatar URL came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AvatarSource {
+    /// A GitHub noreply email, which names the account
+    Noreply,
+    /// The GitHub account with the email as its public email
+    GitHub,
+    Gravatar,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedAvatar {
+    pub url: String,
+    pub source: AvatarSource,
+    /// Unix timestamp of the lookup
+    pub fetched_at: i64,
+}
+
+/// Cached avatars by lowercased email
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AvatarCache {
+    #[serde(default)]
+    pub avatars: HashMap<String, CachedAvatar>,
+}
+
+impl AvatarCache {
+    /// The cached avatar of an email, if it is still fresh at `now`
+    pub fn get_fresh(&self, email: &str, now: i64) -> Option<&CachedAvatar> {
+        self.avatars
+            .get(email)
+            .filter(|avatar| now - avatar.fetched_at < AVATAR_CACHE_TTL_SECS)
+    }
+}
+
+fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+/// Avatar URL of a GitHub noreply email (`id+login@` or `login@users.noreply.github.com`)
+pub fn noreply_avatar_url(email: &str) -> Option<String> {
+    let local = email.strip_suffix("@users.noreply.github.com")?;
+    match local.split_once('+') {
+        Some((id, _)) if !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()) => {
+            Some(format!("https://avatars.githubusercontent.com/u/{}?v=4", id))
+        }
+        Some((_, login)) if !login.is_empty() => Some(format!("https://github.com/{}.png", login)),
+        None if !local.is_empty() => Some(format!("https://github.com/{}.png", local)),
+        _ => None,
+    }
+}
+
+/// Gravatar URL of an email, showing a generated pattern if it has none
+pub fn gravatar_url(email: &str) -> String {
+    let hash: String = Sha256::digest(normalize_email(email).as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("https://www.gravatar.com/avatar/{}?d=identicon&s=80", hash)
+}
+
+/// Look up the GitHub account whose public email is `email`
+async fn lookup_github_avatar(email: &str) -> SearchResult<Option<String>> {
+    let results = search_users(&format!("{} in:email", email), Some(1), Some(1)).await?;
+    Ok(results.items.into_iter().next().map(|user| user.avatar_url))
+}
+
+fn cache_path() -> Result<PathBuf, SearchError> {
+    dirs::cache_dir()
+        .map(|dir| dir.join("linuxgit").join("avatars.json"))
+        .ok_or_else(|| SearchError("Could not determine the cache directory".to_string()))
+}
+
+fn load_cache() -> AvatarCache {
+    cache_path()
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+fn save_cache(cache: &AvatarCache) -> Result<(), SearchError> {
+    let path = cache_path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| SearchError(e.to_string()))?;
+    }
+    let data = serde_json::to_string(cache).map_err(|e| SearchError(e.to_string()))?;
+    std::fs::write(&path, data).map_err(|e| SearchError(e.to_string()))
+}
+
+/// Resolve author emails to avatar URLs, keyed by the emails as given
+///
+/// Emails whose lookup fails (for example when signed out or rate limited)
+/// get their Gravatar URL without it being cached, so they are tried again later.
+pub async fn resolve_avatars(emails: &[String]) -> HashMap<String, String> {
+    let now = chrono::Utc::now().timestamp();
+    let mut cache = load_cache();
+    let mut changed = false;
+    let mut lookups = 0;
+    let mut resolved = HashMap::new();
+
+    for email in emails {
+        let key = normalize_email(email);
+        if key.is_empty() || resolved.contains_key(email) {
+            continue;
+        }
+        if let Some(avatar) = cache.get_fresh(&key, now) {
+            resolved.insert(email.clone(), avatar.url.clone());
+            continue;
+        }
+
+        let found = if let Some(url) = noreply_avatar_url(&key) {
+            Some((url, AvatarSource::Noreply))
+        } else if lookups < MAX_LOOKUPS_PER_BATCH {
+            lookups += 1;
+            match lookup_github_avatar(&key).await {
+                Ok(Some(url)) => Some((url, AvatarSource::GitHub)),
+                Ok(None) => Some((gravatar_url(&key), AvatarSource::Gravatar)),
+                Err(_) => None,
+            }
+        } else {
+            None
+        };
+
+        let url = match found {
+            Some((url, source)) => {
+                cache.avatars.insert(key, CachedAvatar { url: url.clone(), source, fetched_at: now });
+                changed = true;
+                url
+            }
+            None => gravatar_url(email),
+        };
+        resolved.insert(email.clone(), url);
+    }
+
+    if changed {
+        let _ = save_cache(&cache);
+    }
+    resolved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_noreply_avatar_url() {
+        assert_eq!(
+            noreply_avatar_url("583231+octocat@users.noreply.github.com").as_deref(),
+            Some("https://avatars.githubusercontent.com/u/583231?v=4")
+        );
+        assert_eq!(
+            noreply_avatar_url("octocat@users.noreply.github.com").as_deref(),
+            Some("https://github.com/octocat.png")
+        );
+        assert_eq!(noreply_avatar_url("octocat@github.com"), None);
+        assert_eq!(noreply_avatar_url("@users.noreply.github.com"), None);
+    }
+
+    #[test]
+    fn test_gravatar_url() {
+        assert_eq!(
+            gravatar_url(" Test@Example.com "),
+            "https://www.gravatar.com/avatar/973dfe463ec85785f5f95af5ba3906eedb2d931c24e69824a89ea65dba4e813b?d=identicon&s=80"
+        );
+    }
+
+    #[test]
+    fn test_avatar_cache_freshness() {
+        let mut cache = AvatarCache::default();
+        cache.avatars.insert(
+            "dev@example.com".to_string(),
+            CachedAvatar {
+                url: "https://example.com/a.png".to_string(),
+                source: AvatarSource::GitHub,
+                fetched_at: 1_000,
+            },
+        );
+        assert!(cache.get_fresh("dev@example.com", 1_000 + 60).is_some());
+        assert!(cache.get_fresh("dev@example.com", 1_000 + AVATAR_CACHE_TTL_SECS).is_none());
+        assert!(cache.get_fresh("other@example.com", 1_000).is_none());
+    }
+}
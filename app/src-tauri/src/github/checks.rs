@@ -0,0 +1,702 @@
+//! GitHub Checks and Commit Statuses API module
+//!
+//! Provides access to check runs, commit statuses, and a combined CI rollup.
+
+use serde::{Deserialize, Serialize};
+use futures::future::join_all;
+use reqwest::Client;
+
+use super::app_auth::token_for_repo;
+
+/// GitHub App that produced a check run (simplified)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckApp {
+    pub id: i64,
+    pub slug: Option<String>,
+    pub name: String,
+}
+
+/// GitHub Check Run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckRun {
+    pub id: i64,
+    pub name: String,
+    pub head_sha: String,
+    pub status: String,
+    pub conclusion: Option<String>,
+    pub html_url: Option<String>,
+    pub details_url: Option<String>,
+    pub started_at: Option<String>,
+    pub completed_at: Option<String>,
+    pub app: Option<CheckApp>,
+    pub output: Option<CheckRunOutput>,
+    pub check_suite: Option<CheckSuiteRef>,
+}
+
+/// Summary output of a check run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckRunOutput {
+    pub title: Option<String>,
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub annotations_count: i32,
+}
+
+/// Reference to the check suite a check run belongs to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckSuiteRef {
+    pub id: i64,
+}
+
+/// Annotation attached to a check run (file, line range, and message)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckAnnotation {
+    pub path: String,
+    pub start_line: i32,
+    pub end_line: i32,
+    pub start_column: Option<i32>,
+    pub end_column: Option<i32>,
+    pub annotation_level: Option<String>,
+    pub title: Option<String>,
+    pub message: Option<String>,
+    pub raw_details: Option<String>,
+    pub blob_href: Option<String>,
+}
+
+/// Annotations of a single check run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckRunAnnotations {
+    pub check_run_id: i64,
+    pub check_run_name: String,
+    pub annotations: Vec<CheckAnnotation>,
+}
+
+/// GitHub Check Suite
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckSuite {
+    pub id: i64,
+    pub head_branch: Option<String>,
+    pub head_sha: String,
+    pub status: Option<String>,
+    pub conclusion: Option<String>,
+    pub app: Option<CheckApp>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    #[serde(default)]
+    pub latest_check_runs_count: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CheckSuitesResponse {
+    total_count: i32,
+    check_suites: Vec<CheckSuite>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CheckRunsResponse {
+    total_count: i32,
+    check_runs: Vec<CheckRun>,
+}
+
+/// GitHub Commit Status (legacy status API)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitStatus {
+    pub id: i64,
+    pub state: String,
+    pub context: String,
+    pub description: Option<String>,
+    pub target_url: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Combined status for a ref (latest status per context)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CombinedStatus {
+    pub state: String,
+    pub sha: String,
+    pub total_count: i32,
+    pub statuses: Vec<CommitStatus>,
+}
+
+/// Overall CI state of a commit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RollupState {
+    Success,
+    Failing,
+    Pending,
+    None,
+}
+
+/// Check runs and commit statuses of a commit, rolled up into a single state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChecksRollup {
+    pub sha: String,
+    pub state: RollupState,
+    pub total: u32,
+    pub successful: u32,
+    pub failing: u32,
+    pub pending: u32,
+    pub failing_checks: Vec<String>,
+    pub pending_checks: Vec<String>,
+    pub check_runs: Vec<CheckRun>,
+    pub statuses: Vec<CommitStatus>,
+}
+
+/// Error type for Checks API
+#[derive(Debug)]
+pub struct ChecksError(pub String);
+
+impl std::fmt::Display for ChecksError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ChecksError {}
+
+pub type ChecksResult<T> = Result<T, ChecksError>;
+
+/// Client and token for a repository, authenticating as its GitHub App
+/// installation if one is configured for it
+async fn get_repo_client(owner: &str, repo: &str) -> ChecksResult<(Client, String)> {
+    let token = token_for_repo(owner, repo).await.map_err(|e| ChecksError(e.to_string()))?;
+    Ok((Client::new(), token))
+}
+
+/// List check runs for a commit SHA, branch, or tag
+pub async fn list_check_runs_for_ref(
+    owner: &str,
+    repo: &str,
+    git_ref: &str,
+) -> ChecksResult<Vec<CheckRun>> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/commits/{}/check-runs",
+        owner, repo, git_ref
+    );
+
+    let mut check_runs = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let response = client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "LinuxGit")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .query(&[
+                ("per_page", "100".to_string()),
+                ("filter", "latest".to_string()),
+                ("page", page.to_string()),
+            ])
+            .send()
+            .await
+            .map_err(|e| ChecksError(format!("Request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(ChecksError(format!("GitHub API error ({}): {}", status, text)));
+        }
+
+        let data: CheckRunsResponse = response
+            .json()
+            .await
+            .map_err(|e| ChecksError(format!("Failed to parse response: {}", e)))?;
+
+        let done = data.check_runs.is_empty();
+        check_runs.extend(data.check_runs);
+
+        if done || check_runs.len() >= data.total_count.max(0) as usize {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(check_runs)
+}
+
+/// Get the combined commit status for a commit SHA, branch, or tag
+pub async fn get_combined_status(
+    owner: &str,
+    repo: &str,
+    git_ref: &str,
+) -> ChecksResult<CombinedStatus> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/commits/{}/status",
+        owner, repo, git_ref
+    );
+
+    let mut combined: Option<CombinedStatus> = None;
+    let mut page = 1;
+
+    loop {
+        let response = client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "LinuxGit")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .query(&[("per_page", "100".to_string()), ("page", page.to_string())])
+            .send()
+            .await
+            .map_err(|e| ChecksError(format!("Request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(ChecksError(format!("GitHub API error ({}): {}", status, text)));
+        }
+
+        let data: CombinedStatus = response
+            .json()
+            .await
+            .map_err(|e| ChecksError(format!("Failed to parse response: {}", e)))?;
+
+        let done = data.statuses.is_empty();
+        let combined = match combined.as_mut() {
+            Some(combined) => {
+                combined.statuses.extend(data.statuses);
+                combined
+            }
+            None => combined.insert(data),
+        };
+
+        if done || combined.statuses.len() >= combined.total_count.max(0) as usize {
+            break;
+        }
+        page += 1;
+    }
+
+    combined.ok_or_else(|| ChecksError("No combined status returned".to_string()))
+}
+
+/// List all statuses for a commit, newest first (including superseded ones)
+pub async fn list_commit_statuses(
+    owner: &str,
+    repo: &str,
+    git_ref: &str,
+) -> ChecksResult<Vec<CommitStatus>> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/commits/{}/statuses",
+        owner, repo, git_ref
+    );
+
+    let mut statuses = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let response = client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "LinuxGit")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .query(&[("per_page", "100".to_string()), ("page", page.to_string())])
+            .send()
+            .await
+            .map_err(|e| ChecksError(format!("Request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(ChecksError(format!("GitHub API error ({}): {}", status, text)));
+        }
+
+        let batch: Vec<CommitStatus> = response
+            .json()
+            .await
+            .map_err(|e| ChecksError(format!("Failed to parse response: {}", e)))?;
+
+        let done = batch.len() < 100;
+        statuses.extend(batch);
+
+        if done {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(statuses)
+}
+
+/// Create a commit status
+pub async fn create_commit_status(
+    owner: &str,
+    repo: &str,
+    sha: &str,
+    state: &str,
+    context: Option<&str>,
+    description: Option<&str>,
+    target_url: Option<&str>,
+) -> ChecksResult<CommitStatus> {
+    if !["error", "failure", "pending", "success"].contains(&state) {
+        return Err(ChecksError(format!(
+            "Invalid status state '{}': expected error, failure, pending, or success",
+            state
+        )));
+    }
+
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!("https://api.github.com/repos/{}/{}/statuses/{}", owner, repo, sha);
+
+    let mut payload = serde_json::json!({
+        "state": state,
+        "context": context.unwrap_or("default"),
+    });
+    if let Some(d) = description {
+        payload["description"] = serde_json::json!(d);
+    }
+
+    if let Some(u) = target_url {
+        payload["target_url"] = serde_json::json!(u);
+    }
+
+    let response = client
+        .post(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| ChecksError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(ChecksError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| ChecksError(format!("Failed to parse response: {}", e)))
+}
+/// List check suites for a commit SHA, branch, or tag
+pub async fn list_check_suites_for_ref(
+    owner: &str,
+    repo: &str,
+    git_ref: &str,
+) -> ChecksResult<Vec<CheckSuite>> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/commits/{}/check-suites",
+        owner, repo, git_ref
+    );
+
+    let mut check_suites = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let response = client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "LinuxGit")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .query(&[("per_page", "100".to_string()), ("page", page.to_string())])
+            .send()
+            .await
+            .map_err(|e| ChecksError(format!("Request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(ChecksError(format!("GitHub API error ({}): {}", status, text)));
+        }
+
+        let data: CheckSuitesResponse = response
+            .json()
+            .await
+            .map_err(|e| ChecksError(format!("Failed to parse response: {}", e)))?;
+
+        let done = data.check_suites.is_empty();
+        check_suites.extend(data.check_suites);
+
+        if done || check_suites.len() >= data.total_count.max(0) as usize {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(check_suites)
+}
+
+/// List check runs in a check suite
+pub async fn list_check_runs_in_suite(
+    owner: &str,
+    repo: &str,
+    check_suite_id: i64,
+) -> ChecksResult<Vec<CheckRun>> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/check-suites/{}/check-runs",
+        owner, repo, check_suite_id
+    );
+
+    let mut check_runs = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let response = client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "LinuxGit")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .query(&[("per_page", "100".to_string()), ("page", page.to_string())])
+            .send()
+            .await
+            .map_err(|e| ChecksError(format!("Request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(ChecksError(format!("GitHub API error ({}): {}", status, text)));
+        }
+
+        let data: CheckRunsResponse = response
+            .json()
+            .await
+            .map_err(|e| ChecksError(format!("Failed to parse response: {}", e)))?;
+
+        let done = data.check_runs.is_empty();
+        check_runs.extend(data.check_runs);
+
+        if done || check_runs.len() >= data.total_count.max(0) as usize {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(check_runs)
+}
+
+/// List annotations of a check run
+pub async fn list_check_run_annotations(
+    owner: &str,
+    repo: &str,
+    check_run_id: i64,
+) -> ChecksResult<Vec<CheckAnnotation>> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/check-runs/{}/annotations",
+        owner, repo, check_run_id
+    );
+
+    let mut annotations = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let response = client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "LinuxGit")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .query(&[("per_page", "100".to_string()), ("page", page.to_string())])
+            .send()
+            .await
+            .map_err(|e| ChecksError(format!("Request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(ChecksError(format!("GitHub API error ({}): {}", status, text)));
+        }
+
+        let batch: Vec<CheckAnnotation> = response
+            .json()
+            .await
+            .map_err(|e| ChecksError(format!("Failed to parse response: {}", e)))?;
+
+        let done = batch.len() < 100;
+        annotations.extend(batch);
+
+        if done {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(annotations)
+}
+
+/// Collect the annotations of every check run on a commit
+pub async fn get_commit_annotations(
+    owner: &str,
+    repo: &str,
+    sha: &str,
+) -> ChecksResult<Vec<CheckRunAnnotations>> {
+    let check_runs = list_check_runs_for_ref(owner, repo, sha).await?;
+    let annotated: Vec<CheckRun> = check_runs
+        .into_iter()
+        .filter(|run| run.output.as_ref().is_some_and(|o| o.annotations_count > 0))
+        .collect();
+
+    let annotations = join_all(
+        annotated
+            .iter()
+            .map(|run| list_check_run_annotations(owner, repo, run.id)),
+    )
+    .await;
+
+    annotated
+        .into_iter()
+        .zip(annotations)
+        .map(|(run, annotations)| {
+            Ok(CheckRunAnnotations {
+                check_run_id: run.id,
+                check_run_name: run.name,
+                annotations: annotations?,
+            })
+        })
+        .collect()
+}
+
+/// Ask GitHub to re-run a check suite
+pub async fn rerequest_check_suite(
+    owner: &str,
+    repo: &str,
+    check_suite_id: i64,
+) -> ChecksResult<()> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/check-suites/{}/rerequest",
+        owner, repo, check_suite_id
+    );
+
+    let response = client
+        .post(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| ChecksError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(ChecksError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    Ok(())
+}
+
+/// Compute the CI rollup for a commit from its check runs and statuses
+pub fn compute_rollup(sha: &str, check_runs: Vec<CheckRun>, statuses: Vec<CommitStatus>) -> ChecksRollup {
+    let mut successful = 0;
+    let mut failing_checks = Vec::new();
+    let mut pending_checks = Vec::new();
+
+    for run in &check_runs {
+        if run.status != "completed" {
+            pending_checks.push(run.name.clone());
+            continue;
+        }
+        match run.conclusion.as_deref() {
+            Some("success") | Some("neutral") | Some("skipped") => successful += 1,
+            _ => failing_checks.push(run.name.clone()),
+        }
+    }
+
+    for status in &statuses {
+        match status.state.as_str() {
+            "success" => successful += 1,
+            "pending" => pending_checks.push(status.context.clone()),
+            _ => failing_checks.push(status.context.clone()),
+        }
+    }
+
+    let total = (check_runs.len() + statuses.len()) as u32;
+    let state = if !failing_checks.is_empty() {
+        RollupState::Failing
+    } else if !pending_checks.is_empty() {
+        RollupState::Pending
+    } else if total == 0 {
+        RollupState::None
+    } else {
+        RollupState::Success
+    };
+
+    ChecksRollup {
+        sha: sha.to_string(),
+        state,
+        total,
+        successful,
+        failing: failing_checks.len() as u32,
+        pending: pending_checks.len() as u32,
+        failing_checks,
+        pending_checks,
+        check_runs,
+        statuses,
+    }
+}
+
+/// Fetch check runs and statuses for a commit and compute its rollup
+pub async fn get_checks_rollup(owner: &str, repo: &str, sha: &str) -> ChecksResult<ChecksRollup> {
+    let (check_runs, combined) = tokio::try_join!(
+        list_check_runs_for_ref(owner, repo, sha),
+        get_combined_status(owner, repo, sha),
+    )?;
+
+    Ok(compute_rollup(sha, check_runs, combined.statuses))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_run(name: &str, status: &str, conclusion: Option<&str>) -> CheckRun {
+        CheckRun {
+            id: 1,
+            name: name.to_string(),
+            head_sha: "abc".to_string(),
+            status: status.to_string(),
+            conclusion: conclusion.map(|c| c.to_string()),
+            html_url: None,
+            details_url: None,
+            started_at: None,
+            completed_at: None,
+            app: None,
+            output: None,
+            check_suite: None,
+        }
+    }
+
+    #[test]
+    fn test_rollup_failing_takes_precedence() {
+        let runs = vec![
+            check_run("build", "completed", Some("success")),
+            check_run("lint", "completed", Some("failure")),
+            check_run("test", "in_progress", None),
+        ];
+        let rollup = compute_rollup("abc", runs, Vec::new());
+        assert_eq!(rollup.state, RollupState::Failing);
+        assert_eq!(rollup.failing_checks, vec!["lint".to_string()]);
+        assert_eq!(rollup.pending, 1);
+    }
+
+    #[test]
+    fn test_rollup_empty_is_none() {
+        let rollup = compute_rollup("abc", Vec::new(), Vec::new());
+        assert_eq!(rollup.state, RollupState::None);
+    }
+}
@@ -0,0 +1,358 @@
+//! GitHub Collaborators API module
+//!
+//! Provides access to repository collaborators and repository invitations.
+
+use serde::{Deserialize, Serialize};
+use reqwest::Client;
+
+use super::app_auth::token_for_repo;
+use super::get_stored_token;
+
+/// GitHub User (simplified)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollaboratorUser {
+    pub login: String,
+    pub id: i64,
+    pub avatar_url: String,
+    pub html_url: String,
+}
+
+/// Repository permissions of a collaborator
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CollaboratorPermissions {
+    #[serde(default)]
+    pub admin: bool,
+    #[serde(default)]
+    pub maintain: bool,
+    #[serde(default)]
+    pub push: bool,
+    #[serde(default)]
+    pub triage: bool,
+    #[serde(default)]
+    pub pull: bool,
+}
+
+/// GitHub repository collaborator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Collaborator {
+    pub login: String,
+    pub id: i64,
+    pub avatar_url: String,
+    pub html_url: String,
+    #[serde(default)]
+    pub permissions: CollaboratorPermissions,
+    pub role_name: Option<String>,
+}
+
+/// Repository referenced by an invitation (simplified)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvitationRepository {
+    pub id: i64,
+    pub full_name: String,
+    pub html_url: String,
+    pub private: bool,
+}
+
+/// GitHub repository invitation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositoryInvitation {
+    pub id: i64,
+    pub repository: InvitationRepository,
+    pub invitee: Option<CollaboratorUser>,
+    pub inviter: Option<CollaboratorUser>,
+    pub permissions: String,
+    pub created_at: String,
+    pub html_url: String,
+    #[serde(default)]
+    pub expired: bool,
+}
+
+/// Error type for Collaborators API
+#[derive(Debug)]
+pub struct CollaboratorsError(pub String);
+
+impl std::fmt::Display for CollaboratorsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for CollaboratorsError {}
+
+pub type CollaboratorsResult<T> = Result<T, CollaboratorsError>;
+
+fn get_client() -> CollaboratorsResult<(Client, String)> {
+    let token = get_stored_token().map_err(|e| CollaboratorsError(e.to_string()))?;
+    let client = Client::new();
+    Ok((client, token))
+}
+
+/// Client and token for a repository, authenticating as its GitHub App
+/// installation if one is configured for it
+async fn get_repo_client(owner: &str, repo: &str) -> CollaboratorsResult<(Client, String)> {
+    let token = token_for_repo(owner, repo).await.map_err(|e| CollaboratorsError(e.to_string()))?;
+    Ok((Client::new(), token))
+}
+
+/// List collaborators of a repository with their permissions
+pub async fn list_collaborators(
+    owner: &str,
+    repo: &str,
+    affiliation: Option<&str>,
+) -> CollaboratorsResult<Vec<Collaborator>> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!("https://api.github.com/repos/{}/{}/collaborators", owner, repo);
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .query(&[("per_page", "100"), ("affiliation", affiliation.unwrap_or("all"))])
+        .send()
+        .await
+        .map_err(|e| CollaboratorsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(CollaboratorsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| CollaboratorsError(format!("Failed to parse response: {}", e)))
+}
+
+/// Add a collaborator to a repository
+///
+/// Returns the invitation that was sent, or `None` if the user already had access
+/// and only their permission was updated.
+pub async fn add_collaborator(
+    owner: &str,
+    repo: &str,
+    username: &str,
+    permission: Option<&str>,
+) -> CollaboratorsResult<Option<RepositoryInvitation>> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/collaborators/{}",
+        owner, repo, username
+    );
+
+    let payload = serde_json::json!({
+        "permission": permission.unwrap_or("push")
+    });
+
+    let response = client
+        .put(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| CollaboratorsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(CollaboratorsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    if response.status() == reqwest::StatusCode::NO_CONTENT {
+        return Ok(None);
+    }
+
+    response
+        .json()
+        .await
+        .map(Some)
+        .map_err(|e| CollaboratorsError(format!("Failed to parse response: {}", e)))
+}
+
+/// Remove a collaborator from a repository
+pub async fn remove_collaborator(
+    owner: &str,
+    repo: &str,
+    username: &str,
+) -> CollaboratorsResult<()> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/collaborators/{}",
+        owner, repo, username
+    );
+
+    let response = client
+        .delete(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| CollaboratorsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(CollaboratorsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    Ok(())
+}
+
+/// List pending invitations sent for a repository
+pub async fn list_repo_invitations(
+    owner: &str,
+    repo: &str,
+) -> CollaboratorsResult<Vec<RepositoryInvitation>> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!("https://api.github.com/repos/{}/{}/invitations", owner, repo);
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .query(&[("per_page", "100")])
+        .send()
+        .await
+        .map_err(|e| CollaboratorsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(CollaboratorsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| CollaboratorsError(format!("Failed to parse response: {}", e)))
+}
+
+/// Cancel a pending invitation sent for a repository
+pub async fn delete_repo_invitation(
+    owner: &str,
+    repo: &str,
+    invitation_id: i64,
+) -> CollaboratorsResult<()> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/invitations/{}",
+        owner, repo, invitation_id
+    );
+
+    let response = client
+        .delete(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| CollaboratorsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(CollaboratorsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    Ok(())
+}
+
+/// List repository invitations received by the authenticated user
+pub async fn list_user_invitations() -> CollaboratorsResult<Vec<RepositoryInvitation>> {
+    let (client, token) = get_client()?;
+
+    let url = "https://api.github.com/user/repository_invitations";
+
+    let response = client
+        .get(url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .query(&[("per_page", "100")])
+        .send()
+        .await
+        .map_err(|e| CollaboratorsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(CollaboratorsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| CollaboratorsError(format!("Failed to parse response: {}", e)))
+}
+
+/// Accept a repository invitation received by the authenticated user
+pub async fn accept_invitation(invitation_id: i64) -> CollaboratorsResult<()> {
+    let (client, token) = get_client()?;
+
+    let url = format!(
+        "https://api.github.com/user/repository_invitations/{}",
+        invitation_id
+    );
+
+    let response = client
+        .patch(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| CollaboratorsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(CollaboratorsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    Ok(())
+}
+
+/// Decline a repository invitation received by the authenticated user
+pub async fn decline_invitation(invitation_id: i64) -> CollaboratorsResult<()> {
+    let (client, token) = get_client()?;
+
+    let url = format!(
+        "https://api.github.com/user/repository_invitations/{}",
+        invitation_id
+    );
+
+    let response = client
+        .delete(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| CollaboratorsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(CollaboratorsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,258 @@
+//! GitHub Commits API module
+//!
+//! Provides commit comparisons, comments attached directly to commits, and
+//! recent committers.
+
+use serde::{Deserialize, Serialize};
+use reqwest::Client;
+
+use super::app_auth::token_for_repo;
+use super::pull_requests::{PullRequestCommit, PullRequestFile};
+
+/// Comparison between two commits, branches, or tags
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitComparison {
+    pub status: String,
+    pub ahead_by: i32,
+    pub behind_by: i32,
+    pub total_commits: i32,
+    pub html_url: String,
+    pub merge_base_commit: Option<PullRequestCommit>,
+    #[serde(default)]
+    pub commits: Vec<PullRequestCommit>,
+    #[serde(default)]
+    pub files: Vec<PullRequestFile>,
+}
+
+/// GitHub User (simplified)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitCommentUser {
+    pub login: String,
+    pub avatar_url: String,
+}
+
+/// Comment attached directly to a commit (optionally to a line of a file)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitComment {
+    pub id: i64,
+    pub body: String,
+    pub path: Option<String>,
+    pub position: Option<i32>,
+    pub line: Option<i32>,
+    pub commit_id: String,
+    pub user: Option<CommitCommentUser>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub html_url: String,
+}
+
+/// Author of recent commits on a repository
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentCommitter {
+    pub login: String,
+    pub avatar_url: String,
+    pub commits: usize,
+}
+
+/// Commit list entry (only the linked author is needed)
+#[derive(Debug, Deserialize)]
+struct CommitAuthorEntry {
+    author: Option<CommitCommentUser>,
+}
+
+/// Error type for Commits API
+#[derive(Debug)]
+pub struct CommitsError(pub String);
+
+impl std::fmt::Display for CommitsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for CommitsError {}
+
+pub type CommitsResult<T> = Result<T, CommitsError>;
+
+/// Client and token for a repository, authenticating as its GitHub App
+/// installation if one is configured for it
+async fn get_repo_client(owner: &str, repo: &str) -> CommitsResult<(Client, String)> {
+    let token = token_for_repo(owner, repo).await.map_err(|e| CommitsError(e.to_string()))?;
+    Ok((Client::new(), token))
+}
+
+/// Compare two refs
+///
+/// `head` may be `user:branch` (or `user:repo:branch`) to compare against a fork,
+/// so refs do not need to be fetched locally.
+pub async fn compare_commits(
+    owner: &str,
+    repo: &str,
+    base: &str,
+    head: &str,
+) -> CommitsResult<CommitComparison> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/compare/{}...{}",
+        owner, repo, base, head
+    );
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .query(&[("per_page", "250")])
+        .send()
+        .await
+        .map_err(|e| CommitsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(CommitsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| CommitsError(format!("Failed to parse response: {}", e)))
+}
+
+/// List comments on a commit
+pub async fn list_commit_comments(
+    owner: &str,
+    repo: &str,
+    sha: &str,
+) -> CommitsResult<Vec<CommitComment>> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/commits/{}/comments",
+        owner, repo, sha
+    );
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .query(&[("per_page", "100")])
+        .send()
+        .await
+        .map_err(|e| CommitsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(CommitsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| CommitsError(format!("Failed to parse response: {}", e)))
+}
+
+/// Comment on a commit, or on a line of a file changed by it
+pub async fn create_commit_comment(
+    owner: &str,
+    repo: &str,
+    sha: &str,
+    body: &str,
+    path: Option<&str>,
+    line: Option<i32>,
+) -> CommitsResult<CommitComment> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/commits/{}/comments",
+        owner, repo, sha
+    );
+
+    let mut payload = serde_json::json!({
+        "body": body
+    });
+    if let Some(p) = path {
+        payload["path"] = serde_json::json!(p);
+    }
+
+    if let Some(l) = line {
+        payload["line"] = serde_json::json!(l);
+    }
+
+    let response = client
+        .post(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| CommitsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(CommitsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| CommitsError(format!("Failed to parse response: {}", e)))
+}
+
+/// List the GitHub users who authored the most recent commits, most recent first
+///
+/// Commits whose author email is not linked to a GitHub account are skipped.
+pub async fn list_recent_committers(
+    owner: &str,
+    repo: &str,
+    limit: Option<usize>,
+) -> CommitsResult<Vec<RecentCommitter>> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!("https://api.github.com/repos/{}/{}/commits", owner, repo);
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .query(&[("per_page", "100")])
+        .send()
+        .await
+        .map_err(|e| CommitsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(CommitsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    let commits: Vec<CommitAuthorEntry> = response
+        .json()
+        .await
+        .map_err(|e| CommitsError(format!("Failed to parse response: {}", e)))?;
+
+    let mut committers: Vec<RecentCommitter> = Vec::new();
+    for author in commits.into_iter().filter_map(|c| c.author) {
+        match committers.iter_mut().find(|c| c.login == author.login) {
+            Some(existing) => existing.commits += 1,
+            None => committers.push(RecentCommitter {
+                login: author.login,
+                avatar_url: author.avatar_url,
+                commits: 1,
+            }),
+        }
+    }
+    committers.truncate(limit.unwrap_or(20));
+
+    Ok(committers)
+}
@@ -0,0 +1,285 @@
+//! GitHub Contents API module
+//!
+//! Provides reading, creating, updating, and deleting files in a repository
+//! without a local checkout.
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::{Deserialize, Serialize};
+use reqwest::Client;
+
+use super::app_auth::token_for_repo;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ContentResponse {
+    name: String,
+    path: String,
+    sha: String,
+    size: i64,
+    #[serde(rename = "type")]
+    content_type: String,
+    encoding: Option<String>,
+    content: Option<String>,
+    html_url: Option<String>,
+    download_url: Option<String>,
+}
+
+/// File fetched through the contents API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoFile {
+    pub name: String,
+    pub path: String,
+    pub sha: String,
+    pub size: i64,
+    /// UTF-8 text of the file, `None` for binary files
+    pub content: Option<String>,
+    pub is_binary: bool,
+    pub html_url: Option<String>,
+    pub download_url: Option<String>,
+}
+
+/// File metadata returned after a write
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentInfo {
+    pub name: String,
+    pub path: String,
+    pub sha: String,
+    pub html_url: Option<String>,
+}
+
+/// Commit created by a contents API write
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentCommit {
+    pub sha: String,
+    pub html_url: Option<String>,
+    pub message: Option<String>,
+}
+
+/// Result of creating, updating, or deleting a file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentWriteResult {
+    pub content: Option<ContentInfo>,
+    pub commit: ContentCommit,
+}
+
+/// Error type for Contents API
+#[derive(Debug)]
+pub struct ContentsError(pub String);
+
+impl std::fmt::Display for ContentsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ContentsError {}
+
+pub type ContentsResult<T> = Result<T, ContentsError>;
+
+/// Client and token for a repository, authenticating as its GitHub App
+/// installation if one is configured for it
+async fn get_repo_client(owner: &str, repo: &str) -> ContentsResult<(Client, String)> {
+    let token = token_for_repo(owner, repo).await.map_err(|e| ContentsError(e.to_string()))?;
+    Ok((Client::new(), token))
+}
+
+/// Percent-encode each segment of a repository path, keeping the separators
+fn encode_path(path: &str) -> String {
+    path.trim_matches('/')
+        .split('/')
+        .map(|segment| urlencoding::encode(segment).into_owned())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Decode the base64 payload of the contents API (which is wrapped at 60 columns)
+fn decode_content(content: &str) -> ContentsResult<Vec<u8>> {
+    let compact: String = content.chars().filter(|c| !c.is_whitespace()).collect();
+    STANDARD
+        .decode(compact)
+        .map_err(|e| ContentsError(format!("Failed to decode content: {}", e)))
+}
+
+/// Get a file at a ref (defaults to the repository's default branch)
+pub async fn get_file(
+    owner: &str,
+    repo: &str,
+    path: &str,
+    git_ref: Option<&str>,
+) -> ContentsResult<RepoFile> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/contents/{}",
+        owner, repo, encode_path(path)
+    );
+
+    let mut request = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28");
+
+    if let Some(r) = git_ref {
+        request = request.query(&[("ref", r)]);
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| ContentsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(ContentsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    // Directories are returned as arrays and fail to parse as a single entry
+    let data: ContentResponse = response
+        .json()
+        .await
+        .map_err(|_| ContentsError(format!("'{}' is not a file", path)))?;
+
+    if data.content_type != "file" {
+        return Err(ContentsError(format!("'{}' is a {}, not a file", path, data.content_type)));
+    }
+
+    let bytes = match (data.encoding.as_deref(), data.content.as_deref()) {
+        (Some("base64"), Some(content)) => decode_content(content)?,
+        // Files over 1 MB come back without inline content
+        _ => {
+            return Err(ContentsError(format!(
+                "'{}' is too large for the contents API ({} bytes)",
+                path, data.size
+            )))
+        }
+    };
+    let content = String::from_utf8(bytes).ok();
+
+    Ok(RepoFile {
+        name: data.name,
+        path: data.path,
+        sha: data.sha,
+        size: data.size,
+        is_binary: content.is_none(),
+        content,
+        html_url: data.html_url,
+        download_url: data.download_url,
+    })
+}
+
+/// Create a file, or update it when `sha` (the current blob SHA) is given
+pub async fn put_file(
+    owner: &str,
+    repo: &str,
+    path: &str,
+    content: &str,
+    message: &str,
+    branch: Option<&str>,
+    sha: Option<&str>,
+) -> ContentsResult<ContentWriteResult> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/contents/{}",
+        owner, repo, encode_path(path)
+    );
+
+    let mut payload = serde_json::json!({
+        "message": message,
+        "content": STANDARD.encode(content.as_bytes()),
+    });
+    if let Some(b) = branch {
+        payload["branch"] = serde_json::json!(b);
+    }
+
+    if let Some(s) = sha {
+        payload["sha"] = serde_json::json!(s);
+    }
+
+    let response = client
+        .put(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| ContentsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(ContentsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| ContentsError(format!("Failed to parse response: {}", e)))
+}
+
+/// Delete a file (`sha` must be the current blob SHA)
+pub async fn delete_file(
+    owner: &str,
+    repo: &str,
+    path: &str,
+    message: &str,
+    sha: &str,
+    branch: Option<&str>,
+) -> ContentsResult<ContentWriteResult> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/contents/{}",
+        owner, repo, encode_path(path)
+    );
+
+    let mut payload = serde_json::json!({
+        "message": message,
+        "sha": sha,
+    });
+    if let Some(b) = branch {
+        payload["branch"] = serde_json::json!(b);
+    }
+
+    let response = client
+        .delete(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| ContentsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(ContentsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| ContentsError(format!("Failed to parse response: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_path_keeps_separators() {
+        assert_eq!(encode_path("/docs/my file.md"), "docs/my%20file.md");
+    }
+
+    #[test]
+    fn test_decode_wrapped_content() {
+        let decoded = decode_content("SGVsbG8s\nIHdvcmxk\n").unwrap();
+        assert_eq!(decoded, b"Hello, world");
+    }
+}
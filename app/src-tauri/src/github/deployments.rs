@@ -1,10 +1,16 @@
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use super::api::GitHubApiError;
-use super::oauth::get_stored_token;
+use super::app_auth::token_for_repo;
 
 const GITHUB_API_URL: &str = "https://api.github.com";
 
+/// Event emitted for each line a local deploy script prints and for each status posted
+pub const LOCAL_DEPLOYMENT_EVENT: &str = "local-deployment";
+
+/// Maximum length GitHub accepts for a deployment status description
+const MAX_DESCRIPTION_LEN: usize = 140;
+
 fn create_client(token: &str) -> Client {
     Client::builder()
         .user_agent("LinuxGit/1.0")
@@ -25,6 +31,15 @@ fn create_client(token: &str) -> Client {
         .unwrap()
 }
 
+/// Client for a repository, authenticating as its GitHub App installation if
+/// one is configured for it
+async fn get_repo_client(owner: &str, repo: &str) -> Result<Client, GitHubApiError> {
+    let token = token_for_repo(owner, repo)
+        .await
+        .map_err(|e| GitHubApiError::ApiError(e.to_string()))?;
+    Ok(create_client(&token))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeploymentCreator {
     pub login: String,
@@ -93,6 +108,16 @@ pub struct CreateDeploymentStatusRequest {
     pub auto_inactive: Option<bool>,
 }
 
+/// Progress of a deployment run by a local script
+///
+/// Either `state` (a status was posted) or `line` (the script printed output) is set.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalDeploymentEvent {
+    pub deployment_id: i64,
+    pub state: Option<String>,
+    pub line: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeploymentSummary {
     pub total_count: u32,
@@ -116,8 +141,7 @@ pub async fn list_deployments(
     per_page: Option<u32>,
     page: Option<u32>,
 ) -> Result<Vec<Deployment>, GitHubApiError> {
-    let token = get_stored_token().map_err(|e| GitHubApiError::ApiError(e.to_string()))?;
-    let client = create_client(&token);
+    let client = get_repo_client(owner, repo).await?;
 
     let mut url = format!(
         "{}/repos/{}/{}/deployments?per_page={}&page={}",
@@ -154,8 +178,7 @@ pub async fn get_deployment(
     repo: &str,
     deployment_id: i64,
 ) -> Result<Deployment, GitHubApiError> {
-    let token = get_stored_token().map_err(|e| GitHubApiError::ApiError(e.to_string()))?;
-    let client = create_client(&token);
+    let client = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "{}/repos/{}/{}/deployments/{}",
@@ -184,8 +207,7 @@ pub async fn create_deployment(
     repo: &str,
     request: CreateDeploymentRequest,
 ) -> Result<Deployment, GitHubApiError> {
-    let token = get_stored_token().map_err(|e| GitHubApiError::ApiError(e.to_string()))?;
-    let client = create_client(&token);
+    let client = get_repo_client(owner, repo).await?;
 
     let url = format!("{}/repos/{}/{}/deployments", GITHUB_API_URL, owner, repo);
 
@@ -212,8 +234,7 @@ pub async fn delete_deployment(
     repo: &str,
     deployment_id: i64,
 ) -> Result<(), GitHubApiError> {
-    let token = get_stored_token().map_err(|e| GitHubApiError::ApiError(e.to_string()))?;
-    let client = create_client(&token);
+    let client = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "{}/repos/{}/{}/deployments/{}",
@@ -240,8 +261,7 @@ pub async fn list_deployment_statuses(
     deployment_id: i64,
     per_page: Option<u32>,
 ) -> Result<Vec<DeploymentStatus>, GitHubApiError> {
-    let token = get_stored_token().map_err(|e| GitHubApiError::ApiError(e.to_string()))?;
-    let client = create_client(&token);
+    let client = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "{}/repos/{}/{}/deployments/{}/statuses?per_page={}",
@@ -275,8 +295,7 @@ pub async fn create_deployment_status(
     deployment_id: i64,
     request: CreateDeploymentStatusRequest,
 ) -> Result<DeploymentStatus, GitHubApiError> {
-    let token = get_stored_token().map_err(|e| GitHubApiError::ApiError(e.to_string()))?;
-    let client = create_client(&token);
+    let client = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "{}/repos/{}/{}/deployments/{}/statuses",
@@ -344,3 +363,28 @@ pub async fn get_deployment_summary(
         environments: env_map.into_values().collect(),
     })
 }
+
+/// Shorten a status description to the length GitHub accepts
+pub fn status_description(text: &str) -> String {
+    if text.chars().count() <= MAX_DESCRIPTION_LEN {
+        return text.to_string();
+    }
+    let mut short: String = text.chars().take(MAX_DESCRIPTION_LEN - 3).collect();
+    short.push_str("...");
+    short
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_description_is_truncated() {
+        assert_eq!(status_description("Deployed"), "Deployed");
+
+        let long = "x".repeat(200);
+        let short = status_description(&long);
+        assert_eq!(short.chars().count(), MAX_DESCRIPTION_LEN);
+        assert!(short.ends_with("..."));
+    }
+}
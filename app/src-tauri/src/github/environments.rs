@@ -1,7 +1,9 @@
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use super::api::GitHubApiError;
-use super::oauth::get_stored_token;
+use super::deployments::Deployment;
+use super::app_auth::token_for_repo;
+use super::secrets::{encrypt_secret, ActionsPublicKey};
 
 const GITHUB_API_URL: &str = "https://api.github.com";
 
@@ -25,6 +27,15 @@ fn create_client(token: &str) -> Client {
         .unwrap()
 }
 
+/// Client for a repository, authenticating as its GitHub App installation if
+/// one is configured for it
+async fn get_repo_client(owner: &str, repo: &str) -> Result<Client, GitHubApiError> {
+    let token = token_for_repo(owner, repo)
+        .await
+        .map_err(|e| GitHubApiError::ApiError(e.to_string()))?;
+    Ok(create_client(&token))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Environment {
     pub id: i64,
@@ -137,14 +148,30 @@ pub struct BranchPoliciesResponse {
     pub branch_policies: Vec<BranchPolicy>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingDeploymentEnvironment {
+    pub id: i64,
+    pub name: String,
+    pub html_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingDeployment {
+    pub environment: PendingDeploymentEnvironment,
+    pub wait_timer: i32,
+    pub wait_timer_started_at: Option<String>,
+    pub current_user_can_approve: bool,
+    #[serde(default)]
+    pub reviewers: Vec<Reviewer>,
+}
+
 pub async fn list_environments(
     owner: &str,
     repo: &str,
     per_page: Option<u32>,
     page: Option<u32>,
 ) -> Result<EnvironmentsResponse, GitHubApiError> {
-    let token = get_stored_token().map_err(|e| GitHubApiError::ApiError(e.to_string()))?;
-    let client = create_client(&token);
+    let client = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "{}/repos/{}/{}/environments?per_page={}&page={}",
@@ -177,8 +204,7 @@ pub async fn get_environment(
     repo: &str,
     environment_name: &str,
 ) -> Result<Environment, GitHubApiError> {
-    let token = get_stored_token().map_err(|e| GitHubApiError::ApiError(e.to_string()))?;
-    let client = create_client(&token);
+    let client = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "{}/repos/{}/{}/environments/{}",
@@ -208,8 +234,7 @@ pub async fn create_or_update_environment(
     environment_name: &str,
     request: Option<CreateEnvironmentRequest>,
 ) -> Result<Environment, GitHubApiError> {
-    let token = get_stored_token().map_err(|e| GitHubApiError::ApiError(e.to_string()))?;
-    let client = create_client(&token);
+    let client = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "{}/repos/{}/{}/environments/{}",
@@ -245,8 +270,7 @@ pub async fn delete_environment(
     repo: &str,
     environment_name: &str,
 ) -> Result<(), GitHubApiError> {
-    let token = get_stored_token().map_err(|e| GitHubApiError::ApiError(e.to_string()))?;
-    let client = create_client(&token);
+    let client = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "{}/repos/{}/{}/environments/{}",
@@ -272,8 +296,7 @@ pub async fn list_environment_secrets(
     repo: &str,
     environment_name: &str,
 ) -> Result<EnvironmentSecretsResponse, GitHubApiError> {
-    let token = get_stored_token().map_err(|e| GitHubApiError::ApiError(e.to_string()))?;
-    let client = create_client(&token);
+    let client = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "{}/repos/{}/{}/environments/{}/secrets",
@@ -297,13 +320,106 @@ pub async fn list_environment_secrets(
         .map_err(|e| GitHubApiError::ApiError(e.to_string()))
 }
 
+pub async fn get_environment_public_key(
+    owner: &str,
+    repo: &str,
+    environment_name: &str,
+) -> Result<ActionsPublicKey, GitHubApiError> {
+    let client = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "{}/repos/{}/{}/environments/{}/secrets/public-key",
+        GITHUB_API_URL, owner, repo, environment_name
+    );
+
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(GitHubApiError::ApiError(error_text));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| GitHubApiError::ApiError(e.to_string()))
+}
+
+pub async fn set_environment_secret(
+    owner: &str,
+    repo: &str,
+    environment_name: &str,
+    secret_name: &str,
+    value: &str,
+) -> Result<(), GitHubApiError> {
+    let public_key = get_environment_public_key(owner, repo, environment_name).await?;
+    let encrypted_value = encrypt_secret(&public_key.key, value)
+        .map_err(|e| GitHubApiError::ApiError(e.to_string()))?;
+
+    let client = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "{}/repos/{}/{}/environments/{}/secrets/{}",
+        GITHUB_API_URL, owner, repo, environment_name, secret_name
+    );
+
+    let payload = serde_json::json!({
+        "encrypted_value": encrypted_value,
+        "key_id": public_key.key_id
+    });
+
+    let response = client
+        .put(&url)
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(GitHubApiError::ApiError(error_text));
+    }
+
+    Ok(())
+}
+
+pub async fn delete_environment_secret(
+    owner: &str,
+    repo: &str,
+    environment_name: &str,
+    secret_name: &str,
+) -> Result<(), GitHubApiError> {
+    let client = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "{}/repos/{}/{}/environments/{}/secrets/{}",
+        GITHUB_API_URL, owner, repo, environment_name, secret_name
+    );
+
+    let response = client
+        .delete(&url)
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(GitHubApiError::ApiError(error_text));
+    }
+
+    Ok(())
+}
+
 pub async fn list_environment_variables(
     owner: &str,
     repo: &str,
     environment_name: &str,
 ) -> Result<EnvironmentVariablesResponse, GitHubApiError> {
-    let token = get_stored_token().map_err(|e| GitHubApiError::ApiError(e.to_string()))?;
-    let client = create_client(&token);
+    let client = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "{}/repos/{}/{}/environments/{}/variables",
@@ -327,13 +443,137 @@ pub async fn list_environment_variables(
         .map_err(|e| GitHubApiError::ApiError(e.to_string()))
 }
 
+pub async fn get_environment_variable(
+    owner: &str,
+    repo: &str,
+    environment_name: &str,
+    variable_name: &str,
+) -> Result<EnvironmentVariable, GitHubApiError> {
+    let client = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "{}/repos/{}/{}/environments/{}/variables/{}",
+        GITHUB_API_URL, owner, repo, environment_name, variable_name
+    );
+
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(GitHubApiError::ApiError(error_text));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| GitHubApiError::ApiError(e.to_string()))
+}
+
+pub async fn create_environment_variable(
+    owner: &str,
+    repo: &str,
+    environment_name: &str,
+    variable_name: &str,
+    value: &str,
+) -> Result<(), GitHubApiError> {
+    let client = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "{}/repos/{}/{}/environments/{}/variables",
+        GITHUB_API_URL, owner, repo, environment_name
+    );
+
+    let payload = serde_json::json!({
+        "name": variable_name,
+        "value": value
+    });
+
+    let response = client
+        .post(&url)
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(GitHubApiError::ApiError(error_text));
+    }
+
+    Ok(())
+}
+
+pub async fn update_environment_variable(
+    owner: &str,
+    repo: &str,
+    environment_name: &str,
+    variable_name: &str,
+    value: &str,
+) -> Result<(), GitHubApiError> {
+    let client = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "{}/repos/{}/{}/environments/{}/variables/{}",
+        GITHUB_API_URL, owner, repo, environment_name, variable_name
+    );
+
+    let payload = serde_json::json!({
+        "name": variable_name,
+        "value": value
+    });
+
+    let response = client
+        .patch(&url)
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(GitHubApiError::ApiError(error_text));
+    }
+
+    Ok(())
+}
+
+pub async fn delete_environment_variable(
+    owner: &str,
+    repo: &str,
+    environment_name: &str,
+    variable_name: &str,
+) -> Result<(), GitHubApiError> {
+    let client = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "{}/repos/{}/{}/environments/{}/variables/{}",
+        GITHUB_API_URL, owner, repo, environment_name, variable_name
+    );
+
+    let response = client
+        .delete(&url)
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(GitHubApiError::ApiError(error_text));
+    }
+
+    Ok(())
+}
+
 pub async fn list_deployment_branch_policies(
     owner: &str,
     repo: &str,
     environment_name: &str,
 ) -> Result<BranchPoliciesResponse, GitHubApiError> {
-    let token = get_stored_token().map_err(|e| GitHubApiError::ApiError(e.to_string()))?;
-    let client = create_client(&token);
+    let client = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "{}/repos/{}/{}/environments/{}/deployment-branch-policies",
@@ -364,8 +604,7 @@ pub async fn create_deployment_branch_policy(
     name: &str,
     policy_type: Option<&str>,
 ) -> Result<BranchPolicy, GitHubApiError> {
-    let token = get_stored_token().map_err(|e| GitHubApiError::ApiError(e.to_string()))?;
-    let client = create_client(&token);
+    let client = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "{}/repos/{}/{}/environments/{}/deployment-branch-policies",
@@ -404,8 +643,7 @@ pub async fn delete_deployment_branch_policy(
     environment_name: &str,
     branch_policy_id: i64,
 ) -> Result<(), GitHubApiError> {
-    let token = get_stored_token().map_err(|e| GitHubApiError::ApiError(e.to_string()))?;
-    let client = create_client(&token);
+    let client = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "{}/repos/{}/{}/environments/{}/deployment-branch-policies/{}",
@@ -425,3 +663,78 @@ pub async fn delete_deployment_branch_policy(
 
     Ok(())
 }
+
+pub async fn get_pending_deployments(
+    owner: &str,
+    repo: &str,
+    run_id: i64,
+) -> Result<Vec<PendingDeployment>, GitHubApiError> {
+    let client = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "{}/repos/{}/{}/actions/runs/{}/pending_deployments",
+        GITHUB_API_URL, owner, repo, run_id
+    );
+
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(GitHubApiError::ApiError(error_text));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| GitHubApiError::ApiError(e.to_string()))
+}
+
+pub async fn review_pending_deployments(
+    owner: &str,
+    repo: &str,
+    run_id: i64,
+    environment_ids: Vec<i64>,
+    state: &str,
+    comment: &str,
+) -> Result<Vec<Deployment>, GitHubApiError> {
+    if state != "approved" && state != "rejected" {
+        return Err(GitHubApiError::ApiError(format!(
+            "Invalid review state '{}': expected 'approved' or 'rejected'",
+            state
+        )));
+    }
+
+    let client = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "{}/repos/{}/{}/actions/runs/{}/pending_deployments",
+        GITHUB_API_URL, owner, repo, run_id
+    );
+
+    let body = serde_json::json!({
+        "environment_ids": environment_ids,
+        "state": state,
+        "comment": comment
+    });
+
+    let response = client
+        .post(&url)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| GitHubApiError::NetworkError(e.to_string()))?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(GitHubApiError::ApiError(error_text));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| GitHubApiError::ApiError(e.to_string()))
+}
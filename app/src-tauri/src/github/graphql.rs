@@ -0,0 +1,876 @@
+//! GitHub GraphQL API module
+//!
+//! Provides an authenticated GraphQL executor plus typed queries for data the
+//! REST API exposes poorly: pull request review threads, Projects v2 and
+//! Discussions.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+use reqwest::Client;
+
+use super::app_auth::token_for_repo;
+
+const GITHUB_GRAPHQL_URL: &str = "https://api.github.com/graphql";
+
+/// Pagination info of a GraphQL connection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct PageInfo {
+    pub has_next_page: bool,
+    pub end_cursor: Option<String>,
+}
+
+/// A GraphQL connection (`{ nodes, pageInfo }`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct Connection<T> {
+    pub nodes: Vec<T>,
+    pub page_info: Option<PageInfo>,
+}
+
+/// GraphQL actor (user, bot, or organization)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct GraphQLActor {
+    pub login: String,
+    pub avatar_url: Option<String>,
+}
+
+/// Comment inside a pull request review thread
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct ReviewThreadComment {
+    pub id: String,
+    pub database_id: Option<i64>,
+    pub body: String,
+    pub url: String,
+    pub created_at: String,
+    pub author: Option<GraphQLActor>,
+}
+
+/// Pull request review thread with its resolved state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct ReviewThread {
+    pub id: String,
+    pub is_resolved: bool,
+    pub is_outdated: bool,
+    pub is_collapsed: bool,
+    pub path: String,
+    pub line: Option<i32>,
+    pub start_line: Option<i32>,
+    pub diff_side: String,
+    pub resolved_by: Option<GraphQLActor>,
+    #[serde(deserialize_with = "deserialize_nodes")]
+    pub comments: Vec<ReviewThreadComment>,
+}
+
+/// GitHub Projects (v2) board
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct ProjectV2 {
+    pub id: String,
+    pub number: i32,
+    pub title: String,
+    pub short_description: Option<String>,
+    pub url: String,
+    pub closed: bool,
+    pub public: bool,
+    pub updated_at: String,
+}
+
+/// Discussion category
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct DiscussionCategory {
+    pub id: String,
+    pub name: String,
+    pub emoji: String,
+    pub description: Option<String>,
+    pub is_answerable: bool,
+}
+
+/// Discussion thread (summary, without comments)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct Discussion {
+    pub id: String,
+    pub number: i32,
+    pub title: String,
+    pub url: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub author: Option<GraphQLActor>,
+    pub category: DiscussionCategory,
+    pub is_answered: Option<bool>,
+    pub locked: bool,
+    pub closed: bool,
+    #[serde(deserialize_with = "deserialize_total_count")]
+    pub comments: i32,
+}
+
+/// Comment (or reply) on a discussion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct DiscussionComment {
+    pub id: String,
+    pub database_id: Option<i64>,
+    pub body: String,
+    pub url: String,
+    pub created_at: String,
+    pub author: Option<GraphQLActor>,
+    #[serde(default)]
+    pub is_answer: bool,
+    #[serde(default)]
+    pub upvote_count: i32,
+    #[serde(default, deserialize_with = "deserialize_nodes")]
+    pub replies: Vec<DiscussionComment>,
+}
+
+/// Discussion thread with its body and comments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all(deserialize = "camelCase"))]
+pub struct DiscussionDetail {
+    pub id: String,
+    pub number: i32,
+    pub title: String,
+    pub body: String,
+    pub url: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub author: Option<GraphQLActor>,
+    pub category: DiscussionCategory,
+    pub is_answered: Option<bool>,
+    pub locked: bool,
+    pub closed: bool,
+    #[serde(deserialize_with = "deserialize_nodes")]
+    pub comments: Vec<DiscussionComment>,
+}
+
+/// Error entry returned in the `errors` array of a GraphQL response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphQLErrorEntry {
+    pub message: String,
+    #[serde(rename = "type")]
+    pub error_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQLResponse<T> {
+    data: Option<T>,
+    #[serde(default)]
+    errors: Vec<GraphQLErrorEntry>,
+}
+
+/// Error type for GraphQL API
+#[derive(Debug)]
+pub struct GraphQLError(pub String);
+
+impl std::fmt::Display for GraphQLError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for GraphQLError {}
+
+pub type GraphQLResult<T> = Result<T, GraphQLError>;
+
+/// Flattens a `{ nodes: [...] }` connection into a plain list
+fn deserialize_nodes<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    struct Nodes<T> {
+        nodes: Vec<T>,
+    }
+
+    Ok(Nodes::deserialize(deserializer)?.nodes)
+}
+
+/// Flattens a `{ totalCount: n }` connection into its count
+fn deserialize_total_count<'de, D>(deserializer: D) -> Result<i32, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct TotalCount {
+        total_count: i32,
+    }
+
+    Ok(TotalCount::deserialize(deserializer)?.total_count)
+}
+
+/// Client and token for a repository, authenticating as its GitHub App
+/// installation if one is configured for it
+async fn get_repo_client(owner: &str, repo: &str) -> GraphQLResult<(Client, String)> {
+    let token = token_for_repo(owner, repo)
+        .await
+        .map_err(|e| GraphQLError(e.to_string()))?;
+    let client = Client::new();
+    Ok((client, token))
+}
+
+/// Converts a raw GraphQL response into its data, surfacing reported errors
+fn into_result<T>(response: GraphQLResponse<T>) -> GraphQLResult<T> {
+    if !response.errors.is_empty() {
+        let messages: Vec<String> = response.errors.into_iter().map(|e| e.message).collect();
+        return Err(GraphQLError(format!("GraphQL error: {}", messages.join("; "))));
+    }
+
+    response
+        .data
+        .ok_or_else(|| GraphQLError("GraphQL response contained no data".to_string()))
+}
+
+/// Execute a GraphQL query or mutation against a repository and deserialize
+/// its `data` field
+pub async fn execute<T: DeserializeOwned>(
+    owner: &str,
+    repo: &str,
+    query: &str,
+    variables: serde_json::Value,
+) -> GraphQLResult<T> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let payload = serde_json::json!({
+        "query": query,
+        "variables": variables
+    });
+
+    let response = client
+        .post(GITHUB_GRAPHQL_URL)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("User-Agent", "LinuxGit")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| GraphQLError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(GraphQLError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    let body: GraphQLResponse<T> = response
+        .json()
+        .await
+        .map_err(|e| GraphQLError(format!("Failed to parse response: {}", e)))?;
+
+    into_result(body)
+}
+
+const REVIEW_THREAD_FRAGMENT: &str = r#"
+fragment ReviewThreadFields on PullRequestReviewThread {
+  id
+  isResolved
+  isOutdated
+  isCollapsed
+  path
+  line
+  startLine
+  diffSide
+  resolvedBy { login avatarUrl }
+  comments(first: 100) {
+    nodes { id databaseId body url createdAt author { login avatarUrl } }
+  }
+}
+"#;
+
+const REVIEW_THREADS_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $number: Int!, $after: String) {
+  repository(owner: $owner, name: $repo) {
+    pullRequest(number: $number) {
+      reviewThreads(first: 100, after: $after) {
+        pageInfo { hasNextPage endCursor }
+        nodes { ...ReviewThreadFields }
+      }
+    }
+  }
+}
+"#;
+
+const RESOLVE_REVIEW_THREAD_MUTATION: &str = r#"
+mutation($threadId: ID!) {
+  resolveReviewThread(input: { threadId: $threadId }) {
+    thread { ...ReviewThreadFields }
+  }
+}
+"#;
+
+const UNRESOLVE_REVIEW_THREAD_MUTATION: &str = r#"
+mutation($threadId: ID!) {
+  unresolveReviewThread(input: { threadId: $threadId }) {
+    thread { ...ReviewThreadFields }
+  }
+}
+"#;
+
+/// List all review threads of a pull request
+pub async fn list_review_threads(
+    owner: &str,
+    repo: &str,
+    pull_number: i32,
+) -> GraphQLResult<Vec<ReviewThread>> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Data {
+        repository: Option<Repository>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Repository {
+        pull_request: Option<PullRequest>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct PullRequest {
+        review_threads: Connection<ReviewThread>,
+    }
+
+    let mut threads = Vec::new();
+    let mut after: Option<String> = None;
+
+    loop {
+        let data: Data = execute(
+            owner,
+            repo,
+            &format!("{}{}", REVIEW_THREADS_QUERY, REVIEW_THREAD_FRAGMENT),
+            serde_json::json!({
+                "owner": owner,
+                "repo": repo,
+                "number": pull_number,
+                "after": after
+            }),
+        )
+        .await?;
+
+        let connection = data
+            .repository
+            .and_then(|r| r.pull_request)
+            .ok_or_else(|| GraphQLError(format!("Pull request #{} not found", pull_number)))?
+            .review_threads;
+
+        threads.extend(connection.nodes);
+
+        match connection.page_info {
+            Some(PageInfo { has_next_page: true, end_cursor: Some(cursor) }) => after = Some(cursor),
+            _ => break,
+        }
+    }
+
+    Ok(threads)
+}
+
+/// Mark a review thread as resolved
+pub async fn resolve_review_thread(
+    owner: &str,
+    repo: &str,
+    thread_id: &str,
+) -> GraphQLResult<ReviewThread> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Data {
+        resolve_review_thread: Payload,
+    }
+
+    #[derive(Deserialize)]
+    struct Payload {
+        thread: ReviewThread,
+    }
+
+    let data: Data = execute(
+        owner,
+        repo,
+        &format!("{}{}", RESOLVE_REVIEW_THREAD_MUTATION, REVIEW_THREAD_FRAGMENT),
+        serde_json::json!({ "threadId": thread_id }),
+    )
+    .await?;
+
+    Ok(data.resolve_review_thread.thread)
+}
+
+/// Mark a previously resolved review thread as unresolved
+pub async fn unresolve_review_thread(
+    owner: &str,
+    repo: &str,
+    thread_id: &str,
+) -> GraphQLResult<ReviewThread> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Data {
+        unresolve_review_thread: Payload,
+    }
+
+    #[derive(Deserialize)]
+    struct Payload {
+        thread: ReviewThread,
+    }
+
+    let data: Data = execute(
+        owner,
+        repo,
+        &format!("{}{}", UNRESOLVE_REVIEW_THREAD_MUTATION, REVIEW_THREAD_FRAGMENT),
+        serde_json::json!({ "threadId": thread_id }),
+    )
+    .await?;
+
+    Ok(data.unresolve_review_thread.thread)
+}
+
+const MARK_PR_READY_MUTATION: &str = r#"
+mutation($pullRequestId: ID!) {
+  markPullRequestReadyForReview(input: { pullRequestId: $pullRequestId }) {
+    pullRequest { isDraft }
+  }
+}
+"#;
+
+const CONVERT_PR_TO_DRAFT_MUTATION: &str = r#"
+mutation($pullRequestId: ID!) {
+  convertPullRequestToDraft(input: { pullRequestId: $pullRequestId }) {
+    pullRequest { isDraft }
+  }
+}
+"#;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DraftStatePayload {
+    pull_request: DraftState,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DraftState {
+    is_draft: bool,
+}
+
+/// Mark a draft pull request as ready for review, returning the new draft state
+pub async fn mark_pull_request_ready(
+    owner: &str,
+    repo: &str,
+    pull_request_id: &str,
+) -> GraphQLResult<bool> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Data {
+        mark_pull_request_ready_for_review: DraftStatePayload,
+    }
+
+    let data: Data = execute(
+        owner,
+        repo,
+        MARK_PR_READY_MUTATION,
+        serde_json::json!({ "pullRequestId": pull_request_id }),
+    )
+    .await?;
+
+    Ok(data.mark_pull_request_ready_for_review.pull_request.is_draft)
+}
+
+/// Convert an open pull request back to a draft, returning the new draft state
+pub async fn convert_pull_request_to_draft(
+    owner: &str,
+    repo: &str,
+    pull_request_id: &str,
+) -> GraphQLResult<bool> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Data {
+        convert_pull_request_to_draft: DraftStatePayload,
+    }
+
+    let data: Data = execute(
+        owner,
+        repo,
+        CONVERT_PR_TO_DRAFT_MUTATION,
+        serde_json::json!({ "pullRequestId": pull_request_id }),
+    )
+    .await?;
+
+    Ok(data.convert_pull_request_to_draft.pull_request.is_draft)
+}
+
+const PROJECTS_V2_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $first: Int!) {
+  repository(owner: $owner, name: $repo) {
+    projectsV2(first: $first, orderBy: { field: UPDATED_AT, direction: DESC }) {
+      nodes { id number title shortDescription url closed public updatedAt }
+    }
+  }
+}
+"#;
+
+/// List Projects (v2) linked to a repository
+pub async fn list_projects_v2(
+    owner: &str,
+    repo: &str,
+    first: Option<u32>,
+) -> GraphQLResult<Vec<ProjectV2>> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Data {
+        repository: Option<Repository>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Repository {
+        projects_v2: Connection<ProjectV2>,
+    }
+
+    let data: Data = execute(
+        owner,
+        repo,
+        PROJECTS_V2_QUERY,
+        serde_json::json!({
+            "owner": owner,
+            "repo": repo,
+            "first": first.unwrap_or(20)
+        }),
+    )
+    .await?;
+
+    data.repository
+        .map(|r| r.projects_v2.nodes)
+        .ok_or_else(|| GraphQLError(format!("Repository {}/{} not found", owner, repo)))
+}
+
+const DISCUSSIONS_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $first: Int!, $categoryId: ID) {
+  repository(owner: $owner, name: $repo) {
+    discussions(first: $first, categoryId: $categoryId, orderBy: { field: UPDATED_AT, direction: DESC }) {
+      nodes {
+        id
+        number
+        title
+        url
+        createdAt
+        updatedAt
+        author { login avatarUrl }
+        category { id name emoji description isAnswerable }
+        isAnswered
+        locked
+        closed
+        comments { totalCount }
+      }
+    }
+  }
+}
+"#;
+
+/// List discussion threads of a repository, optionally filtered by category
+pub async fn list_discussions(
+    owner: &str,
+    repo: &str,
+    category_id: Option<&str>,
+    first: Option<u32>,
+) -> GraphQLResult<Vec<Discussion>> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Data {
+        repository: Option<Repository>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Repository {
+        discussions: Connection<Discussion>,
+    }
+
+    let data: Data = execute(
+        owner,
+        repo,
+        DISCUSSIONS_QUERY,
+        serde_json::json!({
+            "owner": owner,
+            "repo": repo,
+            "first": first.unwrap_or(30),
+            "categoryId": category_id
+        }),
+    )
+    .await?;
+
+    data.repository
+        .map(|r| r.discussions.nodes)
+        .ok_or_else(|| GraphQLError(format!("Repository {}/{} not found", owner, repo)))
+}
+
+const DISCUSSION_CATEGORIES_QUERY: &str = r#"
+query($owner: String!, $repo: String!) {
+  repository(owner: $owner, name: $repo) {
+    discussionCategories(first: 100) {
+      nodes { id name emoji description isAnswerable }
+    }
+  }
+}
+"#;
+
+const DISCUSSION_QUERY: &str = r#"
+query($owner: String!, $repo: String!, $number: Int!) {
+  repository(owner: $owner, name: $repo) {
+    discussion(number: $number) {
+      id
+      number
+      title
+      body
+      url
+      createdAt
+      updatedAt
+      author { login avatarUrl }
+      category { id name emoji description isAnswerable }
+      isAnswered
+      locked
+      closed
+      comments(first: 100) {
+        nodes {
+          ...DiscussionCommentFields
+          replies(first: 50) {
+            nodes { ...DiscussionCommentFields }
+          }
+        }
+      }
+    }
+  }
+}
+"#;
+
+const DISCUSSION_COMMENT_FRAGMENT: &str = r#"
+fragment DiscussionCommentFields on DiscussionComment {
+  id
+  databaseId
+  body
+  url
+  createdAt
+  author { login avatarUrl }
+  isAnswer
+  upvoteCount
+}
+"#;
+
+const ADD_DISCUSSION_COMMENT_MUTATION: &str = r#"
+mutation($discussionId: ID!, $body: String!, $replyToId: ID) {
+  addDiscussionComment(input: { discussionId: $discussionId, body: $body, replyToId: $replyToId }) {
+    comment { ...DiscussionCommentFields }
+  }
+}
+"#;
+
+const MARK_DISCUSSION_ANSWER_MUTATION: &str = r#"
+mutation($id: ID!) {
+  markDiscussionCommentAsAnswer(input: { id: $id }) {
+    discussion { isAnswered }
+  }
+}
+"#;
+
+const UNMARK_DISCUSSION_ANSWER_MUTATION: &str = r#"
+mutation($id: ID!) {
+  unmarkDiscussionCommentAsAnswer(input: { id: $id }) {
+    discussion { isAnswered }
+  }
+}
+"#;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AnsweredDiscussion {
+    is_answered: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct AnswerPayload {
+    discussion: AnsweredDiscussion,
+}
+
+/// List discussion categories of a repository
+pub async fn list_discussion_categories(
+    owner: &str,
+    repo: &str,
+) -> GraphQLResult<Vec<DiscussionCategory>> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Data {
+        repository: Option<Repository>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Repository {
+        discussion_categories: Connection<DiscussionCategory>,
+    }
+
+    let data: Data = execute(
+        owner,
+        repo,
+        DISCUSSION_CATEGORIES_QUERY,
+        serde_json::json!({ "owner": owner, "repo": repo }),
+    )
+    .await?;
+
+    data.repository
+        .map(|r| r.discussion_categories.nodes)
+        .ok_or_else(|| GraphQLError(format!("Repository {}/{} not found", owner, repo)))
+}
+
+/// Get a discussion with its comments and their replies
+pub async fn get_discussion(
+    owner: &str,
+    repo: &str,
+    number: i32,
+) -> GraphQLResult<DiscussionDetail> {
+    #[derive(Deserialize)]
+    struct Data {
+        repository: Option<Repository>,
+    }
+
+    #[derive(Deserialize)]
+    struct Repository {
+        discussion: Option<DiscussionDetail>,
+    }
+
+    let data: Data = execute(
+        owner,
+        repo,
+        &format!("{}{}", DISCUSSION_QUERY, DISCUSSION_COMMENT_FRAGMENT),
+        serde_json::json!({ "owner": owner, "repo": repo, "number": number }),
+    )
+    .await?;
+
+    data.repository
+        .and_then(|r| r.discussion)
+        .ok_or_else(|| GraphQLError(format!("Discussion #{} not found", number)))
+}
+
+/// Post a comment on a discussion, or a reply to one of its comments
+pub async fn add_discussion_comment(
+    owner: &str,
+    repo: &str,
+    discussion_id: &str,
+    body: &str,
+    reply_to_id: Option<&str>,
+) -> GraphQLResult<DiscussionComment> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Data {
+        add_discussion_comment: Payload,
+    }
+
+    #[derive(Deserialize)]
+    struct Payload {
+        comment: DiscussionComment,
+    }
+
+    let data: Data = execute(
+        owner,
+        repo,
+        &format!("{}{}", ADD_DISCUSSION_COMMENT_MUTATION, DISCUSSION_COMMENT_FRAGMENT),
+        serde_json::json!({
+            "discussionId": discussion_id,
+            "body": body,
+            "replyToId": reply_to_id
+        }),
+    )
+    .await?;
+
+    Ok(data.add_discussion_comment.comment)
+}
+
+/// Mark a discussion comment as the answer; returns the discussion's answered state
+pub async fn mark_discussion_answer(
+    owner: &str,
+    repo: &str,
+    comment_id: &str,
+) -> GraphQLResult<bool> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Data {
+        mark_discussion_comment_as_answer: AnswerPayload,
+    }
+
+    let data: Data = execute(
+        owner,
+        repo,
+        MARK_DISCUSSION_ANSWER_MUTATION,
+        serde_json::json!({ "id": comment_id }),
+    )
+    .await?;
+
+    Ok(data.mark_discussion_comment_as_answer.discussion.is_answered.unwrap_or(false))
+}
+
+/// Unmark a discussion comment as the answer; returns the discussion's answered state
+pub async fn unmark_discussion_answer(
+    owner: &str,
+    repo: &str,
+    comment_id: &str,
+) -> GraphQLResult<bool> {
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Data {
+        unmark_discussion_comment_as_answer: AnswerPayload,
+    }
+
+    let data: Data = execute(
+        owner,
+        repo,
+        UNMARK_DISCUSSION_ANSWER_MUTATION,
+        serde_json::json!({ "id": comment_id }),
+    )
+    .await?;
+
+    Ok(data.unmark_discussion_comment_as_answer.discussion.is_answered.unwrap_or(false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into_result_reports_errors() {
+        let response: GraphQLResponse<serde_json::Value> = serde_json::from_str(
+            r#"{"data": null, "errors": [{"message": "Could not resolve", "type": "NOT_FOUND"}]}"#,
+        )
+        .unwrap();
+
+        let err = into_result(response).unwrap_err();
+        assert!(err.0.contains("Could not resolve"));
+    }
+
+    #[test]
+    fn test_review_thread_flattens_comments() {
+        let thread: ReviewThread = serde_json::from_str(
+            r#"{
+                "id": "T1", "isResolved": true, "isOutdated": false, "isCollapsed": true,
+                "path": "src/lib.rs", "line": 10, "startLine": null, "diffSide": "RIGHT",
+                "resolvedBy": {"login": "octocat", "avatarUrl": null},
+                "comments": {"nodes": [{"id": "C1", "databaseId": 1, "body": "nit",
+                    "url": "https://github.com", "createdAt": "2024-01-01T00:00:00Z", "author": null}]}
+            }"#,
+        )
+        .unwrap();
+
+        assert!(thread.is_resolved);
+        assert_eq!(thread.comments.len(), 1);
+        assert_eq!(thread.comments[0].body, "nit");
+    }
+
+    #[test]
+    fn test_discussion_comment_replies_default_empty() {
+        let comment: DiscussionComment = serde_json::from_str(
+            r#"{"id": "DC1", "databaseId": 7, "body": "thanks", "url": "https://github.com",
+                "createdAt": "2024-01-01T00:00:00Z", "author": null, "isAnswer": true, "upvoteCount": 2}"#,
+        )
+        .unwrap();
+
+        assert!(comment.is_answer);
+        assert!(comment.replies.is_empty());
+    }
+}
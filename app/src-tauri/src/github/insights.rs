@@ -3,9 +3,10 @@
 //! Provides access to repository statistics and traffic data.
 
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 use reqwest::Client;
 
-use super::get_stored_token;
+use super::app_auth::token_for_repo;
 
 /// Repository contributor
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -142,28 +143,27 @@ pub struct CommunityFile {
 }
 
 /// Error type for insights API
-#[derive(Debug)]
-pub struct InsightsError(pub String);
-
-impl std::fmt::Display for InsightsError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
-    }
+#[derive(Debug, Error)]
+pub enum InsightsError {
+    /// GitHub answered 202 because the statistics are still being computed
+    #[error("Statistics are being computed. Please try again later.")]
+    StatsPending,
+    #[error("{0}")]
+    Other(String),
 }
 
-impl std::error::Error for InsightsError {}
-
 pub type InsightsResult<T> = Result<T, InsightsError>;
 
-fn get_client() -> InsightsResult<(Client, String)> {
-    let token = get_stored_token().map_err(|e| InsightsError(e.to_string()))?;
-    let client = Client::new();
-    Ok((client, token))
+/// Client and token for a repository, authenticating as its GitHub App
+/// installation if one is configured for it
+async fn get_repo_client(owner: &str, repo: &str) -> InsightsResult<(Client, String)> {
+    let token = token_for_repo(owner, repo).await.map_err(|e| InsightsError::Other(e.to_string()))?;
+    Ok((Client::new(), token))
 }
 
 /// Get contributors list with stats
 pub async fn get_contributors(owner: &str, repo: &str) -> InsightsResult<Vec<Contributor>> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/stats/contributors",
@@ -178,28 +178,28 @@ pub async fn get_contributors(owner: &str, repo: &str) -> InsightsResult<Vec<Con
         .header("X-GitHub-Api-Version", "2022-11-28")
         .send()
         .await
-        .map_err(|e| InsightsError(format!("Request failed: {}", e)))?;
+        .map_err(|e| InsightsError::Other(format!("Request failed: {}", e)))?;
 
     // GitHub may return 202 if stats are being computed
     if response.status().as_u16() == 202 {
-        return Err(InsightsError("Statistics are being computed. Please try again later.".to_string()));
+        return Err(InsightsError::StatsPending);
     }
 
     if !response.status().is_success() {
         let status = response.status();
         let text = response.text().await.unwrap_or_default();
-        return Err(InsightsError(format!("GitHub API error ({}): {}", status, text)));
+        return Err(InsightsError::Other(format!("GitHub API error ({}): {}", status, text)));
     }
 
     response
         .json()
         .await
-        .map_err(|e| InsightsError(format!("Failed to parse response: {}", e)))
+        .map_err(|e| InsightsError::Other(format!("Failed to parse response: {}", e)))
 }
 
 /// Get weekly commit activity
 pub async fn get_commit_activity(owner: &str, repo: &str) -> InsightsResult<Vec<CommitActivity>> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/stats/commit_activity",
@@ -214,28 +214,28 @@ pub async fn get_commit_activity(owner: &str, repo: &str) -> InsightsResult<Vec<
         .header("X-GitHub-Api-Version", "2022-11-28")
         .send()
         .await
-        .map_err(|e| InsightsError(format!("Request failed: {}", e)))?;
+        .map_err(|e| InsightsError::Other(format!("Request failed: {}", e)))?;
 
     // GitHub may return 202 if stats are being computed
     if response.status().as_u16() == 202 {
-        return Err(InsightsError("Statistics are being computed. Please try again later.".to_string()));
+        return Err(InsightsError::StatsPending);
     }
 
     if !response.status().is_success() {
         let status = response.status();
         let text = response.text().await.unwrap_or_default();
-        return Err(InsightsError(format!("GitHub API error ({}): {}", status, text)));
+        return Err(InsightsError::Other(format!("GitHub API error ({}): {}", status, text)));
     }
 
     response
         .json()
         .await
-        .map_err(|e| InsightsError(format!("Failed to parse response: {}", e)))
+        .map_err(|e| InsightsError::Other(format!("Failed to parse response: {}", e)))
 }
 
 /// Get code frequency stats (additions/deletions per week)
 pub async fn get_code_frequency(owner: &str, repo: &str) -> InsightsResult<Vec<CodeFrequency>> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/stats/code_frequency",
@@ -250,24 +250,24 @@ pub async fn get_code_frequency(owner: &str, repo: &str) -> InsightsResult<Vec<C
         .header("X-GitHub-Api-Version", "2022-11-28")
         .send()
         .await
-        .map_err(|e| InsightsError(format!("Request failed: {}", e)))?;
+        .map_err(|e| InsightsError::Other(format!("Request failed: {}", e)))?;
 
     // GitHub may return 202 if stats are being computed
     if response.status().as_u16() == 202 {
-        return Err(InsightsError("Statistics are being computed. Please try again later.".to_string()));
+        return Err(InsightsError::StatsPending);
     }
 
     if !response.status().is_success() {
         let status = response.status();
         let text = response.text().await.unwrap_or_default();
-        return Err(InsightsError(format!("GitHub API error ({}): {}", status, text)));
+        return Err(InsightsError::Other(format!("GitHub API error ({}): {}", status, text)));
     }
 
     // Response is an array of [timestamp, additions, deletions]
     let raw: Vec<Vec<i64>> = response
         .json()
         .await
-        .map_err(|e| InsightsError(format!("Failed to parse response: {}", e)))?;
+        .map_err(|e| InsightsError::Other(format!("Failed to parse response: {}", e)))?;
 
     Ok(raw
         .into_iter()
@@ -281,7 +281,7 @@ pub async fn get_code_frequency(owner: &str, repo: &str) -> InsightsResult<Vec<C
 
 /// Get participation stats
 pub async fn get_participation(owner: &str, repo: &str) -> InsightsResult<Participation> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/stats/participation",
@@ -296,28 +296,28 @@ pub async fn get_participation(owner: &str, repo: &str) -> InsightsResult<Partic
         .header("X-GitHub-Api-Version", "2022-11-28")
         .send()
         .await
-        .map_err(|e| InsightsError(format!("Request failed: {}", e)))?;
+        .map_err(|e| InsightsError::Other(format!("Request failed: {}", e)))?;
 
     // GitHub may return 202 if stats are being computed
     if response.status().as_u16() == 202 {
-        return Err(InsightsError("Statistics are being computed. Please try again later.".to_string()));
+        return Err(InsightsError::StatsPending);
     }
 
     if !response.status().is_success() {
         let status = response.status();
         let text = response.text().await.unwrap_or_default();
-        return Err(InsightsError(format!("GitHub API error ({}): {}", status, text)));
+        return Err(InsightsError::Other(format!("GitHub API error ({}): {}", status, text)));
     }
 
     response
         .json()
         .await
-        .map_err(|e| InsightsError(format!("Failed to parse response: {}", e)))
+        .map_err(|e| InsightsError::Other(format!("Failed to parse response: {}", e)))
 }
 
 /// Get punch card data (commits by day and hour)
 pub async fn get_punch_card(owner: &str, repo: &str) -> InsightsResult<Vec<PunchCard>> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/stats/punch_card",
@@ -332,24 +332,24 @@ pub async fn get_punch_card(owner: &str, repo: &str) -> InsightsResult<Vec<Punch
         .header("X-GitHub-Api-Version", "2022-11-28")
         .send()
         .await
-        .map_err(|e| InsightsError(format!("Request failed: {}", e)))?;
+        .map_err(|e| InsightsError::Other(format!("Request failed: {}", e)))?;
 
     // GitHub may return 202 if stats are being computed
     if response.status().as_u16() == 202 {
-        return Err(InsightsError("Statistics are being computed. Please try again later.".to_string()));
+        return Err(InsightsError::StatsPending);
     }
 
     if !response.status().is_success() {
         let status = response.status();
         let text = response.text().await.unwrap_or_default();
-        return Err(InsightsError(format!("GitHub API error ({}): {}", status, text)));
+        return Err(InsightsError::Other(format!("GitHub API error ({}): {}", status, text)));
     }
 
     // Response is an array of [day, hour, commits]
     let raw: Vec<Vec<i32>> = response
         .json()
         .await
-        .map_err(|e| InsightsError(format!("Failed to parse response: {}", e)))?;
+        .map_err(|e| InsightsError::Other(format!("Failed to parse response: {}", e)))?;
 
     Ok(raw
         .into_iter()
@@ -363,7 +363,7 @@ pub async fn get_punch_card(owner: &str, repo: &str) -> InsightsResult<Vec<Punch
 
 /// Get traffic views (last 14 days)
 pub async fn get_traffic_views(owner: &str, repo: &str) -> InsightsResult<TrafficViews> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/traffic/views",
@@ -378,23 +378,23 @@ pub async fn get_traffic_views(owner: &str, repo: &str) -> InsightsResult<Traffi
         .header("X-GitHub-Api-Version", "2022-11-28")
         .send()
         .await
-        .map_err(|e| InsightsError(format!("Request failed: {}", e)))?;
+        .map_err(|e| InsightsError::Other(format!("Request failed: {}", e)))?;
 
     if !response.status().is_success() {
         let status = response.status();
         let text = response.text().await.unwrap_or_default();
-        return Err(InsightsError(format!("GitHub API error ({}): {}", status, text)));
+        return Err(InsightsError::Other(format!("GitHub API error ({}): {}", status, text)));
     }
 
     response
         .json()
         .await
-        .map_err(|e| InsightsError(format!("Failed to parse response: {}", e)))
+        .map_err(|e| InsightsError::Other(format!("Failed to parse response: {}", e)))
 }
 
 /// Get traffic clones (last 14 days)
 pub async fn get_traffic_clones(owner: &str, repo: &str) -> InsightsResult<TrafficClones> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/traffic/clones",
@@ -409,23 +409,23 @@ pub async fn get_traffic_clones(owner: &str, repo: &str) -> InsightsResult<Traff
         .header("X-GitHub-Api-Version", "2022-11-28")
         .send()
         .await
-        .map_err(|e| InsightsError(format!("Request failed: {}", e)))?;
+        .map_err(|e| InsightsError::Other(format!("Request failed: {}", e)))?;
 
     if !response.status().is_success() {
         let status = response.status();
         let text = response.text().await.unwrap_or_default();
-        return Err(InsightsError(format!("GitHub API error ({}): {}", status, text)));
+        return Err(InsightsError::Other(format!("GitHub API error ({}): {}", status, text)));
     }
 
     response
         .json()
         .await
-        .map_err(|e| InsightsError(format!("Failed to parse response: {}", e)))
+        .map_err(|e| InsightsError::Other(format!("Failed to parse response: {}", e)))
 }
 
 /// Get top referral sources
 pub async fn get_top_referrers(owner: &str, repo: &str) -> InsightsResult<Vec<Referrer>> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/traffic/popular/referrers",
@@ -440,23 +440,23 @@ pub async fn get_top_referrers(owner: &str, repo: &str) -> InsightsResult<Vec<Re
         .header("X-GitHub-Api-Version", "2022-11-28")
         .send()
         .await
-        .map_err(|e| InsightsError(format!("Request failed: {}", e)))?;
+        .map_err(|e| InsightsError::Other(format!("Request failed: {}", e)))?;
 
     if !response.status().is_success() {
         let status = response.status();
         let text = response.text().await.unwrap_or_default();
-        return Err(InsightsError(format!("GitHub API error ({}): {}", status, text)));
+        return Err(InsightsError::Other(format!("GitHub API error ({}): {}", status, text)));
     }
 
     response
         .json()
         .await
-        .map_err(|e| InsightsError(format!("Failed to parse response: {}", e)))
+        .map_err(|e| InsightsError::Other(format!("Failed to parse response: {}", e)))
 }
 
 /// Get popular content paths
 pub async fn get_popular_paths(owner: &str, repo: &str) -> InsightsResult<Vec<PopularPath>> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/traffic/popular/paths",
@@ -471,23 +471,23 @@ pub async fn get_popular_paths(owner: &str, repo: &str) -> InsightsResult<Vec<Po
         .header("X-GitHub-Api-Version", "2022-11-28")
         .send()
         .await
-        .map_err(|e| InsightsError(format!("Request failed: {}", e)))?;
+        .map_err(|e| InsightsError::Other(format!("Request failed: {}", e)))?;
 
     if !response.status().is_success() {
         let status = response.status();
         let text = response.text().await.unwrap_or_default();
-        return Err(InsightsError(format!("GitHub API error ({}): {}", status, text)));
+        return Err(InsightsError::Other(format!("GitHub API error ({}): {}", status, text)));
     }
 
     response
         .json()
         .await
-        .map_err(|e| InsightsError(format!("Failed to parse response: {}", e)))
+        .map_err(|e| InsightsError::Other(format!("Failed to parse response: {}", e)))
 }
 
 /// Get community profile
 pub async fn get_community_profile(owner: &str, repo: &str) -> InsightsResult<CommunityProfile> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/community/profile",
@@ -502,18 +502,18 @@ pub async fn get_community_profile(owner: &str, repo: &str) -> InsightsResult<Co
         .header("X-GitHub-Api-Version", "2022-11-28")
         .send()
         .await
-        .map_err(|e| InsightsError(format!("Request failed: {}", e)))?;
+        .map_err(|e| InsightsError::Other(format!("Request failed: {}", e)))?;
 
     if !response.status().is_success() {
         let status = response.status();
         let text = response.text().await.unwrap_or_default();
-        return Err(InsightsError(format!("GitHub API error ({}): {}", status, text)));
+        return Err(InsightsError::Other(format!("GitHub API error ({}): {}", status, text)));
     }
 
     response
         .json()
         .await
-        .map_err(|e| InsightsError(format!("Failed to parse response: {}", e)))
+        .map_err(|e| InsightsError::Other(format!("Failed to parse response: {}", e)))
 }
 
 /// Repository languages
@@ -522,7 +522,7 @@ pub struct Languages(pub std::collections::HashMap<String, i64>);
 
 /// Get repository languages
 pub async fn get_languages(owner: &str, repo: &str) -> InsightsResult<Languages> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/languages",
@@ -537,18 +537,18 @@ pub async fn get_languages(owner: &str, repo: &str) -> InsightsResult<Languages>
         .header("X-GitHub-Api-Version", "2022-11-28")
         .send()
         .await
-        .map_err(|e| InsightsError(format!("Request failed: {}", e)))?;
+        .map_err(|e| InsightsError::Other(format!("Request failed: {}", e)))?;
 
     if !response.status().is_success() {
         let status = response.status();
         let text = response.text().await.unwrap_or_default();
-        return Err(InsightsError(format!("GitHub API error ({}): {}", status, text)));
+        return Err(InsightsError::Other(format!("GitHub API error ({}): {}", status, text)));
     }
 
     let map: std::collections::HashMap<String, i64> = response
         .json()
         .await
-        .map_err(|e| InsightsError(format!("Failed to parse response: {}", e)))?;
+        .map_err(|e| InsightsError::Other(format!("Failed to parse response: {}", e)))?;
 
     Ok(Languages(map))
 }
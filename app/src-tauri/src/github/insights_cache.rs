@@ -0,0 +1,243 @@
+//! Local cache of slow repository insights
+//!
+//! The community profile, languages, and contributor statistics change slowly
+//! but are expensive to fetch, and the statistics endpoints answer 202 while
+//! GitHub computes them. Results are cached in a JSON file per repository under
+//! the app data directory and refreshed in the background once they are stale.
+
+use std::collections::HashSet;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::insights::{self, CommunityProfile, Contributor, InsightsError, InsightsResult, Languages};
+
+/// Event emitted when a background refresh has updated a repository's cache
+pub const INSIGHTS_CACHE_UPDATED_EVENT: &str = "insights-cache-updated";
+
+/// Age after which cached insights are refreshed
+pub const CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Delays between retries while GitHub is still computing statistics
+const STATS_RETRY_DELAYS: &[Duration] = &[
+    Duration::from_secs(2),
+    Duration::from_secs(4),
+    Duration::from_secs(8),
+    Duration::from_secs(16),
+];
+
+/// Cached value and when it was fetched
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedEntry<T> {
+    pub data: T,
+    pub fetched_at: String,
+}
+
+/// Cached insights of a repository
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InsightsCache {
+    pub owner: String,
+    pub repo: String,
+    pub community_profile: Option<CachedEntry<CommunityProfile>>,
+    pub languages: Option<CachedEntry<Languages>>,
+    pub contributors: Option<CachedEntry<Vec<Contributor>>>,
+}
+
+/// Cached insights along with their freshness
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedInsights {
+    #[serde(flatten)]
+    pub cache: InsightsCache,
+    /// Some entry is missing or older than the TTL
+    pub stale: bool,
+    /// A background refresh is in progress
+    pub refreshing: bool,
+}
+
+/// Repositories (`owner/repo`) with a refresh in progress
+static REFRESHING: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+
+fn refreshing() -> &'static Mutex<HashSet<String>> {
+    REFRESHING.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+fn cache_path(owner: &str, repo: &str) -> InsightsResult<PathBuf> {
+    dirs::data_dir()
+        .map(|dir| dir.join("linuxgit").join("insights").join(format!("{}__{}.json", owner, repo)))
+        .ok_or_else(|| InsightsError::Other("Could not determine the app data directory".to_string()))
+}
+
+/// Whether an entry fetched at `fetched_at` is still within the TTL at `now`
+pub fn is_fresh(fetched_at: &str, now: DateTime<Utc>) -> bool {
+    DateTime::parse_from_rfc3339(fetched_at)
+        .map(|at| now.signed_duration_since(at).num_seconds() < CACHE_TTL.as_secs() as i64)
+        .unwrap_or(false)
+}
+
+impl InsightsCache {
+    /// Whether any entry is missing or has expired at `now`
+    pub fn is_stale(&self, now: DateTime<Utc>) -> bool {
+        let fetched = [
+            self.community_profile.as_ref().map(|e| e.fetched_at.as_str()),
+            self.languages.as_ref().map(|e| e.fetched_at.as_str()),
+            self.contributors.as_ref().map(|e| e.fetched_at.as_str()),
+        ];
+        fetched.iter().any(|at| !at.is_some_and(|at| is_fresh(at, now)))
+    }
+}
+
+/// Load the cached insights of a repository (empty if nothing has been cached)
+pub fn load_cache(owner: &str, repo: &str) -> InsightsResult<InsightsCache> {
+    let path = cache_path(owner, repo)?;
+    if !path.exists() {
+        return Ok(InsightsCache {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            ..Default::default()
+        });
+    }
+
+    let data = std::fs::read_to_string(&path)
+        .map_err(|e| InsightsError::Other(format!("Failed to read insights cache: {}", e)))?;
+    serde_json::from_str(&data)
+        .map_err(|e| InsightsError::Other(format!("Failed to parse insights cache: {}", e)))
+}
+
+fn save_cache(cache: &InsightsCache) -> InsightsResult<()> {
+    let path = cache_path(&cache.owner, &cache.repo)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| InsightsError::Other(format!("Failed to create insights directory: {}", e)))?;
+    }
+
+    let data = serde_json::to_string_pretty(cache)
+        .map_err(|e| InsightsError::Other(format!("Failed to serialize insights cache: {}", e)))?;
+    std::fs::write(&path, data)
+        .map_err(|e| InsightsError::Other(format!("Failed to write insights cache: {}", e)))
+}
+
+/// Delete the cached insights of a repository
+pub fn clear_cache(owner: &str, repo: &str) -> InsightsResult<()> {
+    let path = cache_path(owner, repo)?;
+    if path.exists() {
+        std::fs::remove_file(&path)
+            .map_err(|e| InsightsError::Other(format!("Failed to delete insights cache: {}", e)))?;
+    }
+    Ok(())
+}
+
+/// Run a statistics request, retrying while GitHub answers 202
+async fn with_stats_retry<T, F, Fut>(fetch: F) -> InsightsResult<T>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = InsightsResult<T>>,
+{
+    for delay in STATS_RETRY_DELAYS {
+        match fetch().await {
+            Err(InsightsError::StatsPending) => tokio::time::sleep(*delay).await,
+            result => return result,
+        }
+    }
+    fetch().await
+}
+
+/// Fetch all cached insights of a repository and update its cache
+///
+/// Entries that fail to refresh keep their previous value; an error is only
+/// returned when nothing could be fetched.
+pub async fn refresh_cache(owner: &str, repo: &str) -> InsightsResult<InsightsCache> {
+    let (community_profile, languages, contributors) = tokio::join!(
+        insights::get_community_profile(owner, repo),
+        insights::get_languages(owner, repo),
+        with_stats_retry(|| insights::get_contributors(owner, repo)),
+    );
+
+    let mut cache = load_cache(owner, repo)?;
+    let fetched_at = Utc::now().to_rfc3339();
+    let mut first_error = None;
+
+    match community_profile {
+        Ok(data) => cache.community_profile = Some(CachedEntry { data, fetched_at: fetched_at.clone() }),
+        Err(e) => first_error = first_error.or(Some(e)),
+    }
+    match languages {
+        Ok(data) => cache.languages = Some(CachedEntry { data, fetched_at: fetched_at.clone() }),
+        Err(e) => first_error = first_error.or(Some(e)),
+    }
+    match contributors {
+        Ok(data) => cache.contributors = Some(CachedEntry { data, fetched_at }),
+        Err(e) => first_error = first_error.or(Some(e)),
+    }
+
+    if let Some(e) = first_error {
+        tracing::warn!("Refreshing insights of {}/{} was incomplete: {}", owner, repo, e);
+        if cache.community_profile.is_none() && cache.languages.is_none() && cache.contributors.is_none() {
+            return Err(e);
+        }
+    }
+
+    save_cache(&cache)?;
+    Ok(cache)
+}
+
+/// Mark a repository as refreshing; returns `false` if a refresh is already running
+pub fn begin_refresh(owner: &str, repo: &str) -> bool {
+    refreshing().lock().unwrap().insert(format!("{}/{}", owner, repo))
+}
+
+/// Mark a repository's refresh as finished
+pub fn end_refresh(owner: &str, repo: &str) {
+    refreshing().lock().unwrap().remove(&format!("{}/{}", owner, repo));
+}
+
+/// Whether a refresh of a repository is in progress
+pub fn is_refreshing(owner: &str, repo: &str) -> bool {
+    refreshing().lock().unwrap().contains(&format!("{}/{}", owner, repo))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(time: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(time).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_is_fresh() {
+        let now = at("2024-01-01T12:00:00Z");
+        assert!(is_fresh("2024-01-01T11:30:00Z", now));
+        assert!(!is_fresh("2024-01-01T10:59:00Z", now));
+        assert!(!is_fresh("garbage", now));
+    }
+
+    #[test]
+    fn test_is_stale_when_an_entry_is_missing() {
+        let now = at("2024-01-01T12:00:00Z");
+        let fetched_at = "2024-01-01T11:30:00Z".to_string();
+        let mut cache = InsightsCache {
+            languages: Some(CachedEntry { data: Languages(Default::default()), fetched_at: fetched_at.clone() }),
+            contributors: Some(CachedEntry { data: Vec::new(), fetched_at }),
+            ..Default::default()
+        };
+        assert!(cache.is_stale(now));
+
+        cache.community_profile = serde_json::from_value(serde_json::json!({
+            "data": {
+                "health_percentage": 80, "description": null, "documentation": null,
+                "updated_at": null, "content_reports_enabled": null,
+                "files": {
+                    "code_of_conduct": null, "code_of_conduct_file": null, "contributing": null,
+                    "issue_template": null, "pull_request_template": null, "license": null, "readme": null
+                }
+            },
+            "fetched_at": "2024-01-01T11:45:00Z"
+        }))
+        .unwrap();
+        assert!(!cache.is_stale(now));
+    }
+}
@@ -5,7 +5,7 @@
 use serde::{Deserialize, Serialize};
 use reqwest::Client;
 
-use super::get_stored_token;
+use super::app_auth::token_for_repo;
 
 /// GitHub User (simplified)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -97,10 +97,11 @@ impl std::error::Error for IssuesError {}
 
 pub type IssuesResult<T> = Result<T, IssuesError>;
 
-fn get_client() -> IssuesResult<(Client, String)> {
-    let token = get_stored_token().map_err(|e| IssuesError(e.to_string()))?;
-    let client = Client::new();
-    Ok((client, token))
+/// Client and token for a repository, authenticating as its GitHub App
+/// installation if one is configured for it
+async fn get_repo_client(owner: &str, repo: &str) -> IssuesResult<(Client, String)> {
+    let token = token_for_repo(owner, repo).await.map_err(|e| IssuesError(e.to_string()))?;
+    Ok((Client::new(), token))
 }
 
 /// List issues for a repository
@@ -112,7 +113,7 @@ pub async fn list_issues(
     direction: Option<&str>,
     per_page: Option<u32>,
 ) -> IssuesResult<Vec<Issue>> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!("https://api.github.com/repos/{}/{}/issues", owner, repo);
 
@@ -157,7 +158,7 @@ pub async fn get_issue(
     repo: &str,
     issue_number: i32,
 ) -> IssuesResult<Issue> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/issues/{}",
@@ -196,7 +197,7 @@ pub async fn create_issue(
     assignees: Option<Vec<String>>,
     milestone: Option<i32>,
 ) -> IssuesResult<Issue> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!("https://api.github.com/repos/{}/{}/issues", owner, repo);
 
@@ -253,7 +254,7 @@ pub async fn update_issue(
     assignees: Option<Vec<String>>,
     milestone: Option<i32>,
 ) -> IssuesResult<Issue> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/issues/{}",
@@ -314,7 +315,7 @@ pub async fn list_issue_comments(
     issue_number: i32,
     per_page: Option<u32>,
 ) -> IssuesResult<Vec<IssueComment>> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/issues/{}/comments",
@@ -356,7 +357,7 @@ pub async fn create_issue_comment(
     issue_number: i32,
     body: &str,
 ) -> IssuesResult<IssueComment> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/issues/{}/comments",
@@ -390,13 +391,117 @@ pub async fn create_issue_comment(
         .map_err(|e| IssuesError(format!("Failed to parse response: {}", e)))
 }
 
+/// Get a single issue comment
+pub async fn get_issue_comment(
+    owner: &str,
+    repo: &str,
+    comment_id: i64,
+) -> IssuesResult<IssueComment> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/issues/comments/{}",
+        owner, repo, comment_id
+    );
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| IssuesError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(IssuesError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| IssuesError(format!("Failed to parse response: {}", e)))
+}
+
+/// Update the body of an issue comment
+pub async fn update_issue_comment(
+    owner: &str,
+    repo: &str,
+    comment_id: i64,
+    body: &str,
+) -> IssuesResult<IssueComment> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/issues/comments/{}",
+        owner, repo, comment_id
+    );
+
+    let payload = serde_json::json!({
+        "body": body
+    });
+
+    let response = client
+        .patch(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| IssuesError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(IssuesError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| IssuesError(format!("Failed to parse response: {}", e)))
+}
+
+/// Delete an issue comment
+pub async fn delete_issue_comment(owner: &str, repo: &str, comment_id: i64) -> IssuesResult<()> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/issues/comments/{}",
+        owner, repo, comment_id
+    );
+
+    let response = client
+        .delete(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| IssuesError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(IssuesError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    Ok(())
+}
+
 /// List labels for a repository
 pub async fn list_labels(
     owner: &str,
     repo: &str,
     per_page: Option<u32>,
 ) -> IssuesResult<Vec<Label>> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!("https://api.github.com/repos/{}/{}/labels", owner, repo);
 
@@ -435,7 +540,7 @@ pub async fn list_milestones(
     state: Option<&str>,
     per_page: Option<u32>,
 ) -> IssuesResult<Vec<Milestone>> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!("https://api.github.com/repos/{}/{}/milestones", owner, repo);
 
@@ -477,7 +582,7 @@ pub async fn add_labels_to_issue(
     issue_number: i32,
     labels: Vec<String>,
 ) -> IssuesResult<Vec<Label>> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/issues/{}/labels",
@@ -518,7 +623,7 @@ pub async fn lock_issue(
     issue_number: i32,
     lock_reason: Option<&str>,
 ) -> IssuesResult<()> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/issues/{}/lock",
@@ -557,7 +662,7 @@ pub async fn unlock_issue(
     repo: &str,
     issue_number: i32,
 ) -> IssuesResult<()> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/issues/{}/lock",
@@ -582,3 +687,433 @@ pub async fn unlock_issue(
 
     Ok(())
 }
+
+/// Add assignees to an issue, keeping any existing ones
+pub async fn add_assignees(
+    owner: &str,
+    repo: &str,
+    issue_number: i32,
+    assignees: Vec<String>,
+) -> IssuesResult<Issue> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/issues/{}/assignees",
+        owner, repo, issue_number
+    );
+
+    let payload = serde_json::json!({
+        "assignees": assignees
+    });
+
+    let response = client
+        .post(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| IssuesError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(IssuesError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| IssuesError(format!("Failed to parse response: {}", e)))
+}
+
+/// Remove assignees from an issue, keeping the others
+pub async fn remove_assignees(
+    owner: &str,
+    repo: &str,
+    issue_number: i32,
+    assignees: Vec<String>,
+) -> IssuesResult<Issue> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/issues/{}/assignees",
+        owner, repo, issue_number
+    );
+
+    let payload = serde_json::json!({
+        "assignees": assignees
+    });
+
+    let response = client
+        .delete(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| IssuesError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(IssuesError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| IssuesError(format!("Failed to parse response: {}", e)))
+}
+
+/// List users that can be assigned to issues in a repository
+pub async fn list_assignable_users(
+    owner: &str,
+    repo: &str,
+    per_page: Option<u32>,
+) -> IssuesResult<Vec<IssueUser>> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!("https://api.github.com/repos/{}/{}/assignees", owner, repo);
+
+    let mut request = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28");
+
+    if let Some(pp) = per_page {
+        request = request.query(&[("per_page", pp.to_string())]);
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| IssuesError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(IssuesError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| IssuesError(format!("Failed to parse response: {}", e)))
+}
+
+/// Create a label in a repository
+pub async fn create_label(
+    owner: &str,
+    repo: &str,
+    name: &str,
+    color: &str,
+    description: Option<&str>,
+) -> IssuesResult<Label> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!("https://api.github.com/repos/{}/{}/labels", owner, repo);
+
+    let mut payload = serde_json::json!({
+        "name": name,
+        "color": color.trim_start_matches('#'),
+    });
+
+    if let Some(d) = description {
+        payload["description"] = serde_json::json!(d);
+    }
+
+    let response = client
+        .post(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| IssuesError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(IssuesError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| IssuesError(format!("Failed to parse response: {}", e)))
+}
+
+/// Update (rename, recolor, or redescribe) a label
+pub async fn update_label(
+    owner: &str,
+    repo: &str,
+    name: &str,
+    new_name: Option<&str>,
+    color: Option<&str>,
+    description: Option<&str>,
+) -> IssuesResult<Label> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/labels/{}",
+        owner, repo, urlencoding::encode(name)
+    );
+
+    let mut payload = serde_json::Map::new();
+    if let Some(n) = new_name {
+        payload.insert("new_name".to_string(), serde_json::json!(n));
+    }
+    if let Some(c) = color {
+        payload.insert("color".to_string(), serde_json::json!(c.trim_start_matches('#')));
+    }
+    if let Some(d) = description {
+        payload.insert("description".to_string(), serde_json::json!(d));
+    }
+
+    let response = client
+        .patch(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| IssuesError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(IssuesError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| IssuesError(format!("Failed to parse response: {}", e)))
+}
+
+/// Delete a label from a repository
+pub async fn delete_label(
+    owner: &str,
+    repo: &str,
+    name: &str,
+) -> IssuesResult<()> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/labels/{}",
+        owner, repo, urlencoding::encode(name)
+    );
+
+    let response = client
+        .delete(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| IssuesError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(IssuesError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    Ok(())
+}
+
+/// Normalize a milestone due date to the ISO 8601 timestamp GitHub expects.
+///
+/// Accepts a plain `YYYY-MM-DD` date or a full RFC 3339 timestamp. An empty
+/// string yields `null`, which clears the due date.
+fn normalize_due_on(due_on: &str) -> IssuesResult<serde_json::Value> {
+    let trimmed = due_on.trim();
+    if trimmed.is_empty() {
+        return Ok(serde_json::Value::Null);
+    }
+
+    if let Ok(date) = chrono::NaiveDate::parse_from_str(trimmed, "%Y-%m-%d") {
+        return Ok(serde_json::json!(format!("{}T00:00:00Z", date.format("%Y-%m-%d"))));
+    }
+
+    chrono::DateTime::parse_from_rfc3339(trimmed)
+        .map(|dt| {
+            serde_json::json!(dt
+                .with_timezone(&chrono::Utc)
+                .format("%Y-%m-%dT%H:%M:%SZ")
+                .to_string())
+        })
+        .map_err(|_| IssuesError(format!("Invalid due date '{}': expected YYYY-MM-DD", due_on)))
+}
+
+/// Create a milestone in a repository
+pub async fn create_milestone(
+    owner: &str,
+    repo: &str,
+    title: &str,
+    description: Option<&str>,
+    due_on: Option<&str>,
+) -> IssuesResult<Milestone> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!("https://api.github.com/repos/{}/{}/milestones", owner, repo);
+
+    let mut payload = serde_json::json!({
+        "title": title,
+    });
+
+    if let Some(d) = description {
+        payload["description"] = serde_json::json!(d);
+    }
+    if let Some(due) = due_on {
+        payload["due_on"] = normalize_due_on(due)?;
+    }
+
+    let response = client
+        .post(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| IssuesError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(IssuesError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| IssuesError(format!("Failed to parse response: {}", e)))
+}
+
+/// Update a milestone (an empty `due_on` clears the due date)
+pub async fn update_milestone(
+    owner: &str,
+    repo: &str,
+    milestone_number: i32,
+    title: Option<&str>,
+    description: Option<&str>,
+    due_on: Option<&str>,
+    state: Option<&str>,
+) -> IssuesResult<Milestone> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/milestones/{}",
+        owner, repo, milestone_number
+    );
+
+    let mut payload = serde_json::Map::new();
+    if let Some(t) = title {
+        payload.insert("title".to_string(), serde_json::json!(t));
+    }
+    if let Some(d) = description {
+        payload.insert("description".to_string(), serde_json::json!(d));
+    }
+    if let Some(due) = due_on {
+        payload.insert("due_on".to_string(), normalize_due_on(due)?);
+    }
+    if let Some(s) = state {
+        payload.insert("state".to_string(), serde_json::json!(s));
+    }
+
+    let response = client
+        .patch(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| IssuesError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(IssuesError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| IssuesError(format!("Failed to parse response: {}", e)))
+}
+
+/// Close a milestone
+pub async fn close_milestone(
+    owner: &str,
+    repo: &str,
+    milestone_number: i32,
+) -> IssuesResult<Milestone> {
+    update_milestone(owner, repo, milestone_number, None, None, None, Some("closed")).await
+}
+
+/// Delete a milestone
+pub async fn delete_milestone(
+    owner: &str,
+    repo: &str,
+    milestone_number: i32,
+) -> IssuesResult<()> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/milestones/{}",
+        owner, repo, milestone_number
+    );
+
+    let response = client
+        .delete(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| IssuesError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(IssuesError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_due_on() {
+        assert_eq!(
+            normalize_due_on("2024-06-30").unwrap(),
+            serde_json::json!("2024-06-30T00:00:00Z")
+        );
+        assert_eq!(
+            normalize_due_on("2024-06-30T12:00:00+02:00").unwrap(),
+            serde_json::json!("2024-06-30T10:00:00Z")
+        );
+        assert_eq!(normalize_due_on("").unwrap(), serde_json::Value::Null);
+        assert!(normalize_due_on("30/06/2024").is_err());
+    }
+}
@@ -0,0 +1,205 @@
+//! Workflow job log parsing
+//!
+//! Splits a raw Actions job log into per-step sections using the step
+//! timestamps reported by the jobs API, and strips ANSI escape codes.
+
+use chrono::{DateTime, FixedOffset};
+use serde::{Deserialize, Serialize};
+
+use super::actions::WorkflowStep;
+
+/// A single log line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogLine {
+    pub timestamp: Option<String>,
+    pub content: String,
+}
+
+/// Log output of a single step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogSection {
+    pub number: Option<i32>,
+    pub name: String,
+    pub status: Option<String>,
+    pub conclusion: Option<String>,
+    pub started_at: Option<String>,
+    pub completed_at: Option<String>,
+    pub has_errors: bool,
+    pub lines: Vec<LogLine>,
+}
+
+/// Job log split into step sections
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobLog {
+    pub job_id: i64,
+    pub sections: Vec<LogSection>,
+}
+
+/// Remove ANSI escape sequences (colors, cursor movement, OSC titles)
+pub fn strip_ansi(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            output.push(c);
+            continue;
+        }
+
+        match chars.peek() {
+            // CSI: ESC [ params final-byte
+            Some('[') => {
+                chars.next();
+                for c in chars.by_ref() {
+                    if ('\u{40}'..='\u{7e}').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC: ESC ] ... BEL or ESC \
+            Some(']') => {
+                chars.next();
+                while let Some(c) = chars.next() {
+                    if c == '\u{07}' {
+                        break;
+                    }
+                    if c == '\u{1b}' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            // Two-character escape
+            Some(_) => {
+                chars.next();
+            }
+            None => {}
+        }
+    }
+
+    output
+}
+
+/// Split a log line into its leading RFC 3339 timestamp and content
+fn split_timestamp(line: &str) -> (Option<DateTime<FixedOffset>>, &str) {
+    if let Some((first, rest)) = line.split_once(' ') {
+        if let Ok(ts) = DateTime::parse_from_rfc3339(first) {
+            return (Some(ts), rest);
+        }
+    }
+    (None, line)
+}
+
+fn parse_time(value: Option<&str>) -> Option<DateTime<FixedOffset>> {
+    value.and_then(|v| DateTime::parse_from_rfc3339(v).ok())
+}
+
+/// Parse a raw job log into sections, one per step that has started
+pub fn parse_job_log(job_id: i64, log: &str, steps: &[WorkflowStep]) -> JobLog {
+    let mut sections: Vec<LogSection> = Vec::new();
+    let mut starts = Vec::new();
+
+    for step in steps {
+        if let Some(start) = parse_time(step.started_at.as_deref()) {
+            starts.push(start);
+            sections.push(LogSection {
+                number: Some(step.number),
+                name: step.name.clone(),
+                status: Some(step.status.clone()),
+                conclusion: step.conclusion.clone(),
+                started_at: step.started_at.clone(),
+                completed_at: step.completed_at.clone(),
+                has_errors: false,
+                lines: Vec::new(),
+            });
+        }
+    }
+
+    if sections.is_empty() {
+        starts.push(DateTime::<FixedOffset>::MIN_UTC.fixed_offset());
+        sections.push(LogSection {
+            number: None,
+            name: "Log".to_string(),
+            status: None,
+            conclusion: None,
+            started_at: None,
+            completed_at: None,
+            has_errors: false,
+            lines: Vec::new(),
+        });
+    }
+
+    let mut current = 0;
+    for raw in log.lines() {
+        let line = strip_ansi(raw.trim_start_matches('\u{feff}'));
+        let (timestamp, content) = split_timestamp(&line);
+
+        if let Some(ts) = timestamp {
+            // Step start times have second precision, so compare truncated to seconds
+            let ts_secs = ts.timestamp();
+            while current + 1 < starts.len() && starts[current + 1].timestamp() <= ts_secs {
+                current += 1;
+            }
+        }
+
+        let section = &mut sections[current];
+        if content.starts_with("##[error]") {
+            section.has_errors = true;
+        }
+        section.lines.push(LogLine {
+            timestamp: timestamp.map(|t| t.to_rfc3339()),
+            content: content.to_string(),
+        });
+    }
+
+    JobLog { job_id, sections }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(number: i32, name: &str, started_at: Option<&str>) -> WorkflowStep {
+        WorkflowStep {
+            name: name.to_string(),
+            status: "completed".to_string(),
+            conclusion: Some("success".to_string()),
+            number,
+            started_at: started_at.map(|s| s.to_string()),
+            completed_at: None,
+        }
+    }
+
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(strip_ansi("\u{1b}[36;1mnpm test\u{1b}[0m"), "npm test");
+        assert_eq!(strip_ansi("\u{1b}]0;title\u{07}plain"), "plain");
+    }
+
+    #[test]
+    fn test_parse_job_log_assigns_lines_to_steps() {
+        let steps = vec![
+            step(1, "Set up job", Some("2024-01-01T00:00:00Z")),
+            step(2, "Run tests", Some("2024-01-01T00:00:05Z")),
+            step(3, "Skipped", None),
+        ];
+        let log = "2024-01-01T00:00:01.1234567Z Current runner version\n\
+                   2024-01-01T00:00:05.5000000Z ##[group]Run cargo test\n\
+                   2024-01-01T00:00:09.0000000Z \u{1b}[31m##[error]Process completed with exit code 101.\n";
+
+        let parsed = parse_job_log(42, log, &steps);
+        assert_eq!(parsed.sections.len(), 2);
+        assert_eq!(parsed.sections[0].lines.len(), 1);
+        assert!(!parsed.sections[0].has_errors);
+        assert_eq!(parsed.sections[1].lines.len(), 2);
+        assert!(parsed.sections[1].has_errors);
+        assert_eq!(parsed.sections[1].lines[0].content, "##[group]Run cargo test");
+    }
+
+    #[test]
+    fn test_parse_job_log_without_steps() {
+        let parsed = parse_job_log(1, "no timestamp here", &[]);
+        assert_eq!(parsed.sections.len(), 1);
+        assert_eq!(parsed.sections[0].lines[0].content, "no timestamp here");
+    }
+}
@@ -7,12 +7,33 @@ pub mod api;
 pub mod actions;
 pub mod releases;
 pub mod pages;
+pub mod pages_artifact;
 pub mod notifications;
 pub mod insights;
 pub mod pull_requests;
 pub mod issues;
 pub mod deployments;
 pub mod environments;
+pub mod graphql;
+pub mod checks;
+pub mod teams;
+pub mod reactions;
+pub mod tasklist;
+pub mod collaborators;
+pub mod secrets;
+pub mod job_logs;
+pub mod run_watcher;
+pub mod commits;
+pub mod contents;
+pub mod templates;
+pub mod workflow_templates;
+pub mod act;
+pub mod search;
+pub mod traffic_history;
+pub mod insights_cache;
+pub mod notification_poller;
+pub mod app_auth;
+pub mod avatars;
 
 pub use oauth::*;
 pub use api::*;
@@ -0,0 +1,81 @@
+//! Notification poller
+//!
+//! Tracks which unread notifications have been seen between polls, so new
+//! ones can be announced, and which of them deserve a desktop notification.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use super::notifications::Notification;
+
+/// Event emitted whenever the unread notifications change
+pub const NOTIFICATIONS_UPDATE_EVENT: &str = "notifications-update";
+
+/// Reasons that trigger a desktop notification
+const DESKTOP_NOTIFY_REASONS: &[&str] = &["mention", "team_mention", "review_requested", "assign"];
+
+/// Payload of the notifications update event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationsUpdate {
+    pub unread_count: usize,
+    pub new_notifications: Vec<Notification>,
+}
+
+/// Identifies the running poller, so a stopped poller exits even if another was started since
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotificationPollerHandle {
+    pub id: u64,
+    pub desktop_notifications: bool,
+}
+
+/// Notifications that are unread now and were not in the previous poll,
+/// or were updated since they were last seen
+pub fn find_new_notifications(
+    seen: &HashSet<(String, String)>,
+    current: &[Notification],
+) -> Vec<Notification> {
+    current
+        .iter()
+        .filter(|n| !seen.contains(&(n.id.clone(), n.updated_at.clone())))
+        .cloned()
+        .collect()
+}
+
+/// Keys recorded for the notifications of a poll
+pub fn seen_keys(notifications: &[Notification]) -> HashSet<(String, String)> {
+    notifications
+        .iter()
+        .map(|n| (n.id.clone(), n.updated_at.clone()))
+        .collect()
+}
+
+/// Whether a notification warrants a desktop notification
+pub fn should_notify_desktop(notification: &Notification) -> bool {
+    DESKTOP_NOTIFY_REASONS.contains(&notification.reason.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::notifications::test_notification as notification;
+
+    #[test]
+    fn test_find_new_notifications() {
+        let before = vec![notification("1", "mention", "2024-01-01T00:00:00Z")];
+        let seen = seen_keys(&before);
+        let after = vec![
+            notification("1", "mention", "2024-01-01T00:00:00Z"),
+            notification("2", "review_requested", "2024-01-02T00:00:00Z"),
+            notification("3", "subscribed", "2024-01-02T00:00:00Z"),
+        ];
+
+        let new = find_new_notifications(&seen, &after);
+        assert_eq!(new.len(), 2);
+        assert!(should_notify_desktop(&new[0]));
+        assert!(!should_notify_desktop(&new[1]));
+
+        let updated = vec![notification("1", "mention", "2024-01-03T00:00:00Z")];
+        assert_eq!(find_new_notifications(&seen, &updated).len(), 1);
+    }
+}
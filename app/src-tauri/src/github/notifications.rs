@@ -446,17 +446,199 @@ pub async fn delete_thread_subscription(thread_id: &str) -> NotificationsResult<
     Ok(())
 }
 
+/// Largest page size the notifications endpoint accepts
+const MAX_PER_PAGE: u32 = 50;
+
+/// Result of a conditional poll of the notifications endpoint
+#[derive(Debug, Clone)]
+pub struct NotificationsPoll {
+    /// `false` when nothing changed since `If-Modified-Since`
+    pub modified: bool,
+    /// `Last-Modified` to send with the next poll
+    pub last_modified: Option<String>,
+    /// Seconds GitHub asks clients to wait before polling again
+    pub poll_interval: u64,
+    /// All unread notifications (empty when not modified)
+    pub notifications: Vec<Notification>,
+}
+
+/// Narrows a list of notifications by reason, subject type, and repository
+///
+/// Empty lists match everything. Subject types are compared case-insensitively,
+/// and `ci` stands for both check suites and workflow runs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotificationFilter {
+    #[serde(default)]
+    pub reasons: Vec<String>,
+    #[serde(default)]
+    pub subject_types: Vec<String>,
+    /// Repository `owner/name`
+    pub repository: Option<String>,
+}
+
+impl NotificationFilter {
+    /// Whether a notification passes the filter
+    pub fn matches(&self, notification: &Notification) -> bool {
+        let reason_ok = self.reasons.is_empty() || self.reasons.contains(&notification.reason);
+
+        let subject_type = notification.subject.subject_type.as_str();
+        let type_ok = self.subject_types.is_empty()
+            || self.subject_types.iter().any(|t| {
+                t.eq_ignore_ascii_case(subject_type)
+                    || (t.eq_ignore_ascii_case("ci") && matches!(subject_type, "CheckSuite" | "WorkflowRun"))
+            });
+
+        let repo_ok = self
+            .repository
+            .as_deref()
+            .is_none_or(|r| r.eq_ignore_ascii_case(&notification.repository.full_name));
+
+        reason_ok && type_ok && repo_ok
+    }
+}
+
+/// List all unread notifications, following pagination
+pub async fn list_all_unread() -> NotificationsResult<Vec<Notification>> {
+    list_all_notifications(false, false).await
+}
+
+/// List all notifications, following pagination
+pub async fn list_all_notifications(all: bool, participating: bool) -> NotificationsResult<Vec<Notification>> {
+    let mut notifications = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let batch = list_notifications(
+            Some(all),
+            Some(participating),
+            None,
+            None,
+            Some(MAX_PER_PAGE),
+            Some(page),
+        )
+        .await?;
+        let done = (batch.len() as u32) < MAX_PER_PAGE;
+        notifications.extend(batch);
+        if done {
+            return Ok(notifications);
+        }
+        page += 1;
+    }
+}
+
+/// Poll unread notifications, only fetching them when they changed since `last_modified`
+pub async fn poll_notifications(last_modified: Option<&str>) -> NotificationsResult<NotificationsPoll> {
+    let (client, token) = get_client()?;
+
+    let mut request = client
+        .get("https://api.github.com/notifications")
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .query(&[("per_page", MAX_PER_PAGE.to_string())]);
+    if let Some(since) = last_modified {
+        request = request.header("If-Modified-Since", since);
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| NotificationsError(format!("Request failed: {}", e)))?;
+
+    let headers = response.headers();
+    let poll_interval = headers
+        .get("x-poll-interval")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(60);
+    let new_last_modified = headers
+        .get("last-modified")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string());
+
+    if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+        return Ok(NotificationsPoll {
+            modified: false,
+            last_modified: last_modified.map(|v| v.to_string()),
+            poll_interval,
+            notifications: Vec::new(),
+        });
+    }
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(NotificationsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    let first_page: Vec<Notification> = response
+        .json()
+        .await
+        .map_err(|e| NotificationsError(format!("Failed to parse response: {}", e)))?;
+
+    // Only the first page is conditional; fetch the rest when it is full
+    let notifications = if (first_page.len() as u32) < MAX_PER_PAGE {
+        first_page
+    } else {
+        list_all_unread().await?
+    };
+
+    Ok(NotificationsPoll {
+        modified: true,
+        last_modified: new_last_modified,
+        poll_interval,
+        notifications,
+    })
+}
+
 /// Get the count of unread notifications
 pub async fn get_unread_count() -> NotificationsResult<u32> {
-    let notifications = list_notifications(
-        Some(false), // only unread
-        None,
-        None,
-        None,
-        Some(1), // we only need the count
-        None,
-    )
-    .await?;
-
-    Ok(notifications.len() as u32)
+    Ok(list_all_unread().await?.len() as u32)
+}
+
+/// A notification fixture shared by the tests of the notification modules
+#[cfg(test)]
+pub(crate) fn test_notification(id: &str, reason: &str, updated_at: &str) -> Notification {
+    serde_json::from_value(serde_json::json!({
+        "id": id, "unread": true, "reason": reason, "updated_at": updated_at,
+        "last_read_at": null, "url": "", "subscription_url": "",
+        "subject": { "title": "Fix it", "type": "PullRequest", "url": null, "latest_comment_url": null },
+        "repository": {
+            "id": 1, "name": "repo", "full_name": "owner/repo", "html_url": "",
+            "description": null, "private": false,
+            "owner": { "login": "owner", "avatar_url": "" }
+        }
+    }))
+    .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification(reason: &str, subject_type: &str, repo: &str) -> Notification {
+        let mut notification = test_notification("1", reason, "");
+        notification.subject.subject_type = subject_type.to_string();
+        notification.repository.full_name = repo.to_string();
+        notification
+    }
+
+    #[test]
+    fn test_notification_filter() {
+        let filter = NotificationFilter {
+            reasons: vec!["review_requested".to_string(), "ci_activity".to_string()],
+            subject_types: vec!["pullrequest".to_string(), "ci".to_string()],
+            repository: None,
+        };
+
+        assert!(filter.matches(&notification("review_requested", "PullRequest", "a/b")));
+        assert!(filter.matches(&notification("ci_activity", "WorkflowRun", "a/b")));
+        assert!(!filter.matches(&notification("mention", "PullRequest", "a/b")));
+        assert!(!filter.matches(&notification("review_requested", "Issue", "a/b")));
+
+        let by_repo = NotificationFilter { repository: Some("A/B".to_string()), ..Default::default() };
+        assert!(by_repo.matches(&notification("mention", "Issue", "a/b")));
+        assert!(!by_repo.matches(&notification("mention", "Issue", "a/c")));
+    }
 }
@@ -5,7 +5,22 @@
 use serde::{Deserialize, Serialize};
 use reqwest::Client;
 
-use super::get_stored_token;
+use super::app_auth::token_for_repo;
+
+/// Event emitted while a directory is being deployed to Pages
+pub const PAGES_DEPLOY_PROGRESS_EVENT: &str = "pages-deploy-progress";
+
+/// Interval between polls of the Pages build
+const BUILD_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Deployment statuses after which a Pages deployment will not succeed
+const FAILED_DEPLOYMENT_STATUSES: &[&str] = &[
+    "deployment_failed",
+    "deployment_content_failed",
+    "deployment_cancelled",
+    "deployment_lost",
+    "not_found",
+];
 
 /// GitHub Pages information
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,11 +78,22 @@ pub struct PagesPusher {
 /// GitHub Pages deployment
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PagesDeployment {
-    pub id: i64,
+    /// Numeric id or build version of the deployment
+    #[serde(deserialize_with = "deserialize_deployment_id")]
+    pub id: String,
     pub status_url: String,
     pub page_url: String,
 }
 
+/// Progress of a directory deployment (stage: preparing, committing, pushing,
+/// building, packaging, uploading, deploying, done)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PagesDeployProgress {
+    pub stage: String,
+    pub message: String,
+    pub build_status: Option<String>,
+}
+
 /// Pages health check
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PagesHealthCheck {
@@ -121,15 +147,32 @@ impl std::error::Error for PagesError2 {}
 
 pub type PagesResult<T> = Result<T, PagesError2>;
 
-fn get_client() -> PagesResult<(Client, String)> {
-    let token = get_stored_token().map_err(|e| PagesError2(e.to_string()))?;
-    let client = Client::new();
-    Ok((client, token))
+/// Deployment ids are numbers for some deployments and build versions for others
+fn deserialize_deployment_id<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(id) => Ok(id),
+        serde_json::Value::Number(id) => Ok(id.to_string()),
+        other => Err(serde::de::Error::custom(format!("invalid deployment id: {}", other))),
+    }
+}
+
+/// Client and token for a repository, authenticating as its GitHub App
+/// installation if one is configured for it
+async fn get_repo_client(owner: &str, repo: &str) -> PagesResult<(Client, String)> {
+    let token = token_for_repo(owner, repo).await.map_err(|e| PagesError2(e.to_string()))?;
+    Ok((Client::new(), token))
 }
 
 /// Get GitHub Pages information for a repository
 pub async fn get_pages_info(owner: &str, repo: &str) -> PagesResult<PagesInfo> {
-    let (client, token) = get_client()?;
+    find_pages_info(owner, repo)
+        .await?
+        .ok_or_else(|| PagesError2("GitHub Pages not enabled for this repository".to_string()))
+}
+
+/// Get GitHub Pages information for a repository, or `None` if Pages is not enabled
+pub async fn find_pages_info(owner: &str, repo: &str) -> PagesResult<Option<PagesInfo>> {
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!("https://api.github.com/repos/{}/{}/pages", owner, repo);
 
@@ -144,7 +187,7 @@ pub async fn get_pages_info(owner: &str, repo: &str) -> PagesResult<PagesInfo> {
         .map_err(|e| PagesError2(format!("Request failed: {}", e)))?;
 
     if response.status().as_u16() == 404 {
-        return Err(PagesError2("GitHub Pages not enabled for this repository".to_string()));
+        return Ok(None);
     }
 
     if !response.status().is_success() {
@@ -156,6 +199,7 @@ pub async fn get_pages_info(owner: &str, repo: &str) -> PagesResult<PagesInfo> {
     response
         .json()
         .await
+        .map(Some)
         .map_err(|e| PagesError2(format!("Failed to parse response: {}", e)))
 }
 
@@ -166,7 +210,7 @@ pub async fn enable_pages(
     branch: &str,
     path: &str,
 ) -> PagesResult<PagesInfo> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!("https://api.github.com/repos/{}/{}/pages", owner, repo);
 
@@ -210,7 +254,7 @@ pub async fn update_pages(
     source_branch: Option<&str>,
     source_path: Option<&str>,
 ) -> PagesResult<()> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!("https://api.github.com/repos/{}/{}/pages", owner, repo);
 
@@ -258,7 +302,7 @@ pub async fn update_pages(
 
 /// Disable GitHub Pages for a repository
 pub async fn disable_pages(owner: &str, repo: &str) -> PagesResult<()> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!("https://api.github.com/repos/{}/{}/pages", owner, repo);
 
@@ -288,7 +332,7 @@ pub async fn list_pages_builds(
     per_page: Option<u32>,
     page: Option<u32>,
 ) -> PagesResult<Vec<PagesBuild>> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!("https://api.github.com/repos/{}/{}/pages/builds", owner, repo);
 
@@ -325,7 +369,7 @@ pub async fn list_pages_builds(
 
 /// Get the latest GitHub Pages build
 pub async fn get_latest_pages_build(owner: &str, repo: &str) -> PagesResult<PagesBuild> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/pages/builds/latest",
@@ -356,7 +400,7 @@ pub async fn get_latest_pages_build(owner: &str, repo: &str) -> PagesResult<Page
 
 /// Get a specific GitHub Pages build
 pub async fn get_pages_build(owner: &str, repo: &str, build_id: i64) -> PagesResult<PagesBuild> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/pages/builds/{}",
@@ -387,7 +431,7 @@ pub async fn get_pages_build(owner: &str, repo: &str, build_id: i64) -> PagesRes
 
 /// Request a GitHub Pages build
 pub async fn request_pages_build(owner: &str, repo: &str) -> PagesResult<PagesBuild> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!("https://api.github.com/repos/{}/{}/pages/builds", owner, repo);
 
@@ -415,7 +459,7 @@ pub async fn request_pages_build(owner: &str, repo: &str) -> PagesResult<PagesBu
 
 /// Get a DNS health check for GitHub Pages
 pub async fn get_pages_health_check(owner: &str, repo: &str) -> PagesResult<PagesHealthCheck> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/pages/health",
@@ -432,6 +476,11 @@ pub async fn get_pages_health_check(owner: &str, repo: &str) -> PagesResult<Page
         .await
         .map_err(|e| PagesError2(format!("Request failed: {}", e)))?;
 
+    // GitHub returns 202 while the health check is being computed
+    if response.status().as_u16() == 202 {
+        return Err(PagesError2("The health check is being computed. Please try again later.".to_string()));
+    }
+
     if !response.status().is_success() {
         let status = response.status();
         let text = response.text().await.unwrap_or_default();
@@ -452,7 +501,7 @@ pub async fn create_pages_deployment(
     pages_build_version: &str,
     oidc_token: &str,
 ) -> PagesResult<PagesDeployment> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/pages/deployments",
@@ -497,12 +546,15 @@ pub struct DeploymentStatus {
     pub status: Option<String>,
 }
 
+/// Get the status of a Pages deployment
+///
+/// `deployment_id` is the numeric id or the build version of the deployment.
 pub async fn get_deployment_status(
     owner: &str,
     repo: &str,
-    deployment_id: i64,
+    deployment_id: impl std::fmt::Display,
 ) -> PagesResult<DeploymentStatus> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/pages/deployments/{}",
@@ -532,8 +584,8 @@ pub async fn get_deployment_status(
 }
 
 /// Cancel a pending deployment
-pub async fn cancel_deployment(owner: &str, repo: &str, deployment_id: i64) -> PagesResult<()> {
-    let (client, token) = get_client()?;
+pub async fn cancel_deployment(owner: &str, repo: &str, deployment_id: impl std::fmt::Display) -> PagesResult<()> {
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/pages/deployments/{}/cancel",
@@ -558,3 +610,78 @@ pub async fn cancel_deployment(owner: &str, repo: &str, deployment_id: i64) -> P
 
     Ok(())
 }
+
+/// Poll the latest Pages build until the build of `commit` has finished
+///
+/// `on_progress` is called with each poll of that build. Errors while the build
+/// has not been created yet are retried until `timeout` elapses.
+pub async fn wait_for_pages_build(
+    owner: &str,
+    repo: &str,
+    commit: &str,
+    timeout: std::time::Duration,
+    mut on_progress: impl FnMut(&PagesBuild),
+) -> PagesResult<PagesBuild> {
+    let deadline = std::time::Instant::now() + timeout;
+
+    loop {
+        if let Ok(build) = get_latest_pages_build(owner, repo).await {
+            if build.commit == commit {
+                on_progress(&build);
+                match build.status.as_str() {
+                    "built" => return Ok(build),
+                    "errored" => {
+                        let message = build
+                            .error
+                            .as_ref()
+                            .and_then(|e| e.message.clone())
+                            .unwrap_or_else(|| "unknown error".to_string());
+                        return Err(PagesError2(format!("Pages build failed: {}", message)));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if std::time::Instant::now() >= deadline {
+            return Err(PagesError2(format!(
+                "Timed out waiting for the Pages build of {}",
+                &commit[..commit.len().min(7)]
+            )));
+        }
+
+        tokio::time::sleep(BUILD_POLL_INTERVAL).await;
+    }
+}
+
+/// Poll a Pages deployment until it has succeeded or failed
+///
+/// `on_progress` is called with each polled status. Errors while polling are
+/// retried until `timeout` elapses.
+pub async fn wait_for_pages_deployment(
+    owner: &str,
+    repo: &str,
+    deployment_id: &str,
+    timeout: std::time::Duration,
+    mut on_progress: impl FnMut(&str),
+) -> PagesResult<String> {
+    let deadline = std::time::Instant::now() + timeout;
+
+    loop {
+        if let Ok(DeploymentStatus { status: Some(status) }) = get_deployment_status(owner, repo, deployment_id).await {
+            on_progress(&status);
+            if status == "succeed" {
+                return Ok(status);
+            }
+            if FAILED_DEPLOYMENT_STATUSES.contains(&status.as_str()) {
+                return Err(PagesError2(format!("Pages deployment failed: {}", status)));
+            }
+        }
+
+        if std::time::Instant::now() >= deadline {
+            return Err(PagesError2(format!("Timed out waiting for the Pages deployment {}", deployment_id)));
+        }
+
+        tokio::time::sleep(BUILD_POLL_INTERVAL).await;
+    }
+}
@@ -0,0 +1,407 @@
+//! Pages artifacts for workflow-built sites
+//!
+//! Sites whose Pages source is a GitHub Actions workflow are deployed from an
+//! artifact named `github-pages`: a zip holding `artifact.tar`, a tarball of
+//! the site. Artifacts can only be uploaded with the credentials of a running
+//! workflow job, which the runner passes in `ACTIONS_*` environment variables,
+//! so these deployments work when the app runs inside a job (such as on a
+//! self-hosted runner) and fail with an explanation elsewhere.
+
+use std::io::Write;
+use std::path::Path;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use flate2::write::DeflateEncoder;
+use flate2::{Compression, Crc};
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use walkdir::WalkDir;
+
+use super::pages::{PagesError2, PagesResult};
+
+/// Name of the artifact Pages deployments are made from
+pub const PAGES_ARTIFACT_NAME: &str = "github-pages";
+
+/// Name of the tarball inside the artifact
+const PAGES_ARTIFACT_FILE: &str = "artifact.tar";
+
+const TAR_BLOCK: usize = 512;
+
+fn tar_field(header: &mut [u8], offset: usize, len: usize, value: &[u8]) {
+    let len = value.len().min(len);
+    header[offset..offset + len].copy_from_slice(&value[..len]);
+}
+
+fn tar_octal(header: &mut [u8], offset: usize, len: usize, value: u64) {
+    let value = format!("{:0width$o}\0", value, width = len - 1);
+    tar_field(header, offset, len, value.as_bytes());
+}
+
+/// Split a path into the ustar prefix and name fields
+fn tar_name(path: &str) -> Option<(&str, &str)> {
+    if path.len() <= 100 {
+        return Some(("", path));
+    }
+    path.match_indices('/')
+        .map(|(i, _)| i)
+        .find(|&i| i <= 155 && path.len() - i - 1 <= 100)
+        .map(|i| (&path[..i], &path[i + 1..]))
+}
+
+fn tar_header(path: &str, size: u64) -> PagesResult<[u8; TAR_BLOCK]> {
+    let (prefix, name) = tar_name(path)
+        .ok_or_else(|| PagesError2(format!("Path is too long for a Pages artifact: {}", path)))?;
+
+    let mut header = [0u8; TAR_BLOCK];
+    tar_field(&mut header, 0, 100, name.as_bytes());
+    tar_octal(&mut header, 100, 8, 0o644);
+    tar_octal(&mut header, 108, 8, 0);
+    tar_octal(&mut header, 116, 8, 0);
+    tar_octal(&mut header, 124, 12, size);
+    tar_octal(&mut header, 136, 12, 0);
+    header[156] = b'0';
+    tar_field(&mut header, 257, 8, b"ustar\x0000");
+    tar_field(&mut header, 345, 155, prefix.as_bytes());
+
+    // The checksum is computed with its own field filled with spaces
+    header[148..156].fill(b' ');
+    let checksum: u32 = header.iter().map(|&b| b as u32).sum();
+    tar_field(&mut header, 148, 8, format!("{:06o}\0 ", checksum).as_bytes());
+    Ok(header)
+}
+
+/// Tarball of the files under `dir` as `./path` entries, following symlinks
+/// and skipping `.git` and `.github` like `actions/upload-pages-artifact`
+pub fn tar_directory(dir: &Path) -> PagesResult<Vec<u8>> {
+    if !dir.is_dir() {
+        return Err(PagesError2(format!("{} is not a directory", dir.display())));
+    }
+
+    let mut tar = Vec::new();
+    let walker = WalkDir::new(dir)
+        .follow_links(true)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !matches!(e.file_name().to_str(), Some(".git" | ".github")));
+    for entry in walker {
+        let entry = entry.map_err(|e| PagesError2(format!("Failed to read {}: {}", dir.display(), e)))?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let relative = entry.path().strip_prefix(dir).unwrap_or(entry.path());
+        let name = relative
+            .to_str()
+            .ok_or_else(|| PagesError2(format!("Invalid file name: {}", relative.display())))?
+            .replace('\\', "/");
+        let content = std::fs::read(entry.path())
+            .map_err(|e| PagesError2(format!("Failed to read {}: {}", entry.path().display(), e)))?;
+
+        tar.extend_from_slice(&tar_header(&format!("./{}", name), content.len() as u64)?);
+        tar.extend_from_slice(&content);
+        tar.resize(tar.len() + (TAR_BLOCK - content.len() % TAR_BLOCK) % TAR_BLOCK, 0);
+    }
+    tar.extend_from_slice(&[0u8; TAR_BLOCK * 2]);
+    Ok(tar)
+}
+
+/// Zip archive holding one deflated file, the layout of uploaded artifacts
+pub fn zip_file(name: &str, content: &[u8]) -> PagesResult<Vec<u8>> {
+    let mut crc = Crc::new();
+    crc.update(content);
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(content)
+        .map_err(|e| PagesError2(format!("Failed to compress the artifact: {}", e)))?;
+    let compressed = encoder
+        .finish()
+        .map_err(|e| PagesError2(format!("Failed to compress the artifact: {}", e)))?;
+    let (Ok(size), Ok(compressed_size)) = (u32::try_from(content.len()), u32::try_from(compressed.len())) else {
+        return Err(PagesError2("The site is too large for a Pages artifact".to_string()));
+    };
+
+    // Version 2.0, no flags, deflate, 1980-01-01 00:00
+    let entry_fields = |zip: &mut Vec<u8>| {
+        for value in [20u16, 0, 8, 0, 0x21] {
+            zip.extend_from_slice(&value.to_le_bytes());
+        }
+        for value in [crc.sum(), compressed_size, size] {
+            zip.extend_from_slice(&value.to_le_bytes());
+        }
+        zip.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        zip.extend_from_slice(&0u16.to_le_bytes());
+    };
+
+    let mut zip = Vec::with_capacity(compressed.len() + 2 * name.len() + 100);
+    zip.extend_from_slice(&0x04034b50u32.to_le_bytes());
+    entry_fields(&mut zip);
+    zip.extend_from_slice(name.as_bytes());
+    zip.extend_from_slice(&compressed);
+
+    let directory_offset = zip.len() as u32;
+    zip.extend_from_slice(&0x02014b50u32.to_le_bytes());
+    zip.extend_from_slice(&20u16.to_le_bytes());
+    entry_fields(&mut zip);
+    // No comment, disk 0, no attributes, local header at offset 0
+    zip.extend_from_slice(&[0u8; 14]);
+    zip.extend_from_slice(name.as_bytes());
+    let directory_size = zip.len() as u32 - directory_offset;
+
+    zip.extend_from_slice(&0x06054b50u32.to_le_bytes());
+    for value in [0u16, 0, 1, 1] {
+        zip.extend_from_slice(&value.to_le_bytes());
+    }
+    zip.extend_from_slice(&directory_size.to_le_bytes());
+    zip.extend_from_slice(&directory_offset.to_le_bytes());
+    zip.extend_from_slice(&0u16.to_le_bytes());
+    Ok(zip)
+}
+
+/// The `github-pages` artifact of a site directory
+pub fn pages_artifact(dir: &Path) -> PagesResult<Vec<u8>> {
+    zip_file(PAGES_ARTIFACT_FILE, &tar_directory(dir)?)
+}
+
+/// Credentials of the running workflow job, from the runner's environment
+pub struct ActionsRuntime {
+    runtime_token: String,
+    results_url: String,
+    id_token_url: String,
+    id_token_request_token: String,
+}
+
+impl ActionsRuntime {
+    pub fn from_env() -> PagesResult<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        match (
+            var("ACTIONS_RUNTIME_TOKEN"),
+            var("ACTIONS_RESULTS_URL"),
+            var("ACTIONS_ID_TOKEN_REQUEST_URL"),
+            var("ACTIONS_ID_TOKEN_REQUEST_TOKEN"),
+        ) {
+            (Some(runtime_token), Some(results_url), Some(id_token_url), Some(id_token_request_token)) => Ok(Self {
+                runtime_token,
+                results_url,
+                id_token_url,
+                id_token_request_token,
+            }),
+            _ => Err(PagesError2(
+                "Pages is deployed by a GitHub Actions workflow; its artifacts can only be uploaded from a workflow job with the `id-token: write` permission"
+                    .to_string(),
+            )),
+        }
+    }
+
+    /// Workflow run and job ids of the artifact service, from the scopes of the runtime token
+    fn backend_ids(&self) -> PagesResult<(String, String)> {
+        let invalid = || PagesError2("Invalid Actions runtime token".to_string());
+        let payload = self.runtime_token.split('.').nth(1).ok_or_else(invalid)?;
+        let payload = URL_SAFE_NO_PAD
+            .decode(payload.trim_end_matches('='))
+            .map_err(|_| invalid())?;
+        let claims: serde_json::Value = serde_json::from_slice(&payload).map_err(|_| invalid())?;
+
+        claims["scp"]
+            .as_str()
+            .unwrap_or("")
+            .split(' ')
+            .find_map(|scope| {
+                let mut parts = scope.split(':');
+                if parts.next() != Some("Actions.Results") {
+                    return None;
+                }
+                Some((parts.next()?.to_string(), parts.next()?.to_string()))
+            })
+            .ok_or_else(invalid)
+    }
+
+    async fn artifact_service<T: DeserializeOwned>(
+        &self,
+        client: &Client,
+        method: &str,
+        body: serde_json::Value,
+    ) -> PagesResult<T> {
+        let url = format!(
+            "{}/twirp/github.actions.results.api.v1.ArtifactService/{}",
+            self.results_url.trim_end_matches('/'),
+            method
+        );
+
+        let response = client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.runtime_token))
+            .header("User-Agent", "LinuxGit")
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| PagesError2(format!("Request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(PagesError2(format!("Artifact service error ({}): {}", status, text)));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| PagesError2(format!("Failed to parse response: {}", e)))
+    }
+
+    /// Upload `artifact` as the `github-pages` artifact of the running job,
+    /// returning its id
+    pub async fn upload_pages_artifact(&self, artifact: Vec<u8>) -> PagesResult<String> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct CreateArtifactResponse {
+            ok: bool,
+            #[serde(default)]
+            signed_upload_url: String,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct FinalizeArtifactResponse {
+            ok: bool,
+            #[serde(default)]
+            artifact_id: serde_json::Value,
+        }
+
+        let (run_id, job_id) = self.backend_ids()?;
+        let client = Client::new();
+
+        let created: CreateArtifactResponse = self
+            .artifact_service(
+                &client,
+                "CreateArtifact",
+                serde_json::json!({
+                    "workflowRunBackendId": run_id,
+                    "workflowJobRunBackendId": job_id,
+                    "name": PAGES_ARTIFACT_NAME,
+                    "version": 4
+                }),
+            )
+            .await?;
+        if !created.ok || created.signed_upload_url.is_empty() {
+            return Err(PagesError2("The artifact service refused to create the Pages artifact".to_string()));
+        }
+
+        let size = artifact.len();
+        let hash = format!("sha256:{:x}", Sha256::digest(&artifact));
+        let response = client
+            .put(&created.signed_upload_url)
+            .header("x-ms-blob-type", "BlockBlob")
+            .body(artifact)
+            .send()
+            .await
+            .map_err(|e| PagesError2(format!("Upload failed: {}", e)))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(PagesError2(format!("Artifact upload failed ({}): {}", status, text)));
+        }
+
+        let finalized: FinalizeArtifactResponse = self
+            .artifact_service(
+                &client,
+                "FinalizeArtifact",
+                serde_json::json!({
+                    "workflowRunBackendId": run_id,
+                    "workflowJobRunBackendId": job_id,
+                    "name": PAGES_ARTIFACT_NAME,
+                    "size": size.to_string(),
+                    "hash": hash
+                }),
+            )
+            .await?;
+        match finalized.artifact_id {
+            serde_json::Value::String(id) if finalized.ok => Ok(id),
+            serde_json::Value::Number(id) if finalized.ok => Ok(id.to_string()),
+            _ => Err(PagesError2("The artifact service did not finalize the Pages artifact".to_string())),
+        }
+    }
+
+    /// OIDC token of the running job, which authorizes the Pages deployment
+    pub async fn oidc_token(&self) -> PagesResult<String> {
+        #[derive(Deserialize)]
+        struct IdTokenResponse {
+            value: String,
+        }
+
+        let response = Client::new()
+            .get(&self.id_token_url)
+            .header("Authorization", format!("Bearer {}", self.id_token_request_token))
+            .header("User-Agent", "LinuxGit")
+            .send()
+            .await
+            .map_err(|e| PagesError2(format!("Request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(PagesError2(format!("Failed to get an OIDC token ({}): {}", status, text)));
+        }
+
+        response
+            .json::<IdTokenResponse>()
+            .await
+            .map(|token| token.value)
+            .map_err(|e| PagesError2(format!("Failed to parse response: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_tar_directory() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("index.html"), "<h1>Hi</h1>").unwrap();
+        std::fs::create_dir_all(dir.path().join(".git")).unwrap();
+        std::fs::write(dir.path().join(".git/HEAD"), "ref").unwrap();
+        let long_dir = "d".repeat(120);
+        std::fs::create_dir_all(dir.path().join(&long_dir)).unwrap();
+        std::fs::write(dir.path().join(&long_dir).join("page.html"), "x").unwrap();
+
+        let tar = tar_directory(dir.path()).unwrap();
+        assert_eq!(tar.len() % TAR_BLOCK, 0);
+
+        let header = &tar[..TAR_BLOCK];
+        let name_end = header.iter().position(|&b| b == 0).unwrap();
+        // Paths over 100 bytes are split into the prefix and name fields
+        assert_eq!(&header[..name_end], b"page.html");
+        let checksum: u32 = header[..148].iter().chain([b' '; 8].iter()).chain(&header[156..]).map(|&b| b as u32).sum();
+        assert_eq!(std::str::from_utf8(&header[148..154]).unwrap(), format!("{:06o}", checksum));
+        assert_eq!(&header[345..345 + long_dir.len() + 2], format!("./{}", long_dir).as_bytes());
+
+        let second = &tar[TAR_BLOCK * 2..TAR_BLOCK * 3];
+        assert!(second.starts_with(b"./index.html\0"));
+        assert_eq!(&tar[TAR_BLOCK * 3..TAR_BLOCK * 3 + 11], b"<h1>Hi</h1>");
+        assert!(!tar.windows(4).any(|w| w == b"HEAD"));
+    }
+
+    #[test]
+    fn test_zip_file() {
+        let content = b"hello pages ".repeat(100);
+        let zip = zip_file("artifact.tar", &content).unwrap();
+        assert!(zip.starts_with(&0x04034b50u32.to_le_bytes()));
+        assert_eq!(&zip[30..42], b"artifact.tar");
+
+        let compressed_size = u32::from_le_bytes(zip[18..22].try_into().unwrap()) as usize;
+        let mut decoder = flate2::read::DeflateDecoder::new(&zip[42..42 + compressed_size]);
+        let mut inflated = Vec::new();
+        decoder.read_to_end(&mut inflated).unwrap();
+        assert_eq!(inflated, content);
+
+        let end = &zip[zip.len() - 22..];
+        assert!(end.starts_with(&0x06054b50u32.to_le_bytes()));
+        let directory_offset = u32::from_le_bytes(end[16..20].try_into().unwrap()) as usize;
+        assert!(zip[directory_offset..].starts_with(&0x02014b50u32.to_le_bytes()));
+    }
+}
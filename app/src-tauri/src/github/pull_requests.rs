@@ -5,7 +5,7 @@
 use serde::{Deserialize, Serialize};
 use reqwest::Client;
 
-use super::get_stored_token;
+use super::app_auth::token_for_repo;
 
 /// GitHub Label
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +23,14 @@ pub struct PullRequestUser {
     pub avatar_url: String,
 }
 
+/// GitHub Team (simplified)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullRequestTeam {
+    pub id: i64,
+    pub name: String,
+    pub slug: String,
+}
+
 /// GitHub Pull Request Branch
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PullRequestBranch {
@@ -36,6 +44,8 @@ pub struct PullRequestBranch {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PullRequest {
     pub id: i64,
+    #[serde(default)]
+    pub node_id: String,
     pub number: i32,
     pub title: String,
     pub body: Option<String>,
@@ -59,6 +69,8 @@ pub struct PullRequest {
     #[serde(default)]
     pub requested_reviewers: Vec<PullRequestUser>,
     #[serde(default)]
+    pub requested_teams: Vec<PullRequestTeam>,
+    #[serde(default)]
     pub labels: Vec<Label>,
     #[serde(default)]
     pub comments: i32,
@@ -82,7 +94,7 @@ pub struct PullRequestReview {
     pub body: Option<String>,
     pub state: String,
     pub html_url: String,
-    pub submitted_at: String,
+    pub submitted_at: Option<String>,
 }
 
 /// GitHub Pull Request Comment
@@ -96,6 +108,99 @@ pub struct PullRequestComment {
     pub html_url: String,
     pub path: Option<String>,
     pub line: Option<i32>,
+    #[serde(default)]
+    pub start_line: Option<i32>,
+    #[serde(default)]
+    pub side: Option<String>,
+    #[serde(default)]
+    pub in_reply_to_id: Option<i64>,
+}
+
+/// Line-level comment attached to a review (LEFT = base side, RIGHT = head side)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewCommentInput {
+    pub path: String,
+    pub body: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub side: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_line: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_side: Option<String>,
+}
+
+/// File changed in a pull request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullRequestFile {
+    pub sha: Option<String>,
+    pub filename: String,
+    pub status: String,
+    pub additions: i32,
+    pub deletions: i32,
+    pub changes: i32,
+    pub patch: Option<String>,
+    pub previous_filename: Option<String>,
+    pub blob_url: Option<String>,
+    pub raw_url: Option<String>,
+}
+
+/// Git author/committer identity of a commit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitIdentity {
+    pub name: String,
+    pub email: String,
+    pub date: String,
+}
+
+/// Git data of a pull request commit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullRequestCommitData {
+    pub message: String,
+    pub author: Option<CommitIdentity>,
+    pub committer: Option<CommitIdentity>,
+}
+
+/// Parent reference of a pull request commit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitParent {
+    pub sha: String,
+}
+
+/// Commit belonging to a pull request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullRequestCommit {
+    pub sha: String,
+    pub html_url: String,
+    pub commit: PullRequestCommitData,
+    pub author: Option<PullRequestUser>,
+    #[serde(default)]
+    pub parents: Vec<CommitParent>,
+}
+
+/// Response of the update-branch endpoint (the update runs asynchronously)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateBranchResult {
+    pub message: String,
+    pub url: String,
+}
+
+/// Mergeability of a pull request as computed by GitHub
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullRequestMergeState {
+    pub mergeable: Option<bool>,
+    /// clean, behind, blocked, dirty, draft, has_hooks, unstable, or unknown
+    pub mergeable_state: String,
+    /// True when the head branch is behind its base and can be updated
+    pub can_update_branch: bool,
+}
+
+/// Local branch with the open pull request whose head matches it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchPullRequest {
+    pub branch: String,
+    pub pull_request: Option<PullRequest>,
 }
 
 /// Error type for Pull Requests API
@@ -112,10 +217,11 @@ impl std::error::Error for PullRequestsError {}
 
 pub type PullRequestsResult<T> = Result<T, PullRequestsError>;
 
-fn get_client() -> PullRequestsResult<(Client, String)> {
-    let token = get_stored_token().map_err(|e| PullRequestsError(e.to_string()))?;
-    let client = Client::new();
-    Ok((client, token))
+/// Client and token for a repository, authenticating as its GitHub App
+/// installation if one is configured for it
+async fn get_repo_client(owner: &str, repo: &str) -> PullRequestsResult<(Client, String)> {
+    let token = token_for_repo(owner, repo).await.map_err(|e| PullRequestsError(e.to_string()))?;
+    Ok((Client::new(), token))
 }
 
 /// List pull requests for a repository
@@ -127,7 +233,7 @@ pub async fn list_pull_requests(
     direction: Option<&str>,
     per_page: Option<u32>,
 ) -> PullRequestsResult<Vec<PullRequest>> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!("https://api.github.com/repos/{}/{}/pulls", owner, repo);
 
@@ -172,7 +278,7 @@ pub async fn get_pull_request(
     repo: &str,
     pull_number: i32,
 ) -> PullRequestsResult<PullRequest> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/pulls/{}",
@@ -211,7 +317,7 @@ pub async fn create_pull_request(
     base: &str,
     draft: bool,
 ) -> PullRequestsResult<PullRequest> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!("https://api.github.com/repos/{}/{}/pulls", owner, repo);
 
@@ -259,7 +365,7 @@ pub async fn update_pull_request(
     state: Option<&str>,
     base: Option<&str>,
 ) -> PullRequestsResult<PullRequest> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/pulls/{}",
@@ -312,7 +418,7 @@ pub async fn merge_pull_request(
     commit_title: Option<&str>,
     commit_message: Option<&str>,
 ) -> PullRequestsResult<()> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/pulls/{}/merge",
@@ -356,7 +462,7 @@ pub async fn list_pr_reviews(
     repo: &str,
     pull_number: i32,
 ) -> PullRequestsResult<Vec<PullRequestReview>> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/pulls/{}/reviews",
@@ -391,7 +497,7 @@ pub async fn list_pr_comments(
     repo: &str,
     pull_number: i32,
 ) -> PullRequestsResult<Vec<PullRequestComment>> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/pulls/{}/comments",
@@ -420,14 +526,15 @@ pub async fn list_pr_comments(
         .map_err(|e| PullRequestsError(format!("Failed to parse response: {}", e)))
 }
 
-/// Request reviewers for a pull request
+/// Request reviewers (user logins and/or team slugs) for a pull request
 pub async fn request_reviewers(
     owner: &str,
     repo: &str,
     pull_number: i32,
     reviewers: Vec<String>,
+    team_reviewers: Vec<String>,
 ) -> PullRequestsResult<()> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/pulls/{}/requested_reviewers",
@@ -435,7 +542,8 @@ pub async fn request_reviewers(
     );
 
     let payload = serde_json::json!({
-        "reviewers": reviewers
+        "reviewers": reviewers,
+        "team_reviewers": team_reviewers
     });
 
     let response = client
@@ -458,21 +566,121 @@ pub async fn request_reviewers(
     Ok(())
 }
 
+/// Remove requested reviewers (user logins and/or team slugs) from a pull request
+pub async fn remove_requested_reviewers(
+    owner: &str,
+    repo: &str,
+    pull_number: i32,
+    reviewers: Vec<String>,
+    team_reviewers: Vec<String>,
+) -> PullRequestsResult<()> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/pulls/{}/requested_reviewers",
+        owner, repo, pull_number
+    );
+
+    let payload = serde_json::json!({
+        "reviewers": reviewers,
+        "team_reviewers": team_reviewers
+    });
+
+    let response = client
+        .delete(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| PullRequestsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(PullRequestsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    Ok(())
+}
+
 /// Create a review for a pull request
+///
+/// When `event` is `None` the review is left PENDING so further comments can be
+/// collected before it is submitted with [`submit_review`].
 pub async fn create_review(
     owner: &str,
     repo: &str,
     pull_number: i32,
     body: Option<&str>,
-    event: &str,  // APPROVE, REQUEST_CHANGES, COMMENT
+    event: Option<&str>,  // APPROVE, REQUEST_CHANGES, COMMENT
+    comments: Vec<ReviewCommentInput>,
+    commit_id: Option<&str>,
 ) -> PullRequestsResult<PullRequestReview> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/pulls/{}/reviews",
         owner, repo, pull_number
     );
 
+    let mut payload = serde_json::json!({});
+
+    if let Some(e) = event {
+        payload["event"] = serde_json::Value::String(e.to_string());
+    }
+    if let Some(b) = body {
+        payload["body"] = serde_json::Value::String(b.to_string());
+    }
+    if let Some(c) = commit_id {
+        payload["commit_id"] = serde_json::Value::String(c.to_string());
+    }
+    if !comments.is_empty() {
+        payload["comments"] = serde_json::to_value(&comments)
+            .map_err(|e| PullRequestsError(format!("Invalid review comments: {}", e)))?;
+    }
+
+    let response = client
+        .post(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| PullRequestsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(PullRequestsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| PullRequestsError(format!("Failed to parse response: {}", e)))
+}
+
+/// Submit a pending review
+pub async fn submit_review(
+    owner: &str,
+    repo: &str,
+    pull_number: i32,
+    review_id: i64,
+    body: Option<&str>,
+    event: &str,  // APPROVE, REQUEST_CHANGES, COMMENT
+) -> PullRequestsResult<PullRequestReview> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/pulls/{}/reviews/{}/events",
+        owner, repo, pull_number, review_id
+    );
+
     let mut payload = serde_json::json!({
         "event": event
     });
@@ -503,3 +711,473 @@ pub async fn create_review(
         .await
         .map_err(|e| PullRequestsError(format!("Failed to parse response: {}", e)))
 }
+
+/// Delete a pending (not yet submitted) review
+pub async fn delete_pending_review(
+    owner: &str,
+    repo: &str,
+    pull_number: i32,
+    review_id: i64,
+) -> PullRequestsResult<()> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/pulls/{}/reviews/{}",
+        owner, repo, pull_number, review_id
+    );
+
+    let response = client
+        .delete(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| PullRequestsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(PullRequestsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    Ok(())
+}
+
+/// Create a single line-level review comment on a pull request
+pub async fn create_review_comment(
+    owner: &str,
+    repo: &str,
+    pull_number: i32,
+    commit_id: &str,
+    comment: &ReviewCommentInput,
+) -> PullRequestsResult<PullRequestComment> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/pulls/{}/comments",
+        owner, repo, pull_number
+    );
+
+    let mut payload = serde_json::to_value(comment)
+        .map_err(|e| PullRequestsError(format!("Invalid review comment: {}", e)))?;
+    payload["commit_id"] = serde_json::Value::String(commit_id.to_string());
+
+    let response = client
+        .post(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| PullRequestsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(PullRequestsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| PullRequestsError(format!("Failed to parse response: {}", e)))
+}
+
+/// Get a single pull request review comment
+pub async fn get_review_comment(
+    owner: &str,
+    repo: &str,
+    comment_id: i64,
+) -> PullRequestsResult<PullRequestComment> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/pulls/comments/{}",
+        owner, repo, comment_id
+    );
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| PullRequestsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(PullRequestsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| PullRequestsError(format!("Failed to parse response: {}", e)))
+}
+
+/// Update the body of a pull request review comment
+pub async fn update_review_comment(
+    owner: &str,
+    repo: &str,
+    comment_id: i64,
+    body: &str,
+) -> PullRequestsResult<PullRequestComment> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/pulls/comments/{}",
+        owner, repo, comment_id
+    );
+
+    let payload = serde_json::json!({
+        "body": body
+    });
+
+    let response = client
+        .patch(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| PullRequestsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(PullRequestsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| PullRequestsError(format!("Failed to parse response: {}", e)))
+}
+
+/// Delete a pull request review comment
+pub async fn delete_review_comment(
+    owner: &str,
+    repo: &str,
+    comment_id: i64,
+) -> PullRequestsResult<()> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/pulls/comments/{}",
+        owner, repo, comment_id
+    );
+
+    let response = client
+        .delete(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| PullRequestsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(PullRequestsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    Ok(())
+}
+
+/// List the files changed in a pull request (all pages, up to GitHub's 3000 file limit)
+pub async fn list_pr_files(
+    owner: &str,
+    repo: &str,
+    pull_number: i32,
+) -> PullRequestsResult<Vec<PullRequestFile>> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/pulls/{}/files",
+        owner, repo, pull_number
+    );
+
+    let mut files = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let response = client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "LinuxGit")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .query(&[("per_page", "100".to_string()), ("page", page.to_string())])
+            .send()
+            .await
+            .map_err(|e| PullRequestsError(format!("Request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(PullRequestsError(format!("GitHub API error ({}): {}", status, text)));
+        }
+
+        let batch: Vec<PullRequestFile> = response
+            .json()
+            .await
+            .map_err(|e| PullRequestsError(format!("Failed to parse response: {}", e)))?;
+
+        let done = batch.len() < 100;
+        files.extend(batch);
+
+        if done || page >= 30 {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(files)
+}
+
+/// Get the raw unified diff of a pull request
+pub async fn get_pr_diff(
+    owner: &str,
+    repo: &str,
+    pull_number: i32,
+) -> PullRequestsResult<String> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/pulls/{}",
+        owner, repo, pull_number
+    );
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github.diff")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| PullRequestsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(PullRequestsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .text()
+        .await
+        .map_err(|e| PullRequestsError(format!("Failed to read response: {}", e)))
+}
+
+/// List the commits of a pull request (GitHub returns at most 250)
+pub async fn list_pr_commits(
+    owner: &str,
+    repo: &str,
+    pull_number: i32,
+) -> PullRequestsResult<Vec<PullRequestCommit>> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/pulls/{}/commits",
+        owner, repo, pull_number
+    );
+
+    let mut commits = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let response = client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "LinuxGit")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .query(&[("per_page", "100".to_string()), ("page", page.to_string())])
+            .send()
+            .await
+            .map_err(|e| PullRequestsError(format!("Request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(PullRequestsError(format!("GitHub API error ({}): {}", status, text)));
+        }
+
+        let batch: Vec<PullRequestCommit> = response
+            .json()
+            .await
+            .map_err(|e| PullRequestsError(format!("Failed to parse response: {}", e)))?;
+
+        let done = batch.len() < 100;
+        commits.extend(batch);
+
+        if done || page >= 3 {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(commits)
+}
+
+/// Get the mergeability of a pull request
+///
+/// GitHub computes `mergeable_state` lazily; while it is being computed the
+/// state is reported as `unknown`.
+pub async fn get_merge_state(
+    owner: &str,
+    repo: &str,
+    pull_number: i32,
+) -> PullRequestsResult<PullRequestMergeState> {
+    let pull_request = get_pull_request(owner, repo, pull_number).await?;
+    let mergeable_state = pull_request
+        .mergeable_state
+        .unwrap_or_else(|| "unknown".to_string());
+
+    Ok(PullRequestMergeState {
+        mergeable: pull_request.mergeable,
+        can_update_branch: pull_request.state == "open" && mergeable_state == "behind",
+        mergeable_state,
+    })
+}
+
+/// Update a pull request branch with the latest changes from its base branch
+pub async fn update_pr_branch(
+    owner: &str,
+    repo: &str,
+    pull_number: i32,
+    expected_head_sha: Option<&str>,
+) -> PullRequestsResult<UpdateBranchResult> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/pulls/{}/update-branch",
+        owner, repo, pull_number
+    );
+
+    let mut payload = serde_json::json!({});
+    if let Some(sha) = expected_head_sha {
+        payload["expected_head_sha"] = serde_json::Value::String(sha.to_string());
+    }
+
+    let response = client
+        .put(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| PullRequestsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(PullRequestsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| PullRequestsError(format!("Failed to parse response: {}", e)))
+}
+
+/// List all pull requests in a given state (every page)
+pub async fn list_all_pull_requests(
+    owner: &str,
+    repo: &str,
+    state: &str,
+) -> PullRequestsResult<Vec<PullRequest>> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!("https://api.github.com/repos/{}/{}/pulls", owner, repo);
+
+    let mut pull_requests = Vec::new();
+    let mut page = 1;
+
+    loop {
+        let response = client
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "LinuxGit")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .query(&[
+                ("state", state.to_string()),
+                ("per_page", "100".to_string()),
+                ("page", page.to_string()),
+            ])
+            .send()
+            .await
+            .map_err(|e| PullRequestsError(format!("Request failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(PullRequestsError(format!("GitHub API error ({}): {}", status, text)));
+        }
+
+        let batch: Vec<PullRequest> = response
+            .json()
+            .await
+            .map_err(|e| PullRequestsError(format!("Failed to parse response: {}", e)))?;
+
+        let done = batch.len() < 100;
+        pull_requests.extend(batch);
+
+        if done || page >= 10 {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(pull_requests)
+}
+
+/// Match local branches against pull requests whose head is `owner:branch`.
+/// `branches` holds `(local name, name on the remote)` pairs, the remote name
+/// being the branch's upstream or, without one, its local name
+pub fn map_branches_to_pull_requests(
+    owner: &str,
+    branches: &[(String, String)],
+    pull_requests: &[PullRequest],
+) -> Vec<BranchPullRequest> {
+    branches
+        .iter()
+        .map(|(branch, head)| {
+            let label = format!("{}:{}", owner, head);
+            let pull_request = pull_requests
+                .iter()
+                .find(|pr| pr.head.label == label)
+                .cloned();
+
+            BranchPullRequest {
+                branch: branch.clone(),
+                pull_request,
+            }
+        })
+        .collect()
+}
+
+/// Find the open pull request (if any) for each of the given branches
+pub async fn get_branch_pull_requests(
+    owner: &str,
+    repo: &str,
+    branches: &[(String, String)],
+) -> PullRequestsResult<Vec<BranchPullRequest>> {
+    let pull_requests = list_all_pull_requests(owner, repo, "open").await?;
+    Ok(map_branches_to_pull_requests(owner, branches, &pull_requests))
+}
@@ -0,0 +1,190 @@
+//! GitHub Reactions API module
+//!
+//! Provides access to reactions on issues, issue comments, and pull request review comments.
+
+use serde::{Deserialize, Serialize};
+use reqwest::Client;
+
+use super::app_auth::token_for_repo;
+
+/// Reaction contents accepted by GitHub
+pub const REACTION_CONTENTS: [&str; 8] = [
+    "+1", "-1", "laugh", "confused", "heart", "hooray", "rocket", "eyes",
+];
+
+/// Kind of item a reaction is attached to
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReactionSubject {
+    Issue,
+    IssueComment,
+    PullRequestComment,
+}
+
+impl ReactionSubject {
+    fn path(&self, subject_id: i64) -> String {
+        match self {
+            ReactionSubject::Issue => format!("issues/{}/reactions", subject_id),
+            ReactionSubject::IssueComment => format!("issues/comments/{}/reactions", subject_id),
+            ReactionSubject::PullRequestComment => format!("pulls/comments/{}/reactions", subject_id),
+        }
+    }
+}
+
+/// GitHub User (simplified)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReactionUser {
+    pub login: String,
+    pub avatar_url: String,
+}
+
+/// GitHub Reaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reaction {
+    pub id: i64,
+    pub content: String,
+    pub user: Option<ReactionUser>,
+    pub created_at: String,
+}
+
+/// Error type for Reactions API
+#[derive(Debug)]
+pub struct ReactionsError(pub String);
+
+impl std::fmt::Display for ReactionsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ReactionsError {}
+
+pub type ReactionsResult<T> = Result<T, ReactionsError>;
+
+/// Client and token for a repository, authenticating as its GitHub App
+/// installation if one is configured for it
+async fn get_repo_client(owner: &str, repo: &str) -> ReactionsResult<(Client, String)> {
+    let token = token_for_repo(owner, repo).await.map_err(|e| ReactionsError(e.to_string()))?;
+    Ok((Client::new(), token))
+}
+
+/// List reactions on an issue, issue comment, or review comment
+pub async fn list_reactions(
+    owner: &str,
+    repo: &str,
+    subject: ReactionSubject,
+    subject_id: i64,
+) -> ReactionsResult<Vec<Reaction>> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/{}",
+        owner, repo, subject.path(subject_id)
+    );
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .query(&[("per_page", "100")])
+        .send()
+        .await
+        .map_err(|e| ReactionsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(ReactionsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| ReactionsError(format!("Failed to parse response: {}", e)))
+}
+
+/// Add a reaction (returns the existing reaction if the user already reacted with it)
+pub async fn add_reaction(
+    owner: &str,
+    repo: &str,
+    subject: ReactionSubject,
+    subject_id: i64,
+    content: &str,
+) -> ReactionsResult<Reaction> {
+    if !REACTION_CONTENTS.contains(&content) {
+        return Err(ReactionsError(format!(
+            "Invalid reaction '{}'. Expected one of: {}",
+            content,
+            REACTION_CONTENTS.join(", ")
+        )));
+    }
+
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/{}",
+        owner, repo, subject.path(subject_id)
+    );
+
+    let payload = serde_json::json!({
+        "content": content
+    });
+
+    let response = client
+        .post(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| ReactionsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(ReactionsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| ReactionsError(format!("Failed to parse response: {}", e)))
+}
+
+/// Remove a reaction by its id
+pub async fn remove_reaction(
+    owner: &str,
+    repo: &str,
+    subject: ReactionSubject,
+    subject_id: i64,
+    reaction_id: i64,
+) -> ReactionsResult<()> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/{}/{}",
+        owner, repo, subject.path(subject_id), reaction_id
+    );
+
+    let response = client
+        .delete(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| ReactionsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(ReactionsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    Ok(())
+}
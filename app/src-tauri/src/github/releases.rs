@@ -2,10 +2,14 @@
 //!
 //! Provides access to GitHub releases and release assets.
 
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use serde::{Deserialize, Serialize};
 use reqwest::Client;
+use tokio::io::AsyncWriteExt;
 
-use super::get_stored_token;
+use super::app_auth::token_for_repo;
 
 /// GitHub Release
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +25,8 @@ pub struct Release {
     pub html_url: String,
     pub tarball_url: Option<String>,
     pub zipball_url: Option<String>,
+    #[serde(default)]
+    pub upload_url: String,
     pub author: Option<ReleaseAuthor>,
     pub assets: Vec<ReleaseAsset>,
 }
@@ -80,6 +86,42 @@ pub struct UpdateReleaseRequest {
     pub prerelease: Option<bool>,
 }
 
+/// Event emitted as a release asset download progresses
+pub const ASSET_DOWNLOAD_PROGRESS_EVENT: &str = "release-asset-download-progress";
+
+/// Progress of a release asset download
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetDownloadProgress {
+    pub asset_id: i64,
+    pub downloaded: u64,
+    pub total: Option<u64>,
+    pub done: bool,
+}
+
+/// Event emitted as each file of a multi-file asset upload starts and finishes
+pub const ASSET_UPLOAD_PROGRESS_EVENT: &str = "release-asset-upload-progress";
+
+/// Progress of one file of a multi-file asset upload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetUploadProgress {
+    pub release_id: i64,
+    pub path: String,
+    pub index: usize,
+    pub total_files: usize,
+    /// `uploading`, `uploaded`, or `failed`
+    pub status: String,
+    pub error: Option<String>,
+}
+
+/// Outcome of uploading one file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetUploadResult {
+    pub path: String,
+    pub asset: Option<ReleaseAsset>,
+    pub replaced: bool,
+    pub error: Option<String>,
+}
+
 /// Error type for releases API
 #[derive(Debug)]
 pub struct ReleasesError(pub String);
@@ -94,10 +136,11 @@ impl std::error::Error for ReleasesError {}
 
 pub type ReleasesResult<T> = Result<T, ReleasesError>;
 
-fn get_client() -> ReleasesResult<(Client, String)> {
-    let token = get_stored_token().map_err(|e| ReleasesError(e.to_string()))?;
-    let client = Client::new();
-    Ok((client, token))
+/// Client and token for a repository, authenticating as its GitHub App
+/// installation if one is configured for it
+async fn get_repo_client(owner: &str, repo: &str) -> ReleasesResult<(Client, String)> {
+    let token = token_for_repo(owner, repo).await.map_err(|e| ReleasesError(e.to_string()))?;
+    Ok((Client::new(), token))
 }
 
 /// List releases for a repository
@@ -107,7 +150,7 @@ pub async fn list_releases(
     per_page: Option<u32>,
     page: Option<u32>,
 ) -> ReleasesResult<Vec<Release>> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!("https://api.github.com/repos/{}/{}/releases", owner, repo);
 
@@ -144,7 +187,7 @@ pub async fn list_releases(
 
 /// Get a specific release by ID
 pub async fn get_release(owner: &str, repo: &str, release_id: i64) -> ReleasesResult<Release> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/releases/{}",
@@ -175,7 +218,7 @@ pub async fn get_release(owner: &str, repo: &str, release_id: i64) -> ReleasesRe
 
 /// Get the latest release
 pub async fn get_latest_release(owner: &str, repo: &str) -> ReleasesResult<Release> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/releases/latest",
@@ -206,7 +249,7 @@ pub async fn get_latest_release(owner: &str, repo: &str) -> ReleasesResult<Relea
 
 /// Get a release by tag name
 pub async fn get_release_by_tag(owner: &str, repo: &str, tag: &str) -> ReleasesResult<Release> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/releases/tags/{}",
@@ -241,7 +284,7 @@ pub async fn create_release(
     repo: &str,
     request: CreateReleaseRequest,
 ) -> ReleasesResult<Release> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!("https://api.github.com/repos/{}/{}/releases", owner, repo);
 
@@ -275,7 +318,7 @@ pub async fn update_release(
     release_id: i64,
     request: UpdateReleaseRequest,
 ) -> ReleasesResult<Release> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/releases/{}",
@@ -307,7 +350,7 @@ pub async fn update_release(
 
 /// Delete a release
 pub async fn delete_release(owner: &str, repo: &str, release_id: i64) -> ReleasesResult<()> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/releases/{}",
@@ -341,7 +384,7 @@ pub async fn generate_release_notes(
     target_commitish: Option<&str>,
     previous_tag_name: Option<&str>,
 ) -> ReleasesResult<String> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/releases/generate-notes",
@@ -397,7 +440,7 @@ pub async fn list_release_assets(
     repo: &str,
     release_id: i64,
 ) -> ReleasesResult<Vec<ReleaseAsset>> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/releases/{}/assets",
@@ -432,7 +475,7 @@ pub async fn get_release_asset(
     repo: &str,
     asset_id: i64,
 ) -> ReleasesResult<ReleaseAsset> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/releases/assets/{}",
@@ -469,7 +512,7 @@ pub async fn update_release_asset(
     name: Option<&str>,
     label: Option<&str>,
 ) -> ReleasesResult<ReleaseAsset> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/releases/assets/{}",
@@ -509,7 +552,7 @@ pub async fn update_release_asset(
 
 /// Delete a release asset
 pub async fn delete_release_asset(owner: &str, repo: &str, asset_id: i64) -> ReleasesResult<()> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!(
         "https://api.github.com/repos/{}/{}/releases/assets/{}",
@@ -535,28 +578,21 @@ pub async fn delete_release_asset(owner: &str, repo: &str, asset_id: i64) -> Rel
     Ok(())
 }
 
-/// Upload a release asset
+/// Upload the content of a release asset under `name`
 pub async fn upload_release_asset(
+    owner: &str,
+    repo: &str,
     upload_url: &str,
-    file_path: &str,
+    name: &str,
+    content: Vec<u8>,
     content_type: &str,
 ) -> ReleasesResult<ReleaseAsset> {
-    let token = get_stored_token().map_err(|e| ReleasesError(e.to_string()))?;
-    let client = Client::new();
-
-    // Read file
-    let file_content = std::fs::read(file_path)
-        .map_err(|e| ReleasesError(format!("Failed to read file: {}", e)))?;
-
-    let file_name = std::path::Path::new(file_path)
-        .file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or("asset");
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     // Parse the upload URL and add the name parameter
     // GitHub upload URLs look like: https://uploads.github.com/repos/owner/repo/releases/123/assets{?name,label}
     let base_url = upload_url.split('{').next().unwrap_or(upload_url);
-    let url = format!("{}?name={}", base_url, urlencoding::encode(file_name));
+    let url = format!("{}?name={}", base_url, urlencoding::encode(name));
 
     let response = client
         .post(&url)
@@ -565,7 +601,7 @@ pub async fn upload_release_asset(
         .header("User-Agent", "LinuxGit")
         .header("X-GitHub-Api-Version", "2022-11-28")
         .header("Content-Type", content_type)
-        .body(file_content)
+        .body(content)
         .send()
         .await
         .map_err(|e| ReleasesError(format!("Request failed: {}", e)))?;
@@ -582,6 +618,157 @@ pub async fn upload_release_asset(
         .map_err(|e| ReleasesError(format!("Failed to parse response: {}", e)))
 }
 
+/// Guess the content type of an asset from its file name
+pub fn guess_content_type(file_name: &str) -> &'static str {
+    let lower = file_name.to_ascii_lowercase();
+    if lower.ends_with(".tar.gz") || lower.ends_with(".tgz") {
+        return "application/gzip";
+    }
+
+    match lower.rsplit_once('.').map(|(_, ext)| ext) {
+        Some("zip") => "application/zip",
+        Some("gz") => "application/gzip",
+        Some("xz") => "application/x-xz",
+        Some("bz2") => "application/x-bzip2",
+        Some("zst") => "application/zstd",
+        Some("tar") => "application/x-tar",
+        Some("deb") => "application/vnd.debian.binary-package",
+        Some("rpm") => "application/x-rpm",
+        Some("appimage") => "application/vnd.appimage",
+        Some("exe") | Some("msi") | Some("dll") => "application/x-msdownload",
+        Some("dmg") => "application/x-apple-diskimage",
+        Some("json") => "application/json",
+        Some("txt") | Some("sha256") | Some("sha512") | Some("md5") => "text/plain",
+        Some("md") => "text/markdown",
+        Some("asc") | Some("sig") => "application/pgp-signature",
+        Some("pdf") => "application/pdf",
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("svg") => "image/svg+xml",
+        _ => "application/octet-stream",
+    }
+}
+
+/// File that holds a partial download of `dest` until it completes
+pub fn partial_download_path(dest: &Path) -> PathBuf {
+    let mut name = dest.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    dest.with_file_name(name)
+}
+
+/// Total size from a `Content-Range` header such as `bytes 100-999/1000`
+fn content_range_total(value: &str) -> Option<u64> {
+    value.rsplit_once('/')?.1.parse().ok()
+}
+
+/// Download a release asset to `dest`, resuming a previous partial download
+///
+/// Data is written to a `.part` file next to `dest`, which is renamed once the
+/// download completes. Setting `cancelled` stops the download and keeps the
+/// partial file so it can be resumed later. Returns the size of the asset.
+pub async fn download_release_asset(
+    owner: &str,
+    repo: &str,
+    asset_id: i64,
+    dest: &Path,
+    cancelled: &AtomicBool,
+    mut on_progress: impl FnMut(u64, Option<u64>),
+) -> ReleasesResult<u64> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/releases/assets/{}",
+        owner, repo, asset_id
+    );
+
+    let partial = partial_download_path(dest);
+    let mut offset = tokio::fs::metadata(&partial).await.map(|m| m.len()).unwrap_or(0);
+
+    // The API redirects to the storage host; reqwest drops Authorization on cross-host redirects
+    let mut request = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/octet-stream")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28");
+    if offset > 0 {
+        request = request.header("Range", format!("bytes={}-", offset));
+    }
+
+    let mut response = request
+        .send()
+        .await
+        .map_err(|e| ReleasesError(format!("Request failed: {}", e)))?;
+
+    let status = response.status();
+    if status == reqwest::StatusCode::RANGE_NOT_SATISFIABLE && offset > 0 {
+        // The partial file already holds the whole asset
+        tokio::fs::rename(&partial, dest)
+            .await
+            .map_err(|e| ReleasesError(format!("Failed to move download: {}", e)))?;
+        on_progress(offset, Some(offset));
+        return Ok(offset);
+    }
+
+    if !status.is_success() {
+        let text = response.text().await.unwrap_or_default();
+        return Err(ReleasesError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    let resumed = status == reqwest::StatusCode::PARTIAL_CONTENT;
+    let total = if resumed {
+        response
+            .headers()
+            .get("content-range")
+            .and_then(|v| v.to_str().ok())
+            .and_then(content_range_total)
+    } else {
+        // The server ignored the range, so start over
+        offset = 0;
+        response.content_length()
+    };
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(&partial)
+        .await
+        .map_err(|e| ReleasesError(format!("Failed to open {}: {}", partial.display(), e)))?;
+
+    let mut downloaded = offset;
+    on_progress(downloaded, total);
+
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| ReleasesError(format!("Download interrupted: {}", e)))?
+    {
+        if cancelled.load(Ordering::Relaxed) {
+            file.flush().await.ok();
+            return Err(ReleasesError("Download cancelled".to_string()));
+        }
+
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| ReleasesError(format!("Failed to write download: {}", e)))?;
+        downloaded += chunk.len() as u64;
+        on_progress(downloaded, total);
+    }
+
+    file.flush()
+        .await
+        .map_err(|e| ReleasesError(format!("Failed to write download: {}", e)))?;
+    drop(file);
+
+    tokio::fs::rename(&partial, dest)
+        .await
+        .map_err(|e| ReleasesError(format!("Failed to move download: {}", e)))?;
+
+    Ok(downloaded)
+}
+
 /// List tags for a repository
 pub async fn list_tags(
     owner: &str,
@@ -589,7 +776,7 @@ pub async fn list_tags(
     per_page: Option<u32>,
     page: Option<u32>,
 ) -> ReleasesResult<Vec<Tag>> {
-    let (client, token) = get_client()?;
+    let (client, token) = get_repo_client(owner, repo).await?;
 
     let url = format!("https://api.github.com/repos/{}/{}/tags", owner, repo);
 
@@ -624,6 +811,99 @@ pub async fn list_tags(
         .map_err(|e| ReleasesError(format!("Failed to parse response: {}", e)))
 }
 
+/// List tag protection patterns of a repository
+pub async fn list_tag_protections(owner: &str, repo: &str) -> ReleasesResult<Vec<TagProtection>> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!("https://api.github.com/repos/{}/{}/tags/protection", owner, repo);
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| ReleasesError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(ReleasesError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| ReleasesError(format!("Failed to parse response: {}", e)))
+}
+
+/// Protect tags matching a pattern (e.g. `v*`) from creation and deletion by non-admins
+pub async fn create_tag_protection(
+    owner: &str,
+    repo: &str,
+    pattern: &str,
+) -> ReleasesResult<TagProtection> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!("https://api.github.com/repos/{}/{}/tags/protection", owner, repo);
+
+    let response = client
+        .post(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&serde_json::json!({ "pattern": pattern }))
+        .send()
+        .await
+        .map_err(|e| ReleasesError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(ReleasesError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| ReleasesError(format!("Failed to parse response: {}", e)))
+}
+
+/// Delete a tag protection pattern
+pub async fn delete_tag_protection(
+    owner: &str,
+    repo: &str,
+    tag_protection_id: i64,
+) -> ReleasesResult<()> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/tags/protection/{}",
+        owner, repo, tag_protection_id
+    );
+
+    let response = client
+        .delete(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| ReleasesError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(ReleasesError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    Ok(())
+}
+
 /// GitHub Tag
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Tag {
@@ -638,3 +918,39 @@ pub struct TagCommit {
     pub sha: String,
     pub url: String,
 }
+
+/// Tag protection pattern
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TagProtection {
+    pub id: i64,
+    pub pattern: String,
+    pub created_at: Option<String>,
+    pub enabled: Option<bool>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guess_content_type() {
+        assert_eq!(guess_content_type("app-1.0.tar.gz"), "application/gzip");
+        assert_eq!(guess_content_type("App.AppImage"), "application/vnd.appimage");
+        assert_eq!(guess_content_type("checksums.sha256"), "text/plain");
+        assert_eq!(guess_content_type("linuxgit"), "application/octet-stream");
+    }
+
+    #[test]
+    fn test_partial_download_path() {
+        assert_eq!(
+            partial_download_path(Path::new("/tmp/app.tar.gz")),
+            PathBuf::from("/tmp/app.tar.gz.part")
+        );
+    }
+
+    #[test]
+    fn test_content_range_total() {
+        assert_eq!(content_range_total("bytes 100-999/1000"), Some(1000));
+        assert_eq!(content_range_total("bytes 100-999/*"), None);
+    }
+}
@@ -0,0 +1,187 @@
+//! Workflow run watcher
+//!
+//! Computes the changes between successive polls of a workflow run and its
+//! jobs, and the adaptive interval to wait before polling again.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::actions::{WorkflowJob, WorkflowRun};
+
+/// Event emitted with every poll that observed changes
+pub const RUN_UPDATE_EVENT: &str = "workflow-run-update";
+/// Event emitted once the watched run has completed
+pub const RUN_COMPLETED_EVENT: &str = "workflow-run-completed";
+/// Event emitted when watching stops because polling kept failing
+pub const RUN_WATCH_ERROR_EVENT: &str = "workflow-run-watch-error";
+
+/// State of a workflow run at one poll
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunSnapshot {
+    pub run: WorkflowRun,
+    pub jobs: Vec<WorkflowJob>,
+}
+
+/// A change observed between two polls
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RunEvent {
+    RunStatusChanged {
+        status: Option<String>,
+        conclusion: Option<String>,
+    },
+    JobStatusChanged {
+        job_id: i64,
+        name: String,
+        status: String,
+        conclusion: Option<String>,
+    },
+    StepStatusChanged {
+        job_id: i64,
+        job_name: String,
+        number: i32,
+        name: String,
+        status: String,
+        conclusion: Option<String>,
+    },
+}
+
+/// Payload of the workflow run watcher events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkflowRunUpdate {
+    pub owner: String,
+    pub repo: String,
+    pub run_id: i64,
+    pub snapshot: Option<RunSnapshot>,
+    pub events: Vec<RunEvent>,
+    pub error: Option<String>,
+}
+
+impl RunSnapshot {
+    /// Whether the run has finished
+    pub fn is_complete(&self) -> bool {
+        self.run.status.as_deref() == Some("completed")
+    }
+}
+
+/// List the run, job, and step status changes from `prev` to `next`
+///
+/// With no previous snapshot, everything that has started is reported.
+pub fn diff_snapshots(prev: Option<&RunSnapshot>, next: &RunSnapshot) -> Vec<RunEvent> {
+    let mut events = Vec::new();
+
+    let run_changed = prev.is_none_or(|p| {
+        p.run.status != next.run.status || p.run.conclusion != next.run.conclusion
+    });
+    if run_changed {
+        events.push(RunEvent::RunStatusChanged {
+            status: next.run.status.clone(),
+            conclusion: next.run.conclusion.clone(),
+        });
+    }
+
+    for job in &next.jobs {
+        let old_job = prev.and_then(|p| p.jobs.iter().find(|j| j.id == job.id));
+
+        if old_job.is_none_or(|o| o.status != job.status || o.conclusion != job.conclusion) {
+            events.push(RunEvent::JobStatusChanged {
+                job_id: job.id,
+                name: job.name.clone(),
+                status: job.status.clone(),
+                conclusion: job.conclusion.clone(),
+            });
+        }
+
+        for step in job.steps.iter().flatten() {
+            let old_step = old_job
+                .and_then(|o| o.steps.as_ref())
+                .and_then(|steps| steps.iter().find(|s| s.number == step.number));
+
+            let changed = match old_step {
+                Some(o) => o.status != step.status || o.conclusion != step.conclusion,
+                None => step.status != "queued" && step.status != "pending",
+            };
+            if changed {
+                events.push(RunEvent::StepStatusChanged {
+                    job_id: job.id,
+                    job_name: job.name.clone(),
+                    number: step.number,
+                    name: step.name.clone(),
+                    status: step.status.clone(),
+                    conclusion: step.conclusion.clone(),
+                });
+            }
+        }
+    }
+
+    events
+}
+
+/// Interval before the next poll
+///
+/// Running jobs are polled quickly; queued runs and runs that have not changed
+/// for a while back off, up to 60 seconds.
+pub fn next_poll_interval(snapshot: &RunSnapshot, unchanged_polls: u32) -> Duration {
+    let base = match snapshot.run.status.as_deref() {
+        Some("in_progress") => 5,
+        _ => 15,
+    };
+    let backoff = base * (1 + u64::from(unchanged_polls.min(6)) / 2);
+    Duration::from_secs(backoff.min(60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::actions::WorkflowStep;
+
+    fn run(status: &str, conclusion: Option<&str>) -> WorkflowRun {
+        serde_json::from_value(serde_json::json!({
+            "id": 1, "name": "CI", "head_branch": "main", "head_sha": "abc", "run_number": 7,
+            "event": "push", "status": status, "conclusion": conclusion, "workflow_id": 2,
+            "created_at": "2024-01-01T00:00:00Z", "updated_at": "2024-01-01T00:00:00Z",
+            "html_url": "", "jobs_url": "", "logs_url": "", "run_started_at": null, "actor": null
+        }))
+        .unwrap()
+    }
+
+    fn job(status: &str, step_status: &str) -> WorkflowJob {
+        WorkflowJob {
+            id: 10,
+            run_id: 1,
+            name: "build".to_string(),
+            status: status.to_string(),
+            conclusion: None,
+            started_at: None,
+            completed_at: None,
+            steps: Some(vec![WorkflowStep {
+                name: "Run tests".to_string(),
+                status: step_status.to_string(),
+                conclusion: None,
+                number: 1,
+                started_at: None,
+                completed_at: None,
+            }]),
+        }
+    }
+
+    #[test]
+    fn test_diff_reports_step_changes() {
+        let before = RunSnapshot { run: run("in_progress", None), jobs: vec![job("in_progress", "queued")] };
+        let after = RunSnapshot { run: run("in_progress", None), jobs: vec![job("in_progress", "in_progress")] };
+
+        let events = diff_snapshots(Some(&before), &after);
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], RunEvent::StepStatusChanged { number: 1, .. }));
+        assert!(diff_snapshots(Some(&after), &after).is_empty());
+    }
+
+    #[test]
+    fn test_poll_interval_backs_off() {
+        let snapshot = RunSnapshot { run: run("in_progress", None), jobs: Vec::new() };
+        assert_eq!(next_poll_interval(&snapshot, 0), Duration::from_secs(5));
+        assert!(next_poll_interval(&snapshot, 10) > Duration::from_secs(5));
+        assert!(next_poll_interval(&snapshot, 100) <= Duration::from_secs(60));
+    }
+}
@@ -0,0 +1,367 @@
+//! GitHub Search API module
+//!
+//! Provides code, issue, repository, and user search, with handling for the
+//! search API's stricter rate limits.
+
+use std::time::Duration;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use reqwest::header::HeaderMap;
+use reqwest::Client;
+
+use super::get_stored_token;
+use super::issues::Issue;
+use super::GitHubRepo;
+
+/// Longest rate-limit wait that is retried automatically instead of failing
+const MAX_RETRY_WAIT_SECS: u64 = 10;
+
+/// Search rate limit state reported with a response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchRateLimit {
+    pub limit: u32,
+    pub remaining: u32,
+    /// Unix timestamp at which the limit resets
+    pub reset: i64,
+}
+
+/// A page of search results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResults<T> {
+    pub total_count: i64,
+    pub incomplete_results: bool,
+    pub items: Vec<T>,
+    #[serde(default)]
+    pub rate_limit: Option<SearchRateLimit>,
+}
+
+/// Matched fragment position within a text match
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextMatchSpan {
+    pub text: String,
+    pub indices: Vec<usize>,
+}
+
+/// Fragment of a file or field that matched the query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextMatch {
+    pub property: Option<String>,
+    pub fragment: String,
+    #[serde(default)]
+    pub matches: Vec<TextMatchSpan>,
+}
+
+/// Repository a code search result belongs to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchRepository {
+    pub id: i64,
+    pub name: String,
+    pub full_name: String,
+    pub private: bool,
+    pub html_url: String,
+}
+
+/// Code search result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CodeSearchItem {
+    pub name: String,
+    pub path: String,
+    pub sha: String,
+    pub html_url: String,
+    pub repository: SearchRepository,
+    #[serde(default)]
+    pub text_matches: Vec<TextMatch>,
+}
+
+/// Issue or pull request search result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueSearchItem {
+    #[serde(flatten)]
+    pub issue: Issue,
+    pub repository_url: String,
+    /// `owner/repo` of the issue, derived from `repository_url`
+    #[serde(default)]
+    pub repository: String,
+    #[serde(default)]
+    pub draft: Option<bool>,
+    #[serde(default)]
+    pub text_matches: Vec<TextMatch>,
+}
+
+/// User or organization search result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserSearchItem {
+    pub login: String,
+    pub id: i64,
+    pub avatar_url: String,
+    pub html_url: String,
+    #[serde(rename = "type")]
+    pub account_type: String,
+}
+
+/// Error type for Search API
+#[derive(Debug)]
+pub struct SearchError(pub String);
+
+impl std::fmt::Display for SearchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for SearchError {}
+
+pub type SearchResult<T> = Result<T, SearchError>;
+
+fn get_client() -> Client {
+    Client::new()
+}
+
+fn header_value<T: std::str::FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+    headers.get(name)?.to_str().ok()?.parse().ok()
+}
+
+/// Read the rate limit headers of a response
+fn parse_rate_limit(headers: &HeaderMap) -> Option<SearchRateLimit> {
+    Some(SearchRateLimit {
+        limit: header_value(headers, "x-ratelimit-limit")?,
+        remaining: header_value(headers, "x-ratelimit-remaining")?,
+        reset: header_value(headers, "x-ratelimit-reset")?,
+    })
+}
+
+/// Seconds to wait before retrying a rate-limited response, if it was rate limited
+fn rate_limit_wait(status: reqwest::StatusCode, headers: &HeaderMap, now: i64) -> Option<u64> {
+    if status != reqwest::StatusCode::FORBIDDEN && status != reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return None;
+    }
+
+    // Secondary rate limits send Retry-After
+    if let Some(secs) = header_value::<u64>(headers, "retry-after") {
+        return Some(secs);
+    }
+
+    match parse_rate_limit(headers) {
+        Some(limit) if limit.remaining == 0 => Some((limit.reset - now).max(1) as u64),
+        _ => None,
+    }
+}
+
+/// Add a scope qualifier to a query
+///
+/// A filter containing `:` is used as a qualifier as-is, `owner/repo` becomes
+/// `repo:owner/repo`, and anything else is treated as a user or organization.
+pub fn scope_query(query: &str, filter: Option<&str>) -> String {
+    let query = query.trim();
+    match filter.map(str::trim).filter(|f| !f.is_empty()) {
+        Some(f) if f.contains(':') => format!("{} {}", query, f),
+        Some(f) if f.contains('/') => format!("{} repo:{}", query, f),
+        Some(f) => format!("{} user:{}", query, f),
+        None => query.to_string(),
+    }
+}
+
+/// Run a search against `/search/{kind}`, retrying once on short rate-limit waits
+async fn search<T: DeserializeOwned>(
+    kind: &str,
+    params: &[(&str, String)],
+) -> SearchResult<SearchResults<T>> {
+    let token = get_stored_token().map_err(|e| SearchError(e.to_string()))?;
+    let client = get_client();
+    let url = format!("https://api.github.com/search/{}", kind);
+
+    let mut retried = false;
+    loop {
+        let response = client
+            .get(&url)
+            .query(params)
+            .header("Authorization", format!("Bearer {}", token))
+            .header("Accept", "application/vnd.github.text-match+json")
+            .header("User-Agent", "LinuxGit")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send()
+            .await
+            .map_err(|e| SearchError(format!("Request failed: {}", e)))?;
+
+        let status = response.status();
+        let headers = response.headers().clone();
+
+        if let Some(wait) = rate_limit_wait(status, &headers, chrono::Utc::now().timestamp()) {
+            if !retried && wait <= MAX_RETRY_WAIT_SECS {
+                retried = true;
+                tokio::time::sleep(Duration::from_secs(wait)).await;
+                continue;
+            }
+            return Err(SearchError(format!(
+                "Search rate limit exceeded, try again in {} seconds",
+                wait
+            )));
+        }
+
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(SearchError(format!("GitHub API error ({}): {}", status, text)));
+        }
+
+        let mut results: SearchResults<T> = response
+            .json()
+            .await
+            .map_err(|e| SearchError(format!("Failed to parse response: {}", e)))?;
+        results.rate_limit = parse_rate_limit(&headers);
+        return Ok(results);
+    }
+}
+
+/// Search code, optionally scoped to a repository, user, or organization
+pub async fn search_code(
+    query: &str,
+    repo_filter: Option<&str>,
+    page: Option<i32>,
+    per_page: Option<i32>,
+) -> SearchResult<SearchResults<CodeSearchItem>> {
+    let q = scope_query(query, repo_filter);
+    if q.is_empty() {
+        return Err(SearchError("Search query cannot be empty".to_string()));
+    }
+
+    search(
+        "code",
+        &[
+            ("q", q),
+            ("page", page.unwrap_or(1).to_string()),
+            ("per_page", per_page.unwrap_or(30).to_string()),
+        ],
+    )
+    .await
+}
+
+/// `owner/repo` from an API repository URL
+fn repository_from_url(url: &str) -> String {
+    url.split_once("/repos/")
+        .map_or(url, |(_, name)| name)
+        .to_string()
+}
+
+/// Search issues and pull requests (e.g. `is:open is:pr review-requested:@me`)
+pub async fn search_issues(
+    query: &str,
+    sort: Option<&str>,
+    order: Option<&str>,
+    page: Option<i32>,
+    per_page: Option<i32>,
+) -> SearchResult<SearchResults<IssueSearchItem>> {
+    let q = query.trim();
+    if q.is_empty() {
+        return Err(SearchError("Search query cannot be empty".to_string()));
+    }
+
+    let mut params = vec![
+        ("q", q.to_string()),
+        ("page", page.unwrap_or(1).to_string()),
+        ("per_page", per_page.unwrap_or(30).to_string()),
+    ];
+    if let Some(sort) = sort {
+        params.push(("sort", sort.to_string()));
+        params.push(("order", order.unwrap_or("desc").to_string()));
+    }
+
+    let mut results: SearchResults<IssueSearchItem> = search("issues", &params).await?;
+    for item in &mut results.items {
+        item.repository = repository_from_url(&item.repository_url);
+    }
+    Ok(results)
+}
+
+/// Search repositories by name, description, or qualifiers
+///
+/// `sort` is one of `stars`, `forks`, `help-wanted-issues`, or `updated`;
+/// without it results are ordered by best match.
+pub async fn search_repos(
+    query: &str,
+    sort: Option<&str>,
+    page: Option<i32>,
+    per_page: Option<i32>,
+) -> SearchResult<SearchResults<GitHubRepo>> {
+    let q = query.trim();
+    if q.is_empty() {
+        return Err(SearchError("Search query cannot be empty".to_string()));
+    }
+
+    let mut params = vec![
+        ("q", q.to_string()),
+        ("page", page.unwrap_or(1).to_string()),
+        ("per_page", per_page.unwrap_or(30).to_string()),
+    ];
+    if let Some(sort) = sort.filter(|s| *s != "best-match") {
+        if !["stars", "forks", "help-wanted-issues", "updated"].contains(&sort) {
+            return Err(SearchError(format!("Invalid sort: {}", sort)));
+        }
+        params.push(("sort", sort.to_string()));
+    }
+
+    search("repositories", &params).await
+}
+
+/// Search users and organizations by login, name, or email
+pub async fn search_users(
+    query: &str,
+    page: Option<i32>,
+    per_page: Option<i32>,
+) -> SearchResult<SearchResults<UserSearchItem>> {
+    let q = query.trim();
+    if q.is_empty() {
+        return Err(SearchError("Search query cannot be empty".to_string()));
+    }
+
+    search(
+        "users",
+        &[
+            ("q", q.to_string()),
+            ("page", page.unwrap_or(1).to_string()),
+            ("per_page", per_page.unwrap_or(30).to_string()),
+        ],
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+    use reqwest::StatusCode;
+
+    #[test]
+    fn test_scope_query() {
+        assert_eq!(scope_query("fn main", None), "fn main");
+        assert_eq!(scope_query("fn main", Some("rust-lang/rust")), "fn main repo:rust-lang/rust");
+        assert_eq!(scope_query("fn main", Some("rust-lang")), "fn main user:rust-lang");
+        assert_eq!(scope_query("fn main", Some("org:tauri-apps")), "fn main org:tauri-apps");
+        assert_eq!(scope_query(" todo ", Some(" ")), "todo");
+    }
+
+    #[test]
+    fn test_repository_from_url() {
+        assert_eq!(
+            repository_from_url("https://api.github.com/repos/octocat/Hello-World"),
+            "octocat/Hello-World"
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_wait() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-limit", HeaderValue::from_static("30"));
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+        headers.insert("x-ratelimit-reset", HeaderValue::from_static("1000"));
+
+        assert_eq!(rate_limit_wait(StatusCode::FORBIDDEN, &headers, 990), Some(10));
+        assert_eq!(rate_limit_wait(StatusCode::OK, &headers, 990), None);
+
+        headers.insert("retry-after", HeaderValue::from_static("3"));
+        assert_eq!(rate_limit_wait(StatusCode::TOO_MANY_REQUESTS, &headers, 990), Some(3));
+
+        let mut forbidden = HeaderMap::new();
+        forbidden.insert("x-ratelimit-remaining", HeaderValue::from_static("12"));
+        assert_eq!(rate_limit_wait(StatusCode::FORBIDDEN, &forbidden, 990), None);
+    }
+}
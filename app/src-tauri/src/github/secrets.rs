@@ -0,0 +1,374 @@
+//! GitHub Actions Secrets and Variables API module
+//!
+//! Provides access to repository-level Actions secrets and configuration variables.
+//! Secret values are encrypted locally with the repository public key (libsodium
+//! sealed box) before being sent to GitHub.
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use crypto_box::{aead::OsRng, PublicKey};
+use serde::{Deserialize, Serialize};
+use reqwest::Client;
+
+use super::app_auth::token_for_repo;
+
+/// Public key used to encrypt Actions secrets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionsPublicKey {
+    pub key_id: String,
+    pub key: String,
+}
+
+/// Actions secret (values are never returned by GitHub)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionsSecret {
+    pub name: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SecretsResponse {
+    total_count: i32,
+    secrets: Vec<ActionsSecret>,
+}
+
+/// Actions configuration variable
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActionsVariable {
+    pub name: String,
+    pub value: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VariablesResponse {
+    total_count: i32,
+    variables: Vec<ActionsVariable>,
+}
+
+/// Error type for Secrets API
+#[derive(Debug)]
+pub struct SecretsError(pub String);
+
+impl std::fmt::Display for SecretsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for SecretsError {}
+
+pub type SecretsResult<T> = Result<T, SecretsError>;
+
+/// Client and token for a repository, authenticating as its GitHub App
+/// installation if one is configured for it
+async fn get_repo_client(owner: &str, repo: &str) -> SecretsResult<(Client, String)> {
+    let token = token_for_repo(owner, repo).await.map_err(|e| SecretsError(e.to_string()))?;
+    Ok((Client::new(), token))
+}
+
+/// Encrypt a secret value for GitHub with a base64-encoded public key
+///
+/// Returns the base64-encoded sealed box expected as `encrypted_value`.
+pub fn encrypt_secret(public_key: &str, value: &str) -> SecretsResult<String> {
+    let key_bytes: [u8; 32] = STANDARD
+        .decode(public_key)
+        .map_err(|e| SecretsError(format!("Invalid public key: {}", e)))?
+        .try_into()
+        .map_err(|_| SecretsError("Invalid public key: expected 32 bytes".to_string()))?;
+
+    let sealed = PublicKey::from(key_bytes)
+        .seal(&mut OsRng, value.as_bytes())
+        .map_err(|e| SecretsError(format!("Failed to encrypt secret: {}", e)))?;
+
+    Ok(STANDARD.encode(sealed))
+}
+
+/// Get the public key used to encrypt repository secrets
+pub async fn get_repo_public_key(owner: &str, repo: &str) -> SecretsResult<ActionsPublicKey> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/actions/secrets/public-key",
+        owner, repo
+    );
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| SecretsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(SecretsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| SecretsError(format!("Failed to parse response: {}", e)))
+}
+
+/// List repository secrets
+pub async fn list_repo_secrets(owner: &str, repo: &str) -> SecretsResult<Vec<ActionsSecret>> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!("https://api.github.com/repos/{}/{}/actions/secrets", owner, repo);
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .query(&[("per_page", "100")])
+        .send()
+        .await
+        .map_err(|e| SecretsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(SecretsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    let data: SecretsResponse = response
+        .json()
+        .await
+        .map_err(|e| SecretsError(format!("Failed to parse response: {}", e)))?;
+
+    Ok(data.secrets)
+}
+
+/// Create or update a repository secret
+pub async fn set_repo_secret(
+    owner: &str,
+    repo: &str,
+    name: &str,
+    value: &str,
+) -> SecretsResult<()> {
+    let public_key = get_repo_public_key(owner, repo).await?;
+    let encrypted_value = encrypt_secret(&public_key.key, value)?;
+
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/actions/secrets/{}",
+        owner, repo, name
+    );
+
+    let payload = serde_json::json!({
+        "encrypted_value": encrypted_value,
+        "key_id": public_key.key_id
+    });
+
+    let response = client
+        .put(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| SecretsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(SecretsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    Ok(())
+}
+
+/// Delete a repository secret
+pub async fn delete_repo_secret(owner: &str, repo: &str, name: &str) -> SecretsResult<()> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/actions/secrets/{}",
+        owner, repo, name
+    );
+
+    let response = client
+        .delete(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| SecretsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(SecretsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    Ok(())
+}
+
+/// List repository variables
+pub async fn list_repo_variables(owner: &str, repo: &str) -> SecretsResult<Vec<ActionsVariable>> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!("https://api.github.com/repos/{}/{}/actions/variables", owner, repo);
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .query(&[("per_page", "30")])
+        .send()
+        .await
+        .map_err(|e| SecretsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(SecretsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    let data: VariablesResponse = response
+        .json()
+        .await
+        .map_err(|e| SecretsError(format!("Failed to parse response: {}", e)))?;
+
+    Ok(data.variables)
+}
+
+/// Create a repository variable
+pub async fn create_repo_variable(
+    owner: &str,
+    repo: &str,
+    name: &str,
+    value: &str,
+) -> SecretsResult<()> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!("https://api.github.com/repos/{}/{}/actions/variables", owner, repo);
+
+    let payload = serde_json::json!({
+        "name": name,
+        "value": value
+    });
+
+    let response = client
+        .post(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| SecretsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(SecretsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    Ok(())
+}
+
+/// Update a repository variable
+pub async fn update_repo_variable(
+    owner: &str,
+    repo: &str,
+    name: &str,
+    value: &str,
+) -> SecretsResult<()> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/actions/variables/{}",
+        owner, repo, name
+    );
+
+    let payload = serde_json::json!({
+        "name": name,
+        "value": value
+    });
+
+    let response = client
+        .patch(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(|e| SecretsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(SecretsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    Ok(())
+}
+
+/// Delete a repository variable
+pub async fn delete_repo_variable(owner: &str, repo: &str, name: &str) -> SecretsResult<()> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!(
+        "https://api.github.com/repos/{}/{}/actions/variables/{}",
+        owner, repo, name
+    );
+
+    let response = client
+        .delete(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(|e| SecretsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(SecretsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_box::SecretKey;
+
+    #[test]
+    fn test_encrypt_secret_roundtrip() {
+        let secret_key = SecretKey::generate(&mut OsRng);
+        let public_key = STANDARD.encode(secret_key.public_key().as_bytes());
+
+        let encrypted = encrypt_secret(&public_key, "hunter2").unwrap();
+        let sealed = STANDARD.decode(encrypted).unwrap();
+
+        assert_eq!(secret_key.unseal(&sealed).unwrap(), b"hunter2");
+    }
+
+    #[test]
+    fn test_encrypt_secret_rejects_bad_key() {
+        assert!(encrypt_secret("c2hvcnQ=", "value").is_err());
+    }
+}
@@ -0,0 +1,177 @@
+//! Markdown task-list parsing
+//!
+//! Parses `- [ ]` / `- [x]` checklists from issue and pull request bodies
+//! and toggles individual items in place.
+
+use serde::{Deserialize, Serialize};
+
+/// A single checklist item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskListItem {
+    pub index: usize,
+    pub line: usize,
+    pub checked: bool,
+    pub text: String,
+}
+
+/// Checklist items of a body and their completion counts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskListProgress {
+    pub total: usize,
+    pub completed: usize,
+    pub items: Vec<TaskListItem>,
+}
+
+/// Locate the checkbox of a task-list line.
+///
+/// Returns the byte offset of the character inside the brackets and whether it is checked.
+fn find_checkbox(line: &str) -> Option<(usize, bool)> {
+    let indent = line.len() - line.trim_start().len();
+    let rest = &line[indent..];
+
+    let marker_len = if rest.starts_with("- ") || rest.starts_with("* ") || rest.starts_with("+ ") {
+        2
+    } else {
+        let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+        if digits == 0 {
+            return None;
+        }
+        let after = &rest[digits..];
+        if after.starts_with(". ") || after.starts_with(") ") {
+            digits + 2
+        } else {
+            return None;
+        }
+    };
+
+    let box_start = indent + marker_len;
+    let candidate = line.get(box_start..box_start + 3)?;
+    let checked = match candidate {
+        "[ ]" => false,
+        "[x]" | "[X]" => true,
+        _ => return None,
+    };
+
+    match line[box_start + 3..].chars().next() {
+        None | Some(' ') | Some('\t') | Some('\r') => Some((box_start + 1, checked)),
+        _ => None,
+    }
+}
+
+/// Whether a line opens or closes a fenced code block
+fn is_fence(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("```") || trimmed.starts_with("~~~")
+}
+
+/// Parse all checklist items from a markdown body, skipping fenced code blocks
+pub fn parse_task_list(body: &str) -> TaskListProgress {
+    let mut items = Vec::new();
+    let mut in_fence = false;
+
+    for (line_no, line) in body.lines().enumerate() {
+        if is_fence(line) {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        if let Some((offset, checked)) = find_checkbox(line) {
+            items.push(TaskListItem {
+                index: items.len(),
+                line: line_no,
+                checked,
+                text: line[offset + 2..].trim().to_string(),
+            });
+        }
+    }
+
+    TaskListProgress {
+        total: items.len(),
+        completed: items.iter().filter(|i| i.checked).count(),
+        items,
+    }
+}
+
+/// Toggle the checklist item at `index`, returning the updated body.
+///
+/// Returns `None` if the body has no item at that index.
+pub fn toggle_task_item(body: &str, index: usize) -> Option<String> {
+    let mut result = String::with_capacity(body.len());
+    let mut in_fence = false;
+    let mut current = 0;
+    let mut toggled = false;
+
+    for raw in body.split_inclusive('\n') {
+        // Same line boundaries as `str::lines` in `parse_task_list`
+        let line = match raw.strip_suffix('\n') {
+            Some(line) => line.strip_suffix('\r').unwrap_or(line),
+            None => raw,
+        };
+        let ending = &raw[line.len()..];
+
+        if is_fence(line) {
+            in_fence = !in_fence;
+        } else if !in_fence && !toggled {
+            if let Some((offset, checked)) = find_checkbox(line) {
+                if current == index {
+                    result.push_str(&line[..offset]);
+                    result.push(if checked { ' ' } else { 'x' });
+                    result.push_str(&line[offset + 1..]);
+                    result.push_str(ending);
+                    toggled = true;
+                    continue;
+                }
+                current += 1;
+            }
+        }
+        result.push_str(raw);
+    }
+
+    if toggled {
+        Some(result)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BODY: &str = "Intro\n\n- [x] Done\n- [ ] Todo\n  * [X] Nested\n```\n- [ ] not a task\n```\n1. [ ] Numbered\n- [link](url)\n";
+
+    #[test]
+    fn test_parse_task_list() {
+        let progress = parse_task_list(BODY);
+        assert_eq!(progress.total, 4);
+        assert_eq!(progress.completed, 2);
+        assert_eq!(progress.items[1].text, "Todo");
+        assert_eq!(progress.items[3].text, "Numbered");
+    }
+
+    #[test]
+    fn test_toggle_task_item() {
+        let toggled = toggle_task_item(BODY, 1).unwrap();
+        assert!(toggled.contains("- [x] Todo\n"));
+        let progress = parse_task_list(&toggled);
+        assert_eq!(progress.completed, 3);
+
+        let untoggled = toggle_task_item(&toggled, 0).unwrap();
+        assert!(untoggled.contains("- [ ] Done\n"));
+        assert!(untoggled.contains("```\n- [ ] not a task\n```"));
+
+        assert!(toggle_task_item(BODY, 4).is_none());
+    }
+
+    #[test]
+    fn test_toggle_empty_task_item() {
+        let body = "- [ ]\r\n- [ ] Second\n- [ ]\n";
+        assert_eq!(parse_task_list(body).total, 3);
+
+        assert_eq!(toggle_task_item(body, 0).unwrap(), "- [x]\r\n- [ ] Second\n- [ ]\n");
+        assert_eq!(toggle_task_item(body, 1).unwrap(), "- [ ]\r\n- [x] Second\n- [ ]\n");
+        assert_eq!(toggle_task_item(body, 2).unwrap(), "- [ ]\r\n- [ ] Second\n- [x]\n");
+    }
+}
@@ -0,0 +1,193 @@
+//! GitHub Teams API module
+//!
+//! Provides access to organization teams, their members, and their repositories.
+
+use serde::{Deserialize, Serialize};
+use reqwest::Client;
+
+use super::collaborators::CollaboratorPermissions;
+use super::app_auth::token_for_repo;
+use super::get_stored_token;
+
+/// GitHub Team
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Team {
+    pub id: i64,
+    pub name: String,
+    pub slug: String,
+    pub description: Option<String>,
+    pub privacy: Option<String>,
+    pub permission: Option<String>,
+    pub html_url: String,
+}
+
+/// Member of a team
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamMember {
+    pub login: String,
+    pub id: i64,
+    pub avatar_url: String,
+    pub html_url: String,
+}
+
+/// Repository a team has access to, with the team's permissions on it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamRepository {
+    pub id: i64,
+    pub name: String,
+    pub full_name: String,
+    pub private: bool,
+    pub html_url: String,
+    pub role_name: Option<String>,
+    #[serde(default)]
+    pub permissions: CollaboratorPermissions,
+}
+
+/// Error type for Teams API
+#[derive(Debug)]
+pub struct TeamsError(pub String);
+
+impl std::fmt::Display for TeamsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for TeamsError {}
+
+pub type TeamsResult<T> = Result<T, TeamsError>;
+
+fn get_client() -> TeamsResult<(Client, String)> {
+    let token = get_stored_token().map_err(|e| TeamsError(e.to_string()))?;
+    let client = Client::new();
+    Ok((client, token))
+}
+
+/// Client and token for a repository, authenticating as its GitHub App
+/// installation if one is configured for it
+async fn get_repo_client(owner: &str, repo: &str) -> TeamsResult<(Client, String)> {
+    let token = token_for_repo(owner, repo).await.map_err(|e| TeamsError(e.to_string()))?;
+    Ok((Client::new(), token))
+}
+
+/// List teams that have access to a repository (the teams that can be requested as reviewers)
+pub async fn list_repo_teams(owner: &str, repo: &str) -> TeamsResult<Vec<Team>> {
+    let (client, token) = get_repo_client(owner, repo).await?;
+
+    let url = format!("https://api.github.com/repos/{}/{}/teams", owner, repo);
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .query(&[("per_page", "100")])
+        .send()
+        .await
+        .map_err(|e| TeamsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(TeamsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| TeamsError(format!("Failed to parse response: {}", e)))
+}
+
+/// List the teams of an organization visible to the authenticated user
+pub async fn list_org_teams(org: &str) -> TeamsResult<Vec<Team>> {
+    let (client, token) = get_client()?;
+
+    let url = format!("https://api.github.com/orgs/{}/teams", org);
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .query(&[("per_page", "100")])
+        .send()
+        .await
+        .map_err(|e| TeamsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(TeamsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| TeamsError(format!("Failed to parse response: {}", e)))
+}
+
+/// List the members of a team, including members of child teams
+///
+/// `role` is one of `member`, `maintainer`, or `all` (the default).
+pub async fn list_team_members(
+    org: &str,
+    team_slug: &str,
+    role: Option<&str>,
+) -> TeamsResult<Vec<TeamMember>> {
+    let (client, token) = get_client()?;
+
+    let url = format!("https://api.github.com/orgs/{}/teams/{}/members", org, team_slug);
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .query(&[("per_page", "100"), ("role", role.unwrap_or("all"))])
+        .send()
+        .await
+        .map_err(|e| TeamsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(TeamsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| TeamsError(format!("Failed to parse response: {}", e)))
+}
+
+/// List the repositories a team has access to
+pub async fn list_team_repos(org: &str, team_slug: &str) -> TeamsResult<Vec<TeamRepository>> {
+    let (client, token) = get_client()?;
+
+    let url = format!("https://api.github.com/orgs/{}/teams/{}/repos", org, team_slug);
+
+    let response = client
+        .get(&url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .query(&[("per_page", "100")])
+        .send()
+        .await
+        .map_err(|e| TeamsError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(TeamsError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|e| TeamsError(format!("Failed to parse response: {}", e)))
+}
@@ -0,0 +1,146 @@
+//! GitHub gitignore and license templates
+//!
+//! Provides GitHub's catalog of `.gitignore` templates and open source licenses
+//! for initializing new repositories.
+
+use serde::{Deserialize, Serialize};
+use reqwest::Client;
+
+use super::get_stored_token;
+
+/// Named `.gitignore` template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitignoreTemplate {
+    pub name: String,
+    pub source: String,
+}
+
+/// License summary from the catalog
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LicenseSummary {
+    pub key: String,
+    pub name: String,
+    pub spdx_id: Option<String>,
+}
+
+/// Full license text and metadata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct License {
+    pub key: String,
+    pub name: String,
+    pub spdx_id: Option<String>,
+    pub description: Option<String>,
+    pub body: String,
+    #[serde(default)]
+    pub permissions: Vec<String>,
+    #[serde(default)]
+    pub conditions: Vec<String>,
+    #[serde(default)]
+    pub limitations: Vec<String>,
+}
+
+/// Error type for Templates API
+#[derive(Debug)]
+pub struct TemplatesError(pub String);
+
+impl std::fmt::Display for TemplatesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for TemplatesError {}
+
+pub type TemplatesResult<T> = Result<T, TemplatesError>;
+
+/// Send a GET request, authenticated when a token is stored (templates are public,
+/// but authenticated requests get a higher rate limit)
+async fn get(url: &str) -> TemplatesResult<reqwest::Response> {
+    let mut request = Client::new()
+        .get(url)
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "LinuxGit")
+        .header("X-GitHub-Api-Version", "2022-11-28");
+
+    if let Ok(token) = get_stored_token() {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| TemplatesError(format!("Request failed: {}", e)))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        return Err(TemplatesError(format!("GitHub API error ({}): {}", status, text)));
+    }
+
+    Ok(response)
+}
+
+/// List the names of available `.gitignore` templates
+pub async fn list_gitignore_templates() -> TemplatesResult<Vec<String>> {
+    let response = get("https://api.github.com/gitignore/templates").await?;
+
+    response
+        .json()
+        .await
+        .map_err(|e| TemplatesError(format!("Failed to parse response: {}", e)))
+}
+
+/// Get a `.gitignore` template by name (e.g. `Rust`, `Node`)
+pub async fn get_gitignore_template(name: &str) -> TemplatesResult<GitignoreTemplate> {
+    let url = format!("https://api.github.com/gitignore/templates/{}", name);
+    let response = get(&url).await?;
+
+    response
+        .json()
+        .await
+        .map_err(|e| TemplatesError(format!("Failed to parse response: {}", e)))
+}
+
+/// List commonly used licenses
+pub async fn list_licenses() -> TemplatesResult<Vec<LicenseSummary>> {
+    let response = get("https://api.github.com/licenses").await?;
+
+    response
+        .json()
+        .await
+        .map_err(|e| TemplatesError(format!("Failed to parse response: {}", e)))
+}
+
+/// Get a license by key (e.g. `mit`, `apache-2.0`)
+pub async fn get_license(key: &str) -> TemplatesResult<License> {
+    let url = format!("https://api.github.com/licenses/{}", key);
+    let response = get(&url).await?;
+
+    response
+        .json()
+        .await
+        .map_err(|e| TemplatesError(format!("Failed to parse response: {}", e)))
+}
+
+/// Fill the year and copyright holder placeholders of a license body
+pub fn fill_license_placeholders(body: &str, year: i32, fullname: &str) -> String {
+    let year = year.to_string();
+    body.replace("[year]", &year)
+        .replace("[yyyy]", &year)
+        .replace("[fullname]", fullname)
+        .replace("[name of copyright owner]", fullname)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_license_placeholders() {
+        let body = "Copyright (c) [year] [fullname]\n";
+        assert_eq!(
+            fill_license_placeholders(body, 2024, "Jane Doe"),
+            "Copyright (c) 2024 Jane Doe\n"
+        );
+    }
+}
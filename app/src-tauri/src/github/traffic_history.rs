@@ -0,0 +1,188 @@
+//! Persistent repository traffic history
+//!
+//! GitHub only reports the last 14 days of views and clones. Snapshots are
+//! merged into a JSON file per repository under the app data directory so
+//! charts can show the long-term series.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::insights::{self, InsightsError, InsightsResult};
+
+/// Interval between scheduled snapshots of tracked repositories
+pub const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Views or clones for one day
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrafficPoint {
+    pub timestamp: String,
+    pub count: i32,
+    pub uniques: i32,
+}
+
+/// Stored traffic series of a repository
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TrafficHistory {
+    pub owner: String,
+    pub repo: String,
+    #[serde(default)]
+    pub views: Vec<TrafficPoint>,
+    #[serde(default)]
+    pub clones: Vec<TrafficPoint>,
+    pub last_snapshot: Option<String>,
+}
+
+/// Directory holding the stored histories
+fn history_dir() -> InsightsResult<PathBuf> {
+    dirs::data_dir()
+        .map(|dir| dir.join("linuxgit").join("traffic"))
+        .ok_or_else(|| InsightsError::Other("Could not determine the app data directory".to_string()))
+}
+
+fn history_path(owner: &str, repo: &str) -> InsightsResult<PathBuf> {
+    Ok(history_dir()?.join(format!("{}__{}.json", owner, repo)))
+}
+
+/// Merge new daily points into a series, sorted by day
+///
+/// A day present in both keeps the new values, since GitHub's most recent day
+/// is still being counted when it is first reported.
+pub fn merge_points(existing: &[TrafficPoint], new: &[TrafficPoint]) -> Vec<TrafficPoint> {
+    let mut by_day: BTreeMap<&str, &TrafficPoint> = BTreeMap::new();
+    for point in existing.iter().chain(new) {
+        by_day.insert(&point.timestamp, point);
+    }
+    by_day.into_values().cloned().collect()
+}
+
+/// Load the stored history of a repository (empty if it has never been snapshotted)
+pub fn load_history(owner: &str, repo: &str) -> InsightsResult<TrafficHistory> {
+    let path = history_path(owner, repo)?;
+    if !path.exists() {
+        return Ok(TrafficHistory {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            ..Default::default()
+        });
+    }
+
+    let data = std::fs::read_to_string(&path)
+        .map_err(|e| InsightsError::Other(format!("Failed to read traffic history: {}", e)))?;
+    serde_json::from_str(&data)
+        .map_err(|e| InsightsError::Other(format!("Failed to parse traffic history: {}", e)))
+}
+
+fn save_history(history: &TrafficHistory) -> InsightsResult<()> {
+    let path = history_path(&history.owner, &history.repo)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| InsightsError::Other(format!("Failed to create traffic directory: {}", e)))?;
+    }
+
+    let data = serde_json::to_string_pretty(history)
+        .map_err(|e| InsightsError::Other(format!("Failed to serialize traffic history: {}", e)))?;
+    std::fs::write(&path, data)
+        .map_err(|e| InsightsError::Other(format!("Failed to write traffic history: {}", e)))
+}
+
+/// Fetch the current traffic of a repository and merge it into its stored history
+///
+/// Snapshotting a repository also starts tracking it for scheduled snapshots.
+pub async fn snapshot_traffic(owner: &str, repo: &str) -> InsightsResult<TrafficHistory> {
+    let (views, clones) = tokio::try_join!(
+        insights::get_traffic_views(owner, repo),
+        insights::get_traffic_clones(owner, repo),
+    )?;
+
+    let views: Vec<TrafficPoint> = views
+        .views
+        .into_iter()
+        .map(|v| TrafficPoint { timestamp: v.timestamp, count: v.count, uniques: v.uniques })
+        .collect();
+    let clones: Vec<TrafficPoint> = clones
+        .clones
+        .into_iter()
+        .map(|c| TrafficPoint { timestamp: c.timestamp, count: c.count, uniques: c.uniques })
+        .collect();
+
+    let mut history = load_history(owner, repo)?;
+    history.views = merge_points(&history.views, &views);
+    history.clones = merge_points(&history.clones, &clones);
+    history.last_snapshot = Some(chrono::Utc::now().to_rfc3339());
+    save_history(&history)?;
+
+    Ok(history)
+}
+
+/// Repositories with a stored history, as `(owner, repo)`
+pub fn tracked_repos() -> InsightsResult<Vec<(String, String)>> {
+    let dir = history_dir()?;
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let entries = std::fs::read_dir(&dir)
+        .map_err(|e| InsightsError::Other(format!("Failed to read traffic directory: {}", e)))?;
+
+    Ok(entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let stem = name.strip_suffix(".json")?;
+            let (owner, repo) = stem.split_once("__")?;
+            Some((owner.to_string(), repo.to_string()))
+        })
+        .collect())
+}
+
+/// Stop tracking a repository and delete its stored history
+pub fn delete_history(owner: &str, repo: &str) -> InsightsResult<()> {
+    let path = history_path(owner, repo)?;
+    if path.exists() {
+        std::fs::remove_file(&path)
+            .map_err(|e| InsightsError::Other(format!("Failed to delete traffic history: {}", e)))?;
+    }
+    Ok(())
+}
+
+/// Snapshot every tracked repository on a fixed interval, forever
+pub async fn run_snapshot_scheduler() {
+    loop {
+        match tracked_repos() {
+            Ok(repos) => {
+                for (owner, repo) in repos {
+                    if let Err(e) = snapshot_traffic(&owner, &repo).await {
+                        tracing::warn!("Traffic snapshot of {}/{} failed: {}", owner, repo, e);
+                    }
+                }
+            }
+            Err(e) => tracing::warn!("Could not list tracked repositories: {}", e),
+        }
+
+        tokio::time::sleep(SNAPSHOT_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(day: &str, count: i32) -> TrafficPoint {
+        TrafficPoint { timestamp: format!("{}T00:00:00Z", day), count, uniques: 1 }
+    }
+
+    #[test]
+    fn test_merge_points_prefers_new_values() {
+        let existing = vec![point("2024-01-01", 3), point("2024-01-02", 1)];
+        let new = vec![point("2024-01-02", 5), point("2024-01-03", 2)];
+
+        let merged = merge_points(&existing, &new);
+        assert_eq!(
+            merged,
+            vec![point("2024-01-01", 3), point("2024-01-02", 5), point("2024-01-03", 2)]
+        );
+    }
+}
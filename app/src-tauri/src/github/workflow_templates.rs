@@ -0,0 +1,298 @@
+//! GitHub Actions workflow templates
+//!
+//! A small built-in library of common workflows. Templates contain `{{key}}`
+//! placeholders that are filled in with values chosen by the user, falling back
+//! to each parameter's default.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use super::templates::{TemplatesError, TemplatesResult};
+
+/// Value a workflow template asks for
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkflowTemplateParam {
+    pub key: &'static str,
+    pub label: &'static str,
+    /// Used when no value is given; parameters without a default are required
+    pub default: Option<&'static str>,
+}
+
+/// Workflow that can be added to a repository
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkflowTemplate {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Suggested path of the workflow file in the repository
+    pub path: &'static str,
+    pub params: &'static [WorkflowTemplateParam],
+    #[serde(skip)]
+    body: &'static str,
+}
+
+const RUST_CI: &str = r#"name: Rust CI
+
+on:
+  push:
+    branches: [{{branch}}]
+  pull_request:
+    branches: [{{branch}}]
+
+env:
+  CARGO_TERM_COLOR: always
+
+jobs:
+  test:
+    runs-on: {{runs_on}}
+    steps:
+      - uses: actions/checkout@v4
+      - uses: dtolnay/rust-toolchain@master
+        with:
+          toolchain: {{toolchain}}
+          components: clippy, rustfmt
+      - uses: Swatinem/rust-cache@v2
+      - run: cargo fmt --all -- --check
+      - run: cargo clippy --workspace --all-targets -- -D warnings
+      - run: cargo test --workspace
+"#;
+
+const NODE_CI: &str = r#"name: Node CI
+
+on:
+  push:
+    branches: [{{branch}}]
+  pull_request:
+    branches: [{{branch}}]
+
+jobs:
+  build:
+    runs-on: {{runs_on}}
+    steps:
+      - uses: actions/checkout@v4
+      - uses: actions/setup-node@v4
+        with:
+          node-version: {{node_version}}
+          cache: {{package_manager}}
+      - run: {{package_manager}} install
+      - run: {{package_manager}} run build --if-present
+      - run: {{package_manager}} test
+"#;
+
+const RELEASE_ON_TAG: &str = r#"name: Release
+
+on:
+  push:
+    tags: ['{{tag_pattern}}']
+
+permissions:
+  contents: write
+
+jobs:
+  release:
+    runs-on: {{runs_on}}
+    steps:
+      - uses: actions/checkout@v4
+      - run: {{build_command}}
+      - uses: softprops/action-gh-release@v2
+        with:
+          generate_release_notes: true
+          files: {{artifacts}}
+"#;
+
+const PAGES_DEPLOY: &str = r#"name: Deploy to GitHub Pages
+
+on:
+  push:
+    branches: [{{branch}}]
+  workflow_dispatch:
+
+permissions:
+  contents: read
+  pages: write
+  id-token: write
+
+concurrency:
+  group: pages
+  cancel-in-progress: false
+
+jobs:
+  build:
+    runs-on: {{runs_on}}
+    steps:
+      - uses: actions/checkout@v4
+      - run: {{build_command}}
+      - uses: actions/upload-pages-artifact@v3
+        with:
+          path: {{output_dir}}
+
+  deploy:
+    needs: build
+    runs-on: {{runs_on}}
+    environment:
+      name: github-pages
+      url: ${{ steps.deployment.outputs.page_url }}
+    steps:
+      - id: deployment
+        uses: actions/deploy-pages@v4
+"#;
+
+const BRANCH: WorkflowTemplateParam = WorkflowTemplateParam {
+    key: "branch",
+    label: "Branch",
+    default: Some("main"),
+};
+
+const RUNS_ON: WorkflowTemplateParam = WorkflowTemplateParam {
+    key: "runs_on",
+    label: "Runner",
+    default: Some("ubuntu-latest"),
+};
+
+const TEMPLATES: &[WorkflowTemplate] = &[
+    WorkflowTemplate {
+        id: "rust-ci",
+        name: "Rust CI",
+        description: "Check formatting, run Clippy and test a Cargo workspace",
+        path: ".github/workflows/rust.yml",
+        params: &[
+            BRANCH,
+            RUNS_ON,
+            WorkflowTemplateParam {
+                key: "toolchain",
+                label: "Toolchain",
+                default: Some("stable"),
+            },
+        ],
+        body: RUST_CI,
+    },
+    WorkflowTemplate {
+        id: "node-ci",
+        name: "Node CI",
+        description: "Install dependencies, build and test a Node.js project",
+        path: ".github/workflows/node.yml",
+        params: &[
+            BRANCH,
+            RUNS_ON,
+            WorkflowTemplateParam {
+                key: "node_version",
+                label: "Node.js version",
+                default: Some("20"),
+            },
+            WorkflowTemplateParam {
+                key: "package_manager",
+                label: "Package manager",
+                default: Some("npm"),
+            },
+        ],
+        body: NODE_CI,
+    },
+    WorkflowTemplate {
+        id: "release-on-tag",
+        name: "Release on tag",
+        description: "Build and publish a GitHub release when a version tag is pushed",
+        path: ".github/workflows/release.yml",
+        params: &[
+            RUNS_ON,
+            WorkflowTemplateParam {
+                key: "tag_pattern",
+                label: "Tag pattern",
+                default: Some("v*"),
+            },
+            WorkflowTemplateParam {
+                key: "build_command",
+                label: "Build command",
+                default: None,
+            },
+            WorkflowTemplateParam {
+                key: "artifacts",
+                label: "Release files (glob)",
+                default: Some("dist/*"),
+            },
+        ],
+        body: RELEASE_ON_TAG,
+    },
+    WorkflowTemplate {
+        id: "pages-deploy",
+        name: "Deploy to GitHub Pages",
+        description: "Build a static site and publish it with GitHub Pages",
+        path: ".github/workflows/pages.yml",
+        params: &[
+            BRANCH,
+            RUNS_ON,
+            WorkflowTemplateParam {
+                key: "build_command",
+                label: "Build command",
+                default: None,
+            },
+            WorkflowTemplateParam {
+                key: "output_dir",
+                label: "Output directory",
+                default: Some("dist"),
+            },
+        ],
+        body: PAGES_DEPLOY,
+    },
+];
+
+/// All built-in workflow templates
+pub fn list_workflow_templates() -> &'static [WorkflowTemplate] {
+    TEMPLATES
+}
+
+/// Fill in a workflow template with the given values
+///
+/// Values must be single lines so they cannot change the structure of the
+/// workflow.
+pub fn render_workflow_template(id: &str, values: &HashMap<String, String>) -> TemplatesResult<String> {
+    let template = TEMPLATES
+        .iter()
+        .find(|t| t.id == id)
+        .ok_or_else(|| TemplatesError(format!("Unknown workflow template: {}", id)))?;
+
+    let mut content = template.body.to_string();
+    for param in template.params {
+        let value = values
+            .get(param.key)
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+            .or(param.default)
+            .ok_or_else(|| TemplatesError(format!("A value for \"{}\" is required", param.label)))?;
+        if value.contains(['\n', '\r']) {
+            return Err(TemplatesError(format!("The value for \"{}\" must be a single line", param.label)));
+        }
+        content = content.replace(&format!("{{{{{}}}}}", param.key), value);
+    }
+
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_templates_fill_every_placeholder() {
+        let values: HashMap<String, String> = [("build_command".to_string(), "make".to_string())].into();
+        for template in list_workflow_templates() {
+            let content = render_workflow_template(template.id, &values).unwrap();
+            // `${{ ... }}` expressions belong to GitHub Actions, not to the template
+            assert!(!content.replace("${{", "").contains("{{"), "{} has unfilled placeholders", template.id);
+        }
+    }
+
+    #[test]
+    fn test_render_workflow_template_values() {
+        let mut values = HashMap::new();
+        values.insert("branch".to_string(), "develop".to_string());
+        let content = render_workflow_template("rust-ci", &values).unwrap();
+        assert!(content.contains("branches: [develop]"));
+        assert!(content.contains("toolchain: stable"));
+
+        assert!(render_workflow_template("pages-deploy", &values).is_err());
+        values.insert("build_command".to_string(), "npm run build\nrm -rf /".to_string());
+        assert!(render_workflow_template("pages-deploy", &values).is_err());
+        assert!(render_workflow_template("unknown", &values).is_err());
+    }
+}
@@ -3,6 +3,7 @@ pub mod error;
 pub mod git;
 pub mod ai;
 pub mod github;
+pub mod settings;
 
 use commands::{AppState, *};
 use tauri::Manager;
@@ -15,6 +16,7 @@ pub fn run() {
         .manage(AppState::default())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_notification::init())
         .setup(|app| {
             // Set window icon (embedded at compile time)
             if let Some(window) = app.get_webview_window("main") {
@@ -40,6 +42,11 @@ pub fn run() {
             } else {
                 println!("No main window found!");
             }
+
+            // Keep traffic history of tracked repositories beyond GitHub's 14 days
+            tauri::async_runtime::spawn(github::traffic_history::run_snapshot_scheduler());
+            // Push repositories with a scheduled mirror to their backup remote
+            tauri::async_runtime::spawn(run_mirror_scheduler(app.handle().clone()));
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -47,6 +54,8 @@ pub fn run() {
             open_repository,
             init_repository,
             get_repository_info,
+            get_repo_health,
+            get_commit_activity,
             // Git config commands
             get_git_config,
             set_git_config,
@@ -56,8 +65,21 @@ pub fn run() {
             stage_files,
             unstage_files,
             discard_changes,
+            stage_commit_group,
             // Commit commands
             create_commit,
+            scan_staged_secrets,
+            get_secret_scan_settings,
+            set_secret_scan_mode,
+            set_repo_secret_allowlist,
+            check_staged_large_files,
+            get_large_file_settings,
+            set_large_file_settings,
+            lint_commit_message,
+            get_commit_lint_settings,
+            set_commit_lint_settings,
+            get_protected_branches,
+            set_protected_branches,
             get_commits,
             get_commit_detail,
             cherry_pick_commit,
@@ -66,10 +88,31 @@ pub fn run() {
             checkout_commit,
             create_tag,
             get_commit_diff,
+            export_history,
+            // Commit search index commands
+            search_index_status,
+            build_search_index,
+            cancel_search_index_build,
+            search_indexed_commits,
+            delete_search_index,
+            // Time format commands
+            get_time_format_settings,
+            set_time_format_settings,
+            format_timestamps,
+            // Mirror commands
+            get_mirror_config,
+            save_mirror_config,
+            delete_mirror_config,
+            run_mirror_push,
             // New commit operations
             merge_commit,
             rebase_onto,
             interactive_rebase,
+            get_rebase_plan,
+            set_rebase_plan,
+            get_rebase_progress,
+            rebase_continue,
+            rebase_abort,
             delete_tag,
             squash_commits,
             amend_commit_message,
@@ -78,17 +121,38 @@ pub fn run() {
             get_branches,
             create_branch,
             checkout_branch,
+            force_checkout_branch,
             delete_branch,
             merge_branch,
             // Diff commands
             get_file_diff,
+            get_diff_summary,
+            apply_edited_patch,
+            get_file_blame,
             // AI commands
             generate_commit_message,
+            generate_commit_message_candidates,
+            refine_commit_message,
+            generate_changelog,
+            explain_commit,
+            explain_hunk,
+            suggest_commit_split,
+            generate_commit_message_stream,
+            cancel_commit_message_generation,
             get_ai_config,
             set_ai_config,
+            delete_ai_api_key,
             check_ollama_status,
             validate_openai_key,
+            list_openai_models,
             list_ollama_models,
+            validate_anthropic_key,
+            list_anthropic_models,
+            list_compatible_models,
+            get_commit_prompt_templates,
+            set_commit_prompt_template,
+            set_repo_commit_prompt_template,
+            preview_commit_prompt,
             // Remote commands
             get_remotes,
             add_remote,
@@ -100,19 +164,78 @@ pub fn run() {
             // Clone & Repository Management commands
             clone_repository,
             scan_for_repos,
+            cancel_repo_scan,
             get_repo_sync_status,
+            get_all_repo_sync_statuses,
+            get_workspaces,
+            save_workspace,
+            delete_workspace,
+            fetch_workspace,
+            pull_workspace,
+            get_workspace_status,
             // Workflow file commands
             list_repository_files,
             list_workflow_files,
             create_workflow_file,
             read_workflow_file,
             delete_workflow_file,
+            list_workflow_templates,
+            render_workflow_template,
+            read_repository_file,
+            write_repository_file,
+            delete_repository_file,
             // GitHub Authentication commands
             github_login,
             github_auth_status,
             github_logout,
             github_get_user,
             github_get_repos,
+            github_get_orgs,
+            github_get_org_repos,
+            github_create_repo,
+            github_publish_repo,
+            github_fork_repo,
+            github_fork_and_add_remote,
+            github_star_repo,
+            github_unstar_repo,
+            github_is_repo_starred,
+            github_get_starred_repos,
+            github_get_watch_level,
+            github_set_watch_level,
+            github_get_repo,
+            github_update_repo,
+            github_archive_repo,
+            github_unarchive_repo,
+            github_delete_repo,
+            github_transfer_repo,
+            github_list_branches,
+            github_list_gitignore_templates,
+            github_get_gitignore_template,
+            github_list_licenses,
+            github_get_license,
+            github_search_code,
+            github_search_issues,
+            github_search_repos,
+            github_search_users,
+            resolve_avatars,
+            get_commits_with_avatars,
+            github_list_recent_committers,
+            github_list_mention_candidates,
+            github_list_collaborators,
+            github_add_collaborator,
+            github_remove_collaborator,
+            github_list_repo_invitations,
+            github_cancel_repo_invitation,
+            github_list_my_invitations,
+            github_accept_invitation,
+            github_decline_invitation,
+            github_list_repo_secrets,
+            github_set_repo_secret,
+            github_delete_repo_secret,
+            github_list_repo_variables,
+            github_create_repo_variable,
+            github_update_repo_variable,
+            github_delete_repo_variable,
             github_get_token,
             // GitHub Actions commands
             github_list_workflows,
@@ -120,6 +243,7 @@ pub fn run() {
             github_get_workflow_run,
             github_get_workflow_run_jobs,
             github_get_workflow_run_logs,
+            github_get_job_log,
             github_trigger_workflow,
             github_cancel_workflow_run,
             github_rerun_workflow,
@@ -129,6 +253,15 @@ pub fn run() {
             github_get_artifact_download_url,
             github_delete_artifact,
             github_delete_workflow_run,
+            github_list_caches,
+            github_get_cache_usage,
+            github_delete_cache,
+            github_delete_caches_by_key,
+            github_get_workflow_run_usage,
+            github_get_repo_actions_usage,
+            github_watch_workflow_run,
+            github_unwatch_workflow_run,
+            github_list_watched_runs,
             // GitHub Releases commands
             github_list_releases,
             github_get_release,
@@ -140,7 +273,14 @@ pub fn run() {
             github_generate_release_notes,
             github_list_release_assets,
             github_delete_release_asset,
+            github_upload_release_assets,
+            github_guided_release,
+            github_download_release_asset,
+            github_cancel_release_asset_download,
             github_list_tags,
+            github_list_tag_protections,
+            github_create_tag_protection,
+            github_delete_tag_protection,
             // GitHub Pages commands
             github_get_pages_info,
             github_enable_pages,
@@ -148,8 +288,12 @@ pub fn run() {
             github_disable_pages,
             github_list_pages_builds,
             github_get_latest_pages_build,
+            github_get_pages_build,
             github_request_pages_build,
             github_get_deployment_status,
+            github_cancel_pages_deployment,
+            github_get_pages_health_check,
+            github_deploy_pages_directory,
             // GitHub Notifications commands
             github_list_notifications,
             github_list_repo_notifications,
@@ -162,6 +306,15 @@ pub fn run() {
             github_set_thread_subscription,
             github_delete_thread_subscription,
             github_get_unread_count,
+            github_list_filtered_notifications,
+            github_bulk_update_notifications,
+            github_start_notification_poller,
+            github_stop_notification_poller,
+            // GitHub App commands
+            github_app_get_config,
+            github_app_register,
+            github_app_remove,
+            github_app_set_repo,
             // GitHub Insights commands
             github_get_contributors,
             github_get_commit_activity,
@@ -174,6 +327,11 @@ pub fn run() {
             github_get_popular_paths,
             github_get_community_profile,
             github_get_languages,
+            github_snapshot_traffic,
+            github_get_traffic_history,
+            github_untrack_traffic,
+            github_get_cached_insights,
+            github_clear_insights_cache,
             // GitHub Pull Requests commands
             github_list_pull_requests,
             github_get_pull_request,
@@ -183,7 +341,45 @@ pub fn run() {
             github_list_pr_reviews,
             github_list_pr_comments,
             github_request_reviewers,
+            github_remove_requested_reviewers,
             github_create_review,
+            github_submit_review,
+            github_delete_pending_review,
+            github_create_review_comment,
+            github_get_review_comment,
+            github_update_review_comment,
+            github_delete_review_comment,
+            github_list_pr_files,
+            github_get_pr_diff,
+            github_list_pr_commits,
+            github_mark_pr_ready,
+            github_convert_pr_to_draft,
+            github_get_pr_merge_state,
+            github_update_pr_branch,
+            github_get_branch_pull_requests,
+            github_list_review_threads,
+            github_resolve_review_thread,
+            github_unresolve_review_thread,
+            github_get_pr_checks,
+            github_list_check_suites,
+            github_list_check_runs,
+            github_list_suite_check_runs,
+            github_list_check_run_annotations,
+            github_get_commit_annotations,
+            github_rerequest_check_suite,
+            github_get_combined_status,
+            github_list_commit_statuses,
+            github_create_commit_status,
+            github_compare,
+            github_list_commit_comments,
+            github_create_commit_comment,
+            github_get_file_content,
+            github_create_or_update_file,
+            github_delete_file,
+            github_list_requestable_teams,
+            github_list_org_teams,
+            github_list_team_members,
+            github_list_team_repos,
             // GitHub Issues commands
             github_list_issues,
             github_get_issue,
@@ -191,11 +387,35 @@ pub fn run() {
             github_update_issue,
             github_list_issue_comments,
             github_create_issue_comment,
+            github_get_issue_comment,
+            github_update_issue_comment,
+            github_delete_issue_comment,
             github_list_labels,
+            github_create_label,
+            github_update_label,
+            github_delete_label,
             github_list_milestones,
+            github_create_milestone,
+            github_update_milestone,
+            github_close_milestone,
+            github_delete_milestone,
+            github_parse_task_list,
+            github_toggle_task_list_item,
             github_add_labels_to_issue,
             github_lock_issue,
             github_unlock_issue,
+            github_add_assignees,
+            github_remove_assignees,
+            github_list_assignable_users,
+            github_list_reactions,
+            github_add_reaction,
+            github_remove_reaction,
+            github_list_discussion_categories,
+            github_list_discussions,
+            github_get_discussion,
+            github_add_discussion_comment,
+            github_mark_discussion_answer,
+            github_unmark_discussion_answer,
             // GitHub Deployments commands
             github_list_deployments,
             github_get_deployment,
@@ -204,6 +424,11 @@ pub fn run() {
             github_list_deployment_statuses,
             github_create_deployment_status,
             github_get_deployment_summary,
+            github_deploy_local_branch,
+            // Local workflow run commands
+            get_act_status,
+            run_workflow_locally,
+            cancel_local_workflow_run,
             // GitHub Environments commands
             github_list_environments,
             github_get_environment,
@@ -211,10 +436,18 @@ pub fn run() {
             github_update_environment,
             github_delete_environment,
             github_list_environment_secrets,
+            github_set_environment_secret,
+            github_delete_environment_secret,
             github_list_environment_variables,
+            github_get_environment_variable,
+            github_create_environment_variable,
+            github_update_environment_variable,
+            github_delete_environment_variable,
             github_list_branch_policies,
             github_create_branch_policy,
             github_delete_branch_policy,
+            github_get_pending_deployments,
+            github_review_pending_deployments,
             // GitHub Security commands
             github_list_dependabot_alerts,
             github_list_code_scanning_alerts,
@@ -222,6 +455,13 @@ pub fn run() {
             github_dismiss_dependabot_alert,
             github_dismiss_code_scanning_alert,
             github_resolve_secret_scanning_alert,
+            github_list_code_scanning_alert_instances,
+            github_upload_sarif,
+            github_get_sarif_upload_status,
+            github_list_repository_advisories,
+            github_create_repository_advisory,
+            github_update_repository_advisory,
+            github_request_advisory_cve,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,91 @@
+//! JSON settings files in the app config directory
+//!
+//! Each feature keeps its settings in its own file under `linuxgit/` in the
+//! config directory. A missing file means the defaults are in use.
+
+use std::path::{Path, PathBuf};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Error type for loading and saving settings
+#[derive(Debug)]
+pub struct SettingsError(pub String);
+
+impl std::fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for SettingsError {}
+
+pub type SettingsResult<T> = Result<T, SettingsError>;
+
+/// Location of the settings file named `file`
+pub fn settings_path(file: &str) -> SettingsResult<PathBuf> {
+    dirs::config_dir()
+        .map(|dir| dir.join("linuxgit").join(file))
+        .ok_or_else(|| SettingsError("Could not determine the config directory".to_string()))
+}
+
+/// Load the settings file named `file`, or the defaults if it was never saved
+pub fn load_json_settings<T: DeserializeOwned + Default>(file: &str) -> SettingsResult<T> {
+    load_json_file(&settings_path(file)?)
+}
+
+/// Save the settings file named `file`
+pub fn save_json_settings<T: Serialize>(file: &str, settings: &T) -> SettingsResult<()> {
+    save_json_file(&settings_path(file)?, settings)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+fn load_json_file<T: DeserializeOwned + Default>(path: &Path) -> SettingsResult<T> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+
+    let data = std::fs::read_to_string(path)
+        .map_err(|e| SettingsError(format!("Failed to read {}: {}", file_name(path), e)))?;
+    serde_json::from_str(&data).map_err(|e| SettingsError(format!("Failed to parse {}: {}", file_name(path), e)))
+}
+
+fn save_json_file<T: Serialize>(path: &Path, settings: &T) -> SettingsResult<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| SettingsError(format!("Failed to create config directory: {}", e)))?;
+    }
+
+    let data = serde_json::to_string_pretty(settings)
+        .map_err(|e| SettingsError(format!("Failed to serialize {}: {}", file_name(path), e)))?;
+    std::fs::write(path, data).map_err(|e| SettingsError(format!("Failed to write {}: {}", file_name(path), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_json_settings_round_trip() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("linuxgit").join("test.json");
+
+        let missing: BTreeMap<String, u32> = load_json_file(&path).unwrap();
+        assert!(missing.is_empty());
+
+        let settings = BTreeMap::from([("threshold".to_string(), 5)]);
+        save_json_file(&path, &settings).unwrap();
+        assert_eq!(load_json_file::<BTreeMap<String, u32>>(&path).unwrap(), settings);
+
+        std::fs::write(&path, "{").unwrap();
+        let error = load_json_file::<BTreeMap<String, u32>>(&path).unwrap_err();
+        assert!(error.0.starts_with("Failed to parse test.json: "));
+    }
+}